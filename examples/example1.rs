@@ -43,10 +43,7 @@ main()
 	}
 
 	println!("\nJPG read result:");
-	for tag in &Metadata::new_from_path(jpg_path).unwrap()
-	{
-		println!("{:?}", tag);
-	}
+	println!("{}", Metadata::new_from_path(jpg_path).unwrap().to_pretty_string());
 
 	// Explicitly read in the ImageDescription by tag or hex
 	let metadata = Metadata::new_from_path(jpg_path).unwrap();
@@ -45,6 +45,12 @@ decode_tag_with_format_exceptions
 				return Ok(raw_tag.set_value_to_int32u_vec(int32u_data).unwrap());
 			},
 
+			(ExifTagFormat::INT32U, ExifTagFormat::IFD) => {
+				// An IFD value is a LONG that is known to be an IFD offset
+				let int32u_data = <INT32U as U8conversion<INT32U>>::from_u8_vec(raw_data, endian);
+				return Ok(raw_tag.set_value_to_int32u_vec(int32u_data).unwrap());
+			},
+
 			(ExifTagFormat::INT16U, ExifTagFormat::INT32U) => {
 				// Not sure how to be more cautious in this case...
 				let int32u_data = <INT32U as U8conversion<INT32U>>::from_u8_vec(raw_data, endian);
@@ -94,6 +100,18 @@ decode_tag_with_format_exceptions
 				}
 			},
 
+			(ExifTagFormat::INT8U, ExifTagFormat::UNDEF) => {
+				// Both are just bytes, e.g. the PhotoshopSettings tag is
+				// sometimes stored as UNDEF instead of INT8U
+				return Ok(ExifTag::from_u16_with_data(
+					hex_tag, 
+					&ExifTagFormat::INT8U, 
+					&raw_data, 
+					&endian, 
+					group
+				).unwrap());
+			},
+
 			_ => {
 				return io_error!(Other, format!("Illegal format for known tag! Tag: {:?} Expected: {:?} Got: {:?}", raw_tag, raw_tag.format(), format));
 			},
@@ -0,0 +1,186 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::endian::Endian;
+use crate::u8conversion::*;
+
+use super::ExifTag;
+
+impl
+ExifTag
+{
+	/// Encodes the tag as 12 byte IFD entry, consisting of the tag's hex
+	/// value, its format, the number of components and either the value
+	/// itself or - if the value needs more than 4 bytes - the offset to it.
+	/// In the latter case, the value is returned as second element and has
+	/// to be stored at `data_area_offset` (relative to the TIFF header).
+	/// Note that the value of offset tags (e.g. `ExifOffset` or
+	/// `StripOffsets`) is encoded as it is stored in the tag, as the actual
+	/// offsets are only known while encoding the entire metadata.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::endian::Endian;
+	/// use little_exif::exif_tag::ExifTag;
+	///
+	/// let (entry, overflow) = ExifTag::ISO(vec![100]).encode_entry(Endian::Little, 0);
+	/// assert_eq!(entry, vec![0x27, 0x88, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x64, 0x00, 0x00, 0x00]);
+	/// assert_eq!(overflow, None);
+	/// ```
+	pub fn
+	encode_entry
+	(
+		&self,
+		endian:           Endian,
+		data_area_offset: u32
+	)
+	-> (Vec<u8>, Option<Vec<u8>>)
+	{
+		return self.encode_entry_with_value(self.value_as_u8_vec(&endian), &endian, data_area_offset);
+	}
+
+	/// Gets the number of components of the tag as stored in its IFD entry,
+	/// e.g. 3 for a `GPSLatitude` tag or the length including the NUL
+	/// terminator for a `STRING` tag. See also `number_of_components`.
+	pub fn
+	component_count
+	(
+		&self
+	)
+	-> u32
+	{
+		self.number_of_components()
+	}
+
+	/// Gets the number of bytes the value of the tag occupies when stored,
+	/// i.e. the number of components times the size of a single component
+	/// of the tag's format (the same computation as when decoding an IFD).
+	/// Values of more than 4 bytes are stored outside of the IFD entry.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::exif_tag::ExifTag;
+	///
+	/// assert_eq!(ExifTag::ISO(vec![100]).byte_count(), 2);
+	/// ```
+	pub fn
+	byte_count
+	(
+		&self
+	)
+	-> u32
+	{
+		self.component_count() * self.format().bytes_per_component()
+	}
+
+	/// Same as `encode_entry`, but uses the given, already encoded value
+	/// instead of the one stored in the tag. Needed for offset tags, whose
+	/// value gets computed while encoding the IFDs.
+	pub(crate) fn
+	encode_entry_with_value
+	(
+		&self,
+		value:            Vec<u8>,
+		endian:           &Endian,
+		data_area_offset: u32
+	)
+	-> (Vec<u8>, Option<Vec<u8>>)
+	{
+		// Strings may be longer than their value with the NUL terminator
+		// when padded with additional NULs
+		let number_of_components = if self.is_string()
+		{
+			self.number_of_components().max(value.len() as u32)
+		}
+		else
+		{
+			self.number_of_components()
+		};
+
+		// Add Tag & Data Format /                                              2 + 2 bytes
+		let mut entry = Vec::new();
+		entry.extend(to_u8_vec_macro!(u16, &self.as_u16(),          endian).iter());
+		entry.extend(to_u8_vec_macro!(u16, &self.format().as_u16(), endian).iter());
+
+		// Add number of components /                                           4 bytes
+		entry.extend(to_u8_vec_macro!(u32, &number_of_components, endian).iter());
+
+		// Optional string padding (i.e. string is shorter than it should be)
+		let mut padded_value = value;
+		if self.is_string()
+		{
+			padded_value.resize(number_of_components as usize, 0x00);
+		}
+
+		// Add offset or value /                                                4 bytes
+		// Depending on the amount of data, either put it directly into
+		// next 4 bytes or write an offset where the data can be found
+		let byte_count = number_of_components * self.format().bytes_per_component();
+		if byte_count > 4
+		{
+			entry.extend(to_u8_vec_macro!(u32, &data_area_offset, endian).iter());
+			return (entry, Some(padded_value));
+		}
+
+		// Make sure that this area is indeed *exactly* 4 bytes long
+		padded_value.resize(4, 0x00);
+		entry.extend(padded_value);
+		return (entry, None);
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use crate::endian::Endian;
+	use crate::exif_tag::ExifTag;
+	use crate::rational::uR64;
+
+	#[test]
+	fn
+	encode_iso_entry()
+	{
+		assert_eq!(
+			ExifTag::ISO(vec![400]).encode_entry(Endian::Big, 1234),
+			(vec![0x88, 0x27, 0x00, 0x03, 0x00, 0x00, 0x00, 0x01, 0x01, 0x90, 0x00, 0x00], None)
+		);
+		assert_eq!(
+			ExifTag::ISO(vec![400]).encode_entry(Endian::Little, 1234),
+			(vec![0x27, 0x88, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x90, 0x01, 0x00, 0x00], None)
+		);
+	}
+
+	#[test]
+	fn
+	component_and_byte_count()
+	{
+		let latitude = ExifTag::GPSLatitude(vec![uR64 { nominator: 48, denominator: 1 }; 3]);
+		assert_eq!(latitude.component_count(), 3);
+		assert_eq!(latitude.byte_count(),      24);
+
+		// Includes the NUL terminator
+		let artist = ExifTag::Artist("Someone".to_string());
+		assert_eq!(artist.component_count(), 8);
+		assert_eq!(artist.byte_count(),      8);
+	}
+
+	#[test]
+	fn
+	encode_image_description_entry()
+	{
+		// Doesn't fit into the entry, so the offset to the data area is used
+		assert_eq!(
+			ExifTag::ImageDescription("Hello World".to_string()).encode_entry(Endian::Big, 0x0102),
+			(
+				vec![0x01, 0x0e, 0x00, 0x02, 0x00, 0x00, 0x00, 0x0c, 0x00, 0x00, 0x01, 0x02],
+				Some(b"Hello World\0".to_vec())
+			)
+		);
+
+		// Short enough to be stored within the entry
+		assert_eq!(
+			ExifTag::ImageDescription("Hi".to_string()).encode_entry(Endian::Little, 0x0102),
+			(vec![0x0e, 0x01, 0x02, 0x00, 0x03, 0x00, 0x00, 0x00, 0x48, 0x69, 0x00, 0x00], None)
+		);
+	}
+}
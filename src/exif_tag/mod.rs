@@ -2,6 +2,7 @@
 // See https://github.com/TechnikTobi/little_exif#license for licensing details
 
 pub(crate) mod decode;
+pub(crate) mod encode;
 pub(super) mod set_value_to;
 
 use paste::paste;
@@ -21,6 +22,24 @@ TagType
 	DATA_OFFSET(Vec<u32>)
 }
 
+/// Returns the value of the given known tag as bytes from the enclosing
+/// function if the tag has the format `UNDEF`, see `as_undefined_bytes`.
+/// For all other formats, nothing is generated.
+macro_rules! return_undefined_bytes {
+	($tag_value:expr, $tag:ident, UNDEF)
+	=>
+	{
+		if let ExifTag::$tag(value) = $tag_value
+		{
+			return Some(value);
+		}
+	};
+
+	($tag_value:expr, $tag:ident, $format_enum:ident)
+	=>
+	{};
+}
+
 macro_rules! build_tag_enum {
 	( 
 		$( (
@@ -102,6 +121,31 @@ macro_rules! build_tag_enum {
 				}
 			}
 
+			/// Gets the name of the tag, e.g. "ImageDescription". Unknown tags 
+			/// are named after their hex value, e.g. "Unknown 0x1234".
+			pub fn
+			name
+			(
+				&self
+			)
+			-> String
+			{
+				match *self
+				{
+					$(
+						ExifTag::$tag(_) => String::from(stringify!($tag)),
+					)*
+
+					ExifTag::StripOffsets(       _, _,       ) => String::from("StripOffsets"),
+					ExifTag::StripByteCounts(    _,          ) => String::from("StripByteCounts"),
+
+					ExifTag::ThumbnailOffset(    _, _,       ) => String::from("ThumbnailOffset"),
+					ExifTag::ThumbnailLength(    _,          ) => String::from("ThumbnailLength"),
+
+					_ => format!("Unknown 0x{:04x}", self.as_u16()),
+				}
+			}
+
 			/// Gets the tag for a given hex value. 
 			/// The tag is initialized with new, empty data.
 			/// If the hex value is unknown, an error is returned.
@@ -138,11 +182,41 @@ macro_rules! build_tag_enum {
 				}
 			}
 
+			/// Gets the format the specification expects for the tag with the
+			/// given hex value, without constructing the tag itself.
+			/// Returns `None` if the hex value is unknown, i.e. in the cases
+			/// where `from_u16` returns an error.
+			pub(crate) fn
+			expected_format
+			(
+				hex_value: u16,
+				group:     &ExifTagGroup
+			)
+			-> Option<ExifTagFormat>
+			{
+				match (hex_value, group)
+				{
+					$(
+						($hex_value, ExifTagGroup::$group) => Some(ExifTagFormat::$format_enum),
+					)*
+
+					(0x0111, _) => Some(ExifTagFormat::INT32U),
+					(0x0117, _) => Some(ExifTagFormat::INT32U),
+
+					(0x0201, _) => Some(ExifTagFormat::INT32U),
+					(0x0202, _) => Some(ExifTagFormat::INT32U),
+
+					_ => None,
+				}
+			}
+
 			/// Gets the tag for a given hex value. 
 			/// The tag is initialized using the given raw data by converting it
 			/// to the appropriate format.
 			/// If the hex value is unknown, the other parameters are used to
 			/// generate an appropriate unknown tag for the specified format.
+			/// For `STRING` format data, all trailing NUL bytes (the NUL
+			/// terminator as well as any additional padding) are removed.
 			/// 
 			/// # Examples
 			/// ```no_run
@@ -169,6 +243,32 @@ macro_rules! build_tag_enum {
 				group:     &ExifTagGroup,
 			)
 			-> Result<ExifTag, String>
+			{
+				if *format != ExifTagFormat::STRING
+				{
+					return Self::from_u16_with_untrimmed_data(hex_value, format, raw_data, endian, group);
+				}
+
+				let trimmed_length = raw_data.iter()
+					.rposition(|byte| *byte != 0x00)
+					.map_or(0, |position| position + 1);
+
+				return Self::from_u16_with_untrimmed_data(hex_value, format, &raw_data[..trimmed_length].to_vec(), endian, group);
+			}
+
+			/// Same as `from_u16_with_data`, but only the last NUL byte of
+			/// `STRING` format data is removed, keeping any additional padding
+			/// as part of the string.
+			pub(crate) fn
+			from_u16_with_untrimmed_data
+			(
+				hex_value: u16,
+				format:    &ExifTagFormat,
+				raw_data:  &Vec<u8>,
+				endian:    &Endian,
+				group:     &ExifTagGroup,
+			)
+			-> Result<ExifTag, String>
 			{
 				match (hex_value, group)
 				{
@@ -191,7 +291,8 @@ macro_rules! build_tag_enum {
 							ExifTagFormat::INT8U       => Ok(ExifTag::UnknownINT8U(      <INT8U       as U8conversion<INT8U      >>::from_u8_vec(raw_data, endian), hex_value, *group)),
 							ExifTagFormat::STRING      => Ok(ExifTag::UnknownSTRING(     <STRING      as U8conversion<STRING     >>::from_u8_vec(raw_data, endian), hex_value, *group)),
 							ExifTagFormat::INT16U      => Ok(ExifTag::UnknownINT16U(     <INT16U      as U8conversion<INT16U     >>::from_u8_vec(raw_data, endian), hex_value, *group)),
-							ExifTagFormat::INT32U      => Ok(ExifTag::UnknownINT32U(     <INT32U      as U8conversion<INT32U     >>::from_u8_vec(raw_data, endian), hex_value, *group)),
+							ExifTagFormat::INT32U
+							| ExifTagFormat::IFD       => Ok(ExifTag::UnknownINT32U(     <INT32U      as U8conversion<INT32U     >>::from_u8_vec(raw_data, endian), hex_value, *group)),
 							ExifTagFormat::RATIONAL64U => Ok(ExifTag::UnknownRATIONAL64U(<RATIONAL64U as U8conversion<RATIONAL64U>>::from_u8_vec(raw_data, endian), hex_value, *group)),
 							ExifTagFormat::INT8S       => Ok(ExifTag::UnknownINT8S(      <INT8S       as U8conversion<INT8S      >>::from_u8_vec(raw_data, endian), hex_value, *group)),
 							ExifTagFormat::UNDEF       => Ok(ExifTag::UnknownUNDEF(      <UNDEF       as U8conversion<UNDEF      >>::from_u8_vec(raw_data, endian), hex_value, *group)),
@@ -284,9 +385,20 @@ macro_rules! build_tag_enum {
 				return true;
 			}
 
-			/// Gets the group (i.e. IFD) the tag belongs to.
+			/// Gets the group (i.e. IFD) the tag belongs to, e.g. `GPS` for
+			/// `GPSLatitude` or `GENERIC` for `ImageDescription` (stored in
+			/// IFD0). For the `Unknown...` variants this is the group stored
+			/// in the variant.
 			/// Note that this is still somewhat problematic, as for some tags
 			/// the value of this function is hard to determine.
+			///
+			/// # Examples
+			/// ```
+			/// use little_exif::exif_tag::ExifTag;
+			/// use little_exif::ifd::ExifTagGroup;
+			///
+			/// assert_eq!(ExifTag::GPSLatitude(Vec::new()).get_group(), ExifTagGroup::GPS);
+			/// ```
 			pub fn
 			get_group
 			(
@@ -441,6 +553,37 @@ macro_rules! build_tag_enum {
 				}
 			}
 
+			/// Gets the raw bytes of a tag with format `UNDEF`, exactly as
+			/// they were read. This includes tags whose structure little_exif
+			/// does not interpret (e.g. `DeviceSettingDescription`) as well as
+			/// unknown `UNDEF` tags. For all other formats, this is `None`.
+			///
+			/// # Examples
+			/// ```
+			/// use little_exif::exif_tag::ExifTag;
+			///
+			/// let tag = ExifTag::DeviceSettingDescription(vec![0x00, 0x01, 0x00, 0x01]);
+			/// assert_eq!(tag.as_undefined_bytes(), Some(&[0x00, 0x01, 0x00, 0x01][..]));
+			/// assert_eq!(ExifTag::ISO(vec![100]).as_undefined_bytes(), None);
+			/// ```
+			pub fn
+			as_undefined_bytes
+			(
+				&self
+			)
+			-> Option<&[u8]>
+			{
+				$(
+					return_undefined_bytes!(self, $tag, $format_enum);
+				)*
+
+				if let ExifTag::UnknownUNDEF(value, _, _) = self
+				{
+					return Some(value);
+				}
+				return None;
+			}
+
 			/// Gets the value stored in the tag as an u8 vector, using the 
 			/// given endianness for conversion.
 			pub fn
@@ -488,9 +631,6 @@ macro_rules! build_tag_enum {
 // Note regarding non-writable tags: Apart from
 // - StripOffsets
 // - StripByteCounts
-// - Opto-ElectricConvFactor (OECF)
-// - SpatialFrequencyResponse
-// - DeviceSettingDescription
 // none of them are part of the EXIF 2.32 specification
 // (Source: https://exiftool.org/TagNames/EXIF.html )
 
@@ -539,6 +679,9 @@ build_tag_enum![
 	(InteroperabilityIndex,       0x0001, STRING,        Some::<u32>(4),    true,      INTEROP),
 	(InteroperabilityVersion,     0x0002, UNDEF,         None::<u32>,       true,      INTEROP),
 
+	(NewSubfileType,              0x00fe, INT32U,        Some::<u32>(1),    true,      GENERIC),                        // Not EXIF but TIFF
+	(SubfileType,                 0x00ff, INT16U,        Some::<u32>(1),    true,      GENERIC),                        // Not EXIF but TIFF, superseded by NewSubfileType
+
 	(ImageWidth,                  0x0100, INT32U,        Some::<u32>(1),    true,      GENERIC),                        // Not EXIF but TIFF   x       x         x             x 
 	(ImageHeight,                 0x0101, INT32U,        Some::<u32>(1),    true,      GENERIC),                        // Not EXIF but TIFF   x       x         x             x 
 	(BitsPerSample,               0x0102, INT16U,        Some::<u32>(3),    true,      GENERIC),                        // Not EXIF but TIFF           x         x             x 
@@ -562,6 +705,7 @@ build_tag_enum![
 	(XResolution,                 0x011a, RATIONAL64U,   Some::<u32>(1),    true,      GENERIC),                        // Not EXIF but TIFF   x       x         x             x 
 	(YResolution,                 0x011b, RATIONAL64U,   Some::<u32>(1),    true,      GENERIC),                        // Not EXIF but TIFF   x       x         x             x 
 	(PlanarConfiguration,         0x011c, INT16U,        Some::<u32>(1),    true,      GENERIC),               
+	(PageName,                    0x011d, STRING,        None::<u32>,       true,      GENERIC),                        // Not EXIF but TIFF
 
 	(ResolutionUnit,              0x0128, INT16U,        Some::<u32>(1),    true,      GENERIC),                        // Not EXIF but TIFF   x       x         x             x 
 
@@ -571,6 +715,7 @@ build_tag_enum![
 	(ModifyDate,                  0x0132, STRING,        Some::<u32>(20),   true,      GENERIC),
 
 	(Artist,                      0x013b, STRING,        None::<u32>,       true,      GENERIC),                        // Not EXIF but TIFF
+	(Predictor,                   0x013d, INT16U,        Some::<u32>(1),    true,      GENERIC),                        // Not EXIF but TIFF
 
 	(WhitePoint,                  0x013e, RATIONAL64U,   Some::<u32>(2),    true,      GENERIC),
 	(PrimaryChromaticities,       0x013f, RATIONAL64U,   Some::<u32>(6),    true,      GENERIC),
@@ -587,17 +732,23 @@ build_tag_enum![
 	(YCbCrPositioning,            0x0213, INT16U,        Some::<u32>(1),    true,      GENERIC),
 	(ReferenceBlackWhite,         0x0214, RATIONAL64U,   Some::<u32>(6),    true,      GENERIC),
 
+	(Rating,                      0x4746, INT16U,        Some::<u32>(1),    true,      GENERIC),                        // Windows/Adobe extension, 0 to 5 stars
+	(RatingPercent,               0x4749, INT16U,        Some::<u32>(1),    true,      GENERIC),                        // Windows/Adobe extension, 0 to 100
+
+	(CFARepeatPatternDim,         0x828d, INT16U,        Some::<u32>(2),    true,      GENERIC),                        // Not EXIF but TIFF/EP, used by raw formats
 	(Copyright,                   0x8298, STRING,        None::<u32>,       true,      GENERIC),
 	(ExposureTime,                0x829a, RATIONAL64U,   Some::<u32>(1),    true,      EXIF),
 	(FNumber,                     0x829d, RATIONAL64U,   Some::<u32>(1),    true,      EXIF),
 
+	(PhotoshopSettings,           0x8649, INT8U,         None::<u32>,       true,      GENERIC),                        // Photoshop Image Resource Block (IRB), e.g. holding IPTC data
+
 	(ExifOffset,                  0x8769, INT32U,        Some::<u32>(1),    false,     GENERIC),
 
 	(ExposureProgram,             0x8822, INT16U,        Some::<u32>(1),    true,      EXIF),
 	(SpectralSensitivity,         0x8824, STRING,        None::<u32>,       true,      EXIF),
 	(GPSInfo,                     0x8825, INT32U,        Some::<u32>(1),    true,      GENERIC),                        // -> GPS Tags: https://exiftool.org/TagNames/GPS.html
 	(ISO,                         0x8827, INT16U,        None::<u32>,       true,      EXIF),
-	(OECF,                        0x8828, UNDEF,         None::<u32>,       false,     EXIF),
+	(OECF,                        0x8828, UNDEF,         None::<u32>,       true,      EXIF),
 	(SensitivityType,             0x8830, INT16U,        Some::<u32>(1),    true,      EXIF),
 	(StandardOutputSensitivity,   0x8831, INT32U,        Some::<u32>(1),    true,      EXIF),
 	(RecommendedExposureIndex,    0x8832, INT32U,        Some::<u32>(1),    true,      EXIF),
@@ -649,7 +800,7 @@ build_tag_enum![
 	(RelatedSoundFile,            0xa004, STRING,        None::<u32>,       true,      EXIF),
 	(InteropOffset,               0xa005, INT32U,        Some::<u32>(1),    true,      EXIF),
 	(FlashEnergy,                 0xa20b, RATIONAL64U,   Some::<u32>(1),    true,      EXIF),
-	(SpatialFrequencyResponse,    0xa20c, INT16U,        Some::<u32>(1),    false,     EXIF),
+	(SpatialFrequencyResponse,    0xa20c, UNDEF,         None::<u32>,       true,      EXIF),
 	(FocalPlaneXResolution,       0xa20e, RATIONAL64U,   Some::<u32>(1),    true,      EXIF),
 	(FocalPlaneYResolution,       0xa20f, RATIONAL64U,   Some::<u32>(1),    true,      EXIF),
 	(FocalPlaneResolutionUnit,    0xa210, INT16U,        Some::<u32>(1),    true,      EXIF),
@@ -658,8 +809,8 @@ build_tag_enum![
 
 	(SensingMethod,               0xa217, INT16U,        Some::<u32>(1),    true,      EXIF),
 
-	(FileSource,                  0xa300, UNDEF,         None::<u32>,       true,      EXIF),
-	(SceneType,                   0xa301, UNDEF,         None::<u32>,       true,      EXIF),
+	(FileSource,                  0xa300, UNDEF,         Some::<u32>(1),    true,      EXIF),
+	(SceneType,                   0xa301, UNDEF,         Some::<u32>(1),    true,      EXIF),
 	(CFAPattern,                  0xa302, UNDEF,         None::<u32>,       true,      EXIF),
 
 	(CustomRendered,              0xa401, INT16U,        Some::<u32>(1),    true,      EXIF),
@@ -672,7 +823,7 @@ build_tag_enum![
 	(Contrast,                    0xa408, INT16U,        Some::<u32>(1),    true,      EXIF),
 	(Saturation,                  0xa409, INT16U,        Some::<u32>(1),    true,      EXIF),
 	(Sharpness,                   0xa40a, INT16U,        Some::<u32>(1),    true,      EXIF),
-	(DeviceSettingDescription,    0xa40b, UNDEF,         None::<u32>,       false,     EXIF),
+	(DeviceSettingDescription,    0xa40b, UNDEF,         None::<u32>,       true,      EXIF),
 
 	(SubjectDistanceRange,        0xa40c, INT16U,        Some::<u32>(1),    true,      EXIF),
 
@@ -726,4 +877,39 @@ impl ExifTag
 			_ => TagType::VALUE
 		}
 	}
+
+	/// Gets the identity of the tag, consisting of its hex value and group.
+	/// Unlike the derived `PartialEq`, which also compares the values, two
+	/// tags with the same identity describe the same entry in the metadata,
+	/// so the identity can be used as key for e.g. a `HashMap` or `HashSet`.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::exif_tag::ExifTag;
+	/// use little_exif::ifd::ExifTagGroup;
+	///
+	/// assert_eq!(ExifTag::ISO(vec![100]).identity(), (0x8827, ExifTagGroup::EXIF));
+	/// ```
+	pub fn
+	identity
+	(
+		&self
+	)
+	-> (u16, ExifTagGroup)
+	{
+		(self.as_u16(), self.get_group())
+	}
+
+	/// Checks if two tags have the same identity (see `identity`), 
+	/// regardless of their values
+	pub fn
+	has_same_identity
+	(
+		&self,
+		other: &ExifTag
+	)
+	-> bool
+	{
+		self.identity() == other.identity()
+	}
 }
\ No newline at end of file
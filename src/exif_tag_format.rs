@@ -31,7 +31,8 @@ ExifTagFormat
 	INT32S,         // signed long          int32s
 	RATIONAL64S,    // signed rational      rational64s
 	FLOAT,          // single float         float
-	DOUBLE          // double float         double
+	DOUBLE,         // double float         double
+	IFD             // IFD offset           ifd (TIFF type 13, a LONG pointing to an IFD)
 }
 
 impl 
@@ -59,6 +60,7 @@ ExifTagFormat
 			ExifTagFormat::RATIONAL64S  => 0x000a,
 			ExifTagFormat::FLOAT        => 0x000b,
 			ExifTagFormat::DOUBLE       => 0x000c,
+			ExifTagFormat::IFD          => 0x000d,
 		}
 	}
 
@@ -83,6 +85,7 @@ ExifTagFormat
 			0x000a  => Some(ExifTagFormat::RATIONAL64S),
 			0x000b  => Some(ExifTagFormat::FLOAT),
 			0x000c  => Some(ExifTagFormat::DOUBLE),
+			0x000d  => Some(ExifTagFormat::IFD),
 			_       => None,
 		}
 	}
@@ -109,6 +112,7 @@ ExifTagFormat
 			0x000a  => 8,
 			0x000b  => 4,
 			0x000c  => 8,
+			0x000d  => 4,
 			_       => panic!("Invalid format value for ExifTagFormat!"),
 		}
 	}
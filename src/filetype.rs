@@ -2,6 +2,7 @@
 // See https://github.com/TechnikTobi/little_exif#license for licensing details
 
 use std::str::FromStr;
+#[cfg(feature = "std_fs")]
 use std::path::Path;
 
 use crate::general_file_io::*;
@@ -13,6 +14,7 @@ FileExtension
 {
 	PNG  {as_zTXt_chunk: bool},
 	JPEG,
+	JP2,
 	JXL,
 	TIFF,
 	WEBP
@@ -36,6 +38,7 @@ FileExtension
 		{
 			"jpg"   => Ok(FileExtension::JPEG),
 			"jpeg"  => Ok(FileExtension::JPEG),
+			"jp2"   => Ok(FileExtension::JP2),
 			"jxl"   => Ok(FileExtension::JXL),
 			"png"   => Ok(FileExtension::PNG{ as_zTXt_chunk: true}),
 			"tif"   => Ok(FileExtension::TIFF),
@@ -48,6 +51,7 @@ FileExtension
 
 
 
+#[cfg(feature = "std_fs")]
 pub fn
 get_file_type
 (
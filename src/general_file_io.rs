@@ -2,10 +2,10 @@
 // See https://github.com/TechnikTobi/little_exif#license for licensing details
 
 pub(crate) const NEWLINE:                u8      = 0x0a;
-pub(crate) const SPACE:                  u8      = 0x20;
 pub(crate) const EXIF:                   [u8; 4] = [0x45, 0x78, 0x69, 0x66];
 pub(crate) const EXIF_HEADER:            [u8; 6] = [0x45, 0x78, 0x69, 0x66, 0x00, 0x00];
 
+#[cfg(feature = "std_fs")]
 macro_rules! perform_file_action {
 	( 
 		$action: expr
@@ -31,10 +31,20 @@ macro_rules! io_error {
 	};
 }
 
+// The functions operating on paths are only available with the "std_fs"
+// feature, so that the crate can be used on targets without a file system
+// (e.g. wasm32-unknown-unknown) via the in-memory API
+
+#[cfg(feature = "std_fs")]
 use std::fs::File;
+#[cfg(feature = "std_fs")]
 use std::fs::OpenOptions;
+#[cfg(feature = "std_fs")]
 use std::path::Path;
+#[cfg(feature = "std_fs")]
+use std::path::PathBuf;
 
+#[cfg(feature = "std_fs")]
 pub(crate) fn
 open_read_file
 (
@@ -53,6 +63,7 @@ open_read_file
 		.open(path)
 }
 
+#[cfg(feature = "std_fs")]
 pub(crate) fn
 open_write_file
 (
@@ -71,5 +82,162 @@ open_write_file
 		.open(path)
 }
 
+/// Gets the path of the temporary file used by `write_atomically`, which is
+/// located in the same directory as the given file so that it can be renamed
+/// without copying the data across file systems
+#[cfg(feature = "std_fs")]
+fn
+temporary_path
+(
+	path: &Path
+)
+-> Result<PathBuf, std::io::Error>
+{
+	let file_name = match path.file_name()
+	{
+		Some(file_name) => file_name.to_string_lossy(),
+		None            => return io_error!(InvalidInput, "Can't write file - Path has no file name!"),
+	};
+
+	return Ok(path.with_file_name(format!(".{}.little_exif.tmp", file_name)));
+}
+
+/// Performs a write operation on a copy of the file at the given path and
+/// replaces the original file by renaming the copy over it once the write
+/// was successful. If the write fails (or the process gets killed in the
+/// middle of it), the original file is left untouched and the copy is
+/// removed (or left behind, respectively).
+#[cfg(feature = "std_fs")]
+pub(crate) fn
+write_atomically
+<F: FnOnce(&Path) -> Result<(), std::io::Error>>
+(
+	path:  &Path,
+	write: F
+)
+-> Result<(), std::io::Error>
+{
+	return replace_atomically(path, true, write);
+}
+
+/// Same as `write_atomically`, but starts with an empty temporary file
+/// instead of a copy of the original, for writers that produce the whole
+/// file themselves. Only the permissions of the original are taken over.
+#[cfg(feature = "std_fs")]
+pub(crate) fn
+write_atomically_from_scratch
+<F: FnOnce(&Path) -> Result<(), std::io::Error>>
+(
+	path:  &Path,
+	write: F
+)
+-> Result<(), std::io::Error>
+{
+	return replace_atomically(path, false, write);
+}
+
+#[cfg(feature = "std_fs")]
+fn
+replace_atomically
+<F: FnOnce(&Path) -> Result<(), std::io::Error>>
+(
+	path:          &Path,
+	copy_original: bool,
+	write:         F
+)
+-> Result<(), std::io::Error>
+{
+	if !path.exists()
+	{
+		return io_error!(NotFound, "Can't open file - File does not exist!");
+	}
+
+	let temporary_path = temporary_path(path)?;
+	let prepare_result = if copy_original
+	{
+		std::fs::copy(path, &temporary_path).map(|_| ())
+	}
+	else
+	{
+		File::create(&temporary_path)
+			.and_then(|file| file.set_permissions(std::fs::metadata(path)?.permissions()))
+	};
+
+	// Make sure that the data has actually been written to disk before the
+	// original gets replaced
+	let write_result = prepare_result
+		.and_then(|_| write(&temporary_path))
+		.and_then(|_| OpenOptions::new().write(true).open(&temporary_path)?.sync_all())
+		.and_then(|_| std::fs::rename(&temporary_path, path));
+
+	if write_result.is_err()
+	{
+		let _ = std::fs::remove_file(&temporary_path);
+	}
+
+	return write_result;
+}
+
+#[cfg(feature = "std_fs")]
 pub(crate) use perform_file_action;
-pub(crate) use io_error;
\ No newline at end of file
+pub(crate) use io_error;
+
+#[cfg(test)]
+#[cfg(feature = "std_fs")]
+mod tests
+{
+	use std::io::Write;
+	use std::path::Path;
+
+	use super::open_write_file;
+	use super::temporary_path;
+	use super::write_atomically;
+	use super::write_atomically_from_scratch;
+
+	#[test]
+	fn
+	failing_write_keeps_original_file()
+	{
+		let path = Path::new("tests/sample2_copy_atomic.png");
+		let _ = std::fs::remove_file(path);
+		std::fs::copy("tests/sample2.png", path).unwrap();
+		let original_data = std::fs::read(path).unwrap();
+
+		// A writer that fails after having written (and truncated) parts of
+		// the file, e.g. due to a full disk
+		let result = write_atomically(path, |temporary_path| {
+			let mut file = open_write_file(temporary_path)?;
+			file.set_len(10)?;
+			file.write_all(b"corrupt")?;
+			return Err(std::io::Error::new(std::io::ErrorKind::Other, "Disk is full"));
+		});
+
+		let error = result.unwrap_err();
+		assert_eq!(error.kind(), std::io::ErrorKind::Other);
+		assert_eq!(error.to_string(), "Disk is full");
+		assert_eq!(std::fs::read(path).unwrap(), original_data);
+		assert!(!temporary_path(path).unwrap().exists());
+
+		// A successful write replaces the file
+		write_atomically(path, |temporary_path| {
+			std::fs::write(temporary_path, b"new data")
+		}).unwrap();
+		assert_eq!(std::fs::read(path).unwrap(), b"new data");
+		assert!(!temporary_path(path).unwrap().exists());
+	}
+	#[test]
+	fn
+	write_from_scratch_starts_with_empty_file()
+	{
+		let path = Path::new("tests/sample2_copy_scratch.png");
+		let _ = std::fs::remove_file(path);
+		std::fs::copy("tests/sample2.png", path).unwrap();
+
+		write_atomically_from_scratch(path, |temporary_path| {
+			assert_eq!(std::fs::metadata(temporary_path)?.len(), 0);
+			return std::fs::write(temporary_path, b"new data");
+		}).unwrap();
+		assert_eq!(std::fs::read(path).unwrap(), b"new data");
+		assert!(!temporary_path(path).unwrap().exists());
+	}
+}
\ No newline at end of file
@@ -5,9 +5,11 @@ pub mod get;
 pub mod set;
 
 use core::panic;
+use std::collections::HashMap;
 use std::io::Cursor;
 use std::io::Read;
 use std::io::Seek;
+use std::io::SeekFrom;
 use std::vec;
 
 use crate::endian::*;
@@ -16,6 +18,11 @@ use crate::exif_tag::ExifTag;
 use crate::exif_tag::TagType;
 use crate::exif_tag_format::ExifTagFormat;
 use crate::general_file_io::io_error;
+use crate::metadata::error::InvalidExifData;
+use crate::metadata::options::ParseStrictness;
+use crate::metadata::options::ReadOptions;
+use crate::metadata::warnings::ParseWarning;
+use crate::metadata::warnings::ParseWarningKind;
 use crate::metadata::Metadata;
 use crate::u8conversion::from_u8_vec_macro;
 use crate::u8conversion::to_u8_vec_macro;
@@ -38,7 +45,7 @@ const IFD_END_NO_LINK:  [u8; 4] = [0x00, 0x00, 0x00, 0x00];
 /// Other IFDs, like e.g. the ExifIFD, are linked via offset tags (in case of 
 /// the ExifIFD offset: 0x8769) that are located in the respective generic IFD 
 /// (most of them in IFD0).
-#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd)]
 #[allow(non_snake_case, non_camel_case_types)]
 pub enum
 ExifTagGroup
@@ -60,6 +67,12 @@ ImageFileDirectory
 	tags:                      Vec<ExifTag>,
 	ifd_type:                  ExifTagGroup,
 	belongs_to_generic_ifd_nr: u32,
+
+	/// The original byte counts of decoded `STRING` tags that were padded
+	/// with more than one trailing NUL. As the NULs are trimmed from the
+	/// value, this is needed to re-encode these tags exactly as they were
+	/// as long as they don't get changed.
+	string_byte_counts:        Vec<(u16, u32)>,
 }
 
 impl
@@ -75,7 +88,7 @@ ImageFileDirectory
 	)
 	-> Self
 	{
-		ImageFileDirectory { tags: tags, ifd_type: group, belongs_to_generic_ifd_nr: nr }
+		ImageFileDirectory { tags: tags, ifd_type: group, belongs_to_generic_ifd_nr: nr, string_byte_counts: Vec::new() }
 	}
 
 	/// Sorts the tags according to their hex value
@@ -104,12 +117,25 @@ ImageFileDirectory
 		group:               &    ExifTagGroup,
 		generic_ifd_nr:           u32,                                          // Reuse value for recursive calls; only gets incremented by caller
 		insert_into:         &mut Vec<ImageFileDirectory>,                      // Stays the same for all calls to this function while decoding
+		remaining_ifd_count: &mut usize,                                        // Stays the same for all calls to this function while decoding
+		options:             &    ReadOptions,
+		warnings:            &mut Vec<ParseWarning>,                            // Stays the same for all calls to this function while decoding
+		mut subifd_offsets:       Option<&mut Vec<(ExifTagGroup, u32)>>,        // If given, SubIFDs are not decoded but their offsets are collected
 	)
 	-> Result<Option<u32>, std::io::Error>
 	{
 		////////////////////////////////////////////////////////////////////////
 		// PREPARATION 
 
+		// Bound the total amount of IFDs that get decoded. Besides limiting
+		// the work for untrusted input, this also stops IFDs that (directly
+		// or indirectly) link to themselves
+		if *remaining_ifd_count == 0
+		{
+			return Err(InvalidExifData::io_error("Exceeded maximum number of IFDs to decode!".to_string()));
+		}
+		*remaining_ifd_count -= 1;
+
 		// Backup the entry position where this IFD started
 		let data_cursor_entry_position = data_cursor.position();
 
@@ -122,7 +148,7 @@ ImageFileDirectory
 		// The first two bytes give us the number of entries in this IFD
 		let mut number_of_entries_buffer = vec![0u8; 2];
 		data_cursor.read_exact(&mut number_of_entries_buffer)?;
-		let number_of_entries = from_u8_vec_macro!(u16, &number_of_entries_buffer.to_vec(), endian);
+		let mut number_of_entries = from_u8_vec_macro!(u16, &number_of_entries_buffer.to_vec(), endian);
 
 		// Check that there is enough data to unpack
 		let required = 0
@@ -135,7 +161,18 @@ ImageFileDirectory
 
 		if required > available
 		{
-			return io_error!(Other, format!("Not enough data to decode IFD! Required: {} Available: {}", required, available));
+			if options.strictness == ParseStrictness::Strict
+			{
+				return Err(InvalidExifData::io_error(format!("Not enough data to decode {:?} IFD! Required: {} Available: {}", group, required, available)));
+			}
+
+			// Decode only the entries that are fully available
+			let available_entries = (available.saturating_sub(2) / IFD_ENTRY_LENGTH as usize) as u16;
+			warnings.push(ParseWarning::new(
+				ParseWarningKind::TruncatedData,
+				format!("Not enough data to decode {:?} IFD with {} entries - Decoding only the first {}", group, number_of_entries, available_entries)
+			));
+			number_of_entries = available_entries;
 		}
 
 		// Temporarily storing specific tags that have been decoded
@@ -153,6 +190,16 @@ ImageFileDirectory
 		// Storing all tags while decoding
 		let mut tags = Vec::new();
 
+		// Storing the SubIFDs decoded via the IFD offset tags separately, so
+		// that they can be replaced in case such a tag occurs more than once
+		let mut subifds_per_tag: Vec<(u16, Vec<ImageFileDirectory>)> = Vec::new();
+
+		// The hex values of the tags seen so far, for detecting duplicates
+		let mut seen_hex_tags = Vec::new();
+
+		// The original byte counts of padded strings, see `string_byte_counts`
+		let mut string_byte_counts = Vec::new();
+
 		// loop through the entries - assumes that the value stored in
 		// `number_of_entries` is correct
 		for _ in 0..number_of_entries
@@ -166,6 +213,31 @@ ImageFileDirectory
 			let hex_format           = from_u8_vec_macro!(u16, &entry_buffer[2..4].to_vec(), endian);
 			let hex_component_number = from_u8_vec_macro!(u32, &entry_buffer[4..8].to_vec(), endian);
 
+			// Check if this tag already occurred in this IFD. If so, only
+			// the last occurrence is kept in lenient mode
+			if seen_hex_tags.contains(&hex_tag)
+			{
+				let entry_offset = data_cursor.position() - data_begin_position - IFD_ENTRY_LENGTH as u64;
+
+				if options.strictness == ParseStrictness::Strict
+				{
+					return Err(InvalidExifData::io_error(format!("Duplicate tag 0x{:04x} in {:?} IFD at offset {}", hex_tag, group, entry_offset)));
+				}
+
+				warnings.push(ParseWarning::new(
+					ParseWarningKind::DuplicateTag,
+					format!("Duplicate tag 0x{:04x} in {:?} IFD at offset {} - Keeping the last occurrence", hex_tag, group, entry_offset)
+				));
+
+				tags.retain(|tag: &ExifTag| tag.as_u16() != hex_tag);
+				subifds_per_tag.retain(|(subifd_hex_tag, _)| *subifd_hex_tag != hex_tag);
+				string_byte_counts.retain(|(string_hex_tag, _)| *string_hex_tag != hex_tag);
+			}
+			else
+			{
+				seen_hex_tags.push(hex_tag);
+			}
+
 			// Decode the format
 			// TODO: What to do in case these two differ but the given format
 			// can be casted into the expected one, e.g. R64U to R64S?
@@ -179,6 +251,15 @@ ImageFileDirectory
 				return io_error!(Other, format!("Illegal format value: {}", hex_format));
 			}
 
+			// Skip tags that are not on the allowlist without reading their
+			// data - except for IFD offset tags, which are needed to get to
+			// the (allowed) tags of the SubIFDs
+			if !options.is_tag_allowed(hex_tag, group)
+			&& !matches!(ExifTag::from_u16(hex_tag, group).map(|tag| tag.get_tag_type()), Ok(TagType::IFD_OFFSET(_)))
+			{
+				continue;
+			}
+
 			// Calculating the number of required bytes to determine if next
 			// 4 bytes are data or an offset to data
 			// Note: It is expected that the format here is "correct" in the
@@ -201,11 +282,27 @@ ImageFileDirectory
 
 				// Read the raw data
 				let mut raw_data_buffer = vec![0u8; byte_count as usize];
-				data_cursor.read_exact(&mut raw_data_buffer)?;
-				raw_data = raw_data_buffer.to_vec();
+				let read_result = data_cursor.read_exact(&mut raw_data_buffer);
 			
 				// Rewind the cursor to the start of the next entry
 				data_cursor.set_position(backup_position);
+
+				// In lenient mode, tags whose data lies (partially) beyond
+				// the end of the data are skipped
+				if let Err(error) = read_result
+				{
+					if options.strictness == ParseStrictness::Strict
+					{
+						return Err(InvalidExifData::io_error(format!("Data of tag 0x{:04x} in {:?} IFD exceeds available data: {}", hex_tag, group, error)));
+					}
+
+					warnings.push(ParseWarning::new(
+						ParseWarningKind::TruncatedData,
+						format!("Data of tag 0x{:04x} in {:?} IFD exceeds available data - Skipping tag", hex_tag, group)
+					));
+					continue;
+				}
+				raw_data = raw_data_buffer.to_vec();
 			}
 			else
 			{
@@ -214,46 +311,72 @@ ImageFileDirectory
 				raw_data = entry_buffer[8..(8+byte_count as usize)].to_vec();
 			}
 
-			// Try to get the tag via its hex value
-			let tag_result = ExifTag::from_u16(hex_tag, group);
+			// Get the format the tag should have according to the spec
+			let expected_format = ExifTag::expected_format(hex_tag, group);
 
 			// Start of by checking if this is an unknown tag
-			if tag_result.is_err()
+			if expected_format.is_none()
 			{
 				// Note: `from_u16_with_data` can NOT be called initially due
 				// to some possible conversion of data needed, e.g. INT16U to
 				// INT32U, which is not accounted for yet at this stage
-				tags.push(ExifTag::from_u16_with_data(
+				let tag = ExifTag::from_u16_with_data(
 					hex_tag, 
 					&format, 
 					&raw_data, 
 					&endian, 
 					group
-				).unwrap());
+				).unwrap();
+				tags.push(handle_string_padding(tag, &raw_data, endian, options.trim_nuls, &mut string_byte_counts));
 				continue;
 			}
 
-			// We can now safely unwrap the result as it can't be an error
-			let mut tag = tag_result.unwrap();
+			// If the format is as expected, the tag can be constructed with
+			// its value right away. Otherwise, only an empty tag is created,
+			// whose value is set below while converting the format.
+			// We can safely unwrap here as the hex value is known
+			let format_matches = expected_format.as_ref() == Some(&format);
+			let mut tag = if format_matches
+			{
+				ExifTag::from_u16_with_data(hex_tag, &format, &raw_data, endian, group).unwrap()
+			}
+			else
+			{
+				ExifTag::from_u16(hex_tag, group).unwrap()
+			};
 
 			// If this is an IFD offset tag, perform a recursive call
 			if let TagType::IFD_OFFSET(subifd_group) = tag.get_tag_type()
 			{
 				// Compute the offset to the SubIFD and save the current position
 				let offset          = from_u8_vec_macro!(u32, &raw_data, endian) as usize;
+
+				// Only collect the offset if the SubIFD is decoded later on
+				if let Some(subifd_offsets) = subifd_offsets.as_deref_mut()
+				{
+					subifd_offsets.retain(|(collected_group, _)| *collected_group != subifd_group);
+					subifd_offsets.push((subifd_group, offset as u32));
+					continue;
+				}
+
 				let backup_position = data_cursor.position();
 
 				// Go to the SubIFD offset and decode that
 				data_cursor.set_position(data_begin_position);
 				data_cursor.seek(std::io::SeekFrom::Current(offset as i64))?;
 
+				let mut subifds = Vec::new();
 				let subifd_decode_result = Self::decode_ifd(
 					data_cursor,
 					data_begin_position,
 					endian,
 					&subifd_group,
 					generic_ifd_nr,
-					insert_into,
+					&mut subifds,
+					remaining_ifd_count,
+					options,
+					warnings,
+					None,
 				);
 
 				// Check that this actually worked
@@ -275,25 +398,35 @@ ImageFileDirectory
 					// So, for now we just assume that `subifd_result` is not
 					// of relevance until evidence suggests otherwise.
 					
+					subifds_per_tag.push((hex_tag, subifds));
 					data_cursor.set_position(backup_position);
 					continue;
 				}
 				else
 				{
-					return io_error!(Other, format!("Could not decode SubIFD {:?}:\n  {}", subifd_group, subifd_decode_result.err().unwrap()));
+					// Errors that are reported to the caller are kept as is
+					let error = subifd_decode_result.err().unwrap();
+					if InvalidExifData::from_io_error(&error).is_some()
+					{
+						return Err(error);
+					}
+					return io_error!(Other, format!("Could not decode SubIFD {:?}:\n  {}", subifd_group, error));
 				}
 			}
 
 			// At this point we check if the format is actually what we expect
 			// it to be and convert it if possible
-			tag = decode_tag_with_format_exceptions(
-				&tag,
-				format,
-				&raw_data,
-				endian,
-				hex_tag,
-				group
-			)?;
+			if !format_matches
+			{
+				tag = decode_tag_with_format_exceptions(
+					&tag,
+					format,
+					&raw_data,
+					endian,
+					hex_tag,
+					group
+				)?;
+			}
 
 			// Now we have at least confirmed that the format is ok (or has
 			// been corrected). Next, we need to differ between the two other
@@ -322,7 +455,7 @@ ImageFileDirectory
 			else // TagType::VALUE
 			{
 				// Simply push this tag onto the vector
-				tags.push(tag);
+				tags.push(handle_string_padding(tag, &raw_data, endian, options.trim_nuls, &mut string_byte_counts));
 			}
 
 		} // end of for-loop
@@ -350,24 +483,29 @@ ImageFileDirectory
 			{
 				let backup_position = data_cursor.position();
 
-				let mut strip_data = Vec::new();
-
 				// Gather the data from the offsets
-				for (offset, byte_count) in offsets.iter().zip(byte_counts.iter())
+				match read_strips(data_cursor, data_begin_position, &offsets, &byte_counts)
 				{
-					data_cursor.set_position(data_begin_position);
-					data_cursor.seek(std::io::SeekFrom::Current(*offset as i64))?;
+					Ok(strip_data) => {
+						// Push StripOffset tag to tags vector
+						tags.push(ExifTag::StripOffsets(Vec::new(), strip_data));
 
-					let mut data_buffer = vec![0u8; *byte_count as usize];
-					data_cursor.read_exact(&mut data_buffer)?;
-					strip_data.push(data_buffer);
-				}
+						// Push StripByteCounts tag to tags vector
+						tags.push(ExifTag::StripByteCounts(byte_counts));
+					},
 
-				// Push StripOffset tag to tags vector
-				tags.push(ExifTag::StripOffsets(Vec::new(), strip_data));
+					Err(error) => {
+						if options.strictness == ParseStrictness::Strict
+						{
+							return Err(InvalidExifData::io_error(format!("Strip data in {:?} IFD exceeds available data: {}", group, error)));
+						}
 
-				// Push StripByteCounts tag to tags vector
-				tags.push(ExifTag::StripByteCounts(byte_counts));
+						warnings.push(ParseWarning::new(
+							ParseWarningKind::TruncatedData,
+							format!("Strip data in {:?} IFD exceeds available data - Skipping StripOffsets and StripByteCounts", group)
+						));
+					},
+				}
 
 				// Restore backup position
 				data_cursor.set_position(backup_position);
@@ -398,17 +536,34 @@ ImageFileDirectory
 					// Gather the data at the offset
 					data_cursor.set_position(data_begin_position);
 					data_cursor.seek(std::io::SeekFrom::Current(offset[0] as i64))?;
-					data_cursor.read_exact(&mut thumbnail_data)?;
 
-					// Push ThumbnailOffset tag to tags vector
-					tags.push(ExifTag::ThumbnailOffset(Vec::new(), thumbnail_data));
+					if let Err(error) = data_cursor.read_exact(&mut thumbnail_data)
+					{
+						if options.strictness == ParseStrictness::Strict
+						{
+							return Err(InvalidExifData::io_error(format!("Thumbnail data in {:?} IFD exceeds available data: {}", group, error)));
+						}
+
+						warnings.push(ParseWarning::new(
+							ParseWarningKind::TruncatedData,
+							format!("Thumbnail data in {:?} IFD exceeds available data - Skipping thumbnail", group)
+						));
+					}
+					else
+					{
+						// Push ThumbnailOffset tag to tags vector
+						tags.push(ExifTag::ThumbnailOffset(Vec::new(), thumbnail_data));
 
-					// Also push ThumbnailLength tag to tags vector
-					tags.push(ExifTag::ThumbnailLength(length));
+						// Also push ThumbnailLength tag to tags vector
+						tags.push(ExifTag::ThumbnailLength(length));
+					}
 				}
 				else
 				{
-					eprintln!("WARNING: Can't decode thumbnail! The ThumbnailOffset and ThumbnailLength tags are expected to contain exactly 1 INT32U value. However, they have {} and {} values.", offset.len(), length.len());
+					warnings.push(ParseWarning::new(
+						ParseWarningKind::InvalidData,
+						format!("Can't decode thumbnail! The ThumbnailOffset and ThumbnailLength tags are expected to contain exactly 1 INT32U value. However, they have {} and {} values.", offset.len(), length.len())
+					));
 				}
 
 				// Restore backup position
@@ -421,11 +576,16 @@ ImageFileDirectory
 		// At this point we are done with decoding the tags of this IFD and its
 		// associated SubIFDs! 
 
-		// Put the current IFD into the given, referenced vector
+		// Put the SubIFDs and the current IFD into the given, referenced vector
+		for (_, subifds) in subifds_per_tag
+		{
+			insert_into.extend(subifds);
+		}
 		insert_into.push(ImageFileDirectory { 
 			tags: tags, 
 			ifd_type: *group, 
-			belongs_to_generic_ifd_nr: generic_ifd_nr
+			belongs_to_generic_ifd_nr: generic_ifd_nr,
+			string_byte_counts: string_byte_counts,
 		});
 
 		// Read in the link to the next IFD and check if its zero
@@ -468,8 +628,11 @@ ImageFileDirectory
 	{
 
 		// Store all relevant tags (IFD tags + offset tags) in a temporary 
-		// location and sort them there
-		let all_relevant_tags = self.tags.iter().chain(ifds_with_offset_info_only
+		// location and sort them there. Offset tags to SubIFDs that were set
+		// by the user are replaced by the ones from the offset information
+		let all_relevant_tags = self.tags.iter().filter(|tag| 
+			Self::get_ifd_type_for_offset_tag(tag).is_none()
+		).chain(ifds_with_offset_info_only
 			.iter()
 			.filter(|ifd| 
 				ifd.get_generic_ifd_nr() == self.get_generic_ifd_nr() &&
@@ -524,7 +687,19 @@ ImageFileDirectory
 			let value = match tag.get_tag_type()
 			{
 				TagType::VALUE => {
-					tag.value_as_u8_vec(&data.get_endian())
+					let mut value = tag.value_as_u8_vec(&data.get_endian());
+
+					// Restore the padding of strings that were decoded with
+					// more than one trailing NUL
+					if let Some((_, byte_count)) = self.string_byte_counts.iter().find(|(hex_tag, _)| *hex_tag == tag.as_u16())
+					{
+						if *byte_count as usize > value.len()
+						{
+							value.resize(*byte_count as usize, 0x00);
+						}
+					}
+
+					value
 				},
 
 				TagType::DATA_OFFSET(_) => {
@@ -595,55 +770,21 @@ ImageFileDirectory
 			ifd_offset_area.extend(vec![0u8; alignment_count as usize]);
 			
 			
-			// Add Tag & Data Format /                                          2 + 2 bytes
-			encode_vec.extend(to_u8_vec_macro!(u16, &tag.as_u16(),          &data.get_endian()).iter());
-			encode_vec.extend(to_u8_vec_macro!(u16, &tag.format().as_u16(), &data.get_endian()).iter());
-
-			// Add number of components /                                       4 bytes
-			let number_of_components: u32 = tag.number_of_components();
-			encode_vec.extend(to_u8_vec_macro!(u32, &number_of_components, &data.get_endian()).iter());
+			// Encode the entry itself; data that does not fit into its 4 byte
+			// value section gets stored in the offset area
+			let (entry, overflow) = tag.encode_entry_with_value(value, &data.get_endian(), *current_offset);
+			encode_vec.extend(entry);
 
-			// Optional string padding (i.e. string is shorter than it should be)
-			let mut string_padding: Vec<u8> = Vec::new();
-			if tag.is_string()
+			if let Some(overflow) = overflow
 			{
-				for _ in 0..(number_of_components - value.len() as u32)
-				{
-					string_padding.push(0x00);
-				}
-			}
-
-			// Add offset or value /                                            4 bytes
-			// Depending on the amount of data, either put it directly into
-			// next 4 bytes or write an offset where the data can be found 
-			let byte_count: u32 = number_of_components * tag.format().bytes_per_component();
-			if byte_count > 4
-			{
-				encode_vec.extend(to_u8_vec_macro!(u32, current_offset, &data.get_endian()).iter());
-				ifd_offset_area.extend(value.iter());
-				ifd_offset_area.extend(string_padding.iter());
-
-				*current_offset += byte_count;
+				*current_offset += overflow.len() as u32;
+				ifd_offset_area.extend(overflow);
 
 				// Re-align 
 				let alignment_count = (4 - *current_offset % 4) % 4;
 				*current_offset += alignment_count;
 				ifd_offset_area.extend(vec![0u8; alignment_count as usize]);
 			}
-			else
-			{
-				let pre_length = encode_vec.len();
-
-				encode_vec.extend(value.iter());
-				encode_vec.extend(string_padding.iter());
-
-				let post_length = encode_vec.len();
-
-				// Make sure that this area is indeed *exactly* 4 bytes long
-				for _ in 0..(4-(post_length - pre_length) ) {
-					encode_vec.push(0x00);
-				}
-			}
 		}
 
 		// Write link and offset data
@@ -652,4 +793,198 @@ ImageFileDirectory
 
 		return Ok(((ifd_offset + 2 + IFD_ENTRY_LENGTH * count_entries as u32) as u64, ifd_offset_vec));
 	}
-}
\ No newline at end of file
+}
+
+/// Reads the data of the strips given by their offsets (relative to the
+/// start of the TIFF data) and byte counts. Strips with the same offset and
+/// byte count - as written e.g. by some scanners for blank strips - are only
+/// read once from the data. Each duplicate still gets its own copy of the
+/// bytes, as `ExifTag::StripOffsets` stores a separate buffer per strip.
+fn
+read_strips
+<T: Read + Seek>
+(
+	data_cursor:         &mut T,
+	data_begin_position: u64,
+	offsets:             &[u32],
+	byte_counts:         &[u32]
+)
+-> Result<Vec<Vec<u8>>, std::io::Error>
+{
+	let mut strip_data:    Vec<Vec<u8>>               = Vec::new();
+	let mut strip_indices: HashMap<(u32, u32), usize> = HashMap::new();
+
+	for (offset, byte_count) in offsets.iter().zip(byte_counts.iter())
+	{
+		if let Some(index) = strip_indices.get(&(*offset, *byte_count))
+		{
+			let duplicate = strip_data[*index].clone();
+			strip_data.push(duplicate);
+			continue;
+		}
+
+		data_cursor.seek(SeekFrom::Start(data_begin_position + *offset as u64))?;
+
+		let mut data_buffer = vec![0u8; *byte_count as usize];
+		data_cursor.read_exact(&mut data_buffer)?;
+
+		strip_indices.insert((*offset, *byte_count), strip_data.len());
+		strip_data.push(data_buffer);
+	}
+
+	return Ok(strip_data);
+}
+
+/// Handles the trailing NULs of a decoded `STRING` tag that are not part of
+/// its (trimmed) value: Either the original byte count gets stored for
+/// re-encoding the tag exactly as it was, or - if `trim_nuls` is false - the
+/// tag is decoded again, keeping the additional NULs as part of its value
+fn
+handle_string_padding
+(
+	tag:                ExifTag,
+	raw_data:           &Vec<u8>,
+	endian:             &Endian,
+	trim_nuls:          bool,
+	string_byte_counts: &mut Vec<(u16, u32)>
+)
+-> ExifTag
+{
+	if !tag.is_string() || raw_data.len() as u32 <= tag.number_of_components()
+	{
+		return tag;
+	}
+
+	if trim_nuls
+	{
+		string_byte_counts.push((tag.as_u16(), raw_data.len() as u32));
+		return tag;
+	}
+
+	return ExifTag::from_u16_with_untrimmed_data(
+		tag.as_u16(),
+		&ExifTagFormat::STRING,
+		raw_data,
+		endian,
+		&tag.get_group()
+	).unwrap_or(tag);
+}
+
+#[cfg(test)]
+mod tests
+{
+	use std::io::Cursor;
+	use std::io::Read;
+	use std::io::Seek;
+	use std::io::SeekFrom;
+
+	use crate::endian::Endian;
+	use crate::exif_tag::ExifTag;
+	use crate::filetype::FileExtension;
+	use crate::metadata::options::DEFAULT_MAX_IFD_COUNT;
+	use crate::metadata::raw::RawEntryIterator;
+	use crate::metadata::Metadata;
+	use crate::rational::uR64;
+
+	use super::read_strips;
+	use super::ExifTagGroup;
+
+	/// Counts the bytes read from the wrapped cursor
+	struct
+	CountingReader
+	{
+		cursor:     Cursor<Vec<u8>>,
+		bytes_read: usize,
+	}
+
+	impl
+	Read
+	for
+	CountingReader
+	{
+		fn
+		read
+		(
+			&mut self,
+			buffer: &mut [u8]
+		)
+		-> std::io::Result<usize>
+		{
+			let read_count = self.cursor.read(buffer)?;
+			self.bytes_read += read_count;
+			return Ok(read_count);
+		}
+	}
+
+	impl
+	Seek
+	for
+	CountingReader
+	{
+		fn
+		seek
+		(
+			&mut self,
+			position: SeekFrom
+		)
+		-> std::io::Result<u64>
+		{
+			return self.cursor.seek(position);
+		}
+	}
+
+	#[test]
+	fn
+	duplicate_strips_are_read_once()
+	{
+		// Three strips, the first and the last referencing the same data
+		let mut reader = CountingReader {
+			cursor:     Cursor::new(vec![0xff, 0xff, 1, 2, 3, 4, 5, 6, 7, 8]),
+			bytes_read: 0,
+		};
+
+		let strips = read_strips(&mut reader, 2, &[0, 4, 0], &[4, 4, 4]).unwrap();
+		assert_eq!(strips, vec![vec![1, 2, 3, 4], vec![5, 6, 7, 8], vec![1, 2, 3, 4]]);
+		assert_eq!(reader.bytes_read, 8);
+
+		// The same offset with a different byte count is a different strip
+		reader.bytes_read = 0;
+		let strips = read_strips(&mut reader, 2, &[0, 0], &[4, 2]).unwrap();
+		assert_eq!(strips, vec![vec![1, 2, 3, 4], vec![1, 2]]);
+		assert_eq!(reader.bytes_read, 6);
+
+		// Strips exceeding the data still fail
+		assert!(read_strips(&mut reader, 2, &[6], &[4]).is_err());
+	}
+
+	#[test]
+	fn
+	ifd_format_offsets_are_followed()
+	{
+		let mut metadata = Metadata::new();
+		metadata.set_endian(Endian::Big);
+		metadata.set_tag(ExifTag::ISO(vec![400]));
+		metadata.set_tag(ExifTag::GPSAltitude(vec![uR64 { nominator: 100, denominator: 1 }]));
+		metadata.set_tag(ExifTag::UnknownINT32U(vec![1234], 0xc000, ExifTagGroup::GENERIC));
+		let mut encoded = metadata.encode().unwrap();
+
+		// Declare the SubIFD offsets and the unknown tag as type 13 (IFD)
+		let positions = RawEntryIterator::new(&encoded, DEFAULT_MAX_IFD_COUNT).unwrap()
+			.filter(|(_, entry)| [0x8769, 0x8825, 0xc000].contains(&entry.tag))
+			.map(|(position, _)| position)
+			.collect::<Vec<usize>>();
+		assert_eq!(positions.len(), 3);
+		for position in positions
+		{
+			encoded[position + 2..position + 4].copy_from_slice(&[0x00, 0x0d]);
+		}
+
+		let metadata = Metadata::new_from_vec(&encoded, FileExtension::TIFF).unwrap();
+		assert_eq!(metadata.get_tag(&ExifTag::ISO(Vec::new())).next(),         Some(&ExifTag::ISO(vec![400])));
+		assert_eq!(metadata.get_tag(&ExifTag::GPSAltitude(Vec::new())).next(), Some(&ExifTag::GPSAltitude(vec![uR64 { nominator: 100, denominator: 1 }])));
+		assert_eq!(
+			metadata.get_tag_by_hex(0xc000, Some(ExifTagGroup::GENERIC)).next(),
+			Some(&ExifTag::UnknownINT32U(vec![1234], 0xc000, ExifTagGroup::GENERIC))
+		);
+	}
+}
@@ -25,6 +25,7 @@ ImageFileDirectory
 			eprintln!("Warning: The tag {:?} is set in an IFD that has not a matching group.", input_tag);
 		}
 		self.tags.retain(|tag| tag.as_u16() != input_tag.as_u16());
+		self.string_byte_counts.retain(|(hex_tag, _)| *hex_tag != input_tag.as_u16());
 		self.tags.push(input_tag);
 		self.sort_tags();
 	}
@@ -40,6 +41,7 @@ ImageFileDirectory
 	)
 	{
 		self.tags.retain(|tag| tag.as_u16() != input_tag.as_u16());
+		self.string_byte_counts.retain(|(hex_tag, _)| *hex_tag != input_tag.as_u16());
 		self.sort_tags();
 	}
 }
\ No newline at end of file
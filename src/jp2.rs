@@ -0,0 +1,423 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+// JPEG 2000 (JP2) files consist of boxes, similar to ISO BMFF. The EXIF data
+// is stored in a top-level UUID box (see `EXIF_UUID`), followed directly by
+// the TIFF structure. As this box is not nested, no other box lengths need
+// to be updated when it gets inserted, replaced or removed.
+
+#[cfg(feature = "std_fs")]
+use std::fs::File;
+#[cfg(feature = "std_fs")]
+use std::io::Read;
+#[cfg(feature = "std_fs")]
+use std::path::Path;
+
+use crate::endian::Endian;
+use crate::metadata::error::MetadataTooLarge;
+use crate::metadata::Metadata;
+use crate::u8conversion::*;
+use crate::general_file_io::*;
+use crate::util::insert_multiple_at;
+use crate::util::range_remove;
+
+pub(crate) const JP2_SIGNATURE: [u8; 12] = [
+	0x00, 0x00, 0x00, 0x0c,
+	0x6a, 0x50, 0x20, 0x20, // "jP  "
+	0x0d, 0x0a, 0x87, 0x0a
+];
+
+/// The start of a raw JPEG 2000 codestream (SOC and SIZ markers)
+pub(crate) const J2K_SIGNATURE: [u8; 4] = [0xff, 0x4f, 0xff, 0x51];
+
+/// "JpgTiffExif->JP2"
+pub(crate) const EXIF_UUID: [u8; 16] = [
+	0x4a, 0x70, 0x67, 0x54, 0x69, 0x66, 0x66, 0x45,
+	0x78, 0x69, 0x66, 0x2d, 0x3e, 0x4a, 0x50, 0x32
+];
+
+/// The UUID of XMP data, see the XMP specification part 3
+pub(crate) const XMP_UUID: [u8; 16] = [
+	0xbe, 0x7a, 0xcf, 0xcb, 0x97, 0xa9, 0x42, 0xe8,
+	0x9c, 0x71, 0x99, 0x94, 0x91, 0xe3, 0xaf, 0xac
+];
+
+#[non_exhaustive]
+struct Jp2BoxType;
+
+impl Jp2BoxType {
+	pub const JP2C: [u8; 4] = [0x6a, 0x70, 0x32, 0x63]; // "jp2c"
+	pub const UUID: [u8; 4] = [0x75, 0x75, 0x69, 0x64]; // "uuid"
+	pub const XML:  [u8; 4] = [0x78, 0x6d, 0x6c, 0x20]; // "xml "
+}
+
+/// The kind of metadata stored in a box, as far as little_exif cares
+#[derive(PartialEq)]
+enum
+Jp2MetadataBox
+{
+	Exif,
+	Xmp,
+	None,
+}
+
+/// The header of a box, consisting of
+/// - 4 bytes that give the box size n (where 0 means that the box extends to
+///   the end of the file and 1 that the size is given by an additional 8
+///   byte field after the type)
+/// - 4 bytes that give the box type (e.g. "jp2c" for the codestream)
+struct
+Jp2BoxHeader
+{
+	box_type:      [u8; 4],
+	header_length: usize,
+	box_length:    usize,
+}
+
+fn
+check_signature
+(
+	file_buffer: &[u8]
+)
+-> Result<(), std::io::Error>
+{
+	if file_buffer.starts_with(&J2K_SIGNATURE)
+	{
+		return io_error!(NotFound, "Raw JPEG 2000 codestream - No metadata!");
+	}
+
+	if !file_buffer.starts_with(&JP2_SIGNATURE)
+	{
+		return io_error!(Other, "This isn't JP2 data!");
+	}
+
+	return Ok(());
+}
+
+/// Reads the header of the box starting at the given position and checks
+/// that the box lies within the file buffer
+fn
+read_box_header
+(
+	file_buffer: &[u8],
+	position:    usize
+)
+-> Result<Jp2BoxHeader, std::io::Error>
+{
+	if position + 8 > file_buffer.len()
+	{
+		return io_error!(UnexpectedEof, "Incomplete JP2 box header!");
+	}
+
+	let length   = from_u8_vec_macro!(u32, &file_buffer[position..position+4].to_vec(), &Endian::Big) as usize;
+	let box_type = [
+		file_buffer[position+4],
+		file_buffer[position+5],
+		file_buffer[position+6],
+		file_buffer[position+7]
+	];
+
+	let (header_length, box_length) = match length
+	{
+		0 => (8, file_buffer.len() - position),
+		1 => {
+			if position + 16 > file_buffer.len()
+			{
+				return io_error!(UnexpectedEof, "Incomplete JP2 box header!");
+			}
+			let extended_length = from_u8_vec_macro!(u64, &file_buffer[position+8..position+16].to_vec(), &Endian::Big);
+			(16, usize::try_from(extended_length).unwrap_or(usize::MAX))
+		},
+		_ => (8, length),
+	};
+
+	// The extended length is a 64 bit value, so the box end may not even
+	// be representable
+	let box_end = position.checked_add(box_length);
+	if box_length < header_length || box_end.map_or(true, |box_end| box_end > file_buffer.len())
+	{
+		return io_error!(Other, format!("Invalid JP2 box length: {}", box_length));
+	}
+
+	return Ok(Jp2BoxHeader { box_type, header_length, box_length });
+}
+
+/// Determines whether the box starting at the given position holds EXIF or
+/// XMP data, based on its type and - for UUID boxes - its UUID
+fn
+classify_box
+(
+	file_buffer: &[u8],
+	position:    usize,
+	header:      &Jp2BoxHeader
+)
+-> Jp2MetadataBox
+{
+	let data = &file_buffer[position + header.header_length..position + header.box_length];
+
+	match header.box_type
+	{
+		Jp2BoxType::UUID if data.starts_with(&EXIF_UUID) => Jp2MetadataBox::Exif,
+		Jp2BoxType::UUID if data.starts_with(&XMP_UUID)  => Jp2MetadataBox::Xmp,
+		Jp2BoxType::XML                                   => Jp2MetadataBox::Xmp,
+		_                                                 => Jp2MetadataBox::None,
+	}
+}
+
+/// Gets the start position and header of the first EXIF UUID box
+fn
+find_exif_box
+(
+	file_buffer: &[u8]
+)
+-> Result<Option<(usize, Jp2BoxHeader)>, std::io::Error>
+{
+	check_signature(file_buffer)?;
+
+	let mut position = 0;
+	while position < file_buffer.len()
+	{
+		let header = read_box_header(file_buffer, position)?;
+		if classify_box(file_buffer, position, &header) == Jp2MetadataBox::Exif
+		{
+			return Ok(Some((position, header)));
+		}
+		position += header.box_length;
+	}
+
+	return Ok(None);
+}
+
+/// Removes all top-level boxes holding the given kinds of metadata
+fn
+clear_boxes
+(
+	file_buffer: &mut Vec<u8>,
+	box_kinds:   &[Jp2MetadataBox]
+)
+-> Result<(), std::io::Error>
+{
+	check_signature(file_buffer)?;
+
+	let mut position = 0;
+	while position < file_buffer.len()
+	{
+		let header = read_box_header(file_buffer, position)?;
+		if box_kinds.contains(&classify_box(file_buffer, position, &header))
+		{
+			range_remove(file_buffer, position, position + header.box_length);
+		}
+		else
+		{
+			position += header.box_length;
+		}
+	}
+
+	return Ok(());
+}
+
+
+
+pub(crate) fn
+clear_metadata
+(
+	file_buffer: &mut Vec<u8>
+)
+-> Result<(), std::io::Error>
+{
+	return clear_boxes(file_buffer, &[Jp2MetadataBox::Exif]);
+}
+
+/// Removes the EXIF as well as the XMP boxes
+pub(crate) fn
+strip_all_metadata
+(
+	file_buffer: &mut Vec<u8>
+)
+-> Result<(), std::io::Error>
+{
+	return clear_boxes(file_buffer, &[Jp2MetadataBox::Exif, Jp2MetadataBox::Xmp]);
+}
+
+#[cfg(feature = "std_fs")]
+pub(crate) fn
+file_clear_metadata
+(
+	path: &Path
+)
+-> Result<(), std::io::Error>
+{
+	return modify_file(path, clear_metadata);
+}
+
+
+
+/// Gets the TIFF data of the first EXIF UUID box
+pub(crate) fn
+read_metadata
+(
+	file_buffer: &Vec<u8>
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	if let Some((start, length)) = exif_byte_range(file_buffer)?
+	{
+		return Ok(file_buffer[start as usize..(start + length) as usize].to_vec());
+	}
+	return io_error!(NotFound, "No EXIF data found!");
+}
+
+/// Gets the start offset and length of the TIFF data of the first EXIF UUID
+/// box. Some writers put an "Exif\0\0" header in front of it, which is
+/// skipped as well.
+pub(crate) fn
+exif_byte_range
+(
+	file_buffer: &[u8]
+)
+-> Result<Option<(u64, u64)>, std::io::Error>
+{
+	// Raw codestreams can't contain any metadata
+	if file_buffer.starts_with(&J2K_SIGNATURE)
+	{
+		return Ok(None);
+	}
+
+	if let Some((position, header)) = find_exif_box(file_buffer)?
+	{
+		let mut start = position + header.header_length + EXIF_UUID.len();
+		if file_buffer[start..].starts_with(&EXIF_HEADER)
+		{
+			start += EXIF_HEADER.len();
+		}
+		return Ok(Some((start as u64, (position + header.box_length - start) as u64)));
+	}
+
+	return Ok(None);
+}
+
+#[cfg(feature = "std_fs")]
+pub(crate) fn
+file_read_metadata
+(
+	path: &Path
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	let mut file = open_read_file(path)?;
+	let mut file_buffer = Vec::new();
+	perform_file_action!(file.read_to_end(&mut file_buffer));
+
+	return read_metadata(&file_buffer);
+}
+
+
+
+/// The maximum size of the EXIF data, as the length field of the UUID box
+/// (4 bytes) also covers the box header and the UUID
+pub(crate) const JP2_MAX_EXIF_SIZE: usize = u32::MAX as usize - 4 - Jp2BoxType::UUID.len() - EXIF_UUID.len();
+
+fn
+encode_metadata_jp2
+(
+	exif_vec: &[u8]
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	MetadataTooLarge::check(JP2_MAX_EXIF_SIZE, exif_vec.len())?;
+
+	let exif_box_length = 0                        // Length has to include
+		+ 4                                        // - the length field
+		+ Jp2BoxType::UUID.len()            as u32 // - the box type
+		+ EXIF_UUID.len()                   as u32 // - the UUID
+		+ exif_vec.len()                    as u32 // - the exif data
+	;
+
+	let mut jp2_exif = Vec::new();
+	jp2_exif.extend(to_u8_vec_macro!(u32, &exif_box_length, &Endian::Big));
+	jp2_exif.extend(Jp2BoxType::UUID);
+	jp2_exif.extend(EXIF_UUID);
+	jp2_exif.extend(exif_vec.iter());
+
+	return Ok(jp2_exif);
+}
+
+/// The EXIF box is placed in front of the (first) codestream box, which
+/// comes after the header boxes. Without a codestream box, it is appended.
+fn
+find_insert_position
+(
+	file_buffer: &[u8]
+)
+-> Result<usize, std::io::Error>
+{
+	let mut position = 0;
+	while position < file_buffer.len()
+	{
+		let header = read_box_header(file_buffer, position)?;
+		if header.box_type == Jp2BoxType::JP2C
+		{
+			return Ok(position);
+		}
+		position += header.box_length;
+	}
+
+	return Ok(file_buffer.len());
+}
+
+pub(crate) fn
+write_metadata
+(
+	file_buffer: &mut Vec<u8>,
+	metadata:    &Metadata
+)
+-> Result<(), std::io::Error>
+{
+	if file_buffer.starts_with(&J2K_SIGNATURE)
+	{
+		return io_error!(Unsupported, "Raw JPEG 2000 codestreams can't hold EXIF data, use the JP2 format instead!");
+	}
+
+	// Encode first, so that the existing EXIF data is kept if this fails
+	let mut encoded_metadata = encode_metadata_jp2(&metadata.encode()?)?;
+
+	clear_metadata(file_buffer)?;
+
+	let     insert_position  = find_insert_position(file_buffer)?;
+	insert_multiple_at(file_buffer, insert_position, &mut encoded_metadata);
+
+	return Ok(());
+}
+
+#[cfg(feature = "std_fs")]
+pub(crate) fn
+file_write_metadata
+(
+	path:     &Path,
+	metadata: &Metadata
+)
+-> Result<(), std::io::Error>
+{
+	return modify_file(path, |file_buffer| write_metadata(file_buffer, metadata));
+}
+
+/// Loads the entire file into memory, applies the given modification and
+/// writes the result to a temporary file that replaces the file, see 
+/// `write_atomically_from_scratch`
+#[cfg(feature = "std_fs")]
+fn
+modify_file
+(
+	path:   &Path,
+	modify: impl FnOnce(&mut Vec<u8>) -> Result<(), std::io::Error>
+)
+-> Result<(), std::io::Error>
+{
+	let mut file: File = open_read_file(path)?;
+	let mut file_buffer: Vec<u8> = Vec::new();
+	perform_file_action!(file.read_to_end(&mut file_buffer));
+
+	modify(&mut file_buffer)?;
+
+	return write_atomically_from_scratch(path, |temporary_path| std::fs::write(temporary_path, &file_buffer));
+}
@@ -1,17 +1,21 @@
 // Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
 // See https://github.com/TechnikTobi/little_exif#license for licensing details
 
+#[cfg(feature = "std_fs")]
 use std::fs::File;
+#[cfg(feature = "std_fs")]
 use std::io::BufReader;
 use std::io::Cursor;
 use std::io::Seek;
 use std::io::SeekFrom;
 use std::io::Read;
 use std::io::Write;
+#[cfg(feature = "std_fs")]
 use std::path::Path;
 
 use crate::endian::Endian;
 use crate::metadata::Metadata;
+use crate::metadata::error::MetadataTooLarge;
 use crate::u8conversion::*;
 use crate::general_file_io::*;
 
@@ -20,6 +24,12 @@ pub(crate) const JPG_SIGNATURE: [u8; 2] = [0xff, 0xd8];
 const JPG_MARKER_PREFIX: u8  = 0xff;
 const JPG_APP1_MARKER:   u16 = 0xffe1;
 
+/// The maximum size of the EXIF data, as it has to fit into a single APP1
+/// segment, whose length field (2 bytes, including itself) also covers the
+/// "Exif\0\0" header. Extended EXIF spanning multiple APP1 segments is not
+/// part of the EXIF standard and not supported by most readers.
+pub(crate) const JPG_MAX_EXIF_SIZE: usize = u16::MAX as usize - 2 - EXIF_HEADER.len();
+
 
 
 fn
@@ -27,14 +37,15 @@ encode_metadata_jpg
 (
 	exif_vec: &Vec<u8>
 )
--> Vec<u8>
+-> Result<Vec<u8>, std::io::Error>
 {
 	// vector storing the data that will be returned
 	let mut jpg_exif: Vec<u8> = Vec::new();
 
 	// Compute the length of the exif data (includes the two bytes of the
-	// actual length field)
-	let length = 2u16 + (EXIF_HEADER.len() as u16) + (exif_vec.len() as u16);
+	// actual length field), which has to fit into a single APP1 segment
+	MetadataTooLarge::check(JPG_MAX_EXIF_SIZE, exif_vec.len())?;
+	let length = (2 + EXIF_HEADER.len() + exif_vec.len()) as u16;
 
 	// Start with the APP1 marker and the length of the data
 	// Then copy the previously encoded EXIF data 
@@ -43,7 +54,7 @@ encode_metadata_jpg
 	jpg_exif.extend(EXIF_HEADER.iter());
 	jpg_exif.extend(exif_vec.iter());
 
-	return jpg_exif;
+	return Ok(jpg_exif);
 }
 
 
@@ -51,10 +62,15 @@ encode_metadata_jpg
 fn
 check_signature
 (
-	file_buffer: &Vec<u8>
+	file_buffer: &[u8]
 )
 -> Result<(), std::io::Error>
 {
+	if file_buffer.len() < JPG_SIGNATURE.len()
+	{
+		return io_error!(InvalidData, "Can't open JPG file - Not enough data for signature!");
+	}
+
 	// Check the signature
 	let signature_is_valid = file_buffer[0..2].iter()
 		.zip(JPG_SIGNATURE.iter())
@@ -70,6 +86,7 @@ check_signature
 	return Ok(());
 }
 
+#[cfg(feature = "std_fs")]
 fn
 file_check_signature
 (
@@ -99,6 +116,22 @@ clear_metadata
 	return clear_segment(file_buffer, 0xe1);
 }
 
+/// Removes the APP1 (EXIF and XMP), APP13 (Photoshop IRB with IPTC data) and
+/// COM segments
+pub(crate) fn
+strip_all_metadata
+(
+	file_buffer: &mut Vec<u8>,
+)
+-> Result<(), std::io::Error>
+{
+	for segment_marker in [0xe1, 0xed, 0xfe]
+	{
+		clear_segment(file_buffer, segment_marker)?;
+	}
+	return Ok(());
+}
+
 
 pub(crate) fn
 clear_segment
@@ -187,6 +220,7 @@ clear_segment
 	}
 }
 
+#[cfg(feature = "std_fs")]
 pub(crate) fn
 file_clear_segment
 (
@@ -211,6 +245,7 @@ file_clear_segment
 	return Ok(());
 }
 
+#[cfg(feature = "std_fs")]
 pub(crate) fn
 file_clear_metadata
 (
@@ -229,7 +264,7 @@ as_u8_vec
 (
 	general_encoded_metadata: &Vec<u8>
 )
--> Vec<u8>
+-> Result<Vec<u8>, std::io::Error>
 {
 	encode_metadata_jpg(general_encoded_metadata)
 }
@@ -244,22 +279,137 @@ write_metadata
 )
 -> Result<(), std::io::Error>
 {
+	// Encode the data specifically for JPG before touching the file buffer,
+	// so that it stays unchanged if the metadata is too large
+	let mut encoded_metadata = encode_metadata_jpg(&metadata.encode()?)?;
+
 	// Remove old metadata
 	clear_metadata(file_buffer)?;
 
-	// Encode the data specifically for JPG
-	let mut encoded_metadata = encode_metadata_jpg(&metadata.encode()?);
-
-	// Insert the metadata right after the signature
-	crate::util::insert_multiple_at(file_buffer, 2, &mut encoded_metadata);
+	// Insert the metadata right after the signature and any APP0 segments 
+	// (JFIF and the JFXX extension that may carry a thumbnail), as these are
+	// required to immediately follow the signature and are kept verbatim
+	let insert_position = get_position_after_app0_segments(file_buffer)?;
+	crate::util::insert_multiple_at(file_buffer, insert_position, &mut encoded_metadata);
 
 	return Ok(());
 }
 
+/// Determines the position directly after the signature and all APP0
+/// segments that immediately follow it
+fn
+get_position_after_app0_segments
+(
+	file_buffer: &Vec<u8>
+)
+-> Result<usize, std::io::Error>
+{
+	let mut position = JPG_SIGNATURE.len();
+
+	while position + 4 <= file_buffer.len()
+		&& file_buffer[position    ] == JPG_MARKER_PREFIX
+		&& file_buffer[position + 1] == 0xe0                                    // APP0 marker
+	{
+		let length = from_u8_vec_macro!(u16, &file_buffer[position+2..position+4].to_vec(), &Endian::Big);
+		position += 2 + length as usize;
+	}
+
+	if position > file_buffer.len()
+	{
+		return io_error!(InvalidData, "APP0 segment exceeds JPEG data!");
+	}
+
+	return Ok(position);
+}
+
+/// Reads a JPEG image from `source` and incrementally writes it to
+/// `destination`, replacing any APP1 segments with the given metadata.
+/// Only the segments before the start of scan (SOS) are processed one by
+/// one; everything from there on is copied without further inspection.
+/// The new APP1 segment is placed directly after the signature and any APP0 
+/// segments, the same way `write_metadata` does. The endianness of an EXIF
+/// APP1 segment that precedes this position is kept (see `write_to_vec`).
+pub(crate) fn
+stream_write_metadata
+<R: Read, W: Write>
+(
+	source:      &mut R,
+	destination: &mut W,
+	metadata:    &Metadata
+)
+-> Result<(), std::io::Error>
+{
+	let mut signature_buffer = [0u8; 2];
+	source.read_exact(&mut signature_buffer)?;
+	check_signature(&signature_buffer)?;
+	destination.write_all(&signature_buffer)?;
+
+	let mut metadata_written = false;
+	let mut existing_exif    = None;
+
+	loop
+	{
+		let mut marker_buffer = [0u8; 2];
+		source.read_exact(&mut marker_buffer)?;
+
+		if marker_buffer[0] != JPG_MARKER_PREFIX
+		{
+			return io_error!(InvalidData, "Expected JPEG marker prefix!");
+		}
+
+		let marker = marker_buffer[1];
+
+		// APP0 segments stay in front of the metadata, old APP1 segments
+		// get removed. Anything else means it is time for the metadata
+		if marker != 0xe0 && marker != 0xe1 && !metadata_written
+		{
+			let adjusted = existing_exif.take().and_then(|exif: Vec<u8>| metadata.with_existing_endian(&exif));
+			let encoded  = adjusted.as_ref().unwrap_or(metadata).encode()?;
+			destination.write_all(&encode_metadata_jpg(&encoded)?)?;
+			metadata_written = true;
+		}
+
+		// Segments without length information (EOI, RSTn, TEM) and the start
+		// of scan, which is followed by the entropy coded image data
+		if marker == 0xd9 || marker == 0xda || (0xd0..=0xd7).contains(&marker) || marker == 0x01
+		{
+			destination.write_all(&marker_buffer)?;
+			std::io::copy(source, destination)?;
+			return Ok(());
+		}
+
+		let mut length_buffer = [0u8; 2];
+		source.read_exact(&mut length_buffer)?;
+		let length = from_u8_vec_macro!(u16, &length_buffer.to_vec(), &Endian::Big);
+
+		if length < 2
+		{
+			return io_error!(InvalidData, "Invalid JPEG segment length!");
+		}
+
+		let mut segment_data = vec![0u8; length as usize - 2];
+		source.read_exact(&mut segment_data)?;
+
+		if marker == 0xe1
+		{
+			if segment_data.starts_with(&EXIF_HEADER) && existing_exif.is_none()
+			{
+				existing_exif = Some(segment_data);
+			}
+			continue;
+		}
+
+		destination.write_all(&marker_buffer)?;
+		destination.write_all(&length_buffer)?;
+		destination.write_all(&segment_data)?;
+	}
+}
+
 /// Writes the given generally encoded metadata to the JP(E)G image file at 
 /// the specified path. 
 /// Note that any previously stored metadata under the APP1 marker gets removed
 /// first before writing the "new" metadata. 
+#[cfg(feature = "std_fs")]
 pub(crate) fn
 file_write_metadata
 (
@@ -289,7 +439,7 @@ file_write_metadata
 pub(crate) fn
 read_metadata
 (
-	file_buffer: &Vec<u8>
+	file_buffer: &[u8]
 )
 -> Result<Vec<u8>, std::io::Error>
 {
@@ -303,6 +453,7 @@ read_metadata
 	return generic_read_metadata(&mut cursor);
 }
 
+#[cfg(feature = "std_fs")]
 pub(crate) fn
 file_read_metadata
 (
@@ -315,6 +466,260 @@ file_read_metadata
 	return generic_read_metadata(&mut buffered_file);
 }
 
+/// Gets the EXIF data of all APP1 segments holding it, see
+/// `generic_read_all_metadata`
+pub(crate) fn
+read_all_metadata
+(
+	file_buffer: &[u8]
+)
+-> Result<Vec<Vec<u8>>, std::io::Error>
+{
+	check_signature(file_buffer)?;
+
+	let mut cursor = Cursor::new(file_buffer);
+
+	// Skip signature
+	cursor.set_position(2);
+
+	return generic_read_all_metadata(&mut cursor);
+}
+
+#[cfg(feature = "std_fs")]
+pub(crate) fn
+file_read_all_metadata
+(
+	path: &Path
+)
+-> Result<Vec<Vec<u8>>, std::io::Error>
+{
+	let mut buffered_file = BufReader::new(file_check_signature(path)?);
+	return generic_read_all_metadata(&mut buffered_file);
+}
+
+/// Gets the start offset and length of the EXIF TIFF payload (i.e. without
+/// the "Exif\0\0" header) stored in the first APP1 segment holding EXIF data
+pub(crate) fn
+exif_byte_range
+(
+	file_buffer: &[u8]
+)
+-> Result<Option<(u64, u64)>, std::io::Error>
+{
+	check_signature(file_buffer)?;
+
+	let mut cursor = Cursor::new(file_buffer);
+
+	// Skip signature
+	cursor.set_position(2);
+
+	let mut byte_buffer = [0u8; 1];
+	let mut previous_byte_was_marker_prefix = false;
+
+	loop
+	{
+		if cursor.read_exact(&mut byte_buffer).is_err()
+		{
+			return Ok(None);
+		}
+
+		if previous_byte_was_marker_prefix
+		{
+			if byte_buffer[0] == 0xd9                                           // EOI marker
+			{
+				return Ok(None);
+			}
+
+			let mut length_buffer = [0u8; 2];
+			cursor.read_exact(&mut length_buffer)?;
+
+			let length           = from_u8_vec_macro!(u16, &length_buffer.to_vec(), &Endian::Big);
+			let remaining_length = match length.checked_sub(2)
+			{
+				Some(remaining_length) => remaining_length as u64,
+				None                   => return io_error!(InvalidData, "Invalid JPEG segment length!"),
+			};
+			let segment_start    = cursor.position();
+
+			if byte_buffer[0] == 0xe1 && file_buffer[segment_start as usize..].starts_with(&EXIF_HEADER)
+			{
+				// The segment has to hold at least the EXIF header and must
+				// not exceed the file data
+				let exif_length = remaining_length.checked_sub(EXIF_HEADER.len() as u64);
+				if exif_length.is_none() || segment_start + remaining_length > file_buffer.len() as u64
+				{
+					return io_error!(InvalidData, "APP1 segment with EXIF data exceeds JPEG data!");
+				}
+
+				return Ok(Some((
+					segment_start + EXIF_HEADER.len() as u64,
+					exif_length.unwrap()
+				)));
+			}
+
+			cursor.seek(SeekFrom::Current(remaining_length as i64))?;
+			if byte_buffer[0] == 0xda                                           // SOS marker
+			{
+				skip_ecs(&mut cursor)?;
+			}
+
+			previous_byte_was_marker_prefix = false;
+		}
+		else
+		{
+			previous_byte_was_marker_prefix = byte_buffer[0] == JPG_MARKER_PREFIX;
+		}
+	}
+}
+
+/// Gets the start offsets of all images stored in a multi-picture object
+/// (MPO) file, e.g. a stereo image. These are listed in the MP Index IFD of
+/// the APP2 "MPF" segment of the first image, with the offsets being 
+/// relative to the TIFF-like header that follows the "MPF\0" identifier
+/// (except for the first image, whose offset is always 0).
+/// For regular JPEGs without such a segment, only the offset 0 is returned.
+pub(crate) fn
+read_mpo_image_offsets
+(
+	file_buffer: &[u8]
+)
+-> Result<Vec<usize>, std::io::Error>
+{
+	check_signature(file_buffer)?;
+
+	let mut cursor = Cursor::new(file_buffer);
+
+	// Skip signature
+	cursor.set_position(2);
+
+	let mut byte_buffer = [0u8; 1];
+	let mut previous_byte_was_marker_prefix = false;
+
+	// The MPF segment is part of the header of the first image, so the 
+	// search ends with the start of its scan
+	let mp_header_start = loop
+	{
+		if cursor.read_exact(&mut byte_buffer).is_err()
+		{
+			return Ok(vec![0]);
+		}
+
+		if previous_byte_was_marker_prefix
+		{
+			if byte_buffer[0] == 0xd9 || byte_buffer[0] == 0xda                 // EOI or SOS marker
+			{
+				return Ok(vec![0]);
+			}
+
+			let mut length_buffer = [0u8; 2];
+			cursor.read_exact(&mut length_buffer)?;
+
+			let length        = from_u8_vec_macro!(u16, &length_buffer.to_vec(), &Endian::Big);
+			let segment_start = cursor.position() as usize;
+
+			if byte_buffer[0] == 0xe2 && file_buffer[segment_start..].starts_with(b"MPF\0")
+			{
+				break segment_start + 4;
+			}
+
+			cursor.seek(SeekFrom::Current(length.saturating_sub(2) as i64))?;
+			previous_byte_was_marker_prefix = false;
+		}
+		else
+		{
+			previous_byte_was_marker_prefix = byte_buffer[0] == JPG_MARKER_PREFIX;
+		}
+	};
+
+	let endian = match file_buffer.get(mp_header_start..mp_header_start+2)
+	{
+		Some([0x49, 0x49]) => Endian::Little,
+		Some([0x4d, 0x4d]) => Endian::Big,
+		_                  => return io_error!(InvalidData, "Invalid MPF header!"),
+	};
+
+	let read_u16 = |position: usize| -> Result<usize, std::io::Error>
+	{
+		match file_buffer.get(mp_header_start+position..mp_header_start+position+2)
+		{
+			Some(bytes) => Ok(from_u8_vec_macro!(u16, &bytes.to_vec(), &endian) as usize),
+			None        => io_error!(InvalidData, "MPF segment is truncated!"),
+		}
+	};
+	let read_u32 = |position: usize| -> Result<usize, std::io::Error>
+	{
+		match file_buffer.get(mp_header_start+position..mp_header_start+position+4)
+		{
+			Some(bytes) => Ok(from_u8_vec_macro!(u32, &bytes.to_vec(), &endian) as usize),
+			None        => io_error!(InvalidData, "MPF segment is truncated!"),
+		}
+	};
+
+	// Go through the MP Index IFD and look for the MP Entry tag (0xb002),
+	// which consists of one 16 byte entry per image
+	let ifd_offset = read_u32(4)?;
+	for entry_nr in 0..read_u16(ifd_offset)?
+	{
+		let entry_position = ifd_offset + 2 + 12 * entry_nr;
+		if read_u16(entry_position)? != 0xb002
+		{
+			continue;
+		}
+
+		let byte_count   = read_u32(entry_position + 4)?;
+		let value_offset = read_u32(entry_position + 8)?;
+
+		let mut image_offsets = Vec::new();
+		for image_nr in 0..byte_count / 16
+		{
+			// Attributes (4 bytes), size (4 bytes), then the offset
+			let image_offset = read_u32(value_offset + 16 * image_nr + 8)?;
+			if image_nr == 0
+			{
+				image_offsets.push(0);
+			}
+			else
+			{
+				image_offsets.push(mp_header_start + image_offset);
+			}
+		}
+		return Ok(image_offsets);
+	}
+
+	return io_error!(InvalidData, "MPF segment without MP Entry tag!");
+}
+
+/// Reads the image dimensions (width, height) from the first start of frame
+/// (SOFn) segment of the JPEG data
+pub(crate) fn
+read_dimensions
+(
+	file_buffer: &[u8]
+)
+-> Result<(u16, u16), std::io::Error>
+{
+	check_signature(file_buffer)?;
+
+	let mut cursor = Cursor::new(file_buffer);
+
+	// Skip signature
+	cursor.set_position(2);
+
+	return generic_read_dimensions(&mut cursor);
+}
+
+#[cfg(feature = "std_fs")]
+pub(crate) fn
+file_read_dimensions
+(
+	path: &Path
+)
+-> Result<(u16, u16), std::io::Error>
+{
+	let mut buffered_file = BufReader::new(file_check_signature(path)?);
+	return generic_read_dimensions(&mut buffered_file);
+}
+
 /// Skips the entropy-coded segment (ECS) that is followed by a start of scan
 /// segment (SOS) and positions the cursor at the start of the next segment,
 /// i.e. a 0xFF byte that is followed by a marker that is NOT 0xD0-0xD7 or 0x00.
@@ -394,7 +799,7 @@ generic_read_metadata
 			if byte_buffer[0] == 0xd9                                           // EOI marker
 			{
 				// No more data to read in
-				return io_error!(Other, "No EXIF data found!");
+				return io_error!(NotFound, "No EXIF data found!");
 			}
 
 			// Read in the length of the segment
@@ -447,4 +852,138 @@ generic_read_metadata
 			previous_byte_was_marker_prefix = byte_buffer[0] == JPG_MARKER_PREFIX;
 		}
 	}
-}
\ No newline at end of file
+}
+
+/// Gets the EXIF data (including the "Exif\0\0" header) of all APP1
+/// segments holding it in the order in which they appear. Usually there is
+/// only one of them, but editing an image with different tools may result
+/// in multiple copies. As the metadata is stored in front of the image data,
+/// the search stops at the first SOS segment.
+fn
+generic_read_all_metadata
+<T: Seek + Read>
+(
+	cursor: &mut T
+)
+-> Result<Vec<Vec<u8>>, std::io::Error>
+{
+	let mut exif_sources = Vec::new();
+
+	let mut byte_buffer = [0u8; 1];
+	let mut previous_byte_was_marker_prefix = false;
+
+	loop
+	{
+		if cursor.read_exact(&mut byte_buffer).is_err()
+		{
+			return Ok(exif_sources);
+		}
+
+		if previous_byte_was_marker_prefix
+		{
+			if byte_buffer[0] == 0xd9 || byte_buffer[0] == 0xda                 // EOI or SOS marker
+			{
+				return Ok(exif_sources);
+			}
+
+			let mut length_buffer = [0u8; 2];
+			cursor.read_exact(&mut length_buffer)?;
+
+			let length           = from_u8_vec_macro!(u16, &length_buffer.to_vec(), &Endian::Big);
+			let remaining_length = match length.checked_sub(2)
+			{
+				Some(remaining_length) => remaining_length as usize,
+				None                   => return io_error!(InvalidData, "Invalid JPEG segment length!"),
+			};
+
+			if byte_buffer[0] == 0xe1                                           // APP1 marker
+			{
+				let mut app1_buffer = vec![0u8; remaining_length];
+				cursor.read_exact(&mut app1_buffer)?;
+
+				if app1_buffer.starts_with(&EXIF_HEADER)
+				{
+					exif_sources.push(app1_buffer);
+				}
+			}
+			else
+			{
+				cursor.seek(SeekFrom::Current(remaining_length as i64))?;
+			}
+
+			previous_byte_was_marker_prefix = false;
+		}
+		else
+		{
+			previous_byte_was_marker_prefix = byte_buffer[0] == JPG_MARKER_PREFIX;
+		}
+	}
+}
+
+fn
+generic_read_dimensions
+<T: Seek + Read>
+(
+	cursor: &mut T
+)
+-> Result<(u16, u16), std::io::Error>
+{
+	let mut byte_buffer = [0u8; 1];
+	let mut previous_byte_was_marker_prefix = false;
+
+	loop
+	{
+		cursor.read_exact(&mut byte_buffer)?;
+
+		if previous_byte_was_marker_prefix
+		{
+			// Fill bytes between segments
+			if byte_buffer[0] == JPG_MARKER_PREFIX
+			{
+				continue;
+			}
+
+			// Reaching the end of the image or the start of scan (SOS) means
+			// that there can't be a SOFn segment anymore
+			if byte_buffer[0] == 0xd9 || byte_buffer[0] == 0xda
+			{
+				return io_error!(Other, "No SOF segment found!");
+			}
+
+			let mut length_buffer = [0u8; 2];
+			cursor.read_exact(&mut length_buffer)?;
+			let length = from_u8_vec_macro!(u16, &length_buffer.to_vec(), &Endian::Big);
+			if length < 2
+			{
+				return io_error!(InvalidData, "Invalid JPEG segment length!");
+			}
+			let remaining_length = (length - 2) as usize;
+
+			match byte_buffer[0]
+			{
+				// SOF0 to SOF15, except for DHT (0xc4), JPG (0xc8) and DAC (0xcc)
+				0xc0..=0xcf if ![0xc4, 0xc8, 0xcc].contains(&byte_buffer[0]) => {
+
+					// Sample precision (1 byte), then height and width (2 bytes each)
+					let mut sof_buffer = [0u8; 5];
+					cursor.read_exact(&mut sof_buffer)?;
+
+					let height = from_u8_vec_macro!(u16, &sof_buffer[1..3].to_vec(), &Endian::Big);
+					let width  = from_u8_vec_macro!(u16, &sof_buffer[3..5].to_vec(), &Endian::Big);
+
+					return Ok((width, height));
+				},
+
+				_ => {
+					cursor.seek(SeekFrom::Current(remaining_length as i64))?;
+				},
+			}
+
+			previous_byte_was_marker_prefix = false;
+		}
+		else
+		{
+			previous_byte_was_marker_prefix = byte_buffer[0] == JPG_MARKER_PREFIX;
+		}
+	}
+}
@@ -1,12 +1,15 @@
 // Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
 // See https://github.com/TechnikTobi/little_exif#license for licensing details
 
+#[cfg(feature = "std_fs")]
 use std::fs::File;
 use std::io::Cursor;
 use std::io::Read;
 use std::io::Seek;
 use std::io::SeekFrom;
+#[cfg(feature = "std_fs")]
 use std::io::Write;
+#[cfg(feature = "std_fs")]
 use std::path::Path;
 
 use crate::endian::Endian;
@@ -41,6 +44,7 @@ impl IsoBmffBoxType {
 	pub const FTYP: [u8; 4] = [0x66, 0x74, 0x79, 0x70]; // "ftyp"
 	pub const JXL:  [u8; 4] = [0x4a, 0x58, 0x4c, 0x20]; // "JXL "
 	pub const JXLC: [u8; 4] = [0x6a, 0x78, 0x6c, 0x63]; // "jxlc"
+	pub const XML:  [u8; 4] = [0x78, 0x6d, 0x6c, 0x20]; // "xml "
 }
 
 /// Checks if the given file buffer vector starts with the necessary bytes that
@@ -93,7 +97,7 @@ check_signature
 {
 	if starts_with_jxl_signature(file_buffer)
 	{
-		return io_error!(Other, "Simple JXL codestream file - No metadata!");
+		return io_error!(NotFound, "Simple JXL codestream file - No metadata!");
 	}
 
 	if !starts_with_iso_bmff_signature(file_buffer)
@@ -104,6 +108,7 @@ check_signature
 	return Ok(());
 }
 
+#[cfg(feature = "std_fs")]
 fn
 file_check_signature
 (
@@ -128,6 +133,29 @@ clear_metadata
 	file_buffer: &mut Vec<u8>
 )
 -> Result<(), std::io::Error>
+{
+	return clear_boxes(file_buffer, &[EXIF]);
+}
+
+/// Removes the EXIF as well as the XMP boxes
+pub(crate) fn
+strip_all_metadata
+(
+	file_buffer: &mut Vec<u8>
+)
+-> Result<(), std::io::Error>
+{
+	return clear_boxes(file_buffer, &[EXIF, IsoBmffBoxType::XML]);
+}
+
+/// Removes all boxes of the given types
+fn
+clear_boxes
+(
+	file_buffer: &mut Vec<u8>,
+	box_types:   &[[u8; 4]]
+)
+-> Result<(), std::io::Error>
 {
 	check_signature(file_buffer)?;
 
@@ -145,22 +173,19 @@ clear_metadata
 		// Next, read the box type
 		let type_buffer = file_buffer[position+4..position+8].to_vec();
 
-		if type_buffer.iter()
-			.zip(EXIF.iter())
-			.filter(|&(read, constant)| read == constant)
-			.count()
-			.eq(&EXIF.len())
+		if box_types.iter().any(|box_type| type_buffer == box_type)
 		{
 			range_remove(file_buffer, position, position+length);
 		}
 		else
 		{
-			// Not an EXIF box so skip it
+			// Not a box to remove so skip it
 			position += length;
 		}
 	}
 }
 
+#[cfg(feature = "std_fs")]
 pub(crate) fn
 file_clear_metadata
 (
@@ -234,6 +259,11 @@ read_metadata
 
 	loop
 	{
+		if cursor.position() >= file_buffer.len() as u64
+		{
+			return io_error!(NotFound, "No EXIF data found!");
+		}
+
 		// Get the first 4 bytes at the current cursor position to determine
 		// the length of the current box (and account for the 8 bytes of length
 		// and box type)
@@ -263,6 +293,65 @@ read_metadata
 	}
 }
 
+/// Gets the start offset and length of the EXIF TIFF payload of the first
+/// Exif box, skipping the 4 bytes giving the offset to the TIFF header
+pub(crate) fn
+exif_byte_range
+(
+	file_buffer: &Vec<u8>
+)
+-> Result<Option<(u64, u64)>, std::io::Error>
+{
+	// Simple JXL codestreams can't contain any metadata
+	if starts_with_jxl_signature(file_buffer)
+	{
+		return Ok(None);
+	}
+
+	check_signature(file_buffer)?;
+
+	let mut position = 0usize;
+
+	while position + 8 <= file_buffer.len()
+	{
+		let length = from_u8_vec_macro!(u32, &file_buffer[position..position+4].to_vec(), &Endian::Big) as usize;
+
+		if length < 8
+		{
+			return io_error!(Other, "Invalid ISO BMFF box length!");
+		}
+
+		let box_end = match position.checked_add(length)
+		{
+			Some(box_end) => box_end,
+			None          => return io_error!(InvalidData, "Invalid ISO BMFF box length!"),
+		};
+
+		if file_buffer[position+4..position+8] == EXIF
+		{
+			// The box has to hold at least the offset to the TIFF header and
+			// must not exceed the file data
+			let mut start = position + 8 + 4;
+			let exif_data = match file_buffer.get(start..box_end)
+			{
+				Some(exif_data) => exif_data,
+				None            => return io_error!(InvalidData, "Exif box exceeds JXL data!"),
+			};
+
+			if exif_data.starts_with(&EXIF_HEADER)
+			{
+				start += EXIF_HEADER.len();
+			}
+			return Ok(Some((start as u64, (box_end - start) as u64)));
+		}
+
+		position = box_end;
+	}
+
+	return Ok(None);
+}
+
+#[cfg(feature = "std_fs")]
 pub(crate) fn
 file_read_metadata
 (
@@ -277,8 +366,15 @@ file_read_metadata
 	file.read(&mut first_12_bytes).unwrap();
 	check_signature(&first_12_bytes.to_vec())?;
 
+	let file_length = file.metadata()?.len();
+
 	loop
 	{
+		if file.stream_position()? >= file_length
+		{
+			return io_error!(NotFound, "No EXIF data found!");
+		}
+
 		// Get the first 4 bytes at the current cursor position to determine
 		// the length of the current box (and account for the 8 bytes of length
 		// and box type)
@@ -410,6 +506,7 @@ write_metadata
 	return Ok(());
 }
 
+#[cfg(feature = "std_fs")]
 pub(crate) fn 
 file_write_metadata
 (
@@ -5,6 +5,7 @@
 //! A small crate for reading and writing (some) EXIF data, written entirely in Rust. Currently supports
 //! - .png 
 //! - .jp(e)g 
+//! - .jp2
 //! - .jxl
 //! - .webp
 //! files and a few dozen tags in IFD0 and ExifIFD. 
@@ -14,6 +15,8 @@
 //! # Usage
 //! ## Write EXIF data
 //! ```no_run
+//! # #[cfg(feature = "std_fs")]
+//! # {
 //! use little_exif::metadata::Metadata;
 //! use little_exif::exif_tag::ExifTag;
 //! 
@@ -22,7 +25,16 @@
 //!     ExifTag::ImageDescription("Hello World!".to_string())
 //! );
 //! metadata.write_to_file(std::path::Path::new("image.png"));
+//! # }
 //! ```
+//!
+//! # Features
+//! - `std_fs` (enabled by default): The functions operating on file paths,
+//!   e.g. `Metadata::new_from_path` and `Metadata::write_to_file`. Without it,
+//!   only the in-memory functions like `Metadata::new_from_vec` and
+//!   `Metadata::write_to_vec` are available, which don't require a file
+//!   system (e.g. for `wasm32-unknown-unknown`).
+//! - `chrono`: Conversions between EXIF date/time values and `chrono` types.
 
 #![forbid(unsafe_code)]
 #![crate_type = "lib"]
@@ -31,7 +43,8 @@
 mod general_file_io;
 pub mod ifd;
 
-mod png;
+pub mod png;
+mod jp2;
 mod jpg;
 mod jxl;
 mod tiff;
@@ -44,4 +57,19 @@ pub mod u8conversion;
 pub mod exif_tag;
 pub mod exif_tag_format;
 pub mod filetype;
-pub mod metadata;
\ No newline at end of file
+pub mod metadata;
+
+/// Removes all metadata (EXIF, XMP, IPTC, comments, ...) from the image file
+/// at the given path, keeping only the image data itself. 
+/// See [`Metadata::strip_all_metadata`](metadata/struct.Metadata.html#method.strip_all_metadata)
+/// for details on what gets removed for each file type.
+#[cfg(feature = "std_fs")]
+pub fn
+strip_all_metadata
+(
+	path: &std::path::Path
+)
+-> Result<(), std::io::Error>
+{
+	return metadata::Metadata::file_strip_all_metadata(path);
+}
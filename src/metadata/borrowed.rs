@@ -0,0 +1,203 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::exif_tag_format::ExifTagFormat;
+use crate::ifd::ExifTagGroup;
+
+use super::raw::RawEntryIterator;
+use super::Metadata;
+
+/// The value of a `STRING` or `UNDEF` tag, borrowed from the raw data
+/// retained by `Metadata` (see `ReadOptions::retain_raw_data`)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+BorrowedValue<'a>
+{
+	/// A `STRING` tag without its trailing NULs
+	Str(&'a str),
+
+	/// An `UNDEF` tag, or a `STRING` tag that is not valid UTF-8
+	Bytes(&'a [u8]),
+}
+
+impl<'a>
+BorrowedValue<'a>
+{
+	/// Gets the raw bytes of the value, regardless of its kind
+	pub fn
+	as_bytes
+	(
+		&self
+	)
+	-> &'a [u8]
+	{
+		match self
+		{
+			BorrowedValue::Str(value)   => value.as_bytes(),
+			BorrowedValue::Bytes(value) => value,
+		}
+	}
+}
+
+/// A `STRING` or `UNDEF` tag as found in the raw data, without decoding it
+/// into an `ExifTag`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct
+BorrowedTag<'a>
+{
+	pub hex:            u16,
+	pub group:          ExifTagGroup,
+	pub generic_ifd_nr: u32,
+	pub value:          BorrowedValue<'a>,
+}
+
+/// Iterates over the `STRING` and `UNDEF` tags of the raw data retained by
+/// `Metadata`, see `Metadata::borrowed_tags`
+pub struct
+BorrowedTagIterator<'a>
+{
+	entries: RawEntryIterator<'a>,
+}
+
+impl<'a>
+Iterator
+for
+BorrowedTagIterator<'a>
+{
+	type Item = BorrowedTag<'a>;
+
+	fn
+	next
+	(
+		&mut self
+	)
+	-> Option<Self::Item>
+	{
+		let data   = self.entries.data();
+		let endian = self.entries.endian().clone();
+
+		for (entry_position, entry) in self.entries.by_ref()
+		{
+			let is_string = entry.format == ExifTagFormat::STRING.as_u16();
+			if !is_string && entry.format != ExifTagFormat::UNDEF.as_u16()
+			{
+				continue;
+			}
+
+			// Both formats use one byte per component, so values of up to
+			// 4 bytes are stored in the entry itself
+			let count          = entry.count as usize;
+			let value_position = if count <= 4 { entry_position + 8 } else { entry.offset(&endian) as usize };
+			let raw_value      = match data.get(value_position..value_position.saturating_add(count))
+			{
+				Some(raw_value) => raw_value,
+				None            => continue,
+			};
+
+			let value = if is_string
+			{
+				let trimmed = match raw_value.iter().rposition(|byte| *byte != 0)
+				{
+					Some(last_position) => &raw_value[..=last_position],
+					None                => &raw_value[..0],
+				};
+
+				match std::str::from_utf8(trimmed)
+				{
+					Ok(string) => BorrowedValue::Str(string),
+					Err(_)     => BorrowedValue::Bytes(raw_value),
+				}
+			}
+			else
+			{
+				BorrowedValue::Bytes(raw_value)
+			};
+
+			return Some(BorrowedTag {
+				hex:            entry.tag,
+				group:          entry.group,
+				generic_ifd_nr: entry.generic_ifd_nr,
+				value,
+			});
+		}
+		return None;
+	}
+}
+
+impl
+Metadata
+{
+	/// Iterates over the `STRING` and `UNDEF` tags of the raw data the struct
+	/// was decoded from, yielding slices into that data instead of copies.
+	/// Requires `ReadOptions::retain_raw_data`, otherwise the iterator is
+	/// empty. Note that the data reflects the image as it was read - changes
+	/// made to the struct afterwards are not visible.
+	/// For scanning many images, combine this with an empty
+	/// `ReadOptions::tag_allowlist`, so that no tags get decoded at all.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::filetype::FileExtension;
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::metadata::options::ReadOptions;
+	///
+	/// let options = ReadOptions { retain_raw_data: true, tag_allowlist: Some(Vec::new()), ..ReadOptions::default() };
+	/// let file    = std::fs::read("image.jpg").unwrap();
+	///
+	/// let metadata = Metadata::new_from_vec_with_options(&file, FileExtension::JPEG, &options).unwrap();
+	/// for tag in metadata.borrowed_tags()
+	/// {
+	///     println!("0x{:04x}: {:?}", tag.hex, tag.value);
+	/// }
+	/// ```
+	pub fn
+	borrowed_tags
+	(
+		&self
+	)
+	-> BorrowedTagIterator<'_>
+	{
+		let entries = self.raw_data.as_deref()
+			.and_then(|raw_data| RawEntryIterator::new(raw_data, self.max_ifd_count).ok())
+			.unwrap_or_else(RawEntryIterator::empty);
+
+		BorrowedTagIterator { entries }
+	}
+
+	/// Gets the value of the first `STRING` tag with the given hex value and
+	/// group from the retained raw data, see `borrowed_tags`
+	pub fn
+	get_borrowed_str
+	(
+		&self,
+		hex_tag: u16,
+		group:   ExifTagGroup
+	)
+	-> Option<&str>
+	{
+		for tag in self.borrowed_tags()
+		{
+			if let (true, BorrowedValue::Str(value)) = (tag.hex == hex_tag && tag.group == group, tag.value)
+			{
+				return Some(value);
+			}
+		}
+		return None;
+	}
+
+	/// Gets the raw bytes of the first `STRING` or `UNDEF` tag with the given
+	/// hex value and group from the retained raw data, see `borrowed_tags`
+	pub fn
+	get_borrowed_bytes
+	(
+		&self,
+		hex_tag: u16,
+		group:   ExifTagGroup
+	)
+	-> Option<&[u8]>
+	{
+		return self.borrowed_tags()
+			.find(|tag| tag.hex == hex_tag && tag.group == group)
+			.map(|tag| tag.value.as_bytes());
+	}
+}
@@ -0,0 +1,132 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+#[cfg(feature = "std_fs")]
+use std::path::Path;
+
+use crate::exif_tag::ExifTag;
+#[cfg(feature = "std_fs")]
+use crate::ifd::ExifTagGroup;
+
+use super::Metadata;
+
+impl
+Metadata
+{
+	/// Gets the serial number of the camera body stored in the `SerialNumber`
+	/// tag (0xa431, called `BodySerialNumber` in the EXIF standard)
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some(serial_number) = metadata.get_body_serial_number()
+	/// {
+	///     println!("Taken with camera {}", serial_number);
+	/// }
+	/// # }
+	/// ```
+	pub fn
+	get_body_serial_number
+	(
+		&self
+	)
+	-> Option<String>
+	{
+		if let Some(ExifTag::SerialNumber(value)) = self.get_tag(&ExifTag::SerialNumber(String::new())).next()
+		{
+			return Some(value.clone());
+		}
+		return None;
+	}
+
+	/// Sets the `SerialNumber` (aka `BodySerialNumber`) tag
+	pub fn
+	set_body_serial_number
+	(
+		&mut self,
+		body_serial_number: &str
+	)
+	{
+		self.set_tag(ExifTag::SerialNumber(body_serial_number.to_string()));
+	}
+
+	/// Gets the name of the camera owner stored in the `OwnerName` tag
+	/// (0xa430, called `CameraOwnerName` in the EXIF standard)
+	pub fn
+	get_camera_owner_name
+	(
+		&self
+	)
+	-> Option<String>
+	{
+		if let Some(ExifTag::OwnerName(value)) = self.get_tag(&ExifTag::OwnerName(String::new())).next()
+		{
+			return Some(value.clone());
+		}
+		return None;
+	}
+
+	/// Sets the `OwnerName` (aka `CameraOwnerName`) tag
+	pub fn
+	set_camera_owner_name
+	(
+		&mut self,
+		camera_owner_name: &str
+	)
+	{
+		self.set_tag(ExifTag::OwnerName(camera_owner_name.to_string()));
+	}
+
+	/// Reads the `Make` (0x010f) and `Model` (0x0110) tags of IFD0 of the
+	/// image at the given path, e.g. for identifying the camera when 
+	/// cataloging images. Trailing NULs are removed from both values.
+	/// Returns `None` if either of the two tags does not exist.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	///
+	/// if let Some((make, model)) = Metadata::camera_identity(std::path::Path::new("image.jpg")).unwrap()
+	/// {
+	///     println!("Taken with a {} {}", make, model);
+	/// }
+	/// ```
+	#[cfg(feature = "std_fs")]
+	pub fn
+	camera_identity
+	(
+		path: &Path
+	)
+	-> Result<Option<(String, String)>, std::io::Error>
+	{
+		let metadata = Self::new_from_path(path)?;
+
+		let ifd0 = match metadata.get_ifd(ExifTagGroup::GENERIC, 0)
+		{
+			Some(ifd0) => ifd0,
+			None       => return Ok(None),
+		};
+
+		let mut make  = None;
+		let mut model = None;
+		for tag in ifd0.get_tags()
+		{
+			match tag
+			{
+				ExifTag::Make(value)  => make  = Some(value.trim_end_matches('\0').to_string()),
+				ExifTag::Model(value) => model = Some(value.trim_end_matches('\0').to_string()),
+				_                     => (),
+			}
+		}
+
+		if let (Some(make), Some(model)) = (make, model)
+		{
+			return Ok(Some((make, model)));
+		}
+		return Ok(None);
+	}
+}
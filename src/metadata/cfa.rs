@@ -0,0 +1,294 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::endian::Endian;
+use crate::exif_tag::ExifTag;
+use crate::general_file_io::io_error;
+
+use super::Metadata;
+
+/// The color of a single element of a color filter array (CFA), as used by
+/// the `CFAPattern` tag (0xa302)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+CfaColor
+{
+	Red,                                                                        // 0
+	Green,                                                                      // 1
+	Blue,                                                                       // 2
+	Cyan,                                                                       // 3
+	Magenta,                                                                    // 4
+	Yellow,                                                                     // 5
+	White,                                                                      // 6
+	Other(u8),
+}
+
+impl
+CfaColor
+{
+	pub fn
+	from_u8
+	(
+		value: u8
+	)
+	-> CfaColor
+	{
+		match value
+		{
+			0 => CfaColor::Red,
+			1 => CfaColor::Green,
+			2 => CfaColor::Blue,
+			3 => CfaColor::Cyan,
+			4 => CfaColor::Magenta,
+			5 => CfaColor::Yellow,
+			6 => CfaColor::White,
+			_ => CfaColor::Other(value),
+		}
+	}
+
+	pub fn
+	as_u8
+	(
+		&self
+	)
+	-> u8
+	{
+		match self
+		{
+			CfaColor::Red          => 0,
+			CfaColor::Green        => 1,
+			CfaColor::Blue         => 2,
+			CfaColor::Cyan         => 3,
+			CfaColor::Magenta      => 4,
+			CfaColor::Yellow       => 5,
+			CfaColor::White        => 6,
+			CfaColor::Other(value) => *value,
+		}
+	}
+}
+
+/// The layout of the color filter array of an image sensor, as stored in the
+/// `CFAPattern` tag: The dimensions of the repeating pattern, followed by
+/// the color of each of its elements in row-major order.
+/// E.g. the common RGGB Bayer pattern has 2 rows and 2 columns with the
+/// colors `[Red, Green, Green, Blue]`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct
+CfaPattern
+{
+	pub columns: u16,
+	pub rows:    u16,
+	pub colors:  Vec<CfaColor>,
+}
+
+impl
+CfaPattern
+{
+	/// Decodes the value of the `CFAPattern` tag: Two `SHORT` values for
+	/// the number of columns and rows, followed by one byte per element.
+	/// Some cameras write the two `SHORT`s in a different byte order than
+	/// the rest of the EXIF data, so the other byte order is tried if the
+	/// dimensions don't match the data length.
+	/// Returns `None` if the data can't be interpreted either way.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::endian::Endian;
+	/// use little_exif::metadata::cfa::CfaColor;
+	/// use little_exif::metadata::cfa::CfaPattern;
+	///
+	/// let pattern = CfaPattern::decode(&[0, 2, 0, 2, 0, 1, 1, 2], &Endian::Big).unwrap();
+	/// assert_eq!(pattern.colors, vec![CfaColor::Red, CfaColor::Green, CfaColor::Green, CfaColor::Blue]);
+	/// ```
+	pub fn
+	decode
+	(
+		data:   &[u8],
+		endian: &Endian
+	)
+	-> Option<CfaPattern>
+	{
+		if data.len() < 4
+		{
+			return None;
+		}
+
+		let other_endian = match endian
+		{
+			Endian::Little => Endian::Big,
+			Endian::Big    => Endian::Little,
+		};
+
+		for current_endian in [endian, &other_endian]
+		{
+			let read_u16 = |bytes: [u8; 2]| match current_endian
+			{
+				Endian::Little => u16::from_le_bytes(bytes),
+				Endian::Big    => u16::from_be_bytes(bytes),
+			};
+
+			let columns = read_u16([data[0], data[1]]);
+			let rows    = read_u16([data[2], data[3]]);
+
+			if columns as usize * rows as usize == data.len() - 4
+			{
+				return Some(CfaPattern {
+					columns,
+					rows,
+					colors: data[4..].iter().map(|value| CfaColor::from_u8(*value)).collect(),
+				});
+			}
+		}
+
+		return None;
+	}
+
+	/// Encodes the pattern as value for the `CFAPattern` tag, using the given
+	/// byte order for the dimensions
+	pub fn
+	encode
+	(
+		&self,
+		endian: &Endian
+	)
+	-> Vec<u8>
+	{
+		let mut data = Vec::new();
+		match endian
+		{
+			Endian::Little => {
+				data.extend(self.columns.to_le_bytes());
+				data.extend(self.rows.to_le_bytes());
+			},
+			Endian::Big => {
+				data.extend(self.columns.to_be_bytes());
+				data.extend(self.rows.to_be_bytes());
+			},
+		}
+		data.extend(self.colors.iter().map(|color| color.as_u8()));
+		return data;
+	}
+}
+
+impl
+Metadata
+{
+	/// Gets the decoded `CFAPattern` tag (0xa302), see `CfaPattern::decode`
+	pub fn
+	get_cfa_pattern
+	(
+		&self
+	)
+	-> Option<CfaPattern>
+	{
+		if let Some(ExifTag::CFAPattern(value)) = self.get_tag(&ExifTag::CFAPattern(Vec::new())).next()
+		{
+			return CfaPattern::decode(value, &self.endian);
+		}
+		return None;
+	}
+
+	/// Sets the `CFAPattern` tag. Fails if the number of colors does not
+	/// match the dimensions of the pattern.
+	pub fn
+	set_cfa_pattern
+	(
+		&mut self,
+		cfa_pattern: &CfaPattern
+	)
+	-> Result<(), std::io::Error>
+	{
+		if cfa_pattern.columns as usize * cfa_pattern.rows as usize != cfa_pattern.colors.len()
+		{
+			return io_error!(
+				InvalidInput,
+				format!(
+					"CFA pattern of {}x{} elements requires {} colors, got {}",
+					cfa_pattern.columns,
+					cfa_pattern.rows,
+					cfa_pattern.columns as usize * cfa_pattern.rows as usize,
+					cfa_pattern.colors.len()
+				)
+			);
+		}
+
+		self.set_tag(ExifTag::CFAPattern(cfa_pattern.encode(&self.endian)));
+		return Ok(());
+	}
+
+	/// Gets the dimensions of the repeating CFA pattern as rows and columns
+	/// from the `CFARepeatPatternDim` tag (0x828d), which is used by raw
+	/// formats like DNG instead of the dimensions in `CFAPattern`
+	pub fn
+	get_cfa_repeat_pattern_dim
+	(
+		&self
+	)
+	-> Option<(u16, u16)>
+	{
+		if let Some(ExifTag::CFARepeatPatternDim(value)) = self.get_tag(&ExifTag::CFARepeatPatternDim(Vec::new())).next()
+		{
+			if let [rows, columns] = value[..]
+			{
+				return Some((rows, columns));
+			}
+		}
+		return None;
+	}
+
+	/// Sets the `CFARepeatPatternDim` tag
+	pub fn
+	set_cfa_repeat_pattern_dim
+	(
+		&mut self,
+		rows:    u16,
+		columns: u16
+	)
+	{
+		self.set_tag(ExifTag::CFARepeatPatternDim(vec![rows, columns]));
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use crate::endian::Endian;
+	use crate::filetype::FileExtension;
+	use crate::metadata::Metadata;
+
+	use super::CfaColor;
+	use super::CfaPattern;
+
+	#[test]
+	fn
+	decode_rggb_pattern()
+	{
+		let rggb = CfaPattern {
+			columns: 2,
+			rows:    2,
+			colors:  vec![CfaColor::Red, CfaColor::Green, CfaColor::Green, CfaColor::Blue],
+		};
+
+		assert_eq!(CfaPattern::decode(&[0x00, 0x02, 0x00, 0x02, 0x00, 0x01, 0x01, 0x02], &Endian::Big),    Some(rggb.clone()));
+		assert_eq!(CfaPattern::decode(&[0x02, 0x00, 0x02, 0x00, 0x00, 0x01, 0x01, 0x02], &Endian::Little), Some(rggb.clone()));
+
+		// Dimensions written in big endian in little endian EXIF data
+		assert_eq!(CfaPattern::decode(&[0x00, 0x02, 0x00, 0x02, 0x00, 0x01, 0x01, 0x02], &Endian::Little), Some(rggb.clone()));
+
+		// Dimensions don't match the number of colors
+		assert_eq!(CfaPattern::decode(&[0x00, 0x02, 0x00, 0x02, 0x00, 0x01, 0x01], &Endian::Big), None);
+
+		// Round trip via the metadata
+		let mut metadata = Metadata::new();
+		metadata.set_cfa_pattern(&rggb).unwrap();
+		metadata.set_cfa_repeat_pattern_dim(2, 2);
+
+		let metadata = Metadata::new_from_vec(&metadata.encode().unwrap(), FileExtension::TIFF).unwrap();
+		assert_eq!(metadata.get_cfa_pattern(),            Some(rggb));
+		assert_eq!(metadata.get_cfa_repeat_pattern_dim(), Some((2, 2)));
+
+		let mut invalid = CfaPattern::decode(&[0x00, 0x02, 0x00, 0x02, 0x00, 0x01, 0x01, 0x02], &Endian::Big).unwrap();
+		invalid.colors.pop();
+		assert!(Metadata::new().set_cfa_pattern(&invalid).is_err());
+	}
+}
@@ -0,0 +1,208 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::exif_tag::ExifTag;
+use crate::general_file_io::io_error;
+use crate::rational::uR64;
+
+use super::Metadata;
+
+/// The number of rationals of the `WhitePoint` tag (x and y)
+const WHITE_POINT_COUNT:            usize = 2;
+
+/// The number of rationals of the `PrimaryChromaticities` tag (x and y of
+/// the red, green and blue primaries)
+const PRIMARY_CHROMATICITIES_COUNT: usize = 6;
+
+/// The number of rationals of the `YCbCrCoefficients` tag (the weights of
+/// red, green and blue for computing the luma)
+const YCBCR_COEFFICIENTS_COUNT:     usize = 3;
+
+/// Checks that a rational array tag has the number of components required by
+/// the standard and none of them has a zero denominator
+fn
+check_rationals
+(
+	tag_name:       &str,
+	values:         &[uR64],
+	expected_count: usize
+)
+-> Result<(), std::io::Error>
+{
+	if values.len() != expected_count
+	{
+		return io_error!(
+			InvalidInput,
+			format!("{} requires {} values, got {}", tag_name, expected_count, values.len())
+		);
+	}
+
+	if values.iter().any(|value| value.denominator == 0)
+	{
+		return io_error!(InvalidInput, format!("{} values must not have a zero denominator", tag_name));
+	}
+
+	return Ok(());
+}
+
+impl
+Metadata
+{
+	/// Gets the chromaticity of the white point of the image from the
+	/// `WhitePoint` tag (0x013e) as x and y, e.g. 0.3127 and 0.3290 for D65.
+	/// Returns `None` if the tag is absent or has not exactly 2 components.
+	pub fn
+	get_white_point
+	(
+		&self
+	)
+	-> Option<Vec<uR64>>
+	{
+		match self.get_tag(&ExifTag::WhitePoint(Vec::new())).next()
+		{
+			Some(ExifTag::WhitePoint(value)) if value.len() == WHITE_POINT_COUNT => Some(value.clone()),
+			_                                                                    => None,
+		}
+	}
+
+	/// Sets the `WhitePoint` tag, see `get_white_point`
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::rational::uR64;
+	///
+	/// let mut metadata = Metadata::new();
+	/// metadata.set_white_point(&[
+	///     uR64 { nominator: 3127, denominator: 10000 },
+	///     uR64 { nominator: 3290, denominator: 10000 },
+	/// ]).unwrap();
+	/// ```
+	pub fn
+	set_white_point
+	(
+		&mut self,
+		white_point: &[uR64]
+	)
+	-> Result<(), std::io::Error>
+	{
+		check_rationals("WhitePoint", white_point, WHITE_POINT_COUNT)?;
+		self.set_tag(ExifTag::WhitePoint(white_point.to_vec()));
+		return Ok(());
+	}
+
+	/// Gets the chromaticities of the red, green and blue primaries from the
+	/// `PrimaryChromaticities` tag (0x013f) as x and y of each, i.e. in the
+	/// order red x, red y, green x, green y, blue x, blue y.
+	/// Returns `None` if the tag is absent or has not exactly 6 components.
+	pub fn
+	get_primary_chromaticities
+	(
+		&self
+	)
+	-> Option<Vec<uR64>>
+	{
+		match self.get_tag(&ExifTag::PrimaryChromaticities(Vec::new())).next()
+		{
+			Some(ExifTag::PrimaryChromaticities(value)) if value.len() == PRIMARY_CHROMATICITIES_COUNT => Some(value.clone()),
+			_                                                                                          => None,
+		}
+	}
+
+	/// Sets the `PrimaryChromaticities` tag, see `get_primary_chromaticities`
+	pub fn
+	set_primary_chromaticities
+	(
+		&mut self,
+		primary_chromaticities: &[uR64]
+	)
+	-> Result<(), std::io::Error>
+	{
+		check_rationals("PrimaryChromaticities", primary_chromaticities, PRIMARY_CHROMATICITIES_COUNT)?;
+		self.set_tag(ExifTag::PrimaryChromaticities(primary_chromaticities.to_vec()));
+		return Ok(());
+	}
+
+	/// Gets the coefficients for transforming RGB to YCbCr image data from
+	/// the `YCbCrCoefficients` tag (0x0211), i.e. the weights of red, green
+	/// and blue in the luma, e.g. 0.299, 0.587 and 0.114 for Rec. 601.
+	/// Returns `None` if the tag is absent or has not exactly 3 components.
+	pub fn
+	get_ycbcr_coefficients
+	(
+		&self
+	)
+	-> Option<Vec<uR64>>
+	{
+		match self.get_tag(&ExifTag::YCbCrCoefficients(Vec::new())).next()
+		{
+			Some(ExifTag::YCbCrCoefficients(value)) if value.len() == YCBCR_COEFFICIENTS_COUNT => Some(value.clone()),
+			_                                                                                  => None,
+		}
+	}
+
+	/// Sets the `YCbCrCoefficients` tag, see `get_ycbcr_coefficients`
+	pub fn
+	set_ycbcr_coefficients
+	(
+		&mut self,
+		ycbcr_coefficients: &[uR64]
+	)
+	-> Result<(), std::io::Error>
+	{
+		check_rationals("YCbCrCoefficients", ycbcr_coefficients, YCBCR_COEFFICIENTS_COUNT)?;
+		self.set_tag(ExifTag::YCbCrCoefficients(ycbcr_coefficients.to_vec()));
+		return Ok(());
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use crate::exif_tag::ExifTag;
+	use crate::filetype::FileExtension;
+	use crate::metadata::Metadata;
+	use crate::rational::uR64;
+
+	#[test]
+	fn
+	d65_white_point()
+	{
+		let d65 = vec![
+			uR64 { nominator: 3127, denominator: 10000 },
+			uR64 { nominator: 3290, denominator: 10000 },
+		];
+
+		let mut metadata = Metadata::new();
+		assert!(metadata.set_white_point(&d65[..1]).is_err());
+		assert!(metadata.set_white_point(&[d65[0].clone(), uR64 { nominator: 1, denominator: 0 }]).is_err());
+		metadata.set_white_point(&d65).unwrap();
+
+		let metadata = Metadata::new_from_vec(&metadata.encode().unwrap(), FileExtension::TIFF).unwrap();
+		assert_eq!(metadata.get_white_point(),            Some(d65));
+		assert_eq!(metadata.get_primary_chromaticities(), None);
+	}
+
+	#[test]
+	fn
+	rec601_ycbcr_coefficients()
+	{
+		let rec601 = vec![
+			uR64 { nominator: 299, denominator: 1000 },
+			uR64 { nominator: 587, denominator: 1000 },
+			uR64 { nominator: 114, denominator: 1000 },
+		];
+
+		let mut metadata = Metadata::new();
+		assert!(metadata.set_ycbcr_coefficients(&[rec601.clone(), rec601.clone()].concat()).is_err());
+		metadata.set_ycbcr_coefficients(&rec601).unwrap();
+
+		let metadata = Metadata::new_from_vec(&metadata.encode().unwrap(), FileExtension::TIFF).unwrap();
+		assert_eq!(metadata.get_ycbcr_coefficients(), Some(rec601));
+
+		// A tag with the wrong number of components is not returned
+		let mut metadata = metadata;
+		metadata.set_tag(ExifTag::YCbCrCoefficients(vec![uR64 { nominator: 1, denominator: 1 }]));
+		assert_eq!(metadata.get_ycbcr_coefficients(), None);
+	}
+}
@@ -0,0 +1,221 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::endian::Endian;
+use crate::exif_tag::ExifTag;
+
+use super::Metadata;
+
+/// Length of the character code prefix of tags like `UserComment`
+const CHARACTER_CODE_LENGTH: usize = 8;
+
+/// The character code stored in the first 8 bytes of the `UNDEF` tags
+/// `UserComment` (0x9286), `GPSProcessingMethod` (0x001b) and
+/// `GPSAreaInformation` (0x001c), telling how the remaining bytes are encoded
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+CharacterCode
+{
+	Ascii,                                                                      // "ASCII\0\0\0"
+	Jis,                                                                        // "JIS\0\0\0\0\0"
+	Unicode,                                                                    // "UNICODE\0" (UCS-2 using the byte order of the EXIF data)
+	Undefined,                                                                  // "\0\0\0\0\0\0\0\0"
+}
+
+impl
+CharacterCode
+{
+	fn
+	from_prefix
+	(
+		prefix: &[u8]
+	)
+	-> Option<CharacterCode>
+	{
+		match prefix
+		{
+			b"ASCII\0\0\0"           => Some(CharacterCode::Ascii),
+			b"JIS\0\0\0\0\0"         => Some(CharacterCode::Jis),
+			b"UNICODE\0"             => Some(CharacterCode::Unicode),
+			[0, 0, 0, 0, 0, 0, 0, 0] => Some(CharacterCode::Undefined),
+			_                        => None,
+		}
+	}
+
+	fn
+	as_prefix
+	(
+		&self
+	)
+	-> &'static [u8; CHARACTER_CODE_LENGTH]
+	{
+		match self
+		{
+			CharacterCode::Ascii     => b"ASCII\0\0\0",
+			CharacterCode::Jis       => b"JIS\0\0\0\0\0",
+			CharacterCode::Unicode   => b"UNICODE\0",
+			CharacterCode::Undefined => b"\0\0\0\0\0\0\0\0",
+		}
+	}
+}
+
+/// Decodes the value of a character code prefixed tag into its character
+/// code and text. Trailing NUL characters and spaces (used as padding by
+/// some cameras) are removed. JIS encoded text is not supported and results
+/// in `None`, as does an unknown character code.
+pub(crate) fn
+decode_character_coded
+(
+	value:  &[u8],
+	endian: &Endian
+)
+-> Option<(CharacterCode, String)>
+{
+	if value.len() < CHARACTER_CODE_LENGTH
+	{
+		return None;
+	}
+
+	let character_code = CharacterCode::from_prefix(&value[..CHARACTER_CODE_LENGTH])?;
+	let text_bytes     = &value[CHARACTER_CODE_LENGTH..];
+
+	let text = match character_code
+	{
+		CharacterCode::Ascii | CharacterCode::Undefined
+			=> String::from_utf8_lossy(text_bytes).to_string(),
+
+		CharacterCode::Unicode
+			=> String::from_utf16_lossy(&text_bytes.chunks_exact(2)
+				.map(|pair| match endian
+				{
+					Endian::Little => u16::from_le_bytes([pair[0], pair[1]]),
+					Endian::Big    => u16::from_be_bytes([pair[0], pair[1]]),
+				})
+				.collect::<Vec<u16>>()
+			),
+
+		CharacterCode::Jis
+			=> return None,
+	};
+
+	return Some((character_code, text.trim_end_matches(['\0', ' ']).to_string()));
+}
+
+/// Encodes the text for a character code prefixed tag, using the `ASCII`
+/// character code if possible and `UNICODE` otherwise
+pub(crate) fn
+encode_character_coded
+(
+	text:   &str,
+	endian: &Endian
+)
+-> Vec<u8>
+{
+	if text.is_ascii()
+	{
+		let mut value = CharacterCode::Ascii.as_prefix().to_vec();
+		value.extend(text.as_bytes());
+		return value;
+	}
+
+	let mut value = CharacterCode::Unicode.as_prefix().to_vec();
+	for unit in text.encode_utf16()
+	{
+		match endian
+		{
+			Endian::Little => value.extend(unit.to_le_bytes()),
+			Endian::Big    => value.extend(unit.to_be_bytes()),
+		}
+	}
+	return value;
+}
+
+impl
+Metadata
+{
+	/// Gets the text of the `UserComment` tag, decoded according to its
+	/// character code prefix
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some(comment) = metadata.get_user_comment()
+	/// {
+	///     println!("{}", comment);
+	/// }
+	/// # }
+	/// ```
+	pub fn
+	get_user_comment
+	(
+		&self
+	)
+	-> Option<String>
+	{
+		if let Some(ExifTag::UserComment(value)) = self.get_tag(&ExifTag::UserComment(Vec::new())).next()
+		{
+			return decode_character_coded(value, &self.endian).map(|(_, text)| text);
+		}
+		return None;
+	}
+
+	/// Sets the `UserComment` tag, using the `ASCII` character code if the
+	/// text only consists of ASCII characters and `UNICODE` otherwise
+	pub fn
+	set_user_comment
+	(
+		&mut self,
+		comment: &str
+	)
+	{
+		self.set_tag(ExifTag::UserComment(encode_character_coded(comment, &self.endian)));
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use crate::endian::Endian;
+	use crate::metadata::Metadata;
+
+	use super::decode_character_coded;
+	use super::encode_character_coded;
+	use super::CharacterCode;
+
+	#[test]
+	fn
+	character_code_round_trip()
+	{
+		for endian in [Endian::Little, Endian::Big]
+		{
+			let encoded = encode_character_coded("Grüße", &endian);
+			assert!(encoded.starts_with(b"UNICODE\0"));
+			assert_eq!(
+				decode_character_coded(&encoded, &endian),
+				Some((CharacterCode::Unicode, "Grüße".to_string()))
+			);
+		}
+
+		assert_eq!(
+			decode_character_coded(b"\0\0\0\0\0\0\0\0Padded   ", &Endian::Little),
+			Some((CharacterCode::Undefined, "Padded".to_string()))
+		);
+		assert_eq!(decode_character_coded(b"JIS\0\0\0\0\0abc", &Endian::Little), None);
+		assert_eq!(decode_character_coded(b"ASCII",            &Endian::Little), None);
+	}
+
+	#[test]
+	fn
+	set_and_get_user_comment()
+	{
+		let mut metadata = Metadata::new();
+		assert_eq!(metadata.get_user_comment(), None);
+
+		metadata.set_user_comment("Hello, World!");
+		assert_eq!(metadata.get_user_comment(), Some("Hello, World!".to_string()));
+	}
+}
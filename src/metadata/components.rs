@@ -0,0 +1,251 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::exif_tag::ExifTag;
+use crate::general_file_io::io_error;
+
+use super::Metadata;
+
+/// The number of bytes of the `ComponentsConfiguration` tag (0x9101)
+const COMPONENTS_CONFIGURATION_LENGTH: usize = 4;
+
+/// A single channel as listed by the `ComponentsConfiguration` tag
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+ComponentType
+{
+	DoesNotExist,                                                               // 0
+	Y,                                                                          // 1
+	Cb,                                                                         // 2
+	Cr,                                                                         // 3
+	R,                                                                          // 4
+	G,                                                                          // 5
+	B,                                                                          // 6
+	Other(u8),
+}
+
+impl
+ComponentType
+{
+	pub fn
+	from_u8
+	(
+		value: u8
+	)
+	-> ComponentType
+	{
+		match value
+		{
+			0 => ComponentType::DoesNotExist,
+			1 => ComponentType::Y,
+			2 => ComponentType::Cb,
+			3 => ComponentType::Cr,
+			4 => ComponentType::R,
+			5 => ComponentType::G,
+			6 => ComponentType::B,
+			_ => ComponentType::Other(value),
+		}
+	}
+
+	pub fn
+	as_u8
+	(
+		&self
+	)
+	-> u8
+	{
+		match self
+		{
+			ComponentType::DoesNotExist => 0,
+			ComponentType::Y            => 1,
+			ComponentType::Cb           => 2,
+			ComponentType::Cr           => 3,
+			ComponentType::R            => 4,
+			ComponentType::G            => 5,
+			ComponentType::B            => 6,
+			ComponentType::Other(value) => *value,
+		}
+	}
+}
+
+impl
+Metadata
+{
+	/// Gets the order of the channels from the `ComponentsConfiguration` tag
+	/// (0x9101), e.g. `[Y, Cb, Cr]` for compressed JPEG data. The trailing
+	/// `DoesNotExist` entries padding the tag to 4 bytes are not returned.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::metadata::components::ComponentType;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if metadata.get_components_configuration() == Some(vec![ComponentType::R, ComponentType::G, ComponentType::B])
+	/// {
+	///     println!("Uncompressed RGB data");
+	/// }
+	/// # }
+	/// ```
+	pub fn
+	get_components_configuration
+	(
+		&self
+	)
+	-> Option<Vec<ComponentType>>
+	{
+		if let Some(ExifTag::ComponentsConfiguration(value)) = self.get_tag(&ExifTag::ComponentsConfiguration(Vec::new())).next()
+		{
+			let mut components = value.iter()
+				.map(|component| ComponentType::from_u8(*component))
+				.collect::<Vec<ComponentType>>();
+
+			while components.last() == Some(&ComponentType::DoesNotExist)
+			{
+				components.pop();
+			}
+			return Some(components);
+		}
+		return None;
+	}
+
+	/// Sets the `ComponentsConfiguration` tag, padding the given channels
+	/// with `DoesNotExist` to 4 bytes. Fails if no or more than 4 channels
+	/// are given.
+	pub fn
+	set_components_configuration
+	(
+		&mut self,
+		components: &[ComponentType]
+	)
+	-> Result<(), std::io::Error>
+	{
+		if components.is_empty() || components.len() > COMPONENTS_CONFIGURATION_LENGTH
+		{
+			return io_error!(
+				InvalidInput,
+				format!(
+					"ComponentsConfiguration requires 1 to {} components, got {}",
+					COMPONENTS_CONFIGURATION_LENGTH,
+					components.len()
+				)
+			);
+		}
+
+		let mut value = components.iter().map(|component| component.as_u8()).collect::<Vec<u8>>();
+		value.resize(COMPONENTS_CONFIGURATION_LENGTH, ComponentType::DoesNotExist.as_u8());
+
+		self.set_tag(ExifTag::ComponentsConfiguration(value));
+		return Ok(());
+	}
+
+	/// Gets the number of bits of each channel from the `BitsPerSample` tag
+	/// (0x0102), e.g. `[8, 8, 8]` for 8-bit RGB data
+	pub fn
+	get_bits_per_sample
+	(
+		&self
+	)
+	-> Option<Vec<u16>>
+	{
+		if let Some(ExifTag::BitsPerSample(value)) = self.get_tag(&ExifTag::BitsPerSample(Vec::new())).next()
+		{
+			return Some(value.clone());
+		}
+		return None;
+	}
+
+	/// Sets the `BitsPerSample` tag. Fails if no value is given or - in case
+	/// the `SamplesPerPixel` tag (0x0115) exists - if the number of values
+	/// does not match the number of samples per pixel (e.g. 3 for RGB).
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let mut metadata = Metadata::new_from_path(std::path::Path::new("image.tif")).unwrap();
+	/// metadata.set_bits_per_sample(&[8, 8, 8]).unwrap();
+	/// # }
+	/// ```
+	pub fn
+	set_bits_per_sample
+	(
+		&mut self,
+		bits_per_sample: &[u16]
+	)
+	-> Result<(), std::io::Error>
+	{
+		if bits_per_sample.is_empty()
+		{
+			return io_error!(InvalidInput, "BitsPerSample requires at least one value");
+		}
+
+		if let Some(ExifTag::SamplesPerPixel(value)) = self.get_tag(&ExifTag::SamplesPerPixel(Vec::new())).next()
+		{
+			if let Some(samples_per_pixel) = value.first()
+			{
+				if *samples_per_pixel as usize != bits_per_sample.len()
+				{
+					return io_error!(
+						InvalidInput,
+						format!(
+							"BitsPerSample requires {} values as given by SamplesPerPixel, got {}",
+							samples_per_pixel,
+							bits_per_sample.len()
+						)
+					);
+				}
+			}
+		}
+
+		self.set_tag(ExifTag::BitsPerSample(bits_per_sample.to_vec()));
+		return Ok(());
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use crate::exif_tag::ExifTag;
+	use crate::filetype::FileExtension;
+	use crate::metadata::Metadata;
+
+	use super::ComponentType;
+
+	#[test]
+	fn
+	components_configuration_round_trip()
+	{
+		let ycbcr = vec![ComponentType::Y, ComponentType::Cb, ComponentType::Cr];
+
+		let mut metadata = Metadata::new();
+		metadata.set_components_configuration(&ycbcr).unwrap();
+		assert_eq!(
+			metadata.get_tag(&ExifTag::ComponentsConfiguration(Vec::new())).next(),
+			Some(&ExifTag::ComponentsConfiguration(vec![1, 2, 3, 0]))
+		);
+
+		let metadata = Metadata::new_from_vec(&metadata.encode().unwrap(), FileExtension::TIFF).unwrap();
+		assert_eq!(metadata.get_components_configuration(), Some(ycbcr));
+
+		assert!(Metadata::new().set_components_configuration(&[]).is_err());
+		assert!(Metadata::new().set_components_configuration(&[ComponentType::R; 5]).is_err());
+	}
+
+	#[test]
+	fn
+	bits_per_sample_round_trip()
+	{
+		let mut metadata = Metadata::new();
+		metadata.set_tag(ExifTag::SamplesPerPixel(vec![3]));
+		assert!(metadata.set_bits_per_sample(&[8]).is_err());
+		metadata.set_bits_per_sample(&[8, 8, 8]).unwrap();
+
+		let metadata = Metadata::new_from_vec(&metadata.encode().unwrap(), FileExtension::TIFF).unwrap();
+		assert_eq!(metadata.get_bits_per_sample(), Some(vec![8, 8, 8]));
+	}
+}
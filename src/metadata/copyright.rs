@@ -0,0 +1,170 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::exif_tag::ExifTag;
+
+use super::Metadata;
+
+/// Separator between multiple names in the `Artist` tag as recommended by
+/// the EXIF standard
+const ARTIST_SEPARATOR: &str = "; ";
+
+impl
+Metadata
+{
+	/// Sets the `Copyright` tag (0x8298). According to the EXIF standard,
+	/// the photographer and the editor copyright are stored in the same
+	/// string, separated by a NUL character. If only the editor copyright is
+	/// given (i.e. `photographer` is empty), the photographer part is a
+	/// single space.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let mut metadata = Metadata::new();
+	/// metadata.set_copyright("Jane Doe, 2024", Some("John Doe, 2024"));
+	/// ```
+	pub fn
+	set_copyright
+	(
+		&mut self,
+		photographer: &str,
+		editor:       Option<&str>
+	)
+	{
+		let value = match editor
+		{
+			Some(editor) if !editor.is_empty() => {
+				let photographer = if photographer.is_empty() { " " } else { photographer };
+				format!("{}\0{}", photographer, editor)
+			},
+			_ => photographer.to_string(),
+		};
+
+		self.set_tag(ExifTag::Copyright(value));
+	}
+
+	/// Gets the photographer and - if present - the editor copyright stored
+	/// in the `Copyright` tag. An empty photographer part (stored as a
+	/// single space) is returned as empty string.
+	pub fn
+	get_copyright
+	(
+		&self
+	)
+	-> Option<(String, Option<String>)>
+	{
+		if let Some(ExifTag::Copyright(value)) = self.get_tag(&ExifTag::Copyright(String::new())).next()
+		{
+			let mut parts        = value.splitn(2, '\0');
+			let     photographer = parts.next().unwrap_or_default();
+			let     editor       = parts.next()
+				.map(|editor| editor.trim_end_matches('\0'))
+				.filter(|editor| !editor.is_empty())
+				.map(|editor| editor.to_string());
+
+			let photographer = if photographer == " " { "" } else { photographer };
+
+			return Some((photographer.to_string(), editor));
+		}
+		return None;
+	}
+
+	/// Sets the `Artist` tag (0x013b) to the given names, separated by a
+	/// semicolon followed by a space as recommended by the EXIF standard.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let mut metadata = Metadata::new();
+	/// metadata.set_artists(&["Jane Doe", "John Doe"]);
+	/// ```
+	pub fn
+	set_artists
+	(
+		&mut self,
+		artists: &[&str]
+	)
+	{
+		self.set_tag(ExifTag::Artist(artists.join(ARTIST_SEPARATOR)));
+	}
+
+	/// Gets the names listed in the `Artist` tag. Returns an empty vector if
+	/// the tag is not present.
+	pub fn
+	get_artists
+	(
+		&self
+	)
+	-> Vec<String>
+	{
+		if let Some(ExifTag::Artist(value)) = self.get_tag(&ExifTag::Artist(String::new())).next()
+		{
+			return value
+				.split(';')
+				.map(|name| name.trim())
+				.filter(|name| !name.is_empty())
+				.map(|name| name.to_string())
+				.collect();
+		}
+		return Vec::new();
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use crate::exif_tag::ExifTag;
+	use crate::metadata::Metadata;
+
+	#[test]
+	fn
+	copyright_round_trip()
+	{
+		let mut metadata = Metadata::new();
+		assert_eq!(metadata.get_copyright(), None);
+
+		metadata.set_copyright("Jane Doe, 2024", Some("John Doe, 2024"));
+		assert_eq!(
+			metadata.get_tag(&ExifTag::Copyright(String::new())).next(),
+			Some(&ExifTag::Copyright("Jane Doe, 2024\0John Doe, 2024".to_string()))
+		);
+		assert_eq!(
+			metadata.get_copyright(),
+			Some(("Jane Doe, 2024".to_string(), Some("John Doe, 2024".to_string())))
+		);
+
+		metadata.set_copyright("", Some("John Doe, 2024"));
+		assert_eq!(
+			metadata.get_tag(&ExifTag::Copyright(String::new())).next(),
+			Some(&ExifTag::Copyright(" \0John Doe, 2024".to_string()))
+		);
+		assert_eq!(
+			metadata.get_copyright(),
+			Some((String::new(), Some("John Doe, 2024".to_string())))
+		);
+
+		metadata.set_copyright("Jane Doe, 2024", None);
+		assert_eq!(
+			metadata.get_copyright(),
+			Some(("Jane Doe, 2024".to_string(), None))
+		);
+	}
+
+	#[test]
+	fn
+	artists_round_trip()
+	{
+		let mut metadata = Metadata::new();
+		assert!(metadata.get_artists().is_empty());
+
+		metadata.set_artists(&["Jane Doe", "John Doe"]);
+		assert_eq!(
+			metadata.get_tag(&ExifTag::Artist(String::new())).next(),
+			Some(&ExifTag::Artist("Jane Doe; John Doe".to_string()))
+		);
+		assert_eq!(metadata.get_artists(), vec!["Jane Doe", "John Doe"]);
+	}
+}
@@ -0,0 +1,615 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::exif_tag::ExifTag;
+
+use super::Metadata;
+
+/// The maximum number of fractional digits that can be represented in the
+/// nanoseconds of an `ExifDateTime`
+const MAX_SUB_SEC_DIGITS: usize = 9;
+
+/// The three date/time values stored in EXIF, each of which consists of a
+/// date/time tag and a sub second tag:
+/// - `Modify`:    `ModifyDate` (0x0132) and `SubSecTime` (0x9290)
+/// - `Original`:  `DateTimeOriginal` (0x9003) and `SubSecTimeOriginal` (0x9291)
+/// - `Digitized`: `CreateDate` (0x9004) and `SubSecTimeDigitized` (0x9292)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+DateTimeKind
+{
+	Modify,
+	Original,
+	Digitized,
+}
+
+impl
+DateTimeKind
+{
+	fn
+	date_time_tag
+	(
+		&self,
+		value: String
+	)
+	-> ExifTag
+	{
+		match self
+		{
+			DateTimeKind::Modify    => ExifTag::ModifyDate(value),
+			DateTimeKind::Original  => ExifTag::DateTimeOriginal(value),
+			DateTimeKind::Digitized => ExifTag::CreateDate(value),
+		}
+	}
+
+	fn
+	sub_sec_tag
+	(
+		&self,
+		value: String
+	)
+	-> ExifTag
+	{
+		match self
+		{
+			DateTimeKind::Modify    => ExifTag::SubSecTime(value),
+			DateTimeKind::Original  => ExifTag::SubSecTimeOriginal(value),
+			DateTimeKind::Digitized => ExifTag::SubSecTimeDigitized(value),
+		}
+	}
+}
+
+/// A date and time as stored in EXIF ("YYYY:MM:DD HH:MM:SS"), together with
+/// the fractional seconds from the corresponding sub second tag.
+/// Note that EXIF date/time values don't carry a time zone.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct
+ExifDateTime
+{
+	pub year:       u16,
+	pub month:      u8,
+	pub day:        u8,
+	pub hour:       u8,
+	pub minute:     u8,
+	pub second:     u8,
+	pub nanosecond: u32,
+}
+
+impl
+ExifDateTime
+{
+	/// Parses a date/time string as stored in EXIF ("YYYY:MM:DD HH:MM:SS").
+	/// The fractional seconds are set to zero.
+	pub fn
+	parse
+	(
+		value: &str
+	)
+	-> Option<ExifDateTime>
+	{
+		let value = value.trim_end_matches('\0');
+		if value.len() != 19
+		{
+			return None;
+		}
+
+		let bytes = value.as_bytes();
+		for (position, separator) in [(4, b':'), (7, b':'), (10, b' '), (13, b':'), (16, b':')]
+		{
+			if bytes[position] != separator
+			{
+				return None;
+			}
+		}
+
+		let number = |start: usize, end: usize| value.get(start..end)?.parse::<u16>().ok();
+
+		return Some(ExifDateTime {
+			year:       number( 0,  4)?,
+			month:      number( 5,  7)? as u8,
+			day:        number( 8, 10)? as u8,
+			hour:       number(11, 13)? as u8,
+			minute:     number(14, 16)? as u8,
+			second:     number(17, 19)? as u8,
+			nanosecond: 0,
+		});
+	}
+
+	/// Formats the date and time as stored in EXIF ("YYYY:MM:DD HH:MM:SS"),
+	/// without the fractional seconds
+	pub fn
+	to_exif_string
+	(
+		&self
+	)
+	-> String
+	{
+		return format!(
+			"{:04}:{:02}:{:02} {:02}:{:02}:{:02}",
+			self.year, self.month, self.day, self.hour, self.minute, self.second
+		);
+	}
+
+	/// Gets the fractional seconds in milliseconds
+	pub fn
+	millisecond
+	(
+		&self
+	)
+	-> u32
+	{
+		return self.nanosecond / 1_000_000;
+	}
+
+	/// Converts into a `chrono::NaiveDateTime`, failing for invalid dates
+	/// or times (e.g. the "0000:00:00 00:00:00" some cameras write)
+	#[cfg(feature = "chrono")]
+	pub fn
+	to_naive_date_time
+	(
+		&self
+	)
+	-> Option<chrono::NaiveDateTime>
+	{
+		return chrono::NaiveDate::from_ymd_opt(self.year as i32, self.month as u32, self.day as u32)?
+			.and_hms_nano_opt(self.hour as u32, self.minute as u32, self.second as u32, self.nanosecond);
+	}
+
+	/// Creates a new `ExifDateTime` from a `chrono::NaiveDateTime`. Fails
+	/// if the year can't be represented using four digits.
+	#[cfg(feature = "chrono")]
+	pub fn
+	from_naive_date_time
+	(
+		date_time: &chrono::NaiveDateTime
+	)
+	-> Option<ExifDateTime>
+	{
+		use chrono::Datelike;
+		use chrono::Timelike;
+
+		if !(0..=9999).contains(&date_time.year())
+		{
+			return None;
+		}
+
+		return Some(ExifDateTime {
+			year:       date_time.year()   as u16,
+			month:      date_time.month()  as u8,
+			day:        date_time.day()    as u8,
+			hour:       date_time.hour()   as u8,
+			minute:     date_time.minute() as u8,
+			second:     date_time.second() as u8,
+			nanosecond: date_time.nanosecond() % 1_000_000_000,
+		});
+	}
+}
+
+/// Parses the ASCII digits of a sub second tag into nanoseconds. The digits
+/// are the fractional part of the second, so "5" is 500ms and "123" is 123ms.
+/// Trailing spaces (as allowed by the EXIF standard) and a leading decimal
+/// point are ignored; digits beyond nanosecond precision are dropped.
+pub(crate) fn
+parse_sub_sec
+(
+	value: &str
+)
+-> Option<u32>
+{
+	let digits = value.trim_end_matches('\0').trim();
+	let digits = digits.strip_prefix('.').unwrap_or(digits);
+
+	if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit())
+	{
+		return None;
+	}
+
+	let mut padded = digits.chars().take(MAX_SUB_SEC_DIGITS).collect::<String>();
+	while padded.len() < MAX_SUB_SEC_DIGITS
+	{
+		padded.push('0');
+	}
+
+	return padded.parse::<u32>().ok();
+}
+
+/// Formats nanoseconds as ASCII digits for a sub second tag, without
+/// trailing zeros (but at least one digit)
+pub(crate) fn
+format_sub_sec
+(
+	nanosecond: u32
+)
+-> String
+{
+	let digits  = format!("{:09}", nanosecond);
+	let trimmed = digits.trim_end_matches('0');
+	if trimmed.is_empty()
+	{
+		return String::from("0");
+	}
+	return trimmed.to_string();
+}
+
+impl
+Metadata
+{
+	/// Gets the fractional seconds (in nanoseconds) stored in the sub second
+	/// tag belonging to the given kind of date/time, e.g. `SubSecTimeOriginal`
+	/// for `DateTimeKind::Original`.
+	pub fn
+	get_sub_sec_time
+	(
+		&self,
+		kind: DateTimeKind
+	)
+	-> Option<u32>
+	{
+		match self.get_tag(&kind.sub_sec_tag(String::new())).next()
+		{
+			Some(ExifTag::SubSecTime(value))
+			| Some(ExifTag::SubSecTimeOriginal(value))
+			| Some(ExifTag::SubSecTimeDigitized(value))
+			=> parse_sub_sec(value),
+
+			_
+			=> None,
+		}
+	}
+
+	/// Sets the sub second tag belonging to the given kind of date/time to
+	/// the given fractional seconds (in nanoseconds)
+	pub fn
+	set_sub_sec_time
+	(
+		&mut self,
+		kind:       DateTimeKind,
+		nanosecond: u32
+	)
+	{
+		self.set_tag(kind.sub_sec_tag(format_sub_sec(nanosecond % 1_000_000_000)));
+	}
+
+	/// Gets the given kind of date/time with the fractional seconds merged in
+	/// from the corresponding sub second tag, if present.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::metadata::datetime::DateTimeKind;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some(date_time) = metadata.get_date_time(DateTimeKind::Original)
+	/// {
+	///     println!("{}.{:03}", date_time.to_exif_string(), date_time.millisecond());
+	/// }
+	/// # }
+	/// ```
+	pub fn
+	get_date_time
+	(
+		&self,
+		kind: DateTimeKind
+	)
+	-> Option<ExifDateTime>
+	{
+		let mut date_time = match self.get_tag(&kind.date_time_tag(String::new())).next()
+		{
+			Some(ExifTag::ModifyDate(value))
+			| Some(ExifTag::DateTimeOriginal(value))
+			| Some(ExifTag::CreateDate(value))
+			=> ExifDateTime::parse(value)?,
+
+			_
+			=> return None,
+		};
+
+		date_time.nanosecond = self.get_sub_sec_time(kind).unwrap_or(0);
+
+		return Some(date_time);
+	}
+
+	/// Sets the given kind of date/time. The fractional seconds are written
+	/// to the corresponding sub second tag if they are non-zero, otherwise an
+	/// existing sub second tag is removed so the two don't contradict.
+	pub fn
+	set_date_time
+	(
+		&mut self,
+		kind:      DateTimeKind,
+		date_time: &ExifDateTime
+	)
+	{
+		self.set_tag(kind.date_time_tag(date_time.to_exif_string()));
+
+		if date_time.nanosecond != 0
+		{
+			self.set_sub_sec_time(kind, date_time.nanosecond);
+		}
+		else if self.get_sub_sec_time(kind).is_some()
+		{
+			let sub_sec_tag = kind.sub_sec_tag(String::new());
+			self.get_ifd_mut(sub_sec_tag.get_group(), 0).remove_tag(sub_sec_tag);
+		}
+	}
+
+	/// Shifts all timestamps by the given (signed) delta, e.g. to correct a
+	/// wrongly set camera clock. This affects the `ModifyDate`,
+	/// `DateTimeOriginal` and `CreateDate` tags (including their sub second
+	/// tags) as well as the UTC based `GPSDateStamp` and `GPSTimeStamp` if
+	/// both of them are present. Timestamps that can't be parsed are left
+	/// untouched.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let mut metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// metadata.shift_timestamps(chrono::Duration::hours(-2));
+	/// ```
+	#[cfg(feature = "chrono")]
+	pub fn
+	shift_timestamps
+	(
+		&mut self,
+		delta: chrono::Duration
+	)
+	{
+		for kind in [DateTimeKind::Modify, DateTimeKind::Original, DateTimeKind::Digitized]
+		{
+			let shifted = self.get_date_time(kind)
+				.and_then(|date_time| date_time.to_naive_date_time())
+				.and_then(|date_time| date_time.checked_add_signed(delta))
+				.and_then(|date_time| ExifDateTime::from_naive_date_time(&date_time));
+
+			if let Some(shifted) = shifted
+			{
+				self.set_date_time(kind, &shifted);
+			}
+		}
+
+		if let Some(shifted) = self.get_gps_date_time()
+			.and_then(|date_time| date_time.checked_add_signed(delta))
+		{
+			self.set_gps_date_time(&shifted);
+		}
+	}
+
+	/// Sets the given kinds of date/time to the current local time (with
+	/// millisecond precision) and their `OffsetTime...` tags to the current
+	/// offset from UTC, e.g. "+02:00". When updating an existing image, only
+	/// `DateTimeKind::Modify` should be passed; for freshly created images,
+	/// all three kinds usually apply.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::metadata::datetime::DateTimeKind;
+	///
+	/// let mut metadata = Metadata::new();
+	/// metadata.stamp_now(&[DateTimeKind::Modify, DateTimeKind::Original, DateTimeKind::Digitized]);
+	/// ```
+	#[cfg(feature = "chrono")]
+	pub fn
+	stamp_now
+	(
+		&mut self,
+		kinds: &[DateTimeKind]
+	)
+	{
+		use chrono::Offset;
+		use super::xmp::XmpDateTime;
+
+		let now = chrono::Local::now();
+		let mut date_time = match ExifDateTime::from_naive_date_time(&now.naive_local())
+		{
+			Some(date_time) => date_time,
+			None            => return,
+		};
+		date_time.nanosecond = date_time.nanosecond / 1_000_000 * 1_000_000;
+
+		let offset_seconds = now.offset().fix().local_minus_utc();
+		let offset_minutes = offset_seconds.abs() / 60;
+		let offset         = format!(
+			"{}{:02}:{:02}",
+			if offset_seconds < 0 { '-' } else { '+' },
+			offset_minutes / 60,
+			offset_minutes % 60
+		);
+
+		for kind in kinds
+		{
+			self.set_xmp_date_time(*kind, &XmpDateTime { date_time, offset: Some(offset.clone()) });
+		}
+	}
+
+	/// Gets the combined `GPSDateStamp` and `GPSTimeStamp` (both UTC)
+	#[cfg(feature = "chrono")]
+	fn
+	get_gps_date_time
+	(
+		&self
+	)
+	-> Option<chrono::NaiveDateTime>
+	{
+		let date = match self.get_tag(&ExifTag::GPSDateStamp(String::new())).next()
+		{
+			Some(ExifTag::GPSDateStamp(value)) 
+				=> chrono::NaiveDate::parse_from_str(value.trim_end_matches('\0'), "%Y:%m:%d").ok()?,
+			_   => return None,
+		};
+
+		let time = match self.get_tag(&ExifTag::GPSTimeStamp(Vec::new())).next()
+		{
+			Some(ExifTag::GPSTimeStamp(value)) 
+				if value.len() == 3 && value.iter().all(|component| component.denominator != 0)
+				=> value,
+			_   => return None,
+		};
+
+		let seconds = time[2].nominator as f64 / time[2].denominator as f64;
+		return date.and_hms_nano_opt(
+			time[0].nominator / time[0].denominator,
+			time[1].nominator / time[1].denominator,
+			seconds.trunc() as u32,
+			(seconds.fract() * 1e9).round() as u32
+		);
+	}
+
+	/// Sets the `GPSDateStamp` and `GPSTimeStamp` tags, storing the seconds
+	/// with millisecond precision
+	#[cfg(feature = "chrono")]
+	fn
+	set_gps_date_time
+	(
+		&mut self,
+		date_time: &chrono::NaiveDateTime
+	)
+	{
+		use chrono::Datelike;
+		use chrono::Timelike;
+		use crate::rational::uR64;
+
+		let milliseconds = date_time.second() * 1000 + date_time.nanosecond() / 1_000_000;
+
+		self.set_tag(ExifTag::GPSDateStamp(format!(
+			"{:04}:{:02}:{:02}", 
+			date_time.year(), date_time.month(), date_time.day()
+		)));
+		self.set_tag(ExifTag::GPSTimeStamp(vec![
+			uR64 { nominator: date_time.hour(),   denominator: 1    },
+			uR64 { nominator: date_time.minute(), denominator: 1    },
+			uR64 { nominator: milliseconds,       denominator: 1000 },
+		]));
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use crate::exif_tag::ExifTag;
+	use crate::metadata::Metadata;
+
+	use super::parse_sub_sec;
+	use super::DateTimeKind;
+	use super::ExifDateTime;
+
+	#[test]
+	fn
+	parse_variable_length_sub_sec()
+	{
+		assert_eq!(parse_sub_sec("5"),    Some(500_000_000));
+		assert_eq!(parse_sub_sec(".5"),   Some(500_000_000));
+		assert_eq!(parse_sub_sec("123"),  Some(123_000_000));
+		assert_eq!(parse_sub_sec("045 "), Some( 45_000_000));
+		assert_eq!(parse_sub_sec(""),     None);
+		assert_eq!(parse_sub_sec("1a"),   None);
+	}
+
+	#[test]
+	fn
+	merge_date_time_original_with_sub_sec()
+	{
+		let mut metadata = Metadata::new();
+		metadata.set_tag(ExifTag::DateTimeOriginal("2024:05:17 13:37:42".to_string()));
+		metadata.set_tag(ExifTag::SubSecTimeOriginal("45".to_string()));
+
+		let date_time = metadata.get_date_time(DateTimeKind::Original).unwrap();
+		assert_eq!(date_time.to_exif_string(), "2024:05:17 13:37:42");
+		assert_eq!(date_time.millisecond(),    450);
+		assert_eq!(date_time.nanosecond,       450_000_000);
+
+		// Only the tags belonging to the given kind are used
+		assert_eq!(metadata.get_date_time(DateTimeKind::Digitized), None);
+	}
+
+	#[test]
+	fn
+	set_date_time_writes_sub_sec()
+	{
+		let mut metadata  = Metadata::new();
+		let mut date_time = ExifDateTime::parse("2024:05:17 13:37:42").unwrap();
+		date_time.nanosecond = 123_000_000;
+
+		metadata.set_date_time(DateTimeKind::Digitized, &date_time);
+		assert_eq!(
+			metadata.get_tag(&ExifTag::SubSecTimeDigitized(String::new())).next(),
+			Some(&ExifTag::SubSecTimeDigitized("123".to_string()))
+		);
+		assert_eq!(metadata.get_date_time(DateTimeKind::Digitized), Some(date_time));
+
+		date_time.nanosecond = 0;
+		metadata.set_date_time(DateTimeKind::Digitized, &date_time);
+		assert_eq!(metadata.get_sub_sec_time(DateTimeKind::Digitized), None);
+	}
+
+	#[test]
+	#[cfg(feature = "chrono")]
+	fn
+	shift_timestamps_across_midnight()
+	{
+		use crate::rational::uR64;
+
+		let mut metadata = Metadata::new();
+		metadata.set_tag(ExifTag::DateTimeOriginal("2024:02:29 23:30:00".to_string()));
+		metadata.set_tag(ExifTag::SubSecTimeOriginal("25".to_string()));
+		metadata.set_tag(ExifTag::ModifyDate("2024:12:31 23:59:59".to_string()));
+		metadata.set_tag(ExifTag::CreateDate("0000:00:00 00:00:00".to_string()));
+		metadata.set_tag(ExifTag::GPSDateStamp("2024:02:29".to_string()));
+		metadata.set_tag(ExifTag::GPSTimeStamp(vec![
+			uR64 { nominator: 23,  denominator: 1  },
+			uR64 { nominator: 15,  denominator: 1  },
+			uR64 { nominator: 305, denominator: 10 },
+		]));
+
+		metadata.shift_timestamps(chrono::Duration::seconds(3600));
+
+		let original = metadata.get_date_time(DateTimeKind::Original).unwrap();
+		assert_eq!(original.to_exif_string(), "2024:03:01 00:30:00");
+		assert_eq!(original.millisecond(),    250);
+
+		assert_eq!(metadata.get_date_time(DateTimeKind::Modify).unwrap().to_exif_string(), "2025:01:01 00:59:59");
+
+		// Invalid timestamps are left untouched
+		assert_eq!(
+			metadata.get_tag(&ExifTag::CreateDate(String::new())).next(),
+			Some(&ExifTag::CreateDate("0000:00:00 00:00:00".to_string()))
+		);
+
+		assert_eq!(
+			metadata.get_tag(&ExifTag::GPSDateStamp(String::new())).next(),
+			Some(&ExifTag::GPSDateStamp("2024:03:01".to_string()))
+		);
+		assert_eq!(
+			metadata.get_tag(&ExifTag::GPSTimeStamp(Vec::new())).next(),
+			Some(&ExifTag::GPSTimeStamp(vec![
+				uR64 { nominator: 0,     denominator: 1    },
+				uR64 { nominator: 15,    denominator: 1    },
+				uR64 { nominator: 30500, denominator: 1000 },
+			]))
+		);
+	}
+
+	#[test]
+	#[cfg(feature = "chrono")]
+	fn
+	stamp_now()
+	{
+		use crate::filetype::FileExtension;
+
+		let mut metadata = Metadata::new();
+		metadata.stamp_now(&[DateTimeKind::Modify, DateTimeKind::Original]);
+
+		let metadata = Metadata::new_from_vec(&metadata.encode().unwrap(), FileExtension::TIFF).unwrap();
+		let written  = metadata.get_date_time(DateTimeKind::Modify).unwrap().to_naive_date_time().unwrap();
+		let elapsed  = chrono::Local::now().naive_local() - written;
+		assert!(elapsed >= chrono::Duration::zero() && elapsed < chrono::Duration::seconds(5));
+
+		let offset = metadata.get_xmp_date_time(DateTimeKind::Original).unwrap().offset.unwrap();
+		assert_eq!(offset.len(), 6);
+		assert!(offset.starts_with('+') || offset.starts_with('-'));
+		assert_eq!(metadata.get_date_time(DateTimeKind::Digitized), None);
+	}
+}
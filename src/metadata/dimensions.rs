@@ -0,0 +1,326 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::exif_tag::ExifTag;
+use crate::rational::greatest_common_divisor;
+
+use super::Metadata;
+
+impl
+Metadata
+{
+	/// Checks if a tag was not read from the EXIF data itself but derived
+	/// from other information in the image file, e.g. the `ExifImageWidth`
+	/// and `ExifImageHeight` tags of a JPEG that are taken from its SOFn
+	/// segment in case the EXIF data does not provide them.
+	/// Derived tags are only returned by `get_tag` and `get_tag_by_hex`, but
+	/// are not part of the IFDs and thus never written back to the image.
+	/// Setting such a tag via `set_tag` turns it into a regular tag.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::exif_tag::ExifTag;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some(width) = metadata.get_tag(&ExifTag::ExifImageWidth(Vec::new())).next()
+	/// {
+	///     println!("{:?} (derived: {})", width, metadata.is_derived(width));
+	/// }
+	/// # }
+	/// ```
+	pub fn
+	is_derived
+	(
+		&self,
+		tag: &ExifTag
+	)
+	-> bool
+	{
+		self.derived_tags.iter().any(|derived_tag|
+			derived_tag.as_u16() == tag.as_u16() && derived_tag.get_group() == tag.get_group()
+		)
+	}
+
+	/// JPEGs store their true dimensions in the SOFn segment, which serve
+	/// as fallback if the EXIF data does not provide them: The given
+	/// dimensions (as read from the SOFn segment) are added as derived
+	/// `ExifImageWidth` and `ExifImageHeight` tags (aka `PixelXDimension`
+	/// and `PixelYDimension`) if these are not present yet. If the SOFn
+	/// segment could not be read, nothing is derived.
+	pub(crate) fn
+	derive_missing_dimensions
+	(
+		&mut self,
+		sof_dimensions: Result<(u16, u16), std::io::Error>
+	)
+	{
+		let (width, height) = match sof_dimensions
+		{
+			Ok(dimensions) => dimensions,
+			Err(_)         => return,
+		};
+
+		for tag in [ExifTag::ExifImageWidth(vec![width as u32]), ExifTag::ExifImageHeight(vec![height as u32])]
+		{
+			if self.get_tag(&tag).next().is_none()
+			{
+				self.derived_tags.push(tag);
+			}
+		}
+	}
+
+	/// Sets the dimensions of the image consistently in the `ImageWidth` and
+	/// `ImageHeight` tags of IFD0 (0x0100 and 0x0101, aka `ImageLength`) and
+	/// the `ExifImageWidth` and `ExifImageHeight` tags of the ExifIFD (0xa002
+	/// and 0xa003, aka `PixelXDimension` and `PixelYDimension`), e.g. after
+	/// resizing the image.
+	/// All four tags are always written as `LONG` values, also for small
+	/// dimensions that would fit into a `SHORT`: The specification allows
+	/// both formats for these tags regardless of the magnitude, and the
+	/// format of a tag is fixed by its `ExifTag` variant.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let mut metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// metadata.set_dimensions(1920, 1080);
+	/// metadata.write_to_file(std::path::Path::new("image.jpg")).unwrap();
+	/// # }
+	/// ```
+	pub fn
+	set_dimensions
+	(
+		&mut self,
+		width:  u32,
+		height: u32
+	)
+	{
+		self.set_tag(ExifTag::ImageWidth(     vec![width ]));
+		self.set_tag(ExifTag::ImageHeight(    vec![height]));
+		self.set_tag(ExifTag::ExifImageWidth( vec![width ]));
+		self.set_tag(ExifTag::ExifImageHeight(vec![height]));
+	}
+
+	/// Gets the dimensions of the stored image (i.e. without applying the
+	/// orientation) from the `ExifImageWidth` and `ExifImageHeight` tags
+	/// (aka `PixelXDimension` and `PixelYDimension`), falling back to 
+	/// `ImageWidth` and `ImageHeight`
+	fn
+	get_stored_dimensions
+	(
+		&self
+	)
+	-> Option<(u32, u32)>
+	{
+		let exif_dimensions = match (
+			self.get_tag(&ExifTag::ExifImageWidth(Vec::new())).next(),
+			self.get_tag(&ExifTag::ExifImageHeight(Vec::new())).next()
+		)
+		{
+			(Some(ExifTag::ExifImageWidth(width)), Some(ExifTag::ExifImageHeight(height)))
+				=> width.first().copied().zip(height.first().copied()),
+			_
+				=> None,
+		};
+
+		if exif_dimensions.is_some()
+		{
+			return exif_dimensions;
+		}
+
+		match (
+			self.get_tag(&ExifTag::ImageWidth(Vec::new())).next(),
+			self.get_tag(&ExifTag::ImageHeight(Vec::new())).next()
+		)
+		{
+			(Some(ExifTag::ImageWidth(width)), Some(ExifTag::ImageHeight(height)))
+				=> width.first().copied().zip(height.first().copied()),
+			_
+				=> None,
+		}
+	}
+
+	/// Gets the number of megapixels of the image, based on the dimensions
+	/// stored in the `ExifImageWidth` and `ExifImageHeight` tags or, if not
+	/// present, the `ImageWidth` and `ImageHeight` tags.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some(megapixels) = metadata.megapixels()
+	/// {
+	///     println!("{:.1} MP", megapixels);
+	/// }
+	/// # }
+	/// ```
+	pub fn
+	megapixels
+	(
+		&self
+	)
+	-> Option<f64>
+	{
+		let (width, height) = self.get_stored_dimensions()?;
+		return Some(width as f64 * height as f64 / 1_000_000.0);
+	}
+
+	/// Gets the dimensions (width, height) of the image as it is displayed,
+	/// i.e. width and height are swapped if the `Orientation` tag indicates
+	/// a rotation by 90 or 270 degrees (see `Orientation::applied_dimensions`).
+	/// Uses the same dimensions as `megapixels` and returns `None` if these
+	/// are absent.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some((width, height)) = metadata.display_dimensions()
+	/// {
+	///     println!("{}x{}", width, height);
+	/// }
+	/// # }
+	/// ```
+	pub fn
+	display_dimensions
+	(
+		&self
+	)
+	-> Option<(u32, u32)>
+	{
+		let (width, height) = self.get_stored_dimensions()?;
+		return match self.get_orientation()
+		{
+			Some(orientation) => Some(orientation.applied_dimensions(width, height)),
+			None              => Some((width, height)),
+		};
+	}
+
+	/// Gets the reduced aspect ratio (width, height) of the image as it is
+	/// displayed, based on `display_dimensions`. Returns `None` if a
+	/// dimension is zero.
+	pub fn
+	aspect_ratio
+	(
+		&self
+	)
+	-> Option<(u32, u32)>
+	{
+		let (width, height) = self.display_dimensions()?;
+
+		if width == 0 || height == 0
+		{
+			return None;
+		}
+
+		let gcd = greatest_common_divisor(width as u64, height as u64) as u32;
+		return Some((width / gcd, height / gcd));
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use crate::exif_tag::ExifTag;
+	use crate::exif_tag_format::ExifTagFormat;
+	use crate::filetype::FileExtension;
+	use crate::ifd::ExifTagGroup;
+	use crate::metadata::orientation::Orientation;
+	use crate::metadata::Metadata;
+
+	#[test]
+	fn
+	megapixels_and_aspect_ratio()
+	{
+		let mut metadata = Metadata::new();
+		assert_eq!(metadata.megapixels(),   None);
+		assert_eq!(metadata.aspect_ratio(), None);
+
+		metadata.set_tag(ExifTag::ExifImageWidth( vec![6000]));
+		metadata.set_tag(ExifTag::ExifImageHeight(vec![4000]));
+		assert_eq!(metadata.megapixels(),   Some(24.0));
+		assert_eq!(metadata.aspect_ratio(), Some((3, 2)));
+
+		// Rotated by 90 degrees: Displayed in portrait format
+		metadata.set_orientation(Orientation::Rotate90);
+		assert_eq!(metadata.megapixels(),   Some(24.0));
+		assert_eq!(metadata.aspect_ratio(), Some((2, 3)));
+
+		metadata.set_orientation(Orientation::Rotate180);
+		assert_eq!(metadata.aspect_ratio(), Some((3, 2)));
+	}
+
+	#[test]
+	fn
+	display_dimensions_apply_orientation()
+	{
+		let mut metadata = Metadata::new();
+		metadata.set_orientation(Orientation::Rotate90);
+		assert_eq!(metadata.display_dimensions(), None);
+
+		metadata.set_tag(ExifTag::ExifImageWidth( vec![4000]));
+		metadata.set_tag(ExifTag::ExifImageHeight(vec![3000]));
+
+		// Orientation 6: Rotate 90 degrees clockwise for displaying
+		let metadata = Metadata::new_from_vec(&metadata.encode().unwrap(), FileExtension::TIFF).unwrap();
+		assert_eq!(metadata.get_orientation().unwrap().as_u16(), 6);
+		assert_eq!(metadata.display_dimensions(),                Some((3000, 4000)));
+
+		let mut metadata = metadata;
+		metadata.set_orientation(Orientation::FlipHorizontal);
+		assert_eq!(metadata.display_dimensions(), Some((4000, 3000)));
+	}
+
+	#[test]
+	fn
+	aspect_ratio_falls_back_to_image_width()
+	{
+		let mut metadata = Metadata::new();
+		metadata.set_tag(ExifTag::ImageWidth( vec![1920]));
+		metadata.set_tag(ExifTag::ImageHeight(vec![1080]));
+		assert_eq!(metadata.aspect_ratio(), Some((16, 9)));
+
+		metadata.set_tag(ExifTag::ImageHeight(vec![0]));
+		assert_eq!(metadata.aspect_ratio(), None);
+	}
+
+	#[test]
+	fn
+	set_dimensions_in_ifd0_and_exif_ifd()
+	{
+		let mut metadata = Metadata::new();
+		metadata.set_dimensions(8000, 6000);
+
+		let metadata = Metadata::new_from_vec(&metadata.encode().unwrap(), FileExtension::TIFF).unwrap();
+		let ifd0     = metadata.get_ifd(ExifTagGroup::GENERIC, 0).unwrap();
+		let exif_ifd = metadata.get_ifd(ExifTagGroup::EXIF,    0).unwrap();
+
+		assert!(ifd0.get_tags().contains(&ExifTag::ImageWidth(vec![8000])));
+		assert!(ifd0.get_tags().contains(&ExifTag::ImageHeight(vec![6000])));
+		assert!(exif_ifd.get_tags().contains(&ExifTag::ExifImageWidth(vec![8000])));
+		assert!(exif_ifd.get_tags().contains(&ExifTag::ExifImageHeight(vec![6000])));
+		assert_eq!(ExifTag::ImageWidth(vec![8000]).format(), ExifTagFormat::INT32U);
+		assert_eq!(metadata.megapixels(), Some(48.0));
+
+		// Small dimensions are written as LONG as well, e.g. the ImageWidth
+		// entry of IFD0: Tag 0x0100, format 4 (LONG), 1 component, 640
+		let mut metadata = Metadata::new();
+		metadata.set_dimensions(640, 480);
+
+		let encoded = metadata.encode().unwrap();
+		let entry   = [0x00, 0x01, 0x04, 0x00, 0x01, 0x00, 0x00, 0x00, 0x80, 0x02, 0x00, 0x00];
+		assert!(encoded.windows(entry.len()).any(|window| window == entry));
+	}
+}
@@ -59,4 +59,148 @@ Metadata
 			}
 		}
 	}
-}
\ No newline at end of file
+
+	/// Removes the tags that identify the owner of the camera or the camera
+	/// itself from all IFDs: `Artist`, `OwnerName` (aka `CameraOwnerName`),
+	/// `SerialNumber` (aka `BodySerialNumber`) and `LensSerialNumber`.
+	/// Note that this does *not* remove the GPS data.
+	pub fn
+	strip_personal_info
+	(
+		&mut self
+	)
+	{
+		let personal_tags = [
+			ExifTag::Artist(String::new()),
+			ExifTag::OwnerName(String::new()),
+			ExifTag::SerialNumber(String::new()),
+			ExifTag::LensSerialNumber(String::new()),
+		];
+
+		for ifd in self.image_file_directories.iter_mut()
+		{
+			for tag in personal_tags.iter()
+			{
+				if ifd.get_ifd_type() == tag.get_group()
+				{
+					ifd.remove_tag(tag.clone());
+				}
+			}
+		}
+	}
+
+	/// Gets the tags that are mandatory according to the EXIF specification
+	/// for the IFDs present in the metadata but are missing, with their
+	/// default values: `ExifVersion` ("0232") and `ComponentsConfiguration`
+	/// (Y, Cb, Cr) for the ExifIFD and `GPSVersionID` (2.3.0.0) for the GPS
+	/// IFD. See `add_mandatory_tags`.
+	pub fn
+	missing_mandatory_tags
+	(
+		&self
+	)
+	-> Vec<ExifTag>
+	{
+		let mut missing_tags = Vec::new();
+
+		for ifd in self.image_file_directories.iter()
+		{
+			for mandatory_tag in mandatory_tags(ifd.get_ifd_type())
+			{
+				if !ifd.get_tags().iter().any(|tag| tag.as_u16() == mandatory_tag.as_u16())
+				{
+					missing_tags.push(mandatory_tag);
+				}
+			}
+		}
+
+		return missing_tags;
+	}
+
+	/// Adds the missing mandatory tags (see `missing_mandatory_tags`) with
+	/// their default values to each IFD that lacks them, making generated
+	/// files more standards-compliant. Existing values are not changed, and
+	/// no IFDs are created.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::exif_tag::ExifTag;
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let mut metadata = Metadata::new();
+	/// metadata.set_tag(ExifTag::ISO(vec![100]));
+	/// metadata.add_mandatory_tags();
+	/// assert!(metadata.missing_mandatory_tags().is_empty());
+	/// ```
+	pub fn
+	add_mandatory_tags
+	(
+		&mut self
+	)
+	{
+		for ifd in self.image_file_directories.iter_mut()
+		{
+			for mandatory_tag in mandatory_tags(ifd.get_ifd_type())
+			{
+				if !ifd.get_tags().iter().any(|tag| tag.as_u16() == mandatory_tag.as_u16())
+				{
+					ifd.set_tag(mandatory_tag);
+				}
+			}
+		}
+	}
+}
+
+/// The mandatory tags of an IFD of the given group with their default values
+fn
+mandatory_tags
+(
+	group: ExifTagGroup
+)
+-> Vec<ExifTag>
+{
+	match group
+	{
+		ExifTagGroup::EXIF => vec![
+			ExifTag::ExifVersion(b"0232".to_vec()),
+			ExifTag::ComponentsConfiguration(vec![1, 2, 3, 0]),
+		],
+
+		ExifTagGroup::GPS => vec![
+			ExifTag::GPSVersionID(vec![2, 3, 0, 0]),
+		],
+
+		_ => Vec::new(),
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use crate::exif_tag::ExifTag;
+	use crate::filetype::FileExtension;
+	use crate::ifd::ExifTagGroup;
+	use crate::metadata::Metadata;
+
+	#[test]
+	fn
+	add_mandatory_tags_to_minimal_exif_ifd()
+	{
+		let mut metadata = Metadata::new();
+		metadata.set_tag(ExifTag::ISO(vec![100]));
+		metadata.set_tag(ExifTag::ComponentsConfiguration(vec![4, 5, 6, 0]));
+
+		assert_eq!(metadata.missing_mandatory_tags(), vec![ExifTag::ExifVersion(b"0232".to_vec())]);
+
+		metadata.add_mandatory_tags();
+		assert!(metadata.missing_mandatory_tags().is_empty());
+
+		let metadata = Metadata::new_from_vec(&metadata.encode().unwrap(), FileExtension::TIFF).unwrap();
+		let exif_ifd = metadata.get_ifd(ExifTagGroup::EXIF, 0).unwrap();
+		assert!(exif_ifd.get_tags().contains(&ExifTag::ExifVersion(b"0232".to_vec())));
+
+		// The existing value is kept and no GPS IFD gets created
+		assert!(exif_ifd.get_tags().contains(&ExifTag::ComponentsConfiguration(vec![4, 5, 6, 0])));
+		assert!(metadata.get_ifd(ExifTagGroup::GPS, 0).is_none());
+	}
+}
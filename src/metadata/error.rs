@@ -0,0 +1,239 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+/// The encoded metadata does not fit into the segment or chunk of the file
+/// format it should be written to, e.g. into a single APP1 segment of a
+/// JPEG. Both values are the size of the generally encoded metadata (i.e.
+/// the TIFF data without any file type specific headers) in bytes.
+/// Returned as inner error of an `std::io::Error` of kind `InvalidInput`,
+/// see `MetadataTooLarge::from_io_error`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct
+MetadataTooLarge
+{
+	pub limit:  usize,
+	pub actual: usize,
+}
+
+impl
+MetadataTooLarge
+{
+	/// Gets the `MetadataTooLarge` error wrapped in the given error, if any
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::metadata::error::MetadataTooLarge;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.tif")).unwrap();
+	/// if let Err(error) = metadata.write_to_file(std::path::Path::new("image.jpg"))
+	/// {
+	///     if let Some(too_large) = MetadataTooLarge::from_io_error(&error)
+	///     {
+	///         println!("Need to remove {} bytes", too_large.actual - too_large.limit);
+	///     }
+	/// }
+	/// # }
+	/// ```
+	pub fn
+	from_io_error
+	(
+		error: &std::io::Error
+	)
+	-> Option<MetadataTooLarge>
+	{
+		return error.get_ref()?.downcast_ref::<MetadataTooLarge>().copied();
+	}
+
+	/// Checks the size of the encoded metadata against the limit
+	pub(crate) fn
+	check
+	(
+		limit:  usize,
+		actual: usize
+	)
+	-> Result<(), std::io::Error>
+	{
+		if actual > limit
+		{
+			return Err(std::io::Error::new(
+				std::io::ErrorKind::InvalidInput,
+				MetadataTooLarge { limit, actual }
+			));
+		}
+		return Ok(());
+	}
+}
+
+impl
+std::fmt::Display for MetadataTooLarge
+{
+	fn
+	fmt
+	(
+		&self,
+		formatter: &mut std::fmt::Formatter<'_>
+	)
+	-> std::fmt::Result
+	{
+		write!(formatter, "Encoded metadata of {} bytes exceeds the limit of {} bytes", self.actual, self.limit)
+	}
+}
+
+impl
+std::error::Error for MetadataTooLarge {}
+
+/// The file uses a format (or a variant of it) that is recognized but not
+/// supported by little_exif, e.g. a BigTIFF file with 8 byte offsets.
+/// Returned as inner error of an `std::io::Error` of kind `Unsupported`,
+/// see `UnsupportedFormat::from_io_error`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct
+UnsupportedFormat
+{
+	pub format: &'static str,
+}
+
+impl
+UnsupportedFormat
+{
+	/// Gets the `UnsupportedFormat` error wrapped in the given error, if any
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::metadata::error::UnsupportedFormat;
+	///
+	/// if let Err(error) = Metadata::new_from_path(std::path::Path::new("image.tif"))
+	/// {
+	///     if let Some(unsupported) = UnsupportedFormat::from_io_error(&error)
+	///     {
+	///         println!("{} files are not supported", unsupported.format);
+	///     }
+	/// }
+	/// # }
+	/// ```
+	pub fn
+	from_io_error
+	(
+		error: &std::io::Error
+	)
+	-> Option<UnsupportedFormat>
+	{
+		return error.get_ref()?.downcast_ref::<UnsupportedFormat>().copied();
+	}
+
+	/// Creates an `std::io::Error` wrapping the error for the given format
+	pub(crate) fn
+	io_error
+	(
+		format: &'static str
+	)
+	-> std::io::Error
+	{
+		return std::io::Error::new(
+			std::io::ErrorKind::Unsupported,
+			UnsupportedFormat { format }
+		);
+	}
+}
+
+impl
+std::fmt::Display for UnsupportedFormat
+{
+	fn
+	fmt
+	(
+		&self,
+		formatter: &mut std::fmt::Formatter<'_>
+	)
+	-> std::fmt::Result
+	{
+		write!(formatter, "Unsupported format: {}", self.format)
+	}
+}
+
+impl
+std::error::Error for UnsupportedFormat {}
+
+/// The EXIF data is malformed in a way that is only tolerated when reading
+/// with `ParseStrictness::Lenient` (e.g. a truncated IFD), or it exceeds a
+/// limit of the `ReadOptions` (e.g. `max_ifd_count`). Unlike other issues
+/// while decoding, which result in empty metadata, this is reported to the
+/// caller, so that it can be told apart from a file without EXIF data.
+/// Returned as inner error of an `std::io::Error` of kind `InvalidData`,
+/// see `InvalidExifData::from_io_error`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct
+InvalidExifData
+{
+	pub message: String,
+}
+
+impl
+InvalidExifData
+{
+	/// Gets the `InvalidExifData` error wrapped in the given error, if any
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::metadata::error::InvalidExifData;
+	///
+	/// if let Err(error) = Metadata::new_from_path(std::path::Path::new("image.tif"))
+	/// {
+	///     if let Some(invalid) = InvalidExifData::from_io_error(&error)
+	///     {
+	///         println!("Malformed EXIF data: {}", invalid.message);
+	///     }
+	/// }
+	/// # }
+	/// ```
+	pub fn
+	from_io_error
+	(
+		error: &std::io::Error
+	)
+	-> Option<InvalidExifData>
+	{
+		return error.get_ref()?.downcast_ref::<InvalidExifData>().cloned();
+	}
+
+	/// Creates an `std::io::Error` wrapping the error with the given message
+	pub(crate) fn
+	io_error
+	(
+		message: String
+	)
+	-> std::io::Error
+	{
+		return std::io::Error::new(
+			std::io::ErrorKind::InvalidData,
+			InvalidExifData { message }
+		);
+	}
+}
+
+impl
+std::fmt::Display for InvalidExifData
+{
+	fn
+	fmt
+	(
+		&self,
+		formatter: &mut std::fmt::Formatter<'_>
+	)
+	-> std::fmt::Result
+	{
+		write!(formatter, "Invalid EXIF data: {}", self.message)
+	}
+}
+
+impl
+std::error::Error for InvalidExifData {}
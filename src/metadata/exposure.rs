@@ -0,0 +1,299 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::exif_tag::ExifTag;
+
+use super::Metadata;
+
+/// The values of the `ExposureProgram` tag (0x8822), describing the program
+/// used by the camera to set the exposure
+/// See https://exiftool.org/TagNames/EXIF.html (0x8822)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+ExposureProgram
+{
+	NotDefined,                                                                 // 0
+	Manual,                                                                     // 1
+	Normal,                                                                     // 2: Program AE
+	AperturePriority,                                                           // 3
+	ShutterPriority,                                                            // 4
+	Creative,                                                                   // 5: Slow speed
+	Action,                                                                     // 6: High speed
+	Portrait,                                                                   // 7
+	Landscape,                                                                  // 8
+	Other(u16),                                                                 // Not part of the EXIF standard
+}
+
+impl
+ExposureProgram
+{
+	/// Gets the exposure program for a value of the `ExposureProgram` tag
+	pub fn
+	from_u16
+	(
+		value: u16
+	)
+	-> ExposureProgram
+	{
+		match value
+		{
+			0 => ExposureProgram::NotDefined,
+			1 => ExposureProgram::Manual,
+			2 => ExposureProgram::Normal,
+			3 => ExposureProgram::AperturePriority,
+			4 => ExposureProgram::ShutterPriority,
+			5 => ExposureProgram::Creative,
+			6 => ExposureProgram::Action,
+			7 => ExposureProgram::Portrait,
+			8 => ExposureProgram::Landscape,
+			_ => ExposureProgram::Other(value),
+		}
+	}
+
+	/// Gets the value as stored in the `ExposureProgram` tag
+	pub fn
+	as_u16
+	(
+		&self
+	)
+	-> u16
+	{
+		match self
+		{
+			ExposureProgram::NotDefined       => 0,
+			ExposureProgram::Manual           => 1,
+			ExposureProgram::Normal           => 2,
+			ExposureProgram::AperturePriority => 3,
+			ExposureProgram::ShutterPriority  => 4,
+			ExposureProgram::Creative         => 5,
+			ExposureProgram::Action           => 6,
+			ExposureProgram::Portrait         => 7,
+			ExposureProgram::Landscape        => 8,
+			ExposureProgram::Other(value)     => *value,
+		}
+	}
+}
+
+/// The values of the `MeteringMode` tag (0x9207). Note that the value 255
+/// (called "other" in the EXIF standard) is represented by `Other(255)`.
+/// See https://exiftool.org/TagNames/EXIF.html (0x9207)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+MeteringMode
+{
+	Unknown,                                                                    // 0
+	Average,                                                                    // 1
+	CenterWeightedAverage,                                                      // 2
+	Spot,                                                                       // 3
+	MultiSpot,                                                                  // 4
+	Pattern,                                                                    // 5: Multi-segment
+	Partial,                                                                    // 6
+	Other(u16),
+}
+
+impl
+MeteringMode
+{
+	/// Gets the metering mode for a value of the `MeteringMode` tag
+	pub fn
+	from_u16
+	(
+		value: u16
+	)
+	-> MeteringMode
+	{
+		match value
+		{
+			0 => MeteringMode::Unknown,
+			1 => MeteringMode::Average,
+			2 => MeteringMode::CenterWeightedAverage,
+			3 => MeteringMode::Spot,
+			4 => MeteringMode::MultiSpot,
+			5 => MeteringMode::Pattern,
+			6 => MeteringMode::Partial,
+			_ => MeteringMode::Other(value),
+		}
+	}
+
+	/// Gets the value as stored in the `MeteringMode` tag
+	pub fn
+	as_u16
+	(
+		&self
+	)
+	-> u16
+	{
+		match self
+		{
+			MeteringMode::Unknown               => 0,
+			MeteringMode::Average               => 1,
+			MeteringMode::CenterWeightedAverage => 2,
+			MeteringMode::Spot                  => 3,
+			MeteringMode::MultiSpot             => 4,
+			MeteringMode::Pattern               => 5,
+			MeteringMode::Partial               => 6,
+			MeteringMode::Other(value)          => *value,
+		}
+	}
+}
+
+/// The values of the `WhiteBalance` tag (0xa403)
+/// See https://exiftool.org/TagNames/EXIF.html (0xa403)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+WhiteBalance
+{
+	Auto,                                                                       // 0
+	Manual,                                                                     // 1
+	Other(u16),                                                                 // Not part of the EXIF standard
+}
+
+impl
+WhiteBalance
+{
+	/// Gets the white balance for a value of the `WhiteBalance` tag
+	pub fn
+	from_u16
+	(
+		value: u16
+	)
+	-> WhiteBalance
+	{
+		match value
+		{
+			0 => WhiteBalance::Auto,
+			1 => WhiteBalance::Manual,
+			_ => WhiteBalance::Other(value),
+		}
+	}
+
+	/// Gets the value as stored in the `WhiteBalance` tag
+	pub fn
+	as_u16
+	(
+		&self
+	)
+	-> u16
+	{
+		match self
+		{
+			WhiteBalance::Auto         => 0,
+			WhiteBalance::Manual       => 1,
+			WhiteBalance::Other(value) => *value,
+		}
+	}
+}
+
+impl
+Metadata
+{
+	/// Gets the decoded `ExposureProgram` tag, if present.
+	pub fn
+	get_exposure_program
+	(
+		&self
+	)
+	-> Option<ExposureProgram>
+	{
+		if let Some(ExifTag::ExposureProgram(value)) = self.get_tag(&ExifTag::ExposureProgram(Vec::new())).next()
+		{
+			return value.first().map(|raw_value| ExposureProgram::from_u16(*raw_value));
+		}
+		return None;
+	}
+
+	/// Sets the `ExposureProgram` tag using the typed representation
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::metadata::exposure::ExposureProgram;
+	///
+	/// let mut metadata = Metadata::new();
+	/// metadata.set_exposure_program(ExposureProgram::AperturePriority);
+	/// assert_eq!(metadata.get_exposure_program(), Some(ExposureProgram::AperturePriority));
+	/// ```
+	pub fn
+	set_exposure_program
+	(
+		&mut self,
+		exposure_program: ExposureProgram
+	)
+	{
+		self.set_tag(ExifTag::ExposureProgram(vec![exposure_program.as_u16()]));
+	}
+
+	/// Gets the decoded `MeteringMode` tag, if present.
+	pub fn
+	get_metering_mode
+	(
+		&self
+	)
+	-> Option<MeteringMode>
+	{
+		if let Some(ExifTag::MeteringMode(value)) = self.get_tag(&ExifTag::MeteringMode(Vec::new())).next()
+		{
+			return value.first().map(|raw_value| MeteringMode::from_u16(*raw_value));
+		}
+		return None;
+	}
+
+	/// Sets the `MeteringMode` tag using the typed representation
+	pub fn
+	set_metering_mode
+	(
+		&mut self,
+		metering_mode: MeteringMode
+	)
+	{
+		self.set_tag(ExifTag::MeteringMode(vec![metering_mode.as_u16()]));
+	}
+
+	/// Gets the decoded `WhiteBalance` tag, if present.
+	pub fn
+	get_white_balance
+	(
+		&self
+	)
+	-> Option<WhiteBalance>
+	{
+		if let Some(ExifTag::WhiteBalance(value)) = self.get_tag(&ExifTag::WhiteBalance(Vec::new())).next()
+		{
+			return value.first().map(|raw_value| WhiteBalance::from_u16(*raw_value));
+		}
+		return None;
+	}
+
+	/// Sets the `WhiteBalance` tag using the typed representation
+	pub fn
+	set_white_balance
+	(
+		&mut self,
+		white_balance: WhiteBalance
+	)
+	{
+		self.set_tag(ExifTag::WhiteBalance(vec![white_balance.as_u16()]));
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::ExposureProgram;
+	use super::MeteringMode;
+	use super::WhiteBalance;
+
+	#[test]
+	fn
+	round_trip_values()
+	{
+		for value in 0..=300
+		{
+			assert_eq!(ExposureProgram::from_u16(value).as_u16(), value);
+			assert_eq!(MeteringMode::from_u16(value).as_u16(),    value);
+			assert_eq!(WhiteBalance::from_u16(value).as_u16(),    value);
+		}
+
+		assert_eq!(MeteringMode::from_u16(255), MeteringMode::Other(255));
+	}
+}
@@ -0,0 +1,254 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::exif_tag::ExifTag;
+
+use super::Metadata;
+
+/// The flash mode encoded in bits 3 and 4 of the `Flash` tag
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+FlashMode
+{
+	Unknown,
+	CompulsoryFiring,
+	CompulsorySuppression,
+	Auto,
+}
+
+/// The status of the returned light encoded in bits 1 and 2 of the `Flash` tag
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+FlashReturn
+{
+	NoDetectionFunction,
+	Reserved,
+	NotDetected,
+	Detected,
+}
+
+/// Typed representation of the bitfield stored in the `Flash` tag (0x9209)
+/// See https://exiftool.org/TagNames/EXIF.html#Flash
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct
+Flash
+{
+	value: u16
+}
+
+impl
+Flash
+{
+	/// Creates a new `Flash` from the raw value of the tag
+	pub fn
+	from_u16
+	(
+		value: u16
+	)
+	-> Flash
+	{
+		Flash { value }
+	}
+
+	/// Creates a new `Flash` from its components
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::flash::Flash;
+	/// use little_exif::metadata::flash::FlashMode;
+	/// use little_exif::metadata::flash::FlashReturn;
+	///
+	/// let flash = Flash::new(true, FlashReturn::NoDetectionFunction, FlashMode::Auto, false);
+	/// assert_eq!(flash.as_u16(), 0x0019);
+	/// ```
+	pub fn
+	new
+	(
+		fired:             bool,
+		return_light:      FlashReturn,
+		mode:              FlashMode,
+		red_eye_reduction: bool
+	)
+	-> Flash
+	{
+		let mut value = fired as u16;
+		value |= (return_light as u16) << 1;
+		value |= (mode as u16) << 3;
+		value |= (red_eye_reduction as u16) << 6;
+		Flash { value }
+	}
+
+	/// Gets the raw value of the bitfield
+	pub fn
+	as_u16
+	(
+		&self
+	)
+	-> u16
+	{
+		self.value
+	}
+
+	/// Bit 0: Did the flash fire?
+	pub fn
+	fired
+	(
+		&self
+	)
+	-> bool
+	{
+		self.value & 0x0001 != 0
+	}
+
+	/// Bits 1 and 2: Status of returned light
+	pub fn
+	return_light
+	(
+		&self
+	)
+	-> FlashReturn
+	{
+		match (self.value >> 1) & 0x0003
+		{
+			0 => FlashReturn::NoDetectionFunction,
+			1 => FlashReturn::Reserved,
+			2 => FlashReturn::NotDetected,
+			_ => FlashReturn::Detected,
+		}
+	}
+
+	/// Bits 3 and 4: The mode of the flash
+	pub fn
+	mode
+	(
+		&self
+	)
+	-> FlashMode
+	{
+		match (self.value >> 3) & 0x0003
+		{
+			0 => FlashMode::Unknown,
+			1 => FlashMode::CompulsoryFiring,
+			2 => FlashMode::CompulsorySuppression,
+			_ => FlashMode::Auto,
+		}
+	}
+
+	/// Bit 5: Is a flash function present at all?
+	pub fn
+	function_present
+	(
+		&self
+	)
+	-> bool
+	{
+		self.value & 0x0020 == 0
+	}
+
+	/// Bit 6: Was red-eye reduction used?
+	pub fn
+	red_eye_reduction
+	(
+		&self
+	)
+	-> bool
+	{
+		self.value & 0x0040 != 0
+	}
+}
+
+impl
+Metadata
+{
+	/// Gets the decoded `Flash` tag, if present.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some(flash) = metadata.get_flash()
+	/// {
+	///     println!("Flash fired: {}", flash.fired());
+	/// }
+	/// # }
+	/// ```
+	pub fn
+	get_flash
+	(
+		&self
+	)
+	-> Option<Flash>
+	{
+		if let Some(ExifTag::Flash(value)) = self.get_tag(&ExifTag::Flash(Vec::new())).next()
+		{
+			return value.first().map(|raw_value| Flash::from_u16(*raw_value));
+		}
+		return None;
+	}
+
+	/// Sets the `Flash` tag using the typed representation
+	pub fn
+	set_flash
+	(
+		&mut self,
+		flash: Flash
+	)
+	{
+		self.set_tag(ExifTag::Flash(vec![flash.as_u16()]));
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use crate::metadata::Metadata;
+
+	use super::Flash;
+	use super::FlashMode;
+	use super::FlashReturn;
+
+	#[test]
+	fn
+	decode_known_values()
+	{
+		// Fired, auto mode
+		let flash = Flash::from_u16(0x0019);
+		assert!(flash.fired());
+		assert_eq!(flash.mode(),         FlashMode::Auto);
+		assert_eq!(flash.return_light(), FlashReturn::NoDetectionFunction);
+		assert!(!flash.red_eye_reduction());
+		assert!(flash.function_present());
+
+		// Off, did not fire
+		let flash = Flash::from_u16(0x0010);
+		assert!(!flash.fired());
+		assert_eq!(flash.mode(), FlashMode::CompulsorySuppression);
+
+		// Fired, compulsory flash mode, red-eye reduction, return light detected
+		let flash = Flash::from_u16(0x004f);
+		assert!(flash.fired());
+		assert_eq!(flash.mode(),         FlashMode::CompulsoryFiring);
+		assert_eq!(flash.return_light(), FlashReturn::Detected);
+		assert!(flash.red_eye_reduction());
+
+		// No flash function
+		assert!(!Flash::from_u16(0x0020).function_present());
+	}
+
+	#[test]
+	fn
+	set_and_get_flash()
+	{
+		let mut metadata = Metadata::new();
+		assert_eq!(metadata.get_flash(), None);
+
+		let flash = Flash::new(true, FlashReturn::NoDetectionFunction, FlashMode::Auto, false);
+		assert_eq!(flash.as_u16(), 0x0019);
+
+		metadata.set_flash(flash);
+		assert_eq!(metadata.get_flash(), Some(flash));
+	}
+}
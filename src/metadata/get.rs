@@ -1,6 +1,8 @@
 // Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
 // See https://github.com/TechnikTobi/little_exif#license for licensing details
 
+use std::collections::HashMap;
+
 use crate::exif_tag::ExifTag;
 use crate::ifd::ExifTagGroup;
 
@@ -16,10 +18,13 @@ Metadata
 	///
 	/// # Examples
 	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
 	/// use little_exif::metadata::Metadata;
 	/// 
 	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.png")).unwrap();
 	/// let tag_data = metadata.get_tag_by_hex(0x010e, None).next().unwrap().value_as_u8_vec(&metadata.get_endian());
+	/// # }
 	/// ```
 	pub fn
 	get_endian
@@ -59,6 +64,80 @@ Metadata
 		).next()
 	}
 
+	/// Counts the tags stored in the struct for each group. Tags of IFDs
+	/// that share a group (e.g. IFD0 and IFD1, which are both `GENERIC`) are
+	/// added up. Groups without any IFD are not part of the result.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::ifd::ExifTagGroup;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// let counts   = metadata.tag_counts();
+	/// println!("EXIF tags: {}", counts.get(&ExifTagGroup::EXIF).unwrap_or(&0));
+	/// # }
+	/// ```
+	pub fn
+	tag_counts
+	(
+		&self
+	)
+	-> HashMap<ExifTagGroup, usize>
+	{
+		let mut counts = HashMap::new();
+		for ifd in &self.image_file_directories
+		{
+			*counts.entry(ifd.get_ifd_type()).or_insert(0) += ifd.get_tags().len();
+		}
+		return counts;
+	}
+
+	/// Gets the total number of tags stored in the struct across all IFDs
+	pub fn
+	total_tag_count
+	(
+		&self
+	)
+	-> usize
+	{
+		self.image_file_directories.iter()
+			.map(|ifd| ifd.get_tags().len())
+			.sum()
+	}
+
+	/// Gets all tags that are not known to little_exif, i.e. the `Unknown...`
+	/// variants, across all IFDs. Their hex value and group are available
+	/// via `as_u16` and `get_group`, e.g. for reporting them upstream.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// for tag in metadata.unknown_tags()
+	/// {
+	///     println!("{:?} 0x{:04x}", tag.get_group(), tag.as_u16());
+	/// }
+	/// # }
+	/// ```
+	pub fn
+	unknown_tags
+	(
+		&self
+	)
+	-> Vec<&ExifTag>
+	{
+		self.image_file_directories.iter()
+			.flat_map(|ifd| ifd.get_tags().iter())
+			.filter(|tag| tag.is_unknown())
+			.collect()
+	}
+
 	/// Gets the maximum generic ifd number that any of the struct's IFDs has
 	pub fn
 	get_max_generic_ifd_number
@@ -142,23 +221,65 @@ impl Metadata
 	{
 		GetTagIterator 
 		{
-			metadata:          &self,
-			current_ifd_index: 0,
-			current_tag_index: 0,
-			tag_hex_value:     hex,
-			group:             group,
+			metadata:              &self,
+			current_ifd_index:     0,
+			current_tag_index:     0,
+			current_derived_index: 0,
+			tag_hex_value:         hex,
+			group:                 group,
 		}
 	}
 }
 
+impl
+Metadata
+{
+	/// Gets a tag of a specific page of a multi-page TIFF, i.e. the tag with
+	/// the given hex value stored in the IFD of the given group that belongs
+	/// to the generic IFD number `generic_ifd_nr` (0 for the first page).
+	/// This allows reading e.g. the `ImageDescription` or `PageName` of each
+	/// page, whereas `get_tag` yields the tags of all pages.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::ifd::ExifTagGroup;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.tif")).unwrap();
+	/// for page in 0..=metadata.get_max_generic_ifd_number()
+	/// {
+	///     println!("{:?}", metadata.get_tag_in_page(page, 0x011d, ExifTagGroup::GENERIC));
+	/// }
+	/// # }
+	/// ```
+	pub fn
+	get_tag_in_page
+	(
+		&self,
+		generic_ifd_nr: u32,
+		hex:            u16,
+		group:          ExifTagGroup,
+	)
+	-> Option<&ExifTag>
+	{
+		return self.get_ifd(group, generic_ifd_nr)?
+			.get_tags()
+			.iter()
+			.find(|tag| tag.as_u16() == hex);
+	}
+}
+
 pub struct
 GetTagIterator<'a>
 {
-	metadata:          &'a Metadata,
-	current_ifd_index: usize,
-	current_tag_index: usize,
-	tag_hex_value:     u16,
-	group:             Option<ExifTagGroup>,
+	metadata:              &'a Metadata,
+	current_ifd_index:     usize,
+	current_tag_index:     usize,
+	current_derived_index: usize,
+	tag_hex_value:         u16,
+	group:                 Option<ExifTagGroup>,
 }
 
 impl<'a> Iterator
@@ -203,6 +324,20 @@ for GetTagIterator<'a>
 				self.current_ifd_index += 1;
 			}
 		}
+
+		// Finally: Check the tags derived from other information in the
+		// image file, see `Metadata::is_derived`
+		while self.current_derived_index < self.metadata.derived_tags.len()
+		{
+			let derived_tag = &self.metadata.derived_tags[self.current_derived_index];
+			self.current_derived_index += 1;
+
+			if derived_tag.as_u16() == self.tag_hex_value
+			&& self.group.map_or(true, |group| group == derived_tag.get_group())
+			{
+				return Some(derived_tag);
+			}
+		}
 		return None;
 	}
 }
\ No newline at end of file
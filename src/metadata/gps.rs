@@ -0,0 +1,813 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::exif_tag::ExifTag;
+use crate::rational::uR64;
+
+use super::comment::decode_character_coded;
+use super::comment::encode_character_coded;
+use super::Metadata;
+
+/// Precision used for the seconds when converting decimal degrees to the
+/// degrees/minutes/seconds representation (i.e. 1/1000 of a second)
+const SECONDS_DENOMINATOR: u32 = 1000;
+
+/// Number of decimal places of the coordinates in a geo URI (RFC 5870),
+/// which corresponds to about 0.1 meters
+const GEO_URI_PRECISION: usize = 6;
+
+/// Converts a coordinate stored as degrees, minutes and seconds (as used by
+/// e.g. the `GPSLatitude` or `GPSDestLongitude` tags) into decimal degrees.
+/// The sign is not part of these tags but stored in the respective `Ref` tag.
+pub(crate) fn
+dms_to_decimal
+(
+	dms: &Vec<uR64>
+)
+-> Option<f64>
+{
+	if dms.len() != 3 || dms.iter().any(|component| component.denominator == 0)
+	{
+		return None;
+	}
+
+	let component = |index: usize| dms[index].nominator as f64 / dms[index].denominator as f64;
+
+	return Some(component(0) + component(1) / 60.0 + component(2) / 3600.0);
+}
+
+/// Converts decimal degrees into degrees, minutes and seconds. The sign gets
+/// dropped and needs to be stored in the respective `Ref` tag.
+pub(crate) fn
+decimal_to_dms
+(
+	decimal: f64
+)
+-> Vec<uR64>
+{
+	// Work with the total number of (fractional) seconds to avoid having to
+	// carry e.g. 60 seconds due to rounding over to the minutes
+	let total = (decimal.abs() * 3600.0 * SECONDS_DENOMINATOR as f64).round() as u64;
+
+	let seconds_per_degree = 3600 * SECONDS_DENOMINATOR as u64;
+	let seconds_per_minute =   60 * SECONDS_DENOMINATOR as u64;
+
+	return vec![
+		uR64 { nominator: (total / seconds_per_degree) as u32,                         denominator: 1                   },
+		uR64 { nominator: ((total % seconds_per_degree) / seconds_per_minute) as u32, denominator: 1                   },
+		uR64 { nominator: (total % seconds_per_minute) as u32,                         denominator: SECONDS_DENOMINATOR },
+	];
+}
+
+/// Converts a direction or bearing (in degrees) to a rational with two
+/// decimal places
+fn
+degrees_to_rational
+(
+	degrees: f64
+)
+-> uR64
+{
+	uR64 { nominator: (degrees.abs() * 100.0).round() as u32, denominator: 100 }
+}
+
+/// The reference for the `GPSImgDirection` and `GPSDestBearing` tags, stored
+/// in `GPSImgDirectionRef` and `GPSDestBearingRef`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+GpsDirectionRef
+{
+	TrueNorth,                                                                  // "T"
+	MagneticNorth,                                                              // "M"
+}
+
+impl
+GpsDirectionRef
+{
+	fn
+	from_str
+	(
+		value: &str
+	)
+	-> Option<GpsDirectionRef>
+	{
+		match value.trim_end_matches('\0')
+		{
+			"T" => Some(GpsDirectionRef::TrueNorth),
+			"M" => Some(GpsDirectionRef::MagneticNorth),
+			_   => None,
+		}
+	}
+
+	fn
+	as_str
+	(
+		&self
+	)
+	-> &'static str
+	{
+		match self
+		{
+			GpsDirectionRef::TrueNorth     => "T",
+			GpsDirectionRef::MagneticNorth => "M",
+		}
+	}
+}
+
+/// The status of the GPS receiver when the image was captured, stored in
+/// the `GPSStatus` tag (0x0009)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+GpsStatus
+{
+	MeasurementActive,                                                          // "A"
+	MeasurementVoid,                                                            // "V"
+}
+
+impl
+GpsStatus
+{
+	fn
+	from_str
+	(
+		value: &str
+	)
+	-> Option<GpsStatus>
+	{
+		match value.trim_end_matches('\0')
+		{
+			"A" => Some(GpsStatus::MeasurementActive),
+			"V" => Some(GpsStatus::MeasurementVoid),
+			_   => None,
+		}
+	}
+
+	fn
+	as_str
+	(
+		&self
+	)
+	-> &'static str
+	{
+		match self
+		{
+			GpsStatus::MeasurementActive => "A",
+			GpsStatus::MeasurementVoid   => "V",
+		}
+	}
+}
+
+/// The GPS measurement mode, stored in the `GPSMeasureMode` tag (0x000a)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+GpsMeasureMode
+{
+	TwoDimensional,                                                             // "2"
+	ThreeDimensional,                                                           // "3"
+}
+
+impl
+GpsMeasureMode
+{
+	fn
+	from_str
+	(
+		value: &str
+	)
+	-> Option<GpsMeasureMode>
+	{
+		match value.trim_end_matches('\0')
+		{
+			"2" => Some(GpsMeasureMode::TwoDimensional),
+			"3" => Some(GpsMeasureMode::ThreeDimensional),
+			_   => None,
+		}
+	}
+
+	fn
+	as_str
+	(
+		&self
+	)
+	-> &'static str
+	{
+		match self
+		{
+			GpsMeasureMode::TwoDimensional   => "2",
+			GpsMeasureMode::ThreeDimensional => "3",
+		}
+	}
+}
+
+/// Whether differential correction was applied to the GPS receiver, stored
+/// in the `GPSDifferential` tag (0x001e)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+GpsDifferential
+{
+	NoCorrection,                                                               // 0
+	Corrected,                                                                  // 1
+}
+
+impl
+GpsDifferential
+{
+	fn
+	from_u16
+	(
+		value: u16
+	)
+	-> Option<GpsDifferential>
+	{
+		match value
+		{
+			0 => Some(GpsDifferential::NoCorrection),
+			1 => Some(GpsDifferential::Corrected),
+			_ => None,
+		}
+	}
+
+	fn
+	as_u16
+	(
+		&self
+	)
+	-> u16
+	{
+		*self as u16
+	}
+}
+
+impl
+Metadata
+{
+	/// Gets the value of a `STRING` GPS reference tag like `GPSDestLatitudeRef`
+	fn
+	get_gps_ref
+	(
+		&self,
+		tag: &ExifTag
+	)
+	-> Option<String>
+	{
+		match self.get_tag(tag).next()
+		{
+			Some(ExifTag::GPSLatitudeRef(value))
+			| Some(ExifTag::GPSLongitudeRef(value))
+			| Some(ExifTag::GPSDestLatitudeRef(value))
+			| Some(ExifTag::GPSDestLongitudeRef(value))
+			| Some(ExifTag::GPSDestBearingRef(value))
+			| Some(ExifTag::GPSImgDirectionRef(value))
+			| Some(ExifTag::GPSStatus(value))
+			| Some(ExifTag::GPSMeasureMode(value))
+			=> Some(value.trim_end_matches('\0').to_string()),
+
+			_
+			=> None,
+		}
+	}
+
+	/// Gets the position at which the image was taken as decimal degrees
+	/// (latitude, longitude) from the `GPSLatitude` and `GPSLongitude` tags.
+	/// Southern latitudes and western longitudes are negative, as determined
+	/// by the corresponding `Ref` tags.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some((latitude, longitude)) = metadata.get_gps_position()
+	/// {
+	///     println!("Taken at {}, {}", latitude, longitude);
+	/// }
+	/// # }
+	/// ```
+	pub fn
+	get_gps_position
+	(
+		&self
+	)
+	-> Option<(f64, f64)>
+	{
+		let mut latitude = match self.get_tag(&ExifTag::GPSLatitude(Vec::new())).next()
+		{
+			Some(ExifTag::GPSLatitude(value)) => dms_to_decimal(value)?,
+			_                                 => return None,
+		};
+
+		let mut longitude = match self.get_tag(&ExifTag::GPSLongitude(Vec::new())).next()
+		{
+			Some(ExifTag::GPSLongitude(value)) => dms_to_decimal(value)?,
+			_                                  => return None,
+		};
+
+		if self.get_gps_ref(&ExifTag::GPSLatitudeRef(String::new())).as_deref() == Some("S")
+		{
+			latitude = -latitude;
+		}
+
+		if self.get_gps_ref(&ExifTag::GPSLongitudeRef(String::new())).as_deref() == Some("W")
+		{
+			longitude = -longitude;
+		}
+
+		return Some((latitude, longitude));
+	}
+
+	/// Sets the `GPSLatitude` and `GPSLongitude` tags together with their
+	/// `Ref` tags from the given decimal degrees
+	pub fn
+	set_gps_position
+	(
+		&mut self,
+		latitude:  f64,
+		longitude: f64
+	)
+	{
+		let latitude_ref  = if latitude  < 0.0 { "S" } else { "N" };
+		let longitude_ref = if longitude < 0.0 { "W" } else { "E" };
+
+		self.set_tag(ExifTag::GPSLatitudeRef( latitude_ref.to_string()));
+		self.set_tag(ExifTag::GPSLatitude(    decimal_to_dms(latitude)));
+		self.set_tag(ExifTag::GPSLongitudeRef(longitude_ref.to_string()));
+		self.set_tag(ExifTag::GPSLongitude(   decimal_to_dms(longitude)));
+	}
+
+	/// Gets the position as a geo URI (RFC 5870), e.g. `geo:48.858400,2.294500`
+	/// for sharing or opening the location in a map application. Returns
+	/// `None` if the image has no (valid) GPS position.
+	///
+	/// # Examples
+	/// ```
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let mut metadata = Metadata::new();
+	/// metadata.set_gps_position(-33.8568, 151.2153);
+	/// assert_eq!(metadata.gps_geo_uri(), Some("geo:-33.856800,151.215300".to_string()));
+	/// ```
+	pub fn
+	gps_geo_uri
+	(
+		&self
+	)
+	-> Option<String>
+	{
+		let (latitude, longitude) = self.get_gps_position()?;
+		return Some(format!("geo:{:.*},{:.*}", GEO_URI_PRECISION, latitude, GEO_URI_PRECISION, longitude));
+	}
+
+	/// Gets the position as decimal degrees with the given number of decimal
+	/// places, e.g. `48.8584, 2.2945` for a precision of 4. Southern latitudes
+	/// and western longitudes are negative. Returns `None` if the image has no
+	/// (valid) GPS position.
+	pub fn
+	gps_decimal_string
+	(
+		&self,
+		precision: usize
+	)
+	-> Option<String>
+	{
+		let (latitude, longitude) = self.get_gps_position()?;
+		return Some(format!("{:.*}, {:.*}", precision, latitude, precision, longitude));
+	}
+
+	/// Gets the destination position as decimal degrees (latitude, longitude)
+	/// from the `GPSDestLatitude` and `GPSDestLongitude` tags. Southern
+	/// latitudes and western longitudes are negative, as determined by the
+	/// corresponding `Ref` tags.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some((latitude, longitude)) = metadata.get_gps_dest_position()
+	/// {
+	///     println!("Destination: {}, {}", latitude, longitude);
+	/// }
+	/// # }
+	/// ```
+	pub fn
+	get_gps_dest_position
+	(
+		&self
+	)
+	-> Option<(f64, f64)>
+	{
+		let mut latitude = match self.get_tag(&ExifTag::GPSDestLatitude(Vec::new())).next()
+		{
+			Some(ExifTag::GPSDestLatitude(value)) => dms_to_decimal(value)?,
+			_                                     => return None,
+		};
+
+		let mut longitude = match self.get_tag(&ExifTag::GPSDestLongitude(Vec::new())).next()
+		{
+			Some(ExifTag::GPSDestLongitude(value)) => dms_to_decimal(value)?,
+			_                                      => return None,
+		};
+
+		if self.get_gps_ref(&ExifTag::GPSDestLatitudeRef(String::new())).as_deref() == Some("S")
+		{
+			latitude = -latitude;
+		}
+
+		if self.get_gps_ref(&ExifTag::GPSDestLongitudeRef(String::new())).as_deref() == Some("W")
+		{
+			longitude = -longitude;
+		}
+
+		return Some((latitude, longitude));
+	}
+
+	/// Sets the `GPSDestLatitude` and `GPSDestLongitude` tags together with
+	/// their `Ref` tags from the given decimal degrees
+	pub fn
+	set_gps_dest_position
+	(
+		&mut self,
+		latitude:  f64,
+		longitude: f64
+	)
+	{
+		let latitude_ref  = if latitude  < 0.0 { "S" } else { "N" };
+		let longitude_ref = if longitude < 0.0 { "W" } else { "E" };
+
+		self.set_tag(ExifTag::GPSDestLatitudeRef( latitude_ref.to_string()));
+		self.set_tag(ExifTag::GPSDestLatitude(    decimal_to_dms(latitude)));
+		self.set_tag(ExifTag::GPSDestLongitudeRef(longitude_ref.to_string()));
+		self.set_tag(ExifTag::GPSDestLongitude(   decimal_to_dms(longitude)));
+	}
+
+	/// Gets the bearing to the destination (in degrees) from the
+	/// `GPSDestBearing` tag and its reference from `GPSDestBearingRef`
+	pub fn
+	get_gps_dest_bearing
+	(
+		&self
+	)
+	-> Option<(f64, GpsDirectionRef)>
+	{
+		if let Some(ExifTag::GPSDestBearing(value)) = self.get_tag(&ExifTag::GPSDestBearing(Vec::new())).next()
+		{
+			let bearing = value.first().filter(|bearing| bearing.denominator != 0)?;
+			let reference = GpsDirectionRef::from_str(
+				&self.get_gps_ref(&ExifTag::GPSDestBearingRef(String::new()))?
+			)?;
+			return Some((bearing.nominator as f64 / bearing.denominator as f64, reference));
+		}
+		return None;
+	}
+
+	/// Sets the `GPSDestBearing` (in degrees) and `GPSDestBearingRef` tags
+	pub fn
+	set_gps_dest_bearing
+	(
+		&mut self,
+		bearing:   f64,
+		reference: GpsDirectionRef
+	)
+	{
+		self.set_tag(ExifTag::GPSDestBearingRef(reference.as_str().to_string()));
+		self.set_tag(ExifTag::GPSDestBearing(vec![degrees_to_rational(bearing)]));
+	}
+
+	/// Gets the direction of the image when it was captured (in degrees)
+	/// from the `GPSImgDirection` tag and its reference from
+	/// `GPSImgDirectionRef`
+	pub fn
+	get_gps_img_direction
+	(
+		&self
+	)
+	-> Option<(f64, GpsDirectionRef)>
+	{
+		if let Some(ExifTag::GPSImgDirection(value)) = self.get_tag(&ExifTag::GPSImgDirection(Vec::new())).next()
+		{
+			let direction = value.first().filter(|direction| direction.denominator != 0)?;
+			let reference = GpsDirectionRef::from_str(
+				&self.get_gps_ref(&ExifTag::GPSImgDirectionRef(String::new()))?
+			)?;
+			return Some((direction.nominator as f64 / direction.denominator as f64, reference));
+		}
+		return None;
+	}
+
+	/// Sets the `GPSImgDirection` (in degrees) and `GPSImgDirectionRef` tags
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::metadata::gps::GpsDirectionRef;
+	///
+	/// let mut metadata = Metadata::new();
+	/// metadata.set_gps_img_direction(271.5, GpsDirectionRef::TrueNorth);
+	/// ```
+	pub fn
+	set_gps_img_direction
+	(
+		&mut self,
+		direction: f64,
+		reference: GpsDirectionRef
+	)
+	{
+		self.set_tag(ExifTag::GPSImgDirectionRef(reference.as_str().to_string()));
+		self.set_tag(ExifTag::GPSImgDirection(vec![degrees_to_rational(direction)]));
+	}
+
+	/// Gets the name of the method used for location finding (e.g. "GPS",
+	/// "CELLID" or "WLAN") from the `GPSProcessingMethod` tag, decoded
+	/// according to its character code prefix like `get_user_comment`
+	pub fn
+	get_gps_processing_method
+	(
+		&self
+	)
+	-> Option<String>
+	{
+		if let Some(ExifTag::GPSProcessingMethod(value)) = self.get_tag(&ExifTag::GPSProcessingMethod(Vec::new())).next()
+		{
+			return decode_character_coded(value, &self.endian).map(|(_, text)| text);
+		}
+		return None;
+	}
+
+	/// Sets the `GPSProcessingMethod` tag, using the `ASCII` character code
+	/// if possible and `UNICODE` otherwise
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let mut metadata = Metadata::new();
+	/// metadata.set_gps_processing_method("GPS");
+	/// ```
+	pub fn
+	set_gps_processing_method
+	(
+		&mut self,
+		processing_method: &str
+	)
+	{
+		self.set_tag(ExifTag::GPSProcessingMethod(encode_character_coded(processing_method, &self.endian)));
+	}
+
+	/// Gets the name of the GPS area from the `GPSAreaInformation` tag,
+	/// decoded according to its character code prefix
+	pub fn
+	get_gps_area_information
+	(
+		&self
+	)
+	-> Option<String>
+	{
+		if let Some(ExifTag::GPSAreaInformation(value)) = self.get_tag(&ExifTag::GPSAreaInformation(Vec::new())).next()
+		{
+			return decode_character_coded(value, &self.endian).map(|(_, text)| text);
+		}
+		return None;
+	}
+
+	/// Sets the `GPSAreaInformation` tag, using the `ASCII` character code
+	/// if possible and `UNICODE` otherwise
+	pub fn
+	set_gps_area_information
+	(
+		&mut self,
+		area_information: &str
+	)
+	{
+		self.set_tag(ExifTag::GPSAreaInformation(encode_character_coded(area_information, &self.endian)));
+	}
+
+	/// Gets the status of the GPS receiver from the `GPSStatus` tag
+	pub fn
+	get_gps_status
+	(
+		&self
+	)
+	-> Option<GpsStatus>
+	{
+		return GpsStatus::from_str(&self.get_gps_ref(&ExifTag::GPSStatus(String::new()))?);
+	}
+
+	/// Sets the `GPSStatus` tag
+	pub fn
+	set_gps_status
+	(
+		&mut self,
+		status: GpsStatus
+	)
+	{
+		self.set_tag(ExifTag::GPSStatus(status.as_str().to_string()));
+	}
+
+	/// Gets the GPS measurement mode from the `GPSMeasureMode` tag
+	pub fn
+	get_gps_measure_mode
+	(
+		&self
+	)
+	-> Option<GpsMeasureMode>
+	{
+		return GpsMeasureMode::from_str(&self.get_gps_ref(&ExifTag::GPSMeasureMode(String::new()))?);
+	}
+
+	/// Sets the `GPSMeasureMode` tag
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::metadata::gps::GpsMeasureMode;
+	///
+	/// let mut metadata = Metadata::new();
+	/// metadata.set_gps_measure_mode(GpsMeasureMode::ThreeDimensional);
+	/// metadata.set_gps_dop(1.25);
+	/// ```
+	pub fn
+	set_gps_measure_mode
+	(
+		&mut self,
+		measure_mode: GpsMeasureMode
+	)
+	{
+		self.set_tag(ExifTag::GPSMeasureMode(measure_mode.as_str().to_string()));
+	}
+
+	/// Gets the dilution of precision of the GPS data from the `GPSDOP` tag,
+	/// i.e. the HDOP for 2D and the PDOP for 3D measurements
+	pub fn
+	get_gps_dop
+	(
+		&self
+	)
+	-> Option<f64>
+	{
+		if let Some(ExifTag::GPSDOP(value)) = self.get_tag(&ExifTag::GPSDOP(Vec::new())).next()
+		{
+			let dop = value.first().filter(|dop| dop.denominator != 0)?;
+			return Some(dop.nominator as f64 / dop.denominator as f64);
+		}
+		return None;
+	}
+
+	/// Sets the `GPSDOP` tag, using two decimal places
+	pub fn
+	set_gps_dop
+	(
+		&mut self,
+		dop: f64
+	)
+	{
+		self.set_tag(ExifTag::GPSDOP(vec![uR64 { nominator: (dop.abs() * 100.0).round() as u32, denominator: 100 }]));
+	}
+
+	/// Gets whether differential correction was applied from the
+	/// `GPSDifferential` tag
+	pub fn
+	get_gps_differential
+	(
+		&self
+	)
+	-> Option<GpsDifferential>
+	{
+		if let Some(ExifTag::GPSDifferential(value)) = self.get_tag(&ExifTag::GPSDifferential(Vec::new())).next()
+		{
+			return value.first().and_then(|raw_value| GpsDifferential::from_u16(*raw_value));
+		}
+		return None;
+	}
+
+	/// Sets the `GPSDifferential` tag
+	pub fn
+	set_gps_differential
+	(
+		&mut self,
+		differential: GpsDifferential
+	)
+	{
+		self.set_tag(ExifTag::GPSDifferential(vec![differential.as_u16()]));
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use crate::exif_tag::ExifTag;
+	use crate::metadata::Metadata;
+	use crate::rational::uR64;
+
+	use super::decimal_to_dms;
+	use super::dms_to_decimal;
+	use super::GpsDifferential;
+	use super::GpsDirectionRef;
+	use super::GpsMeasureMode;
+	use super::GpsStatus;
+
+	#[test]
+	fn
+	dms_conversion()
+	{
+		let dms = decimal_to_dms(-48.8584);
+		assert_eq!(dms, vec![
+			uR64 { nominator: 48,    denominator: 1    },
+			uR64 { nominator: 51,    denominator: 1    },
+			uR64 { nominator: 30240, denominator: 1000 },
+		]);
+		assert!((dms_to_decimal(&dms).unwrap() - 48.8584).abs() < 1e-9);
+
+		// Rounding must not result in 60 seconds
+		let dms = decimal_to_dms(10.0 - 1e-9);
+		assert_eq!(dms[0], uR64 { nominator: 10, denominator: 1 });
+		assert_eq!(dms[2], uR64 { nominator: 0,  denominator: 1000 });
+
+		assert_eq!(dms_to_decimal(&vec![uR64 { nominator: 1, denominator: 1 }]), None);
+	}
+
+	#[test]
+	fn
+	dest_position_and_img_direction_round_trip()
+	{
+		let mut metadata = Metadata::new();
+		assert_eq!(metadata.get_gps_dest_position(), None);
+
+		metadata.set_gps_dest_position(-33.8568, 151.2153);
+		metadata.set_gps_img_direction(271.5, GpsDirectionRef::TrueNorth);
+
+		let (latitude, longitude) = metadata.get_gps_dest_position().unwrap();
+		assert!((latitude  - -33.8568).abs() < 1e-6);
+		assert!((longitude - 151.2153).abs() < 1e-6);
+
+		assert_eq!(metadata.get_gps_img_direction(), Some((271.5, GpsDirectionRef::TrueNorth)));
+		assert_eq!(metadata.get_gps_dest_bearing(),  None);
+	}
+
+	#[test]
+	fn
+	geo_uri_and_decimal_string()
+	{
+		let mut metadata = Metadata::new();
+		assert_eq!(metadata.gps_geo_uri(),         None);
+		assert_eq!(metadata.gps_decimal_string(4), None);
+
+		metadata.set_gps_position(48.8584, 2.2945);
+		assert_eq!(metadata.gps_geo_uri(),         Some("geo:48.858400,2.294500".to_string()));
+		assert_eq!(metadata.gps_decimal_string(4), Some("48.8584, 2.2945".to_string()));
+		assert_eq!(metadata.gps_decimal_string(1), Some("48.9, 2.3".to_string()));
+
+		// The signs are taken from the Ref tags
+		metadata.set_tag(ExifTag::GPSLatitudeRef( "S".to_string()));
+		metadata.set_tag(ExifTag::GPSLongitudeRef("W".to_string()));
+		assert_eq!(metadata.gps_decimal_string(4), Some("-48.8584, -2.2945".to_string()));
+	}
+
+	#[test]
+	fn
+	processing_method_and_area_information_round_trip()
+	{
+		let mut metadata = Metadata::new();
+		assert_eq!(metadata.get_gps_processing_method(), None);
+
+		metadata.set_gps_processing_method("GPS");
+		metadata.set_gps_area_information("Zürich");
+
+		assert_eq!(
+			metadata.get_tag(&ExifTag::GPSProcessingMethod(Vec::new())).next(),
+			Some(&ExifTag::GPSProcessingMethod(b"ASCII\0\0\0GPS".to_vec()))
+		);
+		assert_eq!(metadata.get_gps_processing_method(), Some("GPS".to_string()));
+		assert_eq!(metadata.get_gps_area_information(),  Some("Zürich".to_string()));
+	}
+
+	#[test]
+	fn
+	measure_mode_and_dop_round_trip()
+	{
+		let mut metadata = Metadata::new();
+		assert_eq!(metadata.get_gps_measure_mode(), None);
+		assert_eq!(metadata.get_gps_dop(),          None);
+
+		metadata.set_gps_status(GpsStatus::MeasurementActive);
+		metadata.set_gps_measure_mode(GpsMeasureMode::ThreeDimensional);
+		metadata.set_gps_dop(1.25);
+		metadata.set_gps_differential(GpsDifferential::Corrected);
+
+		// Decode the encoded data to make sure that the values survive
+		let encoded  = metadata.encode().unwrap();
+		let metadata = Metadata::new_from_vec(&encoded, crate::filetype::FileExtension::TIFF).unwrap();
+
+		assert_eq!(
+			metadata.get_tag(&ExifTag::GPSDOP(Vec::new())).next(),
+			Some(&ExifTag::GPSDOP(vec![uR64 { nominator: 125, denominator: 100 }]))
+		);
+		assert_eq!(metadata.get_gps_status(),       Some(GpsStatus::MeasurementActive));
+		assert_eq!(metadata.get_gps_measure_mode(), Some(GpsMeasureMode::ThreeDimensional));
+		assert_eq!(metadata.get_gps_dop(),          Some(1.25));
+		assert_eq!(metadata.get_gps_differential(), Some(GpsDifferential::Corrected));
+	}
+}
@@ -0,0 +1,107 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::endian::Endian;
+
+use super::Metadata;
+
+/// Offset basis and prime of the 64 bit FNV-1a hash
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME:        u64 = 0x00000100000001b3;
+
+/// Computes the 64 bit FNV-1a hash of the given data. Unlike the hashers of
+/// the standard library, its result is guaranteed to be stable across Rust
+/// versions and platforms, so it can be stored e.g. in a database.
+fn
+fnv1a_64
+(
+	data: &[u8]
+)
+-> u64
+{
+	let mut hash = FNV_OFFSET_BASIS;
+	for byte in data
+	{
+		hash ^= *byte as u64;
+		hash  = hash.wrapping_mul(FNV_PRIME);
+	}
+	return hash;
+}
+
+impl
+Metadata
+{
+	/// Computes a fingerprint of the metadata for detecting modifications,
+	/// e.g. by an asset management system. The hash is computed over the
+	/// encoded EXIF data using little endian, with the IFDs and their tags
+	/// in sorted order. Thus, two structs with identical tags have the same
+	/// hash, regardless of the order in which the tags were read or set and
+	/// of the endianness of the original data.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// println!("{:016x}", metadata.content_hash().unwrap());
+	/// # }
+	/// ```
+	pub fn
+	content_hash
+	(
+		&self
+	)
+	-> Result<u64, std::io::Error>
+	{
+		let mut canonical = self.clone();
+		canonical.set_endian(Endian::Little);
+		return Ok(fnv1a_64(&canonical.encode()?));
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use crate::endian::Endian;
+	use crate::exif_tag::ExifTag;
+	use crate::filetype::FileExtension;
+	use crate::metadata::Metadata;
+
+	use super::fnv1a_64;
+
+	#[test]
+	fn
+	fnv1a_reference_values()
+	{
+		assert_eq!(fnv1a_64(b""),       0xcbf29ce484222325);
+		assert_eq!(fnv1a_64(b"a"),      0xaf63dc4c8601ec8c);
+		assert_eq!(fnv1a_64(b"foobar"), 0x85944171f73967e8);
+	}
+
+	#[test]
+	fn
+	content_hash_ignores_insertion_order()
+	{
+		let mut metadata1 = Metadata::new();
+		metadata1.set_tag(ExifTag::ImageDescription("Hello World!".to_string()));
+		metadata1.set_tag(ExifTag::ISO(vec![2706]));
+		metadata1.set_tag(ExifTag::GPSAltitude(vec![crate::rational::uR64 { nominator: 1, denominator: 2 }]));
+
+		let mut metadata2 = Metadata::new();
+		metadata2.set_endian(Endian::Big);
+		metadata2.set_tag(ExifTag::GPSAltitude(vec![crate::rational::uR64 { nominator: 1, denominator: 2 }]));
+		metadata2.set_tag(ExifTag::ISO(vec![2706]));
+		metadata2.set_tag(ExifTag::ImageDescription("Hello World!".to_string()));
+
+		assert_eq!(metadata1.content_hash().unwrap(), metadata2.content_hash().unwrap());
+
+		// Also the same after decoding the (big endian) encoded data
+		let decoded = Metadata::new_from_vec(&metadata2.encode().unwrap(), FileExtension::TIFF).unwrap();
+		assert_eq!(metadata1.content_hash().unwrap(), decoded.content_hash().unwrap());
+
+		metadata2.set_tag(ExifTag::ISO(vec![100]));
+		assert_ne!(metadata1.content_hash().unwrap(), metadata2.content_hash().unwrap());
+	}
+}
@@ -0,0 +1,256 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::exif_tag::ExifTag;
+use crate::general_file_io::io_error;
+
+use super::Metadata;
+
+/// Signature of a resource in a Photoshop Image Resource Block (IRB)
+const IRB_SIGNATURE: [u8; 4] = [0x38, 0x42, 0x49, 0x4d];                        // "8BIM"
+
+/// ID of the IRB resource holding the IPTC-IIM data
+const IRB_RESOURCE_ID_IPTC: u16 = 0x0404;
+
+/// Marker that starts each IPTC-IIM dataset
+const IPTC_TAG_MARKER: u8 = 0x1c;
+
+/// A single IPTC-IIM dataset, e.g. record 2 and dataset 120 for the caption.
+/// See https://exiftool.org/TagNames/IPTC.html
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct
+IptcDataSet
+{
+	pub record:  u8,
+	pub dataset: u8,
+	pub data:    Vec<u8>,
+}
+
+/// Reads a big endian unsigned integer of `length` bytes at the given
+/// position, failing if the data is too short
+fn
+read_be
+(
+	data:     &[u8],
+	position: usize,
+	length:   usize
+)
+-> Result<u64, std::io::Error>
+{
+	let bytes = match position.checked_add(length).and_then(|end| data.get(position..end))
+	{
+		Some(bytes) => bytes,
+		None        => return io_error!(UnexpectedEof, "Unexpected end of IRB/IPTC data!"),
+	};
+
+	return Ok(bytes.iter().fold(0u64, |value, byte| (value << 8) | *byte as u64));
+}
+
+/// Searches the resources of a Photoshop Image Resource Block for the one
+/// with the given ID and returns its data. Each resource consists of the
+/// signature "8BIM", the 2 byte ID, a Pascal string as name (padded to an
+/// even length), the 4 byte size of the data and the data itself (again
+/// padded to an even length).
+pub(crate) fn
+find_irb_resource
+(
+	irb:         &[u8],
+	resource_id: u16
+)
+-> Result<Option<&[u8]>, std::io::Error>
+{
+	let mut position = 0;
+
+	while position < irb.len()
+	{
+		if irb.len() - position < IRB_SIGNATURE.len() || irb[position..position+4] != IRB_SIGNATURE
+		{
+			return io_error!(InvalidData, "Could not find expected IRB resource signature!");
+		}
+
+		let id          = read_be(irb, position + 4, 2)? as u16;
+		let name_length = read_be(irb, position + 6, 1)? as usize;
+
+		// Length byte and name together are padded to an even length
+		position += 6 + ((name_length + 2) & !1);
+
+		let size = read_be(irb, position, 4)? as usize;
+		position += 4;
+
+		let resource_data = match position.checked_add(size).and_then(|end| irb.get(position..end))
+		{
+			Some(resource_data) => resource_data,
+			None                => return io_error!(UnexpectedEof, "IRB resource exceeds available data!"),
+		};
+
+		if id == resource_id
+		{
+			return Ok(Some(resource_data));
+		}
+
+		position += size + (size & 1);
+	}
+
+	return Ok(None);
+}
+
+/// Decodes IPTC-IIM data into its datasets. Each dataset starts with the
+/// marker 0x1c, followed by the record and dataset number and the length
+/// of the data. If the highest bit of the 2 byte length is set, the
+/// remaining bits give the number of bytes that hold the actual length.
+pub(crate) fn
+decode_iptc
+(
+	data: &[u8]
+)
+-> Result<Vec<IptcDataSet>, std::io::Error>
+{
+	let mut datasets = Vec::new();
+	let mut position = 0;
+
+	while position < data.len()
+	{
+		// Some writers pad the data with NUL bytes
+		if data[position] == 0x00
+		{
+			position += 1;
+			continue;
+		}
+
+		if data[position] != IPTC_TAG_MARKER
+		{
+			return io_error!(InvalidData, "Could not find expected IPTC tag marker!");
+		}
+
+		let record  = read_be(data, position + 1, 1)? as u8;
+		let dataset = read_be(data, position + 2, 1)? as u8;
+		let mut length = read_be(data, position + 3, 2)? as usize;
+		position += 5;
+
+		if length & 0x8000 != 0
+		{
+			let length_of_length = length & 0x7fff;
+			if length_of_length > 8
+			{
+				return io_error!(InvalidData, "Extended IPTC dataset length too large!");
+			}
+			length = match usize::try_from(read_be(data, position, length_of_length)?)
+			{
+				Ok(length) => length,
+				Err(_)     => return io_error!(InvalidData, "Extended IPTC dataset length too large!"),
+			};
+			position += length_of_length;
+		}
+
+		let dataset_data = match position.checked_add(length).and_then(|end| data.get(position..end))
+		{
+			Some(dataset_data) => dataset_data,
+			None               => return io_error!(UnexpectedEof, "IPTC dataset exceeds available data!"),
+		};
+
+		datasets.push(IptcDataSet {
+			record,
+			dataset,
+			data: dataset_data.to_vec(),
+		});
+		position += length;
+	}
+
+	return Ok(datasets);
+}
+
+impl
+Metadata
+{
+	/// Gets the IPTC-IIM datasets stored in the Photoshop Image Resource
+	/// Block of the `PhotoshopSettings` tag (0x8649), as found in e.g. TIFF
+	/// and DNG files. Returns `None` if there is no such tag, no IPTC
+	/// resource or if the data can't be decoded.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.tif")).unwrap();
+	/// for dataset in metadata.get_iptc().unwrap_or_default()
+	/// {
+	///     println!("{}:{} {:?}", dataset.record, dataset.dataset, dataset.data);
+	/// }
+	/// # }
+	/// ```
+	pub fn
+	get_iptc
+	(
+		&self
+	)
+	-> Option<Vec<IptcDataSet>>
+	{
+		if let Some(ExifTag::PhotoshopSettings(irb)) = self.get_tag(&ExifTag::PhotoshopSettings(Vec::new())).next()
+		{
+			let iptc_data = find_irb_resource(irb, IRB_RESOURCE_ID_IPTC).ok()??;
+			return decode_iptc(iptc_data).ok();
+		}
+		return None;
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use crate::exif_tag::ExifTag;
+	use crate::metadata::Metadata;
+
+	use super::IptcDataSet;
+
+	#[test]
+	fn
+	get_iptc_from_irb()
+	{
+		let mut irb = Vec::new();
+
+		// Some other resource with a name (length 3 + 1 -> no padding) and
+		// an odd size (-> padded)
+		irb.extend(b"8BIM\x03\xed\x03abc\x00\x00\x00\x03xyz\x00");
+
+		// IPTC resource with an empty name (padded to 2 bytes)
+		let iptc = b"\x1c\x02\x00\x00\x02\x00\x04\x1c\x02\x78\x00\x05Hello";
+		irb.extend(b"8BIM\x04\x04\x00\x00");
+		irb.extend((iptc.len() as u32).to_be_bytes());
+		irb.extend(iptc);
+
+		let mut metadata = Metadata::new();
+		assert_eq!(metadata.get_iptc(), None);
+
+		metadata.set_tag(ExifTag::PhotoshopSettings(irb));
+		assert_eq!(metadata.get_iptc(), Some(vec![
+			IptcDataSet { record: 2, dataset: 0,   data: vec![0x00, 0x04]   },
+			IptcDataSet { record: 2, dataset: 120, data: b"Hello".to_vec() },
+		]));
+
+		// Truncated data
+		metadata.set_tag(ExifTag::PhotoshopSettings(b"8BIM\x04\x04\x00\x00\x00\x00\x00\x10\x1c".to_vec()));
+		assert_eq!(metadata.get_iptc(), None);
+	}
+
+	#[test]
+	fn
+	huge_extended_dataset_length()
+	{
+		// Dataset whose extended length (8 bytes) is u64::MAX
+		let iptc = b"\x1c\x02\x78\x80\x08\xff\xff\xff\xff\xff\xff\xff\xff";
+		assert!(super::decode_iptc(iptc).is_err());
+
+		let mut irb = b"8BIM\x04\x04\x00\x00".to_vec();
+		irb.extend((iptc.len() as u32).to_be_bytes());
+		irb.extend(iptc);
+		irb.push(0x00);
+
+		let mut metadata = Metadata::new();
+		metadata.set_tag(ExifTag::PhotoshopSettings(irb));
+		assert_eq!(metadata.get_iptc(), None);
+
+		assert!(super::read_be(iptc, usize::MAX, 2).is_err());
+	}
+}
@@ -0,0 +1,387 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::exif_tag::ExifTag;
+
+use super::Metadata;
+
+/// Value of the `SensitivityType` tag (0x8830) indicating that only the
+/// `ISOSpeed` tag is used
+const SENSITIVITY_TYPE_ISO_SPEED: u16 = 3;
+
+/// The values of the `SensitivityType` tag (0x8830), telling which of the
+/// sensitivity tags introduced with EXIF 2.3 are used
+/// See https://exiftool.org/TagNames/EXIF.html (0x8830)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+SensitivityType
+{
+	Unknown,                                                                    // 0
+	StandardOutputSensitivity,                                                  // 1
+	RecommendedExposureIndex,                                                   // 2
+	IsoSpeed,                                                                   // 3
+	SosAndRei,                                                                  // 4
+	SosAndIsoSpeed,                                                             // 5
+	ReiAndIsoSpeed,                                                             // 6
+	SosReiAndIsoSpeed,                                                          // 7
+	Other(u16),
+}
+
+impl
+SensitivityType
+{
+	pub fn
+	from_u16
+	(
+		value: u16
+	)
+	-> SensitivityType
+	{
+		match value
+		{
+			0 => SensitivityType::Unknown,
+			1 => SensitivityType::StandardOutputSensitivity,
+			2 => SensitivityType::RecommendedExposureIndex,
+			3 => SensitivityType::IsoSpeed,
+			4 => SensitivityType::SosAndRei,
+			5 => SensitivityType::SosAndIsoSpeed,
+			6 => SensitivityType::ReiAndIsoSpeed,
+			7 => SensitivityType::SosReiAndIsoSpeed,
+			_ => SensitivityType::Other(value),
+		}
+	}
+
+	pub fn
+	as_u16
+	(
+		&self
+	)
+	-> u16
+	{
+		match self
+		{
+			SensitivityType::Unknown                   => 0,
+			SensitivityType::StandardOutputSensitivity => 1,
+			SensitivityType::RecommendedExposureIndex  => 2,
+			SensitivityType::IsoSpeed                  => 3,
+			SensitivityType::SosAndRei                 => 4,
+			SensitivityType::SosAndIsoSpeed            => 5,
+			SensitivityType::ReiAndIsoSpeed            => 6,
+			SensitivityType::SosReiAndIsoSpeed         => 7,
+			SensitivityType::Other(value)              => *value,
+		}
+	}
+
+	/// Checks if the `ISOSpeed` tag (0x8833) is used
+	pub fn
+	uses_iso_speed
+	(
+		&self
+	)
+	-> bool
+	{
+		matches!(self,
+			SensitivityType::IsoSpeed
+			| SensitivityType::SosAndIsoSpeed
+			| SensitivityType::ReiAndIsoSpeed
+			| SensitivityType::SosReiAndIsoSpeed
+		)
+	}
+
+	/// Checks if the `StandardOutputSensitivity` tag (0x8831) is used
+	pub fn
+	uses_standard_output_sensitivity
+	(
+		&self
+	)
+	-> bool
+	{
+		matches!(self,
+			SensitivityType::StandardOutputSensitivity
+			| SensitivityType::SosAndRei
+			| SensitivityType::SosAndIsoSpeed
+			| SensitivityType::SosReiAndIsoSpeed
+		)
+	}
+
+	/// Checks if the `RecommendedExposureIndex` tag (0x8832) is used
+	pub fn
+	uses_recommended_exposure_index
+	(
+		&self
+	)
+	-> bool
+	{
+		matches!(self,
+			SensitivityType::RecommendedExposureIndex
+			| SensitivityType::SosAndRei
+			| SensitivityType::ReiAndIsoSpeed
+			| SensitivityType::SosReiAndIsoSpeed
+		)
+	}
+}
+
+/// The values of all sensitivity related tags of the ExifIFD, as returned
+/// by `Metadata::sensitivity`
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct
+SensitivityInfo
+{
+	/// The `SensitivityType` tag (0x8830)
+	pub sensitivity_type:            Option<SensitivityType>,
+
+	/// The `ISO` tag (0x8827, aka `PhotographicSensitivity`)
+	pub iso:                         Option<u16>,
+
+	/// The `StandardOutputSensitivity` tag (0x8831)
+	pub standard_output_sensitivity: Option<u32>,
+
+	/// The `RecommendedExposureIndex` tag (0x8832)
+	pub recommended_exposure_index:  Option<u32>,
+
+	/// The `ISOSpeed` tag (0x8833)
+	pub iso_speed:                   Option<u32>,
+
+	/// The `ISOSpeedLatitudeyyy` tag (0x8834)
+	pub iso_speed_latitude_yyy:      Option<u32>,
+
+	/// The `ISOSpeedLatitudezzz` tag (0x8835)
+	pub iso_speed_latitude_zzz:      Option<u32>,
+}
+
+impl
+SensitivityInfo
+{
+	/// Gets the value of the most specific tag that is used according to
+	/// the `SensitivityType`, in the order `ISOSpeed`,
+	/// `StandardOutputSensitivity` and `RecommendedExposureIndex`.
+	/// Returns `None` if there is no (known) `SensitivityType` or none of
+	/// the tags it refers to is present.
+	pub fn
+	authoritative_value
+	(
+		&self
+	)
+	-> Option<u32>
+	{
+		let sensitivity_type = self.sensitivity_type?;
+
+		return None
+			.or(self.iso_speed.filter(|_| sensitivity_type.uses_iso_speed()))
+			.or(self.standard_output_sensitivity.filter(|_| sensitivity_type.uses_standard_output_sensitivity()))
+			.or(self.recommended_exposure_index.filter(|_| sensitivity_type.uses_recommended_exposure_index()));
+	}
+}
+
+impl
+Metadata
+{
+	/// Gets the first value of an `INT32U` sensitivity tag, if present
+	fn
+	get_sensitivity_value
+	(
+		&self,
+		tag: &ExifTag
+	)
+	-> Option<u32>
+	{
+		match self.get_tag(tag).next()
+		{
+			Some(ExifTag::StandardOutputSensitivity(value))
+			| Some(ExifTag::RecommendedExposureIndex(value))
+			| Some(ExifTag::ISOSpeed(value))
+			| Some(ExifTag::ISOSpeedLatitudeyyy(value))
+			| Some(ExifTag::ISOSpeedLatitudezzz(value))
+			=> value.first().copied(),
+
+			_
+			=> None,
+		}
+	}
+
+	/// Gets the values of all sensitivity related tags, see `SensitivityInfo`
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let metadata    = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// let sensitivity = metadata.sensitivity();
+	/// if let Some(value) = sensitivity.authoritative_value()
+	/// {
+	///     println!("{:?}: {}", sensitivity.sensitivity_type, value);
+	/// }
+	/// # }
+	/// ```
+	pub fn
+	sensitivity
+	(
+		&self
+	)
+	-> SensitivityInfo
+	{
+		let sensitivity_type = match self.get_tag(&ExifTag::SensitivityType(Vec::new())).next()
+		{
+			Some(ExifTag::SensitivityType(value)) => value.first().map(|value| SensitivityType::from_u16(*value)),
+			_                                     => None,
+		};
+
+		let iso = match self.get_tag(&ExifTag::ISO(Vec::new())).next()
+		{
+			Some(ExifTag::ISO(value)) => value.first().copied(),
+			_                         => None,
+		};
+
+		return SensitivityInfo {
+			sensitivity_type:            sensitivity_type,
+			iso:                         iso,
+			standard_output_sensitivity: self.get_sensitivity_value(&ExifTag::StandardOutputSensitivity(Vec::new())),
+			recommended_exposure_index:  self.get_sensitivity_value(&ExifTag::RecommendedExposureIndex(Vec::new())),
+			iso_speed:                   self.get_sensitivity_value(&ExifTag::ISOSpeed(Vec::new())),
+			iso_speed_latitude_yyy:      self.get_sensitivity_value(&ExifTag::ISOSpeedLatitudeyyy(Vec::new())),
+			iso_speed_latitude_zzz:      self.get_sensitivity_value(&ExifTag::ISOSpeedLatitudezzz(Vec::new())),
+		};
+	}
+
+	/// Gets the ISO sensitivity of the camera. Since EXIF 2.3, the `ISO` tag
+	/// (0x8827, also known as `ISOSpeedRatings` or `PhotographicSensitivity`)
+	/// can be accompanied by more specific tags, with `SensitivityType`
+	/// (0x8830) telling which of them are used. The most specific one is
+	/// preferred, in the order `ISOSpeed`, `StandardOutputSensitivity` and
+	/// `RecommendedExposureIndex`, falling back to the `ISO` tag.
+	/// Note that the `ISO` tag can only store values up to 65535, so larger
+	/// values are only available via the newer tags.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some(iso) = metadata.get_iso()
+	/// {
+	///     println!("ISO {}", iso);
+	/// }
+	/// # }
+	/// ```
+	pub fn
+	get_iso
+	(
+		&self
+	)
+	-> Option<u32>
+	{
+		let sensitivity = self.sensitivity();
+
+		if let Some(specific_value) = sensitivity.authoritative_value()
+		{
+			return Some(specific_value);
+		}
+
+		if let Some(iso) = sensitivity.iso
+		{
+			return Some(iso as u32);
+		}
+
+		// No (valid) `SensitivityType` and no `ISO` tag, so just use
+		// whatever is there
+		return sensitivity.iso_speed
+			.or(sensitivity.standard_output_sensitivity)
+			.or(sensitivity.recommended_exposure_index);
+	}
+
+	/// Sets the ISO sensitivity consistently across the `ISO` tag (capped at
+	/// 65535), the `ISOSpeed` tag and `SensitivityType`, which is set to 3 to
+	/// indicate that `ISOSpeed` is used.
+	pub fn
+	set_iso
+	(
+		&mut self,
+		iso: u32
+	)
+	{
+		self.set_tag(ExifTag::ISO(vec![std::cmp::min(iso, u16::MAX as u32) as u16]));
+		self.set_tag(ExifTag::SensitivityType(vec![SENSITIVITY_TYPE_ISO_SPEED]));
+		self.set_tag(ExifTag::ISOSpeed(vec![iso]));
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use crate::exif_tag::ExifTag;
+	use crate::filetype::FileExtension;
+	use crate::metadata::Metadata;
+
+	use super::SensitivityType;
+
+	#[test]
+	fn
+	get_iso_old_convention()
+	{
+		let mut metadata = Metadata::new();
+		assert_eq!(metadata.get_iso(), None);
+
+		metadata.set_tag(ExifTag::ISO(vec![400]));
+		assert_eq!(metadata.get_iso(), Some(400));
+	}
+
+	#[test]
+	fn
+	get_iso_new_convention()
+	{
+		// Recommended exposure index is used, saturated ISO tag
+		let mut metadata = Metadata::new();
+		metadata.set_tag(ExifTag::ISO(vec![65535]));
+		metadata.set_tag(ExifTag::SensitivityType(vec![2]));
+		metadata.set_tag(ExifTag::RecommendedExposureIndex(vec![102400]));
+		metadata.set_tag(ExifTag::StandardOutputSensitivity(vec![80000]));
+		assert_eq!(metadata.get_iso(), Some(102400));
+
+		// Without any `SensitivityType` or `ISO` tag
+		let mut metadata = Metadata::new();
+		metadata.set_tag(ExifTag::StandardOutputSensitivity(vec![800]));
+		assert_eq!(metadata.get_iso(), Some(800));
+	}
+
+	#[test]
+	fn
+	set_iso_writes_all_tags()
+	{
+		let mut metadata = Metadata::new();
+		metadata.set_iso(204800);
+
+		assert_eq!(metadata.get_iso(), Some(204800));
+		assert_eq!(metadata.get_tag(&ExifTag::ISO(Vec::new())).next(),             Some(&ExifTag::ISO(vec![65535])));
+		assert_eq!(metadata.get_tag(&ExifTag::SensitivityType(Vec::new())).next(), Some(&ExifTag::SensitivityType(vec![3])));
+	}
+
+	#[test]
+	fn
+	sensitivity_with_recommended_exposure_index()
+	{
+		let mut metadata = Metadata::new();
+		metadata.set_tag(ExifTag::ISO(vec![6400]));
+		metadata.set_tag(ExifTag::SensitivityType(vec![2]));
+		metadata.set_tag(ExifTag::RecommendedExposureIndex(vec![6400]));
+		metadata.set_tag(ExifTag::ISOSpeedLatitudeyyy(vec![100]));
+
+		let metadata    = Metadata::new_from_vec(&metadata.encode().unwrap(), FileExtension::TIFF).unwrap();
+		let sensitivity = metadata.sensitivity();
+
+		assert_eq!(sensitivity.sensitivity_type,           Some(SensitivityType::RecommendedExposureIndex));
+		assert_eq!(sensitivity.iso,                        Some(6400));
+		assert_eq!(sensitivity.recommended_exposure_index, Some(6400));
+		assert_eq!(sensitivity.iso_speed,                  None);
+		assert_eq!(sensitivity.iso_speed_latitude_yyy,     Some(100));
+		assert_eq!(sensitivity.authoritative_value(),      Some(6400));
+
+		for value in 0..=10
+		{
+			assert_eq!(SensitivityType::from_u16(value).as_u16(), value);
+		}
+	}
+}
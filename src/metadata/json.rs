@@ -0,0 +1,327 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use std::collections::HashSet;
+
+use crate::endian::Endian;
+use crate::exif_tag::ExifTag;
+use crate::exif_tag::TagType;
+use crate::rational::uR64;
+
+use super::gps::dms_to_decimal;
+use super::pretty::decode_value;
+use super::pretty::is_coordinate;
+use super::pretty::DecodedValue;
+use super::Metadata;
+
+/// Significant digits exiftool rounds rationals to
+const RATIONAL_DIGITS: usize = 10;
+
+/// Significant digits exiftool rounds floats to
+const FLOAT_DIGITS: usize = 7;
+
+/// Significant digits of doubles and coordinates, which exiftool does not
+/// round but prints with Perl's default precision
+const DOUBLE_DIGITS: usize = 15;
+
+impl
+Metadata
+{
+	/// Formats the metadata as JSON object in the style of exiftool's `-j -G
+	/// -n` output, so it can be compared against exiftool, e.g.:
+	/// `{ "EXIF:ImageDescription": "Hello", "EXIF:GPSLatitude": 48.8584 }`
+	///
+	/// - All tags are prefixed with "EXIF:", the family 0 group of exiftool,
+	///   including the GPS tags (e.g. "EXIF:GPSLatitude"). The family 1
+	///   groups printed by `-G1`, like "GPS:GPSLatitude", are not used
+	/// - Single numbers are JSON numbers, lists are strings with the values
+	///   separated by spaces (e.g. `"GPSVersionID": "2 3 0 0"`)
+	/// - Rationals are written as decimals, coordinates as (unsigned) decimal
+	///   degrees, as their sign is stored in the respective `Ref` tag, and
+	///   `GPSTimeStamp` as "hh:mm:ss"
+	/// - Like exiftool, only the first occurrence of a tag is written (e.g.
+	///   `XResolution` of IFD0, but not that of IFD1)
+	/// - Unknown tags and offset tags (e.g. `ExifOffset`) are left out, as
+	///   their values depend on the encoding of the data
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// println!("{}", metadata.to_exiftool_json());
+	/// # }
+	/// ```
+	pub fn
+	to_exiftool_json
+	(
+		&self
+	)
+	-> String
+	{
+		let mut keys    = HashSet::new();
+		let mut entries = Vec::new();
+
+		for ifd in self.image_file_directories.iter()
+		{
+			for tag in ifd.get_tags()
+			{
+				if tag.is_unknown() || !matches!(tag.get_tag_type(), TagType::VALUE)
+				{
+					continue;
+				}
+
+				let key = format!("EXIF:{}", tag.name());
+				if keys.insert(key.clone())
+				{
+					entries.push(format!("  {}: {}", json_string(&key), json_value(tag, &self.endian)));
+				}
+			}
+		}
+
+		if entries.is_empty()
+		{
+			return "{}".to_string();
+		}
+		return format!("{{\n{}\n}}", entries.join(",\n"));
+	}
+}
+
+/// Quotes and escapes the given text as JSON string
+fn
+json_string
+(
+	text: &str
+)
+-> String
+{
+	let mut output = String::from("\"");
+	for character in text.chars()
+	{
+		match character
+		{
+			'"'  => output.push_str("\\\""),
+			'\\' => output.push_str("\\\\"),
+			'\n' => output.push_str("\\n"),
+			'\r' => output.push_str("\\r"),
+			'\t' => output.push_str("\\t"),
+			_ if character.is_control() => output.push_str(&format!("\\u{:04x}", character as u32)),
+			_    => output.push(character),
+		}
+	}
+	output.push('"');
+	return output;
+}
+
+/// Formats a decimal rounded to the given number of significant digits,
+/// which hides floating point artifacts like 48.858399999999996. Values
+/// that are not finite (e.g. due to a zero denominator) are written as the
+/// string "undef", just like exiftool does.
+fn
+decimal
+(
+	value:  f64,
+	digits: usize
+)
+-> String
+{
+	if !value.is_finite()
+	{
+		return "undef".to_string();
+	}
+
+	let rounded = format!("{:.*e}", digits - 1, value).parse::<f64>().unwrap_or(value);
+	return format!("{}", rounded);
+}
+
+/// Writes a single value as JSON number and multiple values as JSON string
+/// with the values separated by spaces. Values that are no valid JSON
+/// numbers (see `decimal`) are always written as string.
+fn
+json_numbers
+(
+	values: Vec<String>
+)
+-> String
+{
+	if values.len() == 1 && values[0] != "undef"
+	{
+		return values[0].clone();
+	}
+	return json_string(&values.join(" "));
+}
+
+/// Formats hours, minutes and seconds as "hh:mm:ss", with the fraction of
+/// the seconds (if any) appended, like exiftool does for the `GPSTimeStamp`
+fn
+time_stamp
+(
+	values: &[uR64]
+)
+-> Option<String>
+{
+	if values.len() != 3 || values.iter().any(|value| value.denominator == 0)
+	{
+		return None;
+	}
+
+	let mut seconds = values.iter()
+		.map(|value| value.nominator as f64 / value.denominator as f64)
+		.fold(0.0, |total, value| total * 60.0 + value);
+
+	let hours   = (seconds / 3600.0).floor();
+	seconds    -= hours * 3600.0;
+	let minutes = (seconds / 60.0).floor();
+	seconds    -= minutes * 60.0;
+
+	let seconds = decimal(seconds, RATIONAL_DIGITS);
+	let padding = if seconds.split('.').next().map_or(0, |whole| whole.len()) < 2 { "0" } else { "" };
+	return Some(format!("{:02}:{:02}:{}{}", hours as u64, minutes as u64, padding, seconds));
+}
+
+fn
+json_value
+(
+	tag:    &ExifTag,
+	endian: &Endian
+)
+-> String
+{
+	match decode_value(tag, endian)
+	{
+		DecodedValue::Text(value) => json_string(&value),
+
+		DecodedValue::UnsignedRationals(values) => {
+			if let (ExifTag::GPSTimeStamp(_), Some(time)) = (tag, time_stamp(&values))
+			{
+				return json_string(&time);
+			}
+			if let (true, Some(degrees)) = (is_coordinate(tag), dms_to_decimal(&values))
+			{
+				return decimal(degrees, DOUBLE_DIGITS);
+			}
+			return json_numbers(values.iter().map(|value| decimal(value.nominator as f64 / value.denominator as f64, RATIONAL_DIGITS)).collect());
+		},
+
+		DecodedValue::SignedRationals(values) => {
+			return json_numbers(values.iter().map(|value| decimal(value.nominator as f64 / value.denominator as f64, RATIONAL_DIGITS)).collect());
+		},
+
+		DecodedValue::Binary(raw_data) => {
+			// exiftool reads these as list of bytes instead of binary data
+			if matches!(tag, ExifTag::ComponentsConfiguration(_) | ExifTag::FileSource(_) | ExifTag::SceneType(_))
+			{
+				return json_numbers(raw_data.iter().map(|byte| byte.to_string()).collect());
+			}
+
+			// ASCII values like the `ExifVersion` ("0232") are written as
+			// text, everything else like exiftool does for binary data
+			if !raw_data.is_empty()
+			&& raw_data.iter().all(|byte| byte.is_ascii_graphic() || *byte == b' ')
+			{
+				return json_string(&String::from_utf8_lossy(&raw_data));
+			}
+			return json_string(&format!("(Binary data {} bytes, use -b option to extract)", raw_data.len()));
+		},
+
+		DecodedValue::Integers(values) => json_numbers(values.iter().map(|value| value.to_string()).collect()),
+		DecodedValue::Floats(values)   => json_numbers(values.iter().map(|value| decimal(*value as f64, FLOAT_DIGITS)).collect()),
+		DecodedValue::Doubles(values)  => json_numbers(values.iter().map(|value| decimal(*value, DOUBLE_DIGITS)).collect()),
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use crate::endian::Endian;
+	use crate::exif_tag::ExifTag;
+	use crate::filetype::FileExtension;
+	use crate::metadata::Metadata;
+	use crate::rational::iR64;
+	use crate::rational::uR64;
+
+	/// Selected fields for the tags set up below, written by hand following
+	/// the format of `exiftool -j -G -n` and not taken from an actual dump
+	/// of exiftool
+	const EXPECTED_JSON: &str = r#"{
+  "EXIF:ImageDescription": "Tour \"Eiffel\"",
+  "EXIF:Make": "Canon",
+  "EXIF:Orientation": 6,
+  "EXIF:XResolution": 72,
+  "EXIF:ExposureTime": 0.004,
+  "EXIF:FNumber": 2.8,
+  "EXIF:ISO": 400,
+  "EXIF:ExifVersion": "0232",
+  "EXIF:ExposureCompensation": -0.3333333333,
+  "EXIF:ComponentsConfiguration": "1 2 3 0",
+  "EXIF:GPSVersionID": "2 3 0 0",
+  "EXIF:GPSLatitudeRef": "N",
+  "EXIF:GPSLatitude": 48.8584,
+  "EXIF:GPSLongitude": 2.2945,
+  "EXIF:GPSTimeStamp": "12:30:15.5"
+}"#;
+
+	fn
+	rational
+	(
+		nominator:   u32,
+		denominator: u32
+	)
+	-> uR64
+	{
+		return uR64 { nominator, denominator };
+	}
+
+	#[test]
+	fn
+	matches_exiftool_dump()
+	{
+		let mut metadata = Metadata::new();
+		metadata.set_endian(Endian::Little);
+		metadata.set_tag(ExifTag::ImageDescription("Tour \"Eiffel\"".to_string()));
+		metadata.set_tag(ExifTag::Make("Canon".to_string()));
+		metadata.set_tag(ExifTag::Orientation(vec![6]));
+		metadata.set_tag(ExifTag::XResolution(vec![rational(72, 1)]));
+		metadata.set_tag(ExifTag::ExposureTime(vec![rational(1, 250)]));
+		metadata.set_tag(ExifTag::FNumber(vec![rational(28, 10)]));
+		metadata.set_tag(ExifTag::ISO(vec![400]));
+		metadata.set_tag(ExifTag::ExifVersion(b"0232".to_vec()));
+		metadata.set_tag(ExifTag::ExposureCompensation(vec![iR64 { nominator: -1, denominator: 3 }]));
+		metadata.set_tag(ExifTag::ComponentsConfiguration(vec![1, 2, 3, 0]));
+		metadata.set_tag(ExifTag::GPSVersionID(vec![2, 3, 0, 0]));
+		metadata.set_tag(ExifTag::GPSLatitudeRef("N".to_string()));
+		metadata.set_tag(ExifTag::GPSLatitude(vec![rational(48, 1), rational(51, 1), rational(3024, 100)]));
+		metadata.set_tag(ExifTag::GPSLongitude(vec![rational(2, 1), rational(17, 1), rational(4020, 100)]));
+		metadata.set_tag(ExifTag::GPSTimeStamp(vec![rational(12, 1), rational(30, 1), rational(155, 10)]));
+
+		// Decode the encoded data so that offset tags like `ExifOffset` exist
+		let metadata = Metadata::new_from_vec(&metadata.encode().unwrap(), FileExtension::TIFF).unwrap();
+		let json     = metadata.to_exiftool_json();
+
+		let lines = json.lines().map(|line| line.trim_end_matches(',')).collect::<Vec<&str>>();
+		for expected in EXPECTED_JSON.lines().map(|line| line.trim_end_matches(','))
+		{
+			assert!(lines.contains(&expected), "Missing {} in:\n{}", expected, json);
+		}
+		assert!(!json.contains("ExifOffset"));
+		assert!(!json.contains("GPSInfo"));
+	}
+
+	#[test]
+	fn
+	time_stamp_padding()
+	{
+		assert_eq!(super::time_stamp(&[rational(9, 1), rational(5, 1), rational(3, 1)]), Some("09:05:03".to_string()));
+		assert_eq!(super::time_stamp(&[rational(0, 1), rational(90, 1), rational(0, 1)]), Some("01:30:00".to_string()));
+		assert_eq!(super::time_stamp(&[rational(1, 0), rational(0, 1), rational(0, 1)]), None);
+	}
+
+	#[test]
+	fn
+	empty_metadata()
+	{
+		assert_eq!(Metadata::new().to_exiftool_json(), "{}");
+	}
+}
@@ -0,0 +1,393 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use std::collections::HashMap;
+use std::io::Cursor;
+
+use crate::endian::Endian;
+use crate::exif_tag::ExifTag;
+use crate::filetype::FileExtension;
+use crate::ifd::ExifTagGroup;
+use crate::ifd::ImageFileDirectory;
+
+use super::options::ReadOptions;
+use super::warnings::ParseWarning;
+use super::Metadata;
+
+/// A hook that gets called with the group and generic IFD number of each
+/// IFD after it has been decoded, see `LazyMetadata::set_decode_hook`
+pub type DecodeHook = Box<dyn FnMut(ExifTagGroup, u32)>;
+
+/// A read-only alternative to `Metadata` for large files of which only a
+/// few tags are needed: Instead of decoding all IFDs (including SubIFDs and
+/// the data of strips) up front, the EXIF data is retained and each IFD is
+/// decoded when a tag of its group is requested for the first time. The
+/// decoded IFDs are cached for subsequent requests.
+/// To get to an IFD, the IFDs referencing it need to be decoded as well,
+/// e.g. IFD0 for the GPS IFD. Other IFDs are never touched.
+///
+/// # Examples
+/// ```no_run
+/// use little_exif::exif_tag::ExifTag;
+/// use little_exif::filetype::FileExtension;
+/// use little_exif::metadata::lazy::LazyMetadata;
+///
+/// let file_data    = std::fs::read("image.tif").unwrap();
+/// let mut metadata = LazyMetadata::new_from_vec(&file_data, FileExtension::TIFF).unwrap();
+///
+/// // Only decodes IFD0, but neither the ExifIFD nor the GPS IFD
+/// if let Some(ExifTag::Model(model)) = metadata.get_tag(&ExifTag::Model(String::new())).unwrap()
+/// {
+///     println!("Taken with {}", model);
+/// }
+/// ```
+pub struct
+LazyMetadata
+{
+	data:                Vec<u8>,
+	data_start_position: u64,
+	endian:              Endian,
+	options:             ReadOptions,
+
+	/// The offsets of the IFDs that are known (but not necessarily decoded)
+	/// so far, starting with IFD0
+	ifd_offsets:         HashMap<(ExifTagGroup, u32), u32>,
+
+	/// The decoded IFDs, including the ones that turned out to be invalid
+	/// or to not exist (`None`)
+	decoded_ifds:        HashMap<(ExifTagGroup, u32), Option<ImageFileDirectory>>,
+
+	remaining_ifd_count: usize,
+	warnings:            Vec<ParseWarning>,
+	decode_hook:         Option<DecodeHook>,
+}
+
+impl
+LazyMetadata
+{
+	/// Reads the EXIF data of the given image without decoding it yet. Only
+	/// the TIFF header of the data is checked.
+	pub fn
+	new_from_vec
+	(
+		file_buffer: &Vec<u8>,
+		file_type:   FileExtension
+	)
+	-> Result<LazyMetadata, std::io::Error>
+	{
+		return Self::new_from_vec_with_options(file_buffer, file_type, &ReadOptions::default());
+	}
+
+	/// Same as `new_from_vec`, but uses the given `ReadOptions` when decoding
+	/// the IFDs. `ReadOptions::retain_raw_data` has no effect.
+	pub fn
+	new_from_vec_with_options
+	(
+		file_buffer: &Vec<u8>,
+		file_type:   FileExtension,
+		options:     &ReadOptions
+	)
+	-> Result<LazyMetadata, std::io::Error>
+	{
+		let mut warnings = Vec::new();
+		let data = Metadata::read_raw_metadata(file_buffer, file_type, options, &mut warnings, "LazyMetadata::new_from_vec")??;
+
+		let (endian, data_start_position, ifd0_offset) = Metadata::decode_header(&mut Cursor::new(&data))?;
+
+		return Ok(LazyMetadata {
+			data,
+			data_start_position,
+			endian,
+			options:             options.clone(),
+			ifd_offsets:         HashMap::from([((ExifTagGroup::GENERIC, 0), ifd0_offset)]),
+			decoded_ifds:        HashMap::new(),
+			remaining_ifd_count: options.max_ifd_count,
+			warnings,
+			decode_hook:         None,
+		});
+	}
+
+	/// Sets a hook that gets called whenever an IFD has been decoded, e.g.
+	/// for checking which parts of a file actually get decoded
+	pub fn
+	set_decode_hook
+	(
+		&mut self,
+		hook: impl FnMut(ExifTagGroup, u32) + 'static
+	)
+	{
+		self.decode_hook = Some(Box::new(hook));
+	}
+
+	/// Gets the endianness of the EXIF data
+	pub fn
+	get_endian
+	(
+		&self
+	)
+	-> Endian
+	{
+		return self.endian.clone();
+	}
+
+	/// Gets the warnings of the container format and of the IFDs that have
+	/// been decoded so far, see `Metadata::warnings`
+	pub fn
+	warnings
+	(
+		&self
+	)
+	-> &[ParseWarning]
+	{
+		return &self.warnings;
+	}
+
+	/// Gets the IFD of the given group that is associated with the given
+	/// generic IFD number, decoding it (and the IFDs referencing it) if this
+	/// has not happened yet. Returns `None` if the IFD does not exist.
+	pub fn
+	get_ifd
+	(
+		&mut self,
+		group:          ExifTagGroup,
+		generic_ifd_nr: u32
+	)
+	-> Result<Option<&ImageFileDirectory>, std::io::Error>
+	{
+		self.decode_ifd(group, generic_ifd_nr)?;
+		return Ok(self.decoded_ifds.get(&(group, generic_ifd_nr)).and_then(|ifd| ifd.as_ref()));
+	}
+
+	/// Gets the tag with the given hex value from the IFD of the given group
+	/// that is associated with the given generic IFD number, see
+	/// `Metadata::get_tag_in_page`
+	pub fn
+	get_tag_in_page
+	(
+		&mut self,
+		generic_ifd_nr: u32,
+		hex:            u16,
+		group:          ExifTagGroup
+	)
+	-> Result<Option<&ExifTag>, std::io::Error>
+	{
+		return Ok(self.get_ifd(group, generic_ifd_nr)?
+			.and_then(|ifd| ifd.get_tags().iter().find(|tag| tag.as_u16() == hex))
+		);
+	}
+
+	/// Gets the tag from the IFDs belonging to IFD0, decoding only the IFD
+	/// of the tag's group (and the IFDs referencing it)
+	pub fn
+	get_tag
+	(
+		&mut self,
+		tag: &ExifTag
+	)
+	-> Result<Option<&ExifTag>, std::io::Error>
+	{
+		return self.get_tag_in_page(0, tag.as_u16(), tag.get_group());
+	}
+
+	/// Decodes the given IFD if this has not happened yet. First, the IFDs
+	/// referencing it get decoded to get its offset: The parent IFD for
+	/// SubIFDs (e.g. IFD0 for the ExifIFD), or the previous generic IFDs,
+	/// which are walked starting with the highest one known so far.
+	fn
+	decode_ifd
+	(
+		&mut self,
+		group:          ExifTagGroup,
+		generic_ifd_nr: u32
+	)
+	-> Result<(), std::io::Error>
+	{
+		if self.decoded_ifds.contains_key(&(group, generic_ifd_nr))
+		{
+			return Ok(());
+		}
+
+		// There can't be more generic IFDs than IFDs in total
+		if generic_ifd_nr as usize >= self.options.max_ifd_count
+		{
+			return Ok(());
+		}
+
+		if !self.ifd_offsets.contains_key(&(group, generic_ifd_nr))
+		{
+			if group == ExifTagGroup::GENERIC
+			{
+				self.walk_generic_ifds(generic_ifd_nr)?;
+			}
+			else if let Some((parent_group, _)) = ImageFileDirectory::new_with_tags(Vec::new(), group, generic_ifd_nr).get_offset_tag_for_parent_ifd()
+			{
+				self.decode_ifd(parent_group, generic_ifd_nr)?;
+			}
+		}
+
+		let offset = match self.ifd_offsets.get(&(group, generic_ifd_nr))
+		{
+			Some(offset) => *offset,
+			None         => {
+				self.decoded_ifds.insert((group, generic_ifd_nr), None);
+				return Ok(());
+			},
+		};
+
+		let mut data_cursor = Cursor::new(&self.data);
+		data_cursor.set_position(self.data_start_position + offset as u64);
+
+		let mut ifds           = Vec::new();
+		let mut subifd_offsets = Vec::new();
+		let next_ifd_offset    = ImageFileDirectory::decode_ifd(
+			&mut data_cursor,
+			self.data_start_position,
+			&self.endian,
+			&group,
+			generic_ifd_nr,
+			&mut ifds,
+			&mut self.remaining_ifd_count,
+			&self.options,
+			&mut self.warnings,
+			Some(&mut subifd_offsets)
+		)?;
+
+		if let Some(next_ifd_offset) = next_ifd_offset.filter(|_| group == ExifTagGroup::GENERIC)
+		{
+			self.ifd_offsets.insert((ExifTagGroup::GENERIC, generic_ifd_nr + 1), next_ifd_offset);
+		}
+		for (subifd_group, subifd_offset) in subifd_offsets
+		{
+			self.ifd_offsets.insert((subifd_group, generic_ifd_nr), subifd_offset);
+		}
+
+		self.decoded_ifds.insert((group, generic_ifd_nr), ifds.pop());
+		if let Some(hook) = self.decode_hook.as_mut()
+		{
+			hook(group, generic_ifd_nr);
+		}
+
+		return Ok(());
+	}
+
+	/// Decodes the generic IFDs preceding the given one, starting with the
+	/// highest one whose offset is known, until the offset of the given IFD
+	/// is known or the chain of generic IFDs ends
+	fn
+	walk_generic_ifds
+	(
+		&mut self,
+		generic_ifd_nr: u32
+	)
+	-> Result<(), std::io::Error>
+	{
+		let mut current_ifd_nr = self.ifd_offsets.keys()
+			.filter(|(group, _)| *group == ExifTagGroup::GENERIC)
+			.map(|(_, ifd_nr)| *ifd_nr)
+			.max()
+			.unwrap_or(0);
+
+		while current_ifd_nr < generic_ifd_nr
+		{
+			self.decode_ifd(ExifTagGroup::GENERIC, current_ifd_nr)?;
+			if !self.ifd_offsets.contains_key(&(ExifTagGroup::GENERIC, current_ifd_nr + 1))
+			{
+				break;
+			}
+			current_ifd_nr += 1;
+		}
+
+		return Ok(());
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use std::cell::RefCell;
+	use std::rc::Rc;
+
+	use crate::exif_tag::ExifTag;
+	use crate::filetype::FileExtension;
+	use crate::ifd::ExifTagGroup;
+	use crate::metadata::Metadata;
+	use crate::metadata::ReadOptions;
+	use crate::rational::uR64;
+
+	use super::LazyMetadata;
+
+	#[test]
+	fn
+	decode_ifds_on_first_access()
+	{
+		let mut metadata = Metadata::new();
+		metadata.set_tag(ExifTag::ImageDescription("Lazy".to_string()));
+		metadata.set_tag(ExifTag::ISO(vec![2706]));
+		metadata.set_gps_position(48.8584, 2.2945);
+		metadata.get_ifd_mut(ExifTagGroup::GENERIC, 1).set_tag(ExifTag::Software("Second page".to_string()));
+		metadata.set_tag(ExifTag::GPSAltitude(vec![uR64 { nominator: 35, denominator: 1 }]));
+		let encoded = metadata.encode().unwrap();
+
+		let decoded_ifds = Rc::new(RefCell::new(Vec::new()));
+		let hook_ifds    = decoded_ifds.clone();
+
+		let mut lazy_metadata = LazyMetadata::new_from_vec(&encoded, FileExtension::TIFF).unwrap();
+		lazy_metadata.set_decode_hook(move |group, generic_ifd_nr| hook_ifds.borrow_mut().push((group, generic_ifd_nr)));
+		assert!(decoded_ifds.borrow().is_empty());
+
+		// Accessing an IFD0 tag decodes neither the ExifIFD nor the GPS IFD
+		assert_eq!(
+			lazy_metadata.get_tag(&ExifTag::ImageDescription(String::new())).unwrap(),
+			Some(&ExifTag::ImageDescription("Lazy".to_string()))
+		);
+		assert_eq!(lazy_metadata.get_tag(&ExifTag::Make(String::new())).unwrap(), None);
+		assert_eq!(*decoded_ifds.borrow(), vec![(ExifTagGroup::GENERIC, 0)]);
+
+		assert_eq!(lazy_metadata.get_tag(&ExifTag::ISO(Vec::new())).unwrap(), Some(&ExifTag::ISO(vec![2706])));
+		assert_eq!(*decoded_ifds.borrow(), vec![(ExifTagGroup::GENERIC, 0), (ExifTagGroup::EXIF, 0)]);
+
+		// Cached IFDs are not decoded again
+		assert_eq!(
+			lazy_metadata.get_tag(&ExifTag::GPSAltitude(Vec::new())).unwrap(),
+			Some(&ExifTag::GPSAltitude(vec![uR64 { nominator: 35, denominator: 1 }]))
+		);
+		assert!(lazy_metadata.get_tag(&ExifTag::GPSLatitude(Vec::new())).unwrap().is_some());
+		assert_eq!(decoded_ifds.borrow().len(), 3);
+
+		assert_eq!(
+			lazy_metadata.get_tag_in_page(1, 0x0131, ExifTagGroup::GENERIC).unwrap(),
+			Some(&ExifTag::Software("Second page".to_string()))
+		);
+		assert_eq!(lazy_metadata.get_ifd(ExifTagGroup::GENERIC, 2).unwrap().map(|ifd| ifd.get_tags().len()), None);
+		assert_eq!(lazy_metadata.get_ifd(ExifTagGroup::INTEROP, 0).unwrap().map(|ifd| ifd.get_tags().len()), None);
+		assert_eq!(decoded_ifds.borrow().last(), Some(&(ExifTagGroup::GENERIC, 1)));
+	}
+
+	#[test]
+	fn
+	large_page_number()
+	{
+		let mut metadata = Metadata::new();
+		metadata.set_tag(ExifTag::ImageDescription("Single page".to_string()));
+		let encoded = metadata.encode().unwrap();
+
+		let decoded_ifds = Rc::new(RefCell::new(Vec::new()));
+		let hook_ifds    = decoded_ifds.clone();
+
+		let mut options = ReadOptions::new();
+		options.max_ifd_count = usize::MAX;
+
+		let mut lazy_metadata = LazyMetadata::new_from_vec_with_options(&encoded, FileExtension::TIFF, &options).unwrap();
+		lazy_metadata.set_decode_hook(move |group, generic_ifd_nr| hook_ifds.borrow_mut().push((group, generic_ifd_nr)));
+
+		// Beyond the end of the chain of generic IFDs, which stops the walk
+		assert_eq!(lazy_metadata.get_tag_in_page(1_000_000, 0x010e, ExifTagGroup::GENERIC).unwrap(), None);
+		assert_eq!(lazy_metadata.get_tag_in_page(1_000_000, 0x0001, ExifTagGroup::INTEROP).unwrap(), None);
+		assert_eq!(*decoded_ifds.borrow(), vec![(ExifTagGroup::GENERIC, 0)]);
+
+		// Beyond max_ifd_count, so the chain doesn't get walked at all
+		let mut lazy_metadata = LazyMetadata::new_from_vec(&encoded, FileExtension::TIFF).unwrap();
+		assert_eq!(lazy_metadata.get_tag_in_page(u32::MAX, 0x010e, ExifTagGroup::GENERIC).unwrap(), None);
+		assert!(lazy_metadata.decoded_ifds.is_empty());
+	}
+}
@@ -0,0 +1,238 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::exif_tag::ExifTag;
+use crate::general_file_io::io_error;
+use crate::rational::uR64;
+
+use super::Metadata;
+
+/// Typed representation of the `LensSpecification` tag (0xa432), which is
+/// called `LensInfo` in this library (following the naming of exiftool).
+/// According to the EXIF standard, an unknown f-number is stored as 0/0.
+/// See https://exiftool.org/TagNames/EXIF.html (0xa432)
+#[derive(Clone, Debug, PartialEq)]
+pub struct
+LensSpecification
+{
+	pub min_focal_length: uR64,
+	pub max_focal_length: uR64,
+	pub min_f_number:     uR64,
+	pub max_f_number:     uR64,
+}
+
+impl
+LensSpecification
+{
+	/// Creates a new `LensSpecification` from the raw values of the tag.
+	/// Fails if there are not exactly 4 components.
+	pub fn
+	from_vec
+	(
+		values: &Vec<uR64>
+	)
+	-> Result<LensSpecification, std::io::Error>
+	{
+		if values.len() != 4
+		{
+			return io_error!(InvalidData, format!("LensSpecification requires 4 components, got {}", values.len()));
+		}
+
+		return Ok(LensSpecification {
+			min_focal_length: values[0].clone(),
+			max_focal_length: values[1].clone(),
+			min_f_number:     values[2].clone(),
+			max_f_number:     values[3].clone(),
+		});
+	}
+
+	/// Gets the raw values as stored in the tag
+	pub fn
+	to_vec
+	(
+		&self
+	)
+	-> Vec<uR64>
+	{
+		vec![
+			self.min_focal_length.clone(),
+			self.max_focal_length.clone(),
+			self.min_f_number.clone(),
+			self.max_f_number.clone(),
+		]
+	}
+}
+
+impl
+Metadata
+{
+	/// Gets the manufacturer of the lens stored in the `LensMake` tag
+	pub fn
+	get_lens_make
+	(
+		&self
+	)
+	-> Option<String>
+	{
+		if let Some(ExifTag::LensMake(value)) = self.get_tag(&ExifTag::LensMake(String::new())).next()
+		{
+			return Some(value.clone());
+		}
+		return None;
+	}
+
+	/// Sets the `LensMake` tag
+	pub fn
+	set_lens_make
+	(
+		&mut self,
+		lens_make: &str
+	)
+	{
+		self.set_tag(ExifTag::LensMake(lens_make.to_string()));
+	}
+
+	/// Gets the model name of the lens stored in the `LensModel` tag
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some(lens_model) = metadata.get_lens_model()
+	/// {
+	///     println!("Shot with {}", lens_model);
+	/// }
+	/// # }
+	/// ```
+	pub fn
+	get_lens_model
+	(
+		&self
+	)
+	-> Option<String>
+	{
+		if let Some(ExifTag::LensModel(value)) = self.get_tag(&ExifTag::LensModel(String::new())).next()
+		{
+			return Some(value.clone());
+		}
+		return None;
+	}
+
+	/// Sets the `LensModel` tag
+	pub fn
+	set_lens_model
+	(
+		&mut self,
+		lens_model: &str
+	)
+	{
+		self.set_tag(ExifTag::LensModel(lens_model.to_string()));
+	}
+
+	/// Gets the serial number of the lens stored in the `LensSerialNumber` tag
+	pub fn
+	get_lens_serial_number
+	(
+		&self
+	)
+	-> Option<String>
+	{
+		if let Some(ExifTag::LensSerialNumber(value)) = self.get_tag(&ExifTag::LensSerialNumber(String::new())).next()
+		{
+			return Some(value.clone());
+		}
+		return None;
+	}
+
+	/// Sets the `LensSerialNumber` tag
+	pub fn
+	set_lens_serial_number
+	(
+		&mut self,
+		lens_serial_number: &str
+	)
+	{
+		self.set_tag(ExifTag::LensSerialNumber(lens_serial_number.to_string()));
+	}
+
+	/// Gets the decoded `LensInfo` (aka `LensSpecification`) tag, if present
+	/// and consisting of exactly 4 components.
+	pub fn
+	get_lens_specification
+	(
+		&self
+	)
+	-> Option<LensSpecification>
+	{
+		if let Some(ExifTag::LensInfo(value)) = self.get_tag(&ExifTag::LensInfo(Vec::new())).next()
+		{
+			return LensSpecification::from_vec(value).ok();
+		}
+		return None;
+	}
+
+	/// Sets the `LensInfo` (aka `LensSpecification`) tag
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::metadata::lens::LensSpecification;
+	/// use little_exif::rational::uR64;
+	///
+	/// let mut metadata = Metadata::new();
+	/// metadata.set_lens_specification(LensSpecification {
+	///     min_focal_length: uR64 { nominator: 24, denominator: 1  },
+	///     max_focal_length: uR64 { nominator: 70, denominator: 1  },
+	///     min_f_number:     uR64 { nominator: 28, denominator: 10 },
+	///     max_f_number:     uR64 { nominator: 28, denominator: 10 },
+	/// });
+	/// ```
+	pub fn
+	set_lens_specification
+	(
+		&mut self,
+		lens_specification: LensSpecification
+	)
+	{
+		self.set_tag(ExifTag::LensInfo(lens_specification.to_vec()));
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use crate::exif_tag::ExifTag;
+	use crate::metadata::Metadata;
+	use crate::rational::uR64;
+
+	use super::LensSpecification;
+
+	#[test]
+	fn
+	lens_specification_requires_four_components()
+	{
+		let values = vec![uR64 { nominator: 50, denominator: 1 }; 3];
+		assert!(LensSpecification::from_vec(&values).is_err());
+
+		let mut metadata = Metadata::new();
+		metadata.set_tag(ExifTag::LensInfo(values));
+		assert_eq!(metadata.get_lens_specification(), None);
+	}
+
+	#[test]
+	fn
+	set_and_get_lens_model()
+	{
+		let mut metadata = Metadata::new();
+		assert_eq!(metadata.get_lens_model(), None);
+
+		metadata.set_lens_model("EF24-70mm f/2.8L II USM");
+		metadata.set_lens_make("Canon");
+		assert_eq!(metadata.get_lens_model(), Some("EF24-70mm f/2.8L II USM".to_string()));
+		assert_eq!(metadata.get_lens_make(),  Some("Canon".to_string()));
+		assert_eq!(metadata.get_lens_serial_number(), None);
+	}
+}
@@ -0,0 +1,711 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::endian::Endian;
+use crate::exif_tag::ExifTag;
+use crate::ifd::ExifTagGroup;
+
+use super::raw::RawEntryIterator;
+use super::Metadata;
+
+/// The tags of Canon maker notes that are decoded by `makernote_fields`.
+/// See https://exiftool.org/TagNames/Canon.html
+const CANON_CAMERA_SETTINGS: u16 = 0x0001;
+const CANON_FOCAL_LENGTH:    u16 = 0x0002;
+const CANON_FILE_NUMBER:     u16 = 0x0008;
+const CANON_LENS_MODEL:      u16 = 0x0095;
+
+/// The positions of the lens related values within the `CameraSettings`
+const CANON_LENS_TYPE_INDEX:        usize = 22;
+const CANON_MAX_FOCAL_LENGTH_INDEX: usize = 23;
+const CANON_MIN_FOCAL_LENGTH_INDEX: usize = 24;
+const CANON_FOCAL_UNITS_INDEX:      usize = 25;
+
+/// The position of the focal length within the `FocalLength` tag
+const CANON_FOCAL_LENGTH_INDEX:     usize = 1;
+
+/// The camera vendors whose `MakerNote` (0x927c) layout can be detected.
+/// See https://exiftool.org/makernote_types.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+MakerNoteVendor
+{
+	Canon,
+	Fujifilm,
+	Nikon,
+	Olympus,
+	Panasonic,
+	Pentax,
+	Sony,
+}
+
+impl
+MakerNoteVendor
+{
+	/// Gets the name of the vendor, e.g. for labeling images in a catalog
+	pub fn
+	as_str
+	(
+		&self
+	)
+	-> &'static str
+	{
+		match self
+		{
+			MakerNoteVendor::Canon     => "Canon",
+			MakerNoteVendor::Fujifilm  => "Fujifilm",
+			MakerNoteVendor::Nikon     => "Nikon",
+			MakerNoteVendor::Olympus   => "Olympus",
+			MakerNoteVendor::Panasonic => "Panasonic",
+			MakerNoteVendor::Pentax    => "Pentax",
+			MakerNoteVendor::Sony      => "Sony",
+		}
+	}
+}
+
+/// What the offsets within the IFD of a `MakerNote` are relative to
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+MakerNoteOffsetBase
+{
+	/// The TIFF header of the EXIF data, like for regular IFDs
+	TiffHeader,
+
+	/// The start of the `MakerNote` data itself
+	MakerNoteStart,
+
+	/// A TIFF header embedded in the `MakerNote` data (e.g. in newer Nikon
+	/// maker notes, 10 bytes after their start)
+	EmbeddedTiffHeader,
+}
+
+/// Information about the layout of a `MakerNote` that is required for
+/// decoding it. The maker note itself is preserved as an `UNDEF` tag, only
+/// some fields of Canon maker notes get decoded (see `makernote_fields`).
+#[derive(Clone, Debug, PartialEq)]
+pub struct
+MakerNote
+{
+	pub vendor:      MakerNoteVendor,
+	pub offset_base: MakerNoteOffsetBase,
+
+	/// The position relative to the TIFF header of the EXIF data that the
+	/// offsets in the maker note IFD refer to
+	pub base_offset: u32,
+
+	/// The position of the maker note IFD within the maker note data
+	pub ifd_offset:  u32,
+
+	/// The endianness of the maker note, if given by the maker note itself.
+	/// Otherwise, the endianness of the EXIF data is used.
+	pub endian:      Option<Endian>,
+}
+
+/// Reads the endianness from the start of a (TIFF or maker note) header
+fn
+endian_from_bytes
+(
+	bytes: &[u8]
+)
+-> Option<Endian>
+{
+	match bytes.get(0..2)?
+	{
+		[0x49, 0x49] => Some(Endian::Little),
+		[0x4d, 0x4d] => Some(Endian::Big),
+		_            => None,
+	}
+}
+
+impl
+MakerNote
+{
+	/// Detects the vendor and offset base of the given `MakerNote` data.
+	/// The `tiff_base_offset` is the position of the data relative to the
+	/// TIFF header of the EXIF data (i.e. the value offset of the tag) and
+	/// `make` the content of the `Make` tag, which is required for vendors
+	/// like Canon whose maker notes don't start with a signature.
+	/// Returns `None` if the layout is unknown.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::makernote::MakerNote;
+	/// use little_exif::metadata::makernote::MakerNoteOffsetBase;
+	///
+	/// let data = b"Nikon\0\x02\x10\0\0MM\0\x2a\0\0\0\x08".to_vec();
+	/// let maker_note = MakerNote::detect(&data, 1000, Some("NIKON CORPORATION")).unwrap();
+	/// assert_eq!(maker_note.offset_base, MakerNoteOffsetBase::EmbeddedTiffHeader);
+	/// assert_eq!(maker_note.base_offset, 1010);
+	/// ```
+	pub fn
+	detect
+	(
+		data:             &[u8],
+		tiff_base_offset: u32,
+		make:             Option<&str>
+	)
+	-> Option<MakerNote>
+	{
+		let new_maker_note = |vendor, offset_base, ifd_offset, endian| {
+			let base_offset = match offset_base
+			{
+				MakerNoteOffsetBase::TiffHeader         => 0,
+				MakerNoteOffsetBase::MakerNoteStart     => tiff_base_offset,
+				MakerNoteOffsetBase::EmbeddedTiffHeader => tiff_base_offset.checked_add(10)?,
+			};
+			Some(MakerNote { vendor, offset_base, base_offset, ifd_offset, endian })
+		};
+
+		// Newer Nikon maker notes: "Nikon\0", version, embedded TIFF header
+		if data.starts_with(b"Nikon\0\x02")
+		{
+			let endian = endian_from_bytes(data.get(10..)?)?;
+			let ifd_offset = match endian
+			{
+				Endian::Little => u32::from_le_bytes(data.get(14..18)?.try_into().ok()?),
+				Endian::Big    => u32::from_be_bytes(data.get(14..18)?.try_into().ok()?),
+			};
+			return new_maker_note(MakerNoteVendor::Nikon, MakerNoteOffsetBase::EmbeddedTiffHeader, ifd_offset.checked_add(10)?, Some(endian));
+		}
+
+		if data.starts_with(b"Nikon\0\x01")
+		{
+			return new_maker_note(MakerNoteVendor::Nikon, MakerNoteOffsetBase::TiffHeader, 8, None);
+		}
+
+		if data.starts_with(b"OLYMPUS\0")
+		{
+			return new_maker_note(MakerNoteVendor::Olympus, MakerNoteOffsetBase::MakerNoteStart, 12, endian_from_bytes(&data[8..]));
+		}
+
+		if data.starts_with(b"OLYMP\0")
+		{
+			return new_maker_note(MakerNoteVendor::Olympus, MakerNoteOffsetBase::TiffHeader, 8, None);
+		}
+
+		// Fujifilm maker notes are always little endian and store the offset
+		// to their IFD after the signature
+		if data.starts_with(b"FUJIFILM")
+		{
+			let ifd_offset = u32::from_le_bytes(data.get(8..12)?.try_into().ok()?);
+			return new_maker_note(MakerNoteVendor::Fujifilm, MakerNoteOffsetBase::MakerNoteStart, ifd_offset, Some(Endian::Little));
+		}
+
+		if data.starts_with(b"Panasonic\0")
+		{
+			return new_maker_note(MakerNoteVendor::Panasonic, MakerNoteOffsetBase::TiffHeader, 12, None);
+		}
+
+		if data.starts_with(b"AOC\0")
+		{
+			return new_maker_note(MakerNoteVendor::Pentax, MakerNoteOffsetBase::MakerNoteStart, 6, endian_from_bytes(&data[4..]));
+		}
+
+		if data.starts_with(b"PENTAX \0")
+		{
+			return new_maker_note(MakerNoteVendor::Pentax, MakerNoteOffsetBase::MakerNoteStart, 10, endian_from_bytes(&data[8..]));
+		}
+
+		if data.starts_with(b"SONY DSC \0") || data.starts_with(b"SONY CAM \0")
+		{
+			return new_maker_note(MakerNoteVendor::Sony, MakerNoteOffsetBase::TiffHeader, 12, None);
+		}
+
+		// Canon maker notes start directly with the IFD
+		if make.map_or(false, |make| make.trim().to_lowercase().starts_with("canon"))
+		{
+			return new_maker_note(MakerNoteVendor::Canon, MakerNoteOffsetBase::TiffHeader, 0, None);
+		}
+
+		// Same for the maker notes of most newer Sony cameras
+		if make.map_or(false, |make| make.trim().to_lowercase().starts_with("sony"))
+		{
+			return new_maker_note(MakerNoteVendor::Sony, MakerNoteOffsetBase::TiffHeader, 0, None);
+		}
+
+		return None;
+	}
+}
+
+/// Reads an integer of the given size (2 or 4 bytes) at the given position
+fn
+read_uint
+(
+	data:     &[u8],
+	position: usize,
+	size:     usize,
+	endian:   &Endian
+)
+-> Option<u32>
+{
+	let bytes = data.get(position..position + size)?;
+	return Some(match (size, endian)
+	{
+		(2, Endian::Little) => u16::from_le_bytes([bytes[0], bytes[1]]) as u32,
+		(2, Endian::Big   ) => u16::from_be_bytes([bytes[0], bytes[1]]) as u32,
+		(_, Endian::Little) => u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+		(_, Endian::Big   ) => u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+	});
+}
+
+/// Gets the values of the entries of the IFD a Canon maker note consists of,
+/// as hex value and raw bytes. The offsets of values that don't fit
+/// into an entry are relative to the TIFF header of the EXIF data, so the
+/// position of the maker note relative to it (`tiff_base_offset`) is needed
+/// for locating them. If that is unknown, the values are assumed to start
+/// directly after the IFD, as written by Canon cameras.
+fn
+canon_entries
+<'a>
+(
+	data:             &'a [u8],
+	endian:           &Endian,
+	tiff_base_offset: Option<u32>
+)
+-> Vec<(u16, &'a [u8])>
+{
+	let entry_count = match read_uint(data, 0, 2, endian)
+	{
+		Some(entry_count) => entry_count as usize,
+		None              => return Vec::new(),
+	};
+
+	let mut entries = Vec::new();
+	for index in 0..entry_count
+	{
+		let position = 2 + index * 12;
+		if let (Some(hex), Some(format), Some(count)) = (
+			read_uint(data, position,     2, endian),
+			read_uint(data, position + 2, 2, endian),
+			read_uint(data, position + 4, 4, endian),
+		)
+		{
+			let component_size = match format
+			{
+				1 | 2 | 6 | 7 => 1,
+				3 | 8         => 2,
+				4 | 9 | 11    => 4,
+				5 | 10 | 12   => 8,
+				_             => continue,
+			};
+			entries.push((hex as u16, position, component_size * count as usize));
+		}
+	}
+
+	let ifd_length = (2 + entry_count * 12 + 4) as u32;
+	let base       = tiff_base_offset.or_else(|| entries.iter()
+		.filter(|(_, _, length)| *length > 4)
+		.filter_map(|(_, position, _)| read_uint(data, position + 8, 4, endian))
+		.min()?
+		.checked_sub(ifd_length)
+	);
+
+	return entries.into_iter().filter_map(|(hex, position, length)| {
+		let value_position = if length <= 4
+		{
+			position + 8
+		}
+		else
+		{
+			read_uint(data, position + 8, 4, endian)?.checked_sub(base?)? as usize
+		};
+		return Some((hex, data.get(value_position..value_position + length)?));
+	}).collect();
+}
+
+/// Decodes the lens and exposure related fields of a Canon maker note,
+/// see `Metadata::makernote_fields`
+fn
+decode_canon_maker_note
+(
+	data:             &[u8],
+	endian:           &Endian,
+	tiff_base_offset: Option<u32>
+)
+-> Vec<(String, String)>
+{
+	let entries   = canon_entries(data, endian, tiff_base_offset);
+	let get_entry = |hex: u16| entries.iter().find(|(entry_hex, _)| *entry_hex == hex).map(|(_, value)| *value);
+	let get_short = |value: &[u8], index: usize| read_uint(value, index * 2, 2, endian);
+
+	let mut fields       = Vec::new();
+	let mut focal_units  = 1;
+	let format_focal     = |focal_length: u32, focal_units: u32| format!("{} mm", focal_length as f64 / focal_units as f64);
+
+	if let Some(camera_settings) = get_entry(CANON_CAMERA_SETTINGS)
+	{
+		focal_units = get_short(camera_settings, CANON_FOCAL_UNITS_INDEX).filter(|units| *units > 0).unwrap_or(1);
+
+		if let Some(lens_type) = get_short(camera_settings, CANON_LENS_TYPE_INDEX)
+		{
+			fields.push(("LensType".to_string(), lens_type.to_string()));
+		}
+		if let Some(min_focal_length) = get_short(camera_settings, CANON_MIN_FOCAL_LENGTH_INDEX)
+		{
+			fields.push(("MinFocalLength".to_string(), format_focal(min_focal_length, focal_units)));
+		}
+		if let Some(max_focal_length) = get_short(camera_settings, CANON_MAX_FOCAL_LENGTH_INDEX)
+		{
+			fields.push(("MaxFocalLength".to_string(), format_focal(max_focal_length, focal_units)));
+		}
+	}
+
+	if let Some(focal_length) = get_entry(CANON_FOCAL_LENGTH).and_then(|value| get_short(value, CANON_FOCAL_LENGTH_INDEX))
+	{
+		fields.push(("FocalLength".to_string(), format_focal(focal_length, focal_units)));
+	}
+
+	if let Some(lens_model) = get_entry(CANON_LENS_MODEL)
+	{
+		let lens_model = String::from_utf8_lossy(lens_model).trim_end_matches('\0').trim().to_string();
+		if !lens_model.is_empty()
+		{
+			fields.push(("LensModel".to_string(), lens_model));
+		}
+	}
+
+	if let Some(file_number) = get_entry(CANON_FILE_NUMBER).and_then(|value| read_uint(value, 0, 4, endian))
+	{
+		fields.push(("FileNumber".to_string(), file_number.to_string()));
+	}
+
+	return fields;
+}
+
+impl
+Metadata
+{
+	/// Detects the layout of the `MakerNote` tag (see `MakerNote::detect`),
+	/// using the `Make` tag of this struct. As the position of the maker
+	/// note in the original file is not preserved when decoding, it has to
+	/// be provided as `tiff_base_offset`.
+	pub fn
+	detect_maker_note
+	(
+		&self,
+		tiff_base_offset: u32
+	)
+	-> Option<MakerNote>
+	{
+		let make = match self.get_tag(&ExifTag::Make(String::new())).next()
+		{
+			Some(ExifTag::Make(make)) => Some(make.as_str()),
+			_                         => None,
+		};
+
+		if let Some(ExifTag::MakerNote(data)) = self.get_tag(&ExifTag::MakerNote(Vec::new())).next()
+		{
+			return MakerNote::detect(data, tiff_base_offset, make);
+		}
+		return None;
+	}
+
+	/// Gets the name of the camera vendor whose layout the `MakerNote` tag
+	/// follows, detected via its signature and the `Make` tag (see 
+	/// `MakerNote::detect`). The maker note itself is not decoded.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let (Some(vendor), Some(maker_note)) = (metadata.makernote_vendor(), metadata.makernote_raw())
+	/// {
+	///     println!("{} maker note with {} bytes", vendor, maker_note.len());
+	/// }
+	/// # }
+	/// ```
+	pub fn
+	makernote_vendor
+	(
+		&self
+	)
+	-> Option<String>
+	{
+		// The vendor does not depend on the position of the maker note
+		return self.detect_maker_note(0).map(|maker_note| maker_note.vendor.as_str().to_string());
+	}
+
+	/// Decodes the lens and exposure related fields of the `MakerNote` tag
+	/// as pairs of field name and formatted value, e.g. for cataloging.
+	/// Currently, only Canon maker notes are supported, for which these are
+	/// `LensType` (the numeric ID of the lens), `MinFocalLength`,
+	/// `MaxFocalLength`, `FocalLength`, `LensModel` and `FileNumber` (the
+	/// image counter of the camera), as far as present. For other vendors
+	/// or if there is no maker note, the result is empty.
+	/// The values of some fields are stored outside of the maker note's IFD
+	/// at offsets relative to the EXIF data. These are located exactly if
+	/// the struct was read using `ReadOptions::retain_raw_data`, otherwise
+	/// they are assumed to follow the IFD directly, as in Canon images.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// for (name, value) in metadata.makernote_fields()
+	/// {
+	///     println!("{}: {}", name, value);
+	/// }
+	/// # }
+	/// ```
+	pub fn
+	makernote_fields
+	(
+		&self
+	)
+	-> Vec<(String, String)>
+	{
+		let (maker_note, data) = match (self.detect_maker_note(0), self.makernote_raw())
+		{
+			(Some(maker_note), Some(data)) if maker_note.vendor == MakerNoteVendor::Canon => (maker_note, data),
+			_                                                                            => return Vec::new(),
+		};
+
+		// Get the position of the maker note from the retained raw data
+		let tiff_base_offset = self.raw_data.as_deref()
+			.and_then(|raw_data| RawEntryIterator::new(raw_data, self.max_ifd_count).ok())
+			.and_then(|mut entries| {
+				let endian = entries.endian().clone();
+				entries.find(|(_, entry)| entry.tag == 0x927c && entry.group == ExifTagGroup::EXIF)
+					.map(|(_, entry)| entry.offset(&endian))
+			});
+
+		let endian = maker_note.endian.unwrap_or_else(|| self.endian.clone());
+		return decode_canon_maker_note(data, &endian, tiff_base_offset);
+	}
+
+	/// Gets the raw bytes of the `MakerNote` tag, as preserved when decoding
+	pub fn
+	makernote_raw
+	(
+		&self
+	)
+	-> Option<&[u8]>
+	{
+		if let Some(ExifTag::MakerNote(data)) = self.get_tag(&ExifTag::MakerNote(Vec::new())).next()
+		{
+			return Some(data);
+		}
+		return None;
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use crate::endian::Endian;
+	use crate::exif_tag::ExifTag;
+	use crate::filetype::FileExtension;
+	use crate::ifd::ExifTagGroup;
+	use crate::metadata::options::ReadOptions;
+	use crate::metadata::options::DEFAULT_MAX_IFD_COUNT;
+	use crate::metadata::raw::RawEntryIterator;
+	use crate::metadata::Metadata;
+
+	use super::decode_canon_maker_note;
+	use super::MakerNote;
+	use super::MakerNoteOffsetBase;
+	use super::MakerNoteVendor;
+
+	#[test]
+	fn
+	detect_nikon_maker_note()
+	{
+		// Signature, version 2.10, embedded big endian TIFF header with the
+		// IFD directly following it
+		let mut data = b"Nikon\0\x02\x10\0\0".to_vec();
+		data.extend([0x4d, 0x4d, 0x00, 0x2a, 0x00, 0x00, 0x00, 0x08]);
+		data.extend([0x00, 0x00]);
+
+		assert_eq!(MakerNote::detect(&data, 800, Some("NIKON CORPORATION")), Some(MakerNote {
+			vendor:      MakerNoteVendor::Nikon,
+			offset_base: MakerNoteOffsetBase::EmbeddedTiffHeader,
+			base_offset: 810,
+			ifd_offset:  18,
+			endian:      Some(Endian::Big),
+		}));
+
+		// Truncated header
+		assert_eq!(MakerNote::detect(&data[..12], 800, None), None);
+
+		// Offsets that would overflow
+		assert_eq!(MakerNote::detect(&data, u32::MAX - 5, None), None);
+		data[14..18].copy_from_slice(&u32::MAX.to_be_bytes());
+		assert_eq!(MakerNote::detect(&data, 800, None), None);
+	}
+
+	#[test]
+	fn
+	detect_canon_maker_note()
+	{
+		// Canon maker notes have no signature and start with the IFD
+		let data = [0x01, 0x00, 0x01, 0x00, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+
+		assert_eq!(MakerNote::detect(&data, 800, None), None);
+		assert_eq!(MakerNote::detect(&data, 800, Some("Canon")), Some(MakerNote {
+			vendor:      MakerNoteVendor::Canon,
+			offset_base: MakerNoteOffsetBase::TiffHeader,
+			base_offset: 0,
+			ifd_offset:  0,
+			endian:      None,
+		}));
+	}
+
+	#[test]
+	fn
+	label_canon_and_sony_maker_notes()
+	{
+		let mut metadata = Metadata::new();
+		assert_eq!(metadata.makernote_vendor(), None);
+		assert_eq!(metadata.makernote_raw(),    None);
+
+		// Canon: No signature, so the vendor is only known via the make
+		let canon_data = vec![0x01, 0x00, 0x01, 0x00, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+		metadata.set_tag(ExifTag::MakerNote(canon_data.clone()));
+		assert_eq!(metadata.makernote_vendor(), None);
+
+		metadata.set_tag(ExifTag::Make("Canon".to_string()));
+		assert_eq!(metadata.makernote_vendor(), Some("Canon".to_string()));
+		assert_eq!(metadata.makernote_raw(),    Some(canon_data.as_slice()));
+
+		// Sony: The signature takes precedence over the make
+		let mut sony_data = b"SONY DSC \0\0\0".to_vec();
+		sony_data.extend([0x00, 0x00]);
+		metadata.set_tag(ExifTag::MakerNote(sony_data.clone()));
+		metadata.set_tag(ExifTag::Make("Unknown".to_string()));
+		assert_eq!(metadata.makernote_vendor(), Some("Sony".to_string()));
+		assert_eq!(metadata.makernote_raw(),    Some(sony_data.as_slice()));
+
+		// Newer Sony maker notes without signature
+		metadata.set_tag(ExifTag::MakerNote(canon_data));
+		metadata.set_tag(ExifTag::Make("SONY".to_string()));
+		assert_eq!(metadata.makernote_vendor(), Some("Sony".to_string()));
+	}
+
+	/// Builds a little endian Canon maker note for an EF 24-105mm lens at
+	/// 50mm, whose values are stored `gap` bytes after its IFD. Offsets are
+	/// relative to the TIFF header, assuming the maker note is located at
+	/// `tiff_base_offset`.
+	fn
+	canon_maker_note
+	(
+		tiff_base_offset: u32,
+		gap:              u32
+	)
+	-> Vec<u8>
+	{
+		let mut camera_settings = vec![0u16; 26];
+		camera_settings[22] = 237;
+		camera_settings[23] = 105;
+		camera_settings[24] = 24;
+		camera_settings[25] = 1;
+
+		let mut lens_model = b"EF24-105mm f/4L IS USM".to_vec();
+		lens_model.resize(32, 0);
+
+		let values_start = tiff_base_offset + 2 + 4 * 12 + 4 + gap;
+
+		let mut data = 4u16.to_le_bytes().to_vec();
+		for (hex, format, count, value) in [
+			(0x0001u16, 3u16, 26u32, values_start),
+			(0x0002,    3,    4,     values_start + 52),
+			(0x0008,    4,    1,     1001234),
+			(0x0095,    2,    32,    values_start + 60),
+		]
+		{
+			data.extend(hex.to_le_bytes());
+			data.extend(format.to_le_bytes());
+			data.extend(count.to_le_bytes());
+			data.extend(value.to_le_bytes());
+		}
+		data.extend([0x00; 4]);
+		data.extend(vec![0x00; gap as usize]);
+
+		data.extend(camera_settings.iter().flat_map(|value| value.to_le_bytes()));
+		data.extend([0u16, 50, 0, 0].iter().flat_map(|value| value.to_le_bytes()));
+		data.extend(lens_model);
+		return data;
+	}
+
+	fn
+	expected_canon_fields
+	()
+	-> Vec<(String, String)>
+	{
+		return [
+			("LensType",       "237"),
+			("MinFocalLength", "24 mm"),
+			("MaxFocalLength", "105 mm"),
+			("FocalLength",    "50 mm"),
+			("LensModel",      "EF24-105mm f/4L IS USM"),
+			("FileNumber",     "1001234"),
+		].iter().map(|(name, value)| (name.to_string(), value.to_string())).collect();
+	}
+
+	#[test]
+	fn
+	decode_canon_lens_fields()
+	{
+		let data = canon_maker_note(1000, 0);
+		assert_eq!(decode_canon_maker_note(&data, &Endian::Little, Some(1000)), expected_canon_fields());
+
+		// Without the position, the values are expected right after the IFD
+		assert_eq!(decode_canon_maker_note(&data, &Endian::Little, None), expected_canon_fields());
+
+		// Offsets pointing outside of the maker note are ignored
+		let fields = decode_canon_maker_note(&data, &Endian::Little, Some(0));
+		assert_eq!(fields, vec![("FileNumber".to_string(), "1001234".to_string())]);
+
+		assert_eq!(decode_canon_maker_note(&[], &Endian::Little, None), Vec::new());
+	}
+
+	#[test]
+	fn
+	makernote_fields_of_supported_vendors_only()
+	{
+		let mut metadata = Metadata::new();
+		assert!(metadata.makernote_fields().is_empty());
+
+		metadata.set_tag(ExifTag::MakerNote(canon_maker_note(1000, 0)));
+		metadata.set_tag(ExifTag::Make("Canon".to_string()));
+		assert_eq!(metadata.makernote_fields(), expected_canon_fields());
+
+		let mut nikon_data = b"Nikon\0\x01\0".to_vec();
+		nikon_data.extend([0x00, 0x00]);
+		metadata.set_tag(ExifTag::MakerNote(nikon_data));
+		metadata.set_tag(ExifTag::Make("NIKON CORPORATION".to_string()));
+		assert!(metadata.makernote_fields().is_empty());
+	}
+
+	#[test]
+	fn
+	makernote_fields_with_retained_raw_data()
+	{
+		// Determine where the maker note ends up when encoding and store the
+		// values with a gap, which can only be handled using the raw data
+		let mut metadata = Metadata::new();
+		metadata.set_tag(ExifTag::Make("Canon".to_string()));
+		metadata.set_tag(ExifTag::MakerNote(canon_maker_note(0, 16)));
+
+		let encoded          = metadata.encode().unwrap();
+		let mut entries      = RawEntryIterator::new(&encoded, DEFAULT_MAX_IFD_COUNT).unwrap();
+		let tiff_base_offset = entries.find(|(_, entry)| entry.tag == 0x927c && entry.group == ExifTagGroup::EXIF)
+			.map(|(_, entry)| entry.offset(&Endian::Little))
+			.unwrap();
+
+		metadata.set_tag(ExifTag::MakerNote(canon_maker_note(tiff_base_offset, 16)));
+		let encoded = metadata.encode().unwrap();
+
+		let options  = ReadOptions { retain_raw_data: true, ..ReadOptions::default() };
+		let metadata = Metadata::new_from_vec_with_options(&encoded, FileExtension::TIFF, &options).unwrap();
+		assert_eq!(metadata.makernote_fields(), expected_canon_fields());
+
+		let metadata = Metadata::new_from_vec(&encoded, FileExtension::TIFF).unwrap();
+		assert_ne!(metadata.makernote_fields(), expected_canon_fields());
+	}
+}
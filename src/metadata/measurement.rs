@@ -0,0 +1,365 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::endian::Endian;
+use crate::exif_tag::ExifTag;
+use crate::general_file_io::io_error;
+use crate::rational::iR64;
+use crate::rational::uR64;
+use crate::u8conversion::U8conversion;
+
+use super::Metadata;
+
+/// The number of bytes of a single (signed or unsigned) rational
+const RATIONAL_LENGTH: usize = 8;
+
+/// A table of measured values as stored in the `OECF` (0x8828) and
+/// `SpatialFrequencyResponse` (0xa20c) tags: The number of columns and rows,
+/// a name for each column and the values in row-major order.
+/// E.g. an OECF table might have the columns "Log exposure", "Red output
+/// level", "Green output level" and "Blue output level".
+#[derive(Clone, Debug, PartialEq)]
+pub struct
+MeasurementTable<T>
+{
+	pub columns:      u16,
+	pub rows:         u16,
+	pub column_names: Vec<String>,
+	pub values:       Vec<T>,
+}
+
+/// The opto-electronic conversion function of the `OECF` tag, whose values
+/// are signed rationals
+pub type OecfTable = MeasurementTable<iR64>;
+
+/// The spatial frequency table of the `SpatialFrequencyResponse` tag, whose
+/// values are unsigned rationals
+pub type SpatialFrequencyTable = MeasurementTable<uR64>;
+
+impl<T>
+MeasurementTable<T>
+{
+	/// Gets the value in the given row and column
+	pub fn
+	get
+	(
+		&self,
+		row:    u16,
+		column: u16
+	)
+	-> Option<&T>
+	{
+		if row >= self.rows || column >= self.columns
+		{
+			return None;
+		}
+		return self.values.get(row as usize * self.columns as usize + column as usize);
+	}
+
+	/// Checks that there is a name for each column and a value for each
+	/// cell, and that the names can be stored as NUL terminated strings
+	fn
+	validate
+	(
+		&self,
+		tag_name: &str
+	)
+	-> Result<(), std::io::Error>
+	{
+		if self.column_names.len() != self.columns as usize
+		{
+			return io_error!(
+				InvalidInput,
+				format!("{} with {} columns requires {} column names, got {}", tag_name, self.columns, self.columns, self.column_names.len())
+			);
+		}
+
+		let cell_count = self.columns as usize * self.rows as usize;
+		if self.values.len() != cell_count
+		{
+			return io_error!(
+				InvalidInput,
+				format!("{} of {}x{} cells requires {} values, got {}", tag_name, self.columns, self.rows, cell_count, self.values.len())
+			);
+		}
+
+		if self.column_names.iter().any(|name| name.contains('\0'))
+		{
+			return io_error!(InvalidInput, format!("{} column names must not contain NUL characters", tag_name));
+		}
+
+		return Ok(());
+	}
+}
+
+/// Decodes a table: Two `SHORT` values for the number of columns and rows,
+/// followed by a NUL terminated name for each column and the rationals
+fn
+decode_table
+<T: U8conversion<T>>
+(
+	data:   &[u8],
+	endian: &Endian
+)
+-> Option<MeasurementTable<T>>
+{
+	let read_u16 = |bytes: &[u8]| match endian
+	{
+		Endian::Little => u16::from_le_bytes([bytes[0], bytes[1]]),
+		Endian::Big    => u16::from_be_bytes([bytes[0], bytes[1]]),
+	};
+
+	if data.len() < 4
+	{
+		return None;
+	}
+
+	let columns = read_u16(&data[0..2]);
+	let rows    = read_u16(&data[2..4]);
+
+	let mut position     = 4;
+	let mut column_names = Vec::new();
+	for _ in 0..columns
+	{
+		let name_length = data.get(position..)?.iter().position(|byte| *byte == 0)?;
+		column_names.push(String::from_utf8_lossy(&data[position..position + name_length]).to_string());
+		position += name_length + 1;
+	}
+
+	let values_data = &data[position..];
+	if values_data.len() != columns as usize * rows as usize * RATIONAL_LENGTH
+	{
+		return None;
+	}
+
+	let values = values_data.chunks_exact(RATIONAL_LENGTH)
+		.map(|value| T::from_u8_vec(&value.to_vec(), endian))
+		.collect();
+
+	return Some(MeasurementTable { columns, rows, column_names, values });
+}
+
+/// Encodes a table, see `decode_table`
+fn
+encode_table
+<T: U8conversion<T>>
+(
+	table:  &MeasurementTable<T>,
+	endian: &Endian
+)
+-> Vec<u8>
+{
+	let mut data = Vec::new();
+	data.extend(table.columns.to_u8_vec(endian));
+	data.extend(table.rows.to_u8_vec(endian));
+	for name in &table.column_names
+	{
+		data.extend(name.as_bytes());
+		data.push(0x00);
+	}
+	for value in &table.values
+	{
+		data.extend(value.to_u8_vec(endian));
+	}
+	return data;
+}
+
+impl
+MeasurementTable<iR64>
+{
+	/// Decodes the value of the `OECF` tag. Returns `None` if the data is
+	/// incomplete or its length does not match the table dimensions.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::endian::Endian;
+	/// use little_exif::metadata::measurement::OecfTable;
+	///
+	/// let data  = std::fs::read("oecf.bin").unwrap();
+	/// let table = OecfTable::decode(&data, &Endian::Little).unwrap();
+	/// println!("{:?}", table.column_names);
+	/// ```
+	pub fn
+	decode
+	(
+		data:   &[u8],
+		endian: &Endian
+	)
+	-> Option<OecfTable>
+	{
+		return decode_table(data, endian);
+	}
+
+	/// Encodes the table as value for the `OECF` tag
+	pub fn
+	encode
+	(
+		&self,
+		endian: &Endian
+	)
+	-> Vec<u8>
+	{
+		return encode_table(self, endian);
+	}
+}
+
+impl
+MeasurementTable<uR64>
+{
+	/// Decodes the value of the `SpatialFrequencyResponse` tag. Returns
+	/// `None` if the data is incomplete or its length does not match the
+	/// table dimensions.
+	pub fn
+	decode
+	(
+		data:   &[u8],
+		endian: &Endian
+	)
+	-> Option<SpatialFrequencyTable>
+	{
+		return decode_table(data, endian);
+	}
+
+	/// Encodes the table as value for the `SpatialFrequencyResponse` tag
+	pub fn
+	encode
+	(
+		&self,
+		endian: &Endian
+	)
+	-> Vec<u8>
+	{
+		return encode_table(self, endian);
+	}
+}
+
+impl
+Metadata
+{
+	/// Gets the decoded `OECF` tag (0x8828), see `OecfTable::decode`
+	pub fn
+	get_oecf
+	(
+		&self
+	)
+	-> Option<OecfTable>
+	{
+		if let Some(ExifTag::OECF(value)) = self.get_tag(&ExifTag::OECF(Vec::new())).next()
+		{
+			return OecfTable::decode(value, &self.endian);
+		}
+		return None;
+	}
+
+	/// Sets the `OECF` tag. Fails if the number of column names or values
+	/// does not match the dimensions of the table.
+	pub fn
+	set_oecf
+	(
+		&mut self,
+		oecf: &OecfTable
+	)
+	-> Result<(), std::io::Error>
+	{
+		oecf.validate("OECF")?;
+		self.set_tag(ExifTag::OECF(oecf.encode(&self.endian)));
+		return Ok(());
+	}
+
+	/// Gets the decoded `SpatialFrequencyResponse` tag (0xa20c), see
+	/// `SpatialFrequencyTable::decode`
+	pub fn
+	get_spatial_frequency_response
+	(
+		&self
+	)
+	-> Option<SpatialFrequencyTable>
+	{
+		if let Some(ExifTag::SpatialFrequencyResponse(value)) = self.get_tag(&ExifTag::SpatialFrequencyResponse(Vec::new())).next()
+		{
+			return SpatialFrequencyTable::decode(value, &self.endian);
+		}
+		return None;
+	}
+
+	/// Sets the `SpatialFrequencyResponse` tag. Fails if the number of column
+	/// names or values does not match the dimensions of the table.
+	pub fn
+	set_spatial_frequency_response
+	(
+		&mut self,
+		spatial_frequency_response: &SpatialFrequencyTable
+	)
+	-> Result<(), std::io::Error>
+	{
+		spatial_frequency_response.validate("SpatialFrequencyResponse")?;
+		self.set_tag(ExifTag::SpatialFrequencyResponse(spatial_frequency_response.encode(&self.endian)));
+		return Ok(());
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use crate::endian::Endian;
+	use crate::filetype::FileExtension;
+	use crate::metadata::Metadata;
+	use crate::rational::iR64;
+	use crate::rational::uR64;
+
+	use super::OecfTable;
+	use super::SpatialFrequencyTable;
+
+	#[test]
+	fn
+	decode_oecf()
+	{
+		// 2 columns, 2 rows (little endian), the column names and 4 values
+		let mut data = vec![0x02, 0x00, 0x02, 0x00];
+		data.extend(b"Log exposure\0Output level\0");
+		for (nominator, denominator) in [(-1i32, 1i32), (0, 1), (0, 1), (255, 1)]
+		{
+			data.extend(nominator.to_le_bytes());
+			data.extend(denominator.to_le_bytes());
+		}
+
+		let table = OecfTable::decode(&data, &Endian::Little).unwrap();
+		assert_eq!(table.columns,      2);
+		assert_eq!(table.rows,         2);
+		assert_eq!(table.column_names, vec!["Log exposure".to_string(), "Output level".to_string()]);
+		assert_eq!(table.get(0, 0),    Some(&iR64 { nominator: -1,  denominator: 1 }));
+		assert_eq!(table.get(1, 1),    Some(&iR64 { nominator: 255, denominator: 1 }));
+		assert_eq!(table.get(2, 0),    None);
+		assert_eq!(table.encode(&Endian::Little), data);
+
+		// Missing values
+		assert_eq!(OecfTable::decode(&data[..data.len() - 8], &Endian::Little), None);
+
+		let mut metadata = Metadata::new();
+		metadata.set_oecf(&table).unwrap();
+
+		let metadata = Metadata::new_from_vec(&metadata.encode().unwrap(), FileExtension::TIFF).unwrap();
+		assert_eq!(metadata.get_oecf(), Some(table));
+	}
+
+	#[test]
+	fn
+	spatial_frequency_response_round_trip()
+	{
+		let mut table = SpatialFrequencyTable {
+			columns:      2,
+			rows:         1,
+			column_names: vec!["Spatial frequency".to_string(), "Horizontal SFR".to_string()],
+			values:       vec![uR64 { nominator: 1, denominator: 2 }],
+		};
+
+		let mut metadata = Metadata::new();
+		assert!(metadata.set_spatial_frequency_response(&table).is_err());
+
+		table.values.push(uR64 { nominator: 9, denominator: 10 });
+		metadata.set_spatial_frequency_response(&table).unwrap();
+
+		let metadata = Metadata::new_from_vec(&metadata.encode().unwrap(), FileExtension::TIFF).unwrap();
+		assert_eq!(metadata.get_spatial_frequency_response(), Some(table));
+	}
+}
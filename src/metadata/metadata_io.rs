@@ -1,18 +1,43 @@
 // Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
 // See https://github.com/TechnikTobi/little_exif#license for licensing details
 
+#[cfg(feature = "std_fs")]
+use std::fs::File;
+use std::io::Cursor;
+use std::io::Read;
+#[cfg(feature = "std_fs")]
+use std::io::Seek;
+#[cfg(feature = "std_fs")]
+use std::io::SeekFrom;
+use std::io::Write;
+#[cfg(feature = "std_fs")]
 use std::path::Path;
 
+use crate::endian::Endian;
+#[cfg(feature = "std_fs")]
 use crate::filetype::get_file_type;
 use crate::filetype::FileExtension;
 use crate::general_file_io::io_error;
+#[cfg(feature = "std_fs")]
+use crate::general_file_io::open_write_file;
+#[cfg(feature = "std_fs")]
+use crate::general_file_io::write_atomically;
+#[cfg(feature = "std_fs")]
+use crate::general_file_io::write_atomically_from_scratch;
+use crate::general_file_io::EXIF_HEADER;
 
+use crate::jp2;
 use crate::jpg;
 use crate::jxl;
 use crate::png;
 use crate::tiff;
 use crate::webp;
 
+use super::options::ReadOptions;
+#[cfg(feature = "std_fs")]
+use super::options::WriteOptions;
+use super::warnings::ParseWarning;
+use super::warnings::ParseWarningKind;
 use super::Metadata;
 
 impl
@@ -30,7 +55,6 @@ Metadata
 	/// let file_data = fs::read("image.jpg").unwrap();
 	/// let mut metadata: Metadata = Metadata::new_from_vec(&file_data, FileExtension::JPEG).unwrap();
 	/// ```
-	#[allow(unreachable_patterns)]
 	pub fn
 	new_from_vec
 	(
@@ -39,14 +63,279 @@ Metadata
 	)
 	-> Result<Metadata, std::io::Error>
 	{
-		let raw_pre_decode_general = match file_type
+		return Self::new_from_vec_with_options(file_buffer, file_type, &ReadOptions::default());
+	}
+
+	/// Same as `new_from_vec`, but uses the given `ReadOptions` instead of
+	/// the default ones, e.g. for reading files with minor issues in lenient
+	/// mode.
+	pub fn
+	new_from_vec_with_options
+	(
+		file_buffer: &Vec<u8>,
+		file_type:   FileExtension,
+		options:     &ReadOptions
+	)
+	-> Result<Metadata, std::io::Error>
+	{
+		let mut warnings = Vec::new();
+		let exif_sources = Self::read_exif_sources(file_buffer, file_type, options, &mut warnings, "new_from_vec")?;
+
+		let (raw_pre_decode_general, other_exif_sources) = Self::split_exif_sources(exif_sources);
+
+		let mut metadata = Self::general_decoding_wrapper(raw_pre_decode_general, options, warnings)?;
+		metadata.reconcile_exif_sources(other_exif_sources, options);
+
+		if file_type == FileExtension::JPEG
+		{
+			metadata.derive_missing_dimensions(jpg::read_dimensions(file_buffer));
+		}
+
+		return Ok(metadata);
+	}
+
+	/// Same as `new_from_vec`, but for an image that is embedded at the given
+	/// byte offset within a larger buffer, e.g. a JPEG extracted from a PDF.
+	/// For JPEGs, the data is parsed in place without copying the image out
+	/// of the buffer first.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use std::fs;
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::filetype::FileExtension;
+	/// 
+	/// let container_data = fs::read("document.bin").unwrap();
+	/// let metadata = Metadata::new_from_vec_at(&container_data, 1024, FileExtension::JPEG).unwrap();
+	/// ```
+	pub fn
+	new_from_vec_at
+	(
+		data:      &[u8],
+		offset:    usize,
+		file_type: FileExtension
+	)
+	-> Result<Metadata, std::io::Error>
+	{
+		if offset > data.len()
+		{
+			return io_error!(
+				InvalidInput, 
+				format!("Offset {} exceeds data of length {}", offset, data.len())
+			);
+		}
+
+		let embedded_data = &data[offset..];
+
+		if file_type != FileExtension::JPEG
+		{
+			return Self::new_from_vec(&embedded_data.to_vec(), file_type);
+		}
+
+		let mut metadata = Self::general_decoding_wrapper(jpg::read_metadata(embedded_data), &ReadOptions::default(), Vec::new())?;
+
+		metadata.derive_missing_dimensions(jpg::read_dimensions(embedded_data));
+
+		return Ok(metadata);
+	}
+
+	/// Gets the number of images stored in a JPEG, which is more than one for
+	/// multi-picture object (MPO) files like stereo images or some burst 
+	/// shots. Each of these images has its own EXIF data, see
+	/// `new_from_mpo_image`. Regular JPEGs contain a single image.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use std::fs;
+	/// use little_exif::metadata::Metadata;
+	/// 
+	/// let file_data = fs::read("image.mpo").unwrap();
+	/// for index in 0..Metadata::mpo_image_count(&file_data).unwrap()
+	/// {
+	///     let metadata = Metadata::new_from_mpo_image(&file_data, index).unwrap();
+	/// }
+	/// ```
+	pub fn
+	mpo_image_count
+	(
+		file_buffer: &[u8]
+	)
+	-> Result<usize, std::io::Error>
+	{
+		return Ok(jpg::read_mpo_image_offsets(file_buffer)?.len());
+	}
+
+	/// Constructs a new `Metadata` object with the metadata of the image with
+	/// the given index within a multi-picture object (MPO) file. The index 0
+	/// refers to the first image, whose metadata is also returned by 
+	/// `new_from_vec`.
+	pub fn
+	new_from_mpo_image
+	(
+		file_buffer: &[u8],
+		index:       usize
+	)
+	-> Result<Metadata, std::io::Error>
+	{
+		let image_offsets = jpg::read_mpo_image_offsets(file_buffer)?;
+
+		match image_offsets.get(index)
+		{
+			Some(offset) => return Self::new_from_vec_at(file_buffer, *offset, FileExtension::JPEG),
+			None         => return io_error!(
+				InvalidInput, 
+				format!("Image index {} exceeds image count {}", index, image_offsets.len())
+			),
+		}
+	}
+
+	/// Constructs a new `Metadata` object with the metadata from the image at the specified path.
+	/// - If unable to read the file (e.g. does not exist, unsupported file type, etc.), this (currently) panics.
+	/// - If unable to decode the metadata, a new, empty object gets created and returned.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// 
+	/// let mut metadata: Metadata = Metadata::new_from_path(std::path::Path::new("image.png")).unwrap();
+	/// ```
+	#[cfg(feature = "std_fs")]
+	pub fn
+	new_from_path
+	(
+		path: &Path
+	)
+	-> Result<Metadata, std::io::Error>
+	{
+		return Self::new_from_path_with_options(path, &ReadOptions::default());
+	}
+
+	/// Same as `new_from_path`, but uses the given `ReadOptions` instead of
+	/// the default ones, e.g. for reading files with minor issues in lenient
+	/// mode.
+	#[cfg(feature = "std_fs")]
+	pub fn
+	new_from_path_with_options
+	(
+		path:    &Path,
+		options: &ReadOptions
+	)
+	-> Result<Metadata, std::io::Error>
+	{
+		let file_type = get_file_type(path)?;
+
+		// Call the file specific decoders as a starting point for obtaining
+		// the raw EXIF data that gets further processed
+		let mut warnings = Vec::new();
+		let exif_sources = Self::file_read_exif_sources(path, file_type, options, &mut warnings, "new_from_path")?;
+
+		let (raw_pre_decode_general, other_exif_sources) = Self::split_exif_sources(exif_sources);
+
+		let mut metadata = Self::general_decoding_wrapper(raw_pre_decode_general, options, warnings)?;
+		metadata.reconcile_exif_sources(other_exif_sources, options);
+
+		if file_type == FileExtension::JPEG
+		{
+			metadata.derive_missing_dimensions(jpg::file_read_dimensions(path));
+		}
+
+		return Ok(metadata);
+	}
+
+	/// Splits the result of `read_exif_sources` into the first EXIF source,
+	/// which gets decoded, and the other ones, see `reconcile_exif_sources`
+	fn
+	split_exif_sources
+	(
+		exif_sources: Result<Vec<Vec<u8>>, std::io::Error>
+	)
+	-> (Result<Vec<u8>, std::io::Error>, Vec<Vec<u8>>)
+	{
+		match exif_sources
+		{
+			Ok(mut exif_sources) if !exif_sources.is_empty() => {
+				let first_exif_source = exif_sources.remove(0);
+				return (Ok(first_exif_source), exif_sources);
+			},
+			Ok(_)      => return (io_error!(NotFound, "No EXIF data found!"), Vec::new()),
+			Err(error) => return (Err(error), Vec::new()),
+		}
+	}
+
+	/// Merges the tags of the given EXIF sources into this struct, which is
+	/// expected to hold the decoded first source. PNGs and JPEGs may hold
+	/// more than one copy of the EXIF data, e.g. after being edited by
+	/// different tools. The first source takes precedence: Tags of the other
+	/// sources are only added if they don't exist in the respective IFD yet.
+	/// Finding more than one source is recorded as warning.
+	fn
+	reconcile_exif_sources
+	(
+		&mut self,
+		other_exif_sources: Vec<Vec<u8>>,
+		options:            &ReadOptions
+	)
+	{
+		if other_exif_sources.is_empty()
+		{
+			return;
+		}
+
+		self.warnings.push(ParseWarning::new(
+			ParseWarningKind::MultipleExifSources,
+			format!("Found {} sources of EXIF data - Merging them, preferring the first one", other_exif_sources.len() + 1)
+		));
+
+		for exif_source in other_exif_sources.iter()
+		{
+			let image_file_directories = match Self::decode(&mut Cursor::new(exif_source), options)
+			{
+				Ok((_, image_file_directories, _)) => image_file_directories,
+				Err(_)                             => continue,
+			};
+
+			for ifd in image_file_directories
+			{
+				for tag in ifd.get_tags()
+				{
+					let tag_exists = self.get_ifd(ifd.get_ifd_type(), ifd.get_generic_ifd_nr())
+						.map_or(false, |own_ifd| own_ifd.get_tags().iter().any(|own_tag| own_tag.as_u16() == tag.as_u16()));
+
+					if !tag_exists
+					{
+						self.get_ifd_mut(ifd.get_ifd_type(), ifd.get_generic_ifd_nr()).set_tag(tag.clone());
+					}
+				}
+			}
+		}
+	}
+
+	/// Calls the file specific function for reading the raw EXIF data from
+	/// the given file buffer. The outer result fails if the file type is not
+	/// supported, the inner one if reading the data fails. Non-fatal issues
+	/// of the container format are added to `warnings`.
+	#[allow(unreachable_patterns)]
+	pub(crate) fn
+	read_raw_metadata
+	(
+		file_buffer:   &Vec<u8>,
+		file_type:     FileExtension,
+		options:       &ReadOptions,
+		warnings:      &mut Vec<ParseWarning>,
+		function_name: &str
+	)
+	-> Result<Result<Vec<u8>, std::io::Error>, std::io::Error>
+	{
+		Ok(match file_type
 		{
 			FileExtension::JPEG 
 				=>  jpg::read_metadata(file_buffer),
 			FileExtension::JXL
 				=>  jxl::read_metadata(file_buffer),
+			FileExtension::JP2
+				=>  jp2::read_metadata(file_buffer),
 			FileExtension::PNG { as_zTXt_chunk: _ }
-				=>  png::vec::read_metadata(file_buffer),
+				=>  png::vec::read_metadata(file_buffer, options.strictness, warnings),
 			FileExtension::TIFF
 				=> tiff::vec::read_metadata(file_buffer),
 			FileExtension::WEBP
@@ -55,202 +344,634 @@ Metadata
 				=> return io_error!(
 					Other, 
 					format!(
-						"Function 'new_from_vec' not yet implemented for {:?}", 
+						"Function '{}' not yet implemented for {:?}", 
+						function_name,
 						file_type
 					)
 				),
-		};
+		})
+	}
+
+	/// Calls the file specific function for reading all EXIF sources of the
+	/// given file buffer in a single pass, see `reconcile_exif_sources`. Only
+	/// PNGs and JPEGs can hold more than one source, for all other types the
+	/// result of `read_raw_metadata` is the only one.
+	fn
+	read_exif_sources
+	(
+		file_buffer:   &Vec<u8>,
+		file_type:     FileExtension,
+		options:       &ReadOptions,
+		warnings:      &mut Vec<ParseWarning>,
+		function_name: &str
+	)
+	-> Result<Result<Vec<Vec<u8>>, std::io::Error>, std::io::Error>
+	{
+		Ok(match file_type
+		{
+			FileExtension::JPEG
+				=>  jpg::read_all_metadata(file_buffer),
+			FileExtension::PNG { as_zTXt_chunk: _ }
+				=>  png::vec::read_all_metadata(file_buffer, options.strictness, warnings),
+			_
+				=>  Self::read_raw_metadata(file_buffer, file_type, options, warnings, function_name)?
+					.map(|exif_source| vec![exif_source]),
+		})
+	}
 
-		return Self::general_decoding_wrapper(raw_pre_decode_general);
+	/// Same as `read_exif_sources`, but for the file at the given path
+	#[cfg(feature = "std_fs")]
+	fn
+	file_read_exif_sources
+	(
+		path:          &Path,
+		file_type:     FileExtension,
+		options:       &ReadOptions,
+		warnings:      &mut Vec<ParseWarning>,
+		function_name: &str
+	)
+	-> Result<Result<Vec<Vec<u8>>, std::io::Error>, std::io::Error>
+	{
+		Ok(match file_type
+		{
+			FileExtension::JPEG
+				=>  jpg::file_read_all_metadata(path),
+			FileExtension::PNG { as_zTXt_chunk: _ }
+				=>  std::fs::read(path).and_then(|file_buffer| png::vec::read_all_metadata(&file_buffer, options.strictness, warnings)),
+			_
+				=>  Self::file_read_raw_metadata(path, file_type, options, warnings, function_name)?
+					.map(|exif_source| vec![exif_source]),
+		})
 	}
 
-	/// Constructs a new `Metadata` object with the metadata from the image at the specified path.
-	/// - If unable to read the file (e.g. does not exist, unsupported file type, etc.), this (currently) panics.
-	/// - If unable to decode the metadata, a new, empty object gets created and returned.
+	/// Same as `read_raw_metadata`, but for the file at the given path
+	#[allow(unreachable_patterns)]
+	#[cfg(feature = "std_fs")]
+	pub(crate) fn
+	file_read_raw_metadata
+	(
+		path:          &Path,
+		file_type:     FileExtension,
+		options:       &ReadOptions,
+		warnings:      &mut Vec<ParseWarning>,
+		function_name: &str
+	)
+	-> Result<Result<Vec<u8>, std::io::Error>, std::io::Error>
+	{
+		Ok(match file_type
+		{
+			FileExtension::JPEG 
+				=>  jpg::file_read_metadata(&path),
+			FileExtension::JXL
+				=>  jxl::file_read_metadata(&path),
+			FileExtension::JP2
+				=>  jp2::file_read_metadata(&path),
+			FileExtension::PNG { as_zTXt_chunk: _ } 
+				=>  png::file::read_metadata(&path, options.strictness, warnings),
+			FileExtension::TIFF
+				=> tiff::file::read_metadata(&path),
+			FileExtension::WEBP 
+				=> webp::file::read_metadata(&path),
+			_
+				=> return io_error!(
+					Other, 
+					format!(
+						"Function '{}' not yet implemented for {:?}", 
+						function_name,
+						file_type
+					)
+				),
+		})
+	}
+
+	/// Checks if an image stored as a `Vec<u8>` contains EXIF data. This only
+	/// looks for the container specific location of the EXIF data (e.g. the
+	/// APP1 segment of a JPEG) without decoding the IFDs, so it is cheaper
+	/// than constructing a `Metadata` object.
+	/// As a TIFF file is itself structured like EXIF data, this is always
+	/// true for valid TIFFs. Fails if the image data can't be parsed.
+	pub fn
+	has_exif
+	(
+		file_buffer: &Vec<u8>,
+		file_type:   FileExtension
+	)
+	-> Result<bool, std::io::Error>
+	{
+		let raw_pre_decode_general = Self::read_raw_metadata(file_buffer, file_type, &ReadOptions::default(), &mut Vec::new(), "has_exif")?;
+		return Self::exif_found(raw_pre_decode_general);
+	}
+
+	/// Same as `has_exif`, but for the image at the given path
 	///
 	/// # Examples
 	/// ```no_run
 	/// use little_exif::metadata::Metadata;
 	/// 
-	/// let mut metadata: Metadata = Metadata::new_from_path(std::path::Path::new("image.png")).unwrap();
+	/// if Metadata::file_has_exif(std::path::Path::new("image.jpg")).unwrap()
+	/// {
+	///     println!("Found EXIF data!");
+	/// }
 	/// ```
+	#[cfg(feature = "std_fs")]
+	pub fn
+	file_has_exif
+	(
+		path: &Path
+	)
+	-> Result<bool, std::io::Error>
+	{
+		let file_type              = get_file_type(path)?;
+		let raw_pre_decode_general = Self::file_read_raw_metadata(path, file_type, &ReadOptions::default(), &mut Vec::new(), "file_has_exif")?;
+		return Self::exif_found(raw_pre_decode_general);
+	}
+
+	/// Maps the result of reading the raw EXIF data to whether there is any.
+	/// Only the absence of EXIF data (`ErrorKind::NotFound`) results in
+	/// `false`, other errors (e.g. due to corrupted image data) are passed on.
+	fn
+	exif_found
+	(
+		raw_pre_decode_general: Result<Vec<u8>, std::io::Error>
+	)
+	-> Result<bool, std::io::Error>
+	{
+		match raw_pre_decode_general
+		{
+			Ok(_)                                                      => return Ok(true),
+			Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+			Err(error)                                                 => return Err(error),
+		}
+	}
+
 	#[allow(unreachable_patterns)]
 	pub fn
-	new_from_path
+	clear_metadata
+	(
+		file_buffer: &mut Vec<u8>,
+		file_type:   FileExtension
+	)
+	-> Result<(), std::io::Error>
+	{
+		match file_type
+		{
+			FileExtension::JPEG 
+				=>  jpg::clear_metadata(file_buffer),
+			FileExtension::JXL
+				=>  jxl::clear_metadata(file_buffer),
+			FileExtension::JP2
+				=>  jp2::clear_metadata(file_buffer),
+			FileExtension::PNG { as_zTXt_chunk: _ }
+				=>  png::vec::clear_metadata(file_buffer),
+			FileExtension::TIFF
+				=> tiff::vec::clear_metadata(file_buffer),
+			FileExtension::WEBP
+				=> webp::vec::clear_metadata(file_buffer),
+			_
+				=> return io_error!(
+					Other, 
+					format!(
+						"Function 'clear_metadata' not yet implemented for {:?}", 
+						file_type
+					)
+				),
+		}
+	}
+
+	/// Removes all metadata from the image, not only the EXIF data:
+	/// - JPEG: APP1 (EXIF, XMP), APP13 (IPTC) and COM segments
+	/// - JP2:  EXIF and XMP UUID boxes as well as xml boxes
+	/// - JXL:  Exif and xml boxes
+	/// - PNG:  tEXt, zTXt, iTXt and eXIf chunks
+	/// - TIFF: All tags not required for decoding the image, see
+	///         `reduce_to_a_minimum`
+	/// - WebP: EXIF and XMP chunks
+	#[allow(unreachable_patterns)]
+	pub fn
+	strip_all_metadata
+	(
+		file_buffer: &mut Vec<u8>,
+		file_type:   FileExtension
+	)
+	-> Result<(), std::io::Error>
+	{
+		match file_type
+		{
+			FileExtension::JPEG 
+				=>  jpg::strip_all_metadata(file_buffer),
+			FileExtension::JXL
+				=>  jxl::strip_all_metadata(file_buffer),
+			FileExtension::JP2
+				=>  jp2::strip_all_metadata(file_buffer),
+			FileExtension::PNG { as_zTXt_chunk: _ }
+				=>  png::vec::strip_all_metadata(file_buffer),
+			FileExtension::TIFF
+				=> tiff::vec::clear_metadata(file_buffer),
+			FileExtension::WEBP
+				=> webp::vec::strip_all_metadata(file_buffer),
+			_
+				=> return io_error!(
+					Other, 
+					format!(
+						"Function 'strip_all_metadata' not yet implemented for {:?}", 
+						file_type
+					)
+				),
+		}
+	}
+
+	/// Removes all metadata from the image file at the given path, see
+	/// `strip_all_metadata`
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	///
+	/// Metadata::file_strip_all_metadata(std::path::Path::new("image.jpg")).unwrap();
+	/// ```
+	#[cfg(feature = "std_fs")]
+	pub fn
+	file_strip_all_metadata
 	(
 		path: &Path
 	)
-	-> Result<Metadata, std::io::Error>
+	-> Result<(), std::io::Error>
 	{
 		let file_type = get_file_type(path)?;
 
-		// Call the file specific decoders as a starting point for obtaining
-		// the raw EXIF data that gets further processed
-		let raw_pre_decode_general = match file_type
+		let mut file_buffer = std::fs::read(path)?;
+		Self::strip_all_metadata(&mut file_buffer, file_type)?;
+
+		return write_atomically_from_scratch(path, |temporary_path| std::fs::write(temporary_path, &file_buffer));
+	}
+
+	/// Clears the APP12 segment in a JPEG file that contains data resulting
+	/// from exporting the file via Photoshop. This may be required in order
+	/// for other software to see e.g. the ImageDescription written in the
+	/// APP1 exif segment by little_exif
+	#[allow(unreachable_patterns)]
+	pub fn
+	clear_app12_segment
+	(
+		file_buffer: &mut Vec<u8>,
+		file_type:   FileExtension
+	)
+	-> Result<(), std::io::Error>
+	{
+		match file_type
+		{
+			FileExtension::JPEG 
+				=>  jpg::clear_segment(file_buffer, 0xec),
+			_
+				=> return io_error!(
+					Other, 
+					format!(
+						"Function 'clear_app12_segment' not available for {:?} (only relevant for JPEG)", 
+						file_type
+					)
+				),
+		}
+	}
+
+	/// Clears the APP13 segment in a JPEG file that contains data resulting
+	/// from exporting the file via Photoshop. This may be required in order
+	/// for other software to see e.g. the ImageDescription written in the
+	/// APP1 exif segment by little_exif
+	#[allow(unreachable_patterns)]
+	pub fn
+	clear_app13_segment
+	(
+		file_buffer: &mut Vec<u8>,
+		file_type:   FileExtension
+	)
+	-> Result<(), std::io::Error>
+	{
+		match file_type
+		{
+			FileExtension::JPEG 
+				=>  jpg::clear_segment(file_buffer, 0xed),
+			_
+				=> return io_error!(
+					Other, 
+					format!(
+						"Function 'clear_app13_segment' not available for {:?} (only relevant for JPEG)", 
+						file_type
+					)
+				),
+		}
+	}
+
+	/// Clears the APP12 segment in a JPEG file that contains data resulting
+	/// from exporting the file via Photoshop. This may be required in order
+	/// for other software to see e.g. the ImageDescription written in the
+	/// APP1 exif segment by little_exif
+	#[allow(unreachable_patterns)]
+	#[cfg(feature = "std_fs")]
+	pub fn
+	file_clear_app12_segment
+	(
+		path: &Path
+	)
+	-> Result<(), std::io::Error>
+	{
+		let file_type = get_file_type(path)?;
+
+		match file_type
+		{
+			FileExtension::JPEG 
+				=>  write_atomically(path, |temporary_path| jpg::file_clear_segment(temporary_path, 0xec)),
+			_
+				=> return io_error!(
+					Other, 
+					format!(
+						"Function 'file_clear_app12_segment' not available for {:?} (only relevant for JPEG)", 
+						file_type
+					)
+				),
+		}
+	}
+
+	/// Clears the APP13 segment in a JPEG file that contains data resulting
+	/// from exporting the file via Photoshop. This may be required in order
+	/// for other software to see e.g. the ImageDescription written in the
+	/// APP1 exif segment by little_exif
+	#[allow(unreachable_patterns)]
+	#[cfg(feature = "std_fs")]
+	pub fn
+	file_clear_app13_segment
+	(
+		path: &Path
+	)
+	-> Result<(), std::io::Error>
+	{
+		let file_type = get_file_type(path)?;
+
+		match file_type
+		{
+			FileExtension::JPEG 
+				=>  write_atomically(path, |temporary_path| jpg::file_clear_segment(temporary_path, 0xed)),
+			_
+				=> return io_error!(
+					Other, 
+					format!(
+						"Function 'file_clear_app13_segment' not available for {:?} (only relevant for JPEG)", 
+						file_type
+					)
+				),
+		}
+	}
+
+	/// Reads the payload of a raw profile other than EXIF from a PNG, e.g.
+	/// IPTC data stored in a zTXt or tEXt chunk with the keyword
+	/// "Raw profile type iptc" (`profile_type` = "iptc").
+	///
+	/// # Examples
+	/// ```no_run
+	/// use std::fs;
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::filetype::FileExtension;
+	/// 
+	/// let file_data = fs::read("image.png").unwrap();
+	/// let iptc_data = Metadata::read_raw_profile(&file_data, FileExtension::PNG { as_zTXt_chunk: true }, "iptc").unwrap();
+	/// ```
+	#[allow(unreachable_patterns)]
+	pub fn
+	read_raw_profile
+	(
+		file_buffer:  &Vec<u8>,
+		file_type:    FileExtension,
+		profile_type: &str
+	)
+	-> Result<Vec<u8>, std::io::Error>
+	{
+		match file_type
 		{
-			FileExtension::JPEG 
-				=>  jpg::file_read_metadata(&path),
-			FileExtension::JXL
-				=>  jxl::file_read_metadata(&path),
-			FileExtension::PNG { as_zTXt_chunk: _ } 
-				=>  png::file::read_metadata(&path),
-			FileExtension::TIFF
-				=> tiff::file::read_metadata(&path),
-			FileExtension::WEBP 
-				=> webp::file::read_metadata(&path),
+			FileExtension::PNG { as_zTXt_chunk: _ }
+				=>  png::vec::read_raw_profile(file_buffer, ReadOptions::default().strictness, &mut Vec::new(), profile_type),
 			_
 				=> return io_error!(
 					Other, 
 					format!(
-						"Function 'new_from_path' not yet implemented for {:?}", 
+						"Function 'read_raw_profile' not available for {:?} (only relevant for PNG)", 
 						file_type
 					)
 				),
-		};
-
-		return Self::general_decoding_wrapper(raw_pre_decode_general);
+		}
 	}
 
+	/// Writes the payload as raw profile of the given type to a PNG, using
+	/// the same encoding as for the EXIF data. Depending on `as_zTXt_chunk`,
+	/// the data is stored compressed in a zTXt or uncompressed in a tEXt 
+	/// chunk. Any previous raw profile of this type gets replaced.
 	#[allow(unreachable_patterns)]
 	pub fn
-	clear_metadata
+	write_raw_profile
 	(
-		file_buffer: &mut Vec<u8>,
-		file_type:   FileExtension
+		file_buffer:  &mut Vec<u8>,
+		file_type:    FileExtension,
+		profile_type: &str,
+		payload:      &[u8]
 	)
 	-> Result<(), std::io::Error>
 	{
 		match file_type
 		{
-			FileExtension::JPEG 
-				=>  jpg::clear_metadata(file_buffer),
-			FileExtension::JXL
-				=>  jxl::clear_metadata(file_buffer),
-			FileExtension::PNG { as_zTXt_chunk: _ }
-				=>  png::vec::clear_metadata(file_buffer),
-			FileExtension::TIFF
-				=> tiff::vec::clear_metadata(file_buffer),
-			FileExtension::WEBP
-				=> webp::vec::clear_metadata(file_buffer),
+			FileExtension::PNG { as_zTXt_chunk }
+				=>  png::vec::write_raw_profile(file_buffer, profile_type, payload, as_zTXt_chunk),
 			_
 				=> return io_error!(
 					Other, 
 					format!(
-						"Function 'clear_metadata' not yet implemented for {:?}", 
+						"Function 'write_raw_profile' not available for {:?} (only relevant for PNG)", 
 						file_type
 					)
 				),
 		}
 	}
 
-	/// Clears the APP12 segment in a JPEG file that contains data resulting
-	/// from exporting the file via Photoshop. This may be required in order
-	/// for other software to see e.g. the ImageDescription written in the
-	/// APP1 exif segment by little_exif
+	/// Reads the XMP packet of a PNG, stored in an iTXt chunk with the
+	/// keyword "XML:com.adobe.xmp". Note that the packet is returned as is,
+	/// i.e. it is not parsed in any way. Fails with `ErrorKind::NotFound`
+	/// if the PNG has no XMP packet.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use std::fs;
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::filetype::FileExtension;
+	/// 
+	/// let file_data = fs::read("image.png").unwrap();
+	/// let xmp_packet = Metadata::get_xmp(&file_data, FileExtension::PNG { as_zTXt_chunk: true }).unwrap();
+	/// ```
 	#[allow(unreachable_patterns)]
 	pub fn
-	clear_app12_segment
+	get_xmp
 	(
-		file_buffer: &mut Vec<u8>,
+		file_buffer: &Vec<u8>,
 		file_type:   FileExtension
 	)
-	-> Result<(), std::io::Error>
+	-> Result<String, std::io::Error>
 	{
 		match file_type
 		{
-			FileExtension::JPEG 
-				=>  jpg::clear_segment(file_buffer, 0xec),
+			FileExtension::PNG { as_zTXt_chunk: _ }
+				=>  png::vec::read_xmp(file_buffer, ReadOptions::default().strictness, &mut Vec::new()),
 			_
 				=> return io_error!(
 					Other, 
 					format!(
-						"Function 'clear_app12_segment' not available for {:?} (only relevant for JPEG)", 
+						"Function 'get_xmp' not available for {:?} (only relevant for PNG)", 
 						file_type
 					)
 				),
 		}
 	}
 
-	/// Clears the APP13 segment in a JPEG file that contains data resulting
-	/// from exporting the file via Photoshop. This may be required in order
-	/// for other software to see e.g. the ImageDescription written in the
-	/// APP1 exif segment by little_exif
+	/// Writes the XMP packet to a PNG as uncompressed iTXt chunk with the
+	/// keyword "XML:com.adobe.xmp", as required by the XMP specification.
+	/// Any previous XMP packet gets replaced, while the EXIF data is kept.
 	#[allow(unreachable_patterns)]
 	pub fn
-	clear_app13_segment
+	set_xmp
 	(
 		file_buffer: &mut Vec<u8>,
-		file_type:   FileExtension
+		file_type:   FileExtension,
+		packet:      &str
 	)
 	-> Result<(), std::io::Error>
 	{
 		match file_type
 		{
-			FileExtension::JPEG 
-				=>  jpg::clear_segment(file_buffer, 0xed),
+			FileExtension::PNG { as_zTXt_chunk: _ }
+				=>  png::vec::write_xmp(file_buffer, packet),
 			_
 				=> return io_error!(
 					Other, 
 					format!(
-						"Function 'clear_app13_segment' not available for {:?} (only relevant for JPEG)", 
+						"Function 'set_xmp' not available for {:?} (only relevant for PNG)", 
 						file_type
 					)
 				),
 		}
 	}
 
-	/// Clears the APP12 segment in a JPEG file that contains data resulting
-	/// from exporting the file via Photoshop. This may be required in order
-	/// for other software to see e.g. the ImageDescription written in the
-	/// APP1 exif segment by little_exif
+	/// Recomputes the CRCs of all chunks in a PNG file and replaces those
+	/// that don't match, e.g. to make a file with a corrupted ancillary 
+	/// chunk readable in strict mode again. Returns the number of repaired
+	/// chunks.
 	#[allow(unreachable_patterns)]
 	pub fn
-	file_clear_app12_segment
+	repair_crcs
 	(
-		path: &Path
+		file_buffer: &mut Vec<u8>,
+		file_type:   FileExtension
 	)
-	-> Result<(), std::io::Error>
+	-> Result<u32, std::io::Error>
 	{
-		let file_type = get_file_type(path)?;
-
 		match file_type
 		{
-			FileExtension::JPEG 
-				=>  jpg::file_clear_segment(path, 0xec),
+			FileExtension::PNG { as_zTXt_chunk: _ }
+				=>  png::vec::repair_crcs(file_buffer),
 			_
 				=> return io_error!(
 					Other, 
 					format!(
-						"Function 'file_clear_app12_segment' not available for {:?} (only relevant for JPEG)", 
+						"Function 'repair_crcs' not available for {:?} (only relevant for PNG)", 
 						file_type
 					)
 				),
 		}
 	}
 
-	/// Clears the APP13 segment in a JPEG file that contains data resulting
-	/// from exporting the file via Photoshop. This may be required in order
-	/// for other software to see e.g. the ImageDescription written in the
-	/// APP1 exif segment by little_exif
-	#[allow(unreachable_patterns)]
+	/// Same as `repair_crcs`, but for the file at the given path. The file
+	/// only gets rewritten if at least one CRC was repaired.
+	#[cfg(feature = "std_fs")]
 	pub fn
-	file_clear_app13_segment
+	file_repair_crcs
 	(
 		path: &Path
 	)
-	-> Result<(), std::io::Error>
+	-> Result<u32, std::io::Error>
 	{
 		let file_type = get_file_type(path)?;
 
+		let mut file_buffer    = std::fs::read(path)?;
+		let     repair_counter = Self::repair_crcs(&mut file_buffer, file_type)?;
+
+		if repair_counter > 0
+		{
+			write_atomically_from_scratch(path, |temporary_path| std::fs::write(temporary_path, &file_buffer))?;
+		}
+
+		return Ok(repair_counter);
+	}
+
+	/// Gets the start offset and length (in bytes) of the EXIF data within
+	/// the image file at the given path, e.g. for passing the raw data to
+	/// another library. Except for PNG files, this is the TIFF payload
+	/// starting with the endianness information ("II" or "MM"):
+	/// - JPEG: Within the APP1 segment, after the "Exif\0\0" header
+	/// - JXL:  Within the Exif box, after the offset to the TIFF header
+	/// - TIFF: The entire file
+	/// - WebP: Within the EXIF chunk
+	/// For PNG files, this is the data of the zTXt/tEXt chunk that stores
+	/// the EXIF data as hex-encoded (and possibly compressed) raw profile.
+	/// Returns `None` if there is no EXIF data.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let path = std::path::Path::new("image.jpg");
+	/// if let Some((start, length)) = Metadata::exif_byte_range(path).unwrap()
+	/// {
+	///     let file_buffer = std::fs::read(path).unwrap();
+	///     let tiff_data   = &file_buffer[start as usize..(start + length) as usize];
+	/// }
+	/// ```
+	#[cfg(feature = "std_fs")]
+	pub fn
+	exif_byte_range
+	(
+		path: &Path
+	)
+	-> Result<Option<(u64, u64)>, std::io::Error>
+	{
+		let file_type   = get_file_type(path)?;
+		let file_buffer = std::fs::read(path)?;
+
+		return Self::exif_byte_range_in_vec(&file_buffer, file_type);
+	}
+
+	/// Same as `exif_byte_range`, but for an image stored as a `Vec<u8>`
+	#[allow(unreachable_patterns)]
+	pub fn
+	exif_byte_range_in_vec
+	(
+		file_buffer: &Vec<u8>,
+		file_type:   FileExtension
+	)
+	-> Result<Option<(u64, u64)>, std::io::Error>
+	{
 		match file_type
 		{
 			FileExtension::JPEG 
-				=>  jpg::file_clear_segment(path, 0xed),
+				=>  jpg::exif_byte_range(file_buffer),
+			FileExtension::JXL
+				=>  jxl::exif_byte_range(file_buffer),
+			FileExtension::JP2
+				=>  jp2::exif_byte_range(file_buffer),
+			FileExtension::PNG { as_zTXt_chunk: _ }
+				=>  png::vec::exif_byte_range(file_buffer),
+			FileExtension::TIFF
+				=> Ok(Some((0, file_buffer.len() as u64))),
+			FileExtension::WEBP 
+				=> webp::vec::exif_byte_range(file_buffer),
 			_
 				=> return io_error!(
 					Other, 
 					format!(
-						"Function 'file_clear_app13_segment' not available for {:?} (only relevant for JPEG)", 
+						"Function 'exif_byte_range' not yet implemented for {:?}", 
 						file_type
 					)
 				),
@@ -258,6 +979,7 @@ Metadata
 	}
 
 	#[allow(unreachable_patterns)]
+	#[cfg(feature = "std_fs")]
 	pub fn
 	file_clear_metadata
 	(
@@ -267,18 +989,21 @@ Metadata
 	{
 		let file_type = get_file_type(path)?;
 
-		match file_type
+		// See `write_to_file` regarding the temporary copy
+		return write_atomically(path, |temporary_path| match file_type
 		{
 			FileExtension::JPEG 
-				=>  jpg::file_clear_metadata(&path),
+				=>  jpg::file_clear_metadata(temporary_path),
 			FileExtension::JXL
-				=>  jxl::file_clear_metadata(&path),
+				=>  jxl::file_clear_metadata(temporary_path),
+			FileExtension::JP2
+				=>  jp2::file_clear_metadata(temporary_path),
 			FileExtension::PNG { as_zTXt_chunk: _ }
-				=>  png::file::clear_metadata(&path),
+				=>  png::file::clear_metadata(temporary_path),
 			FileExtension::TIFF
-				=> tiff::file::clear_metadata(&path),
+				=> tiff::file::clear_metadata(temporary_path),
 			FileExtension::WEBP 
-				=> webp::file::clear_metadata(&path),
+				=> webp::file::clear_metadata(temporary_path),
 			_
 				=> return io_error!(
 					Other, 
@@ -287,7 +1012,7 @@ Metadata
 						file_type
 					)
 				),
-		}
+		});
 	}
 
 	/// Converts the metadata into a file specific vector of bytes
@@ -313,16 +1038,70 @@ Metadata
 			FileExtension::PNG { as_zTXt_chunk } 
 				=>  png::as_u8_vec(&general_encoded_metadata, as_zTXt_chunk),
 			FileExtension::JPEG 
-				=>  jpg::as_u8_vec(&general_encoded_metadata),
+				=>  jpg::as_u8_vec(&general_encoded_metadata)?,
 			FileExtension::WEBP 
-				=> webp::as_u8_vec(&general_encoded_metadata),
+				=> webp::as_u8_vec(&general_encoded_metadata)?,
 			_
 				=> Vec::new(),
 		})
 	}
 
+	/// Gets the number of bytes of the generally encoded metadata, i.e. 
+	/// without any file type specific headers or encodings
+	pub fn
+	encoded_size
+	(
+		&self
+	)
+	-> Result<usize, std::io::Error>
+	{
+		Ok(self.encode()?.len())
+	}
+
+	/// If the endianness has not been set explicitly via `set_endian` and the
+	/// existing (raw, not yet decoded) metadata of the target image uses a 
+	/// different endianness, this returns a copy of the struct that uses the
+	/// existing one instead.
+	pub(crate) fn
+	with_existing_endian
+	(
+		&self,
+		existing_raw_metadata: &[u8]
+	)
+	-> Option<Metadata>
+	{
+		if self.endian_is_explicit
+		{
+			return None;
+		}
+
+		let tiff_data = existing_raw_metadata.strip_prefix(&EXIF_HEADER).unwrap_or(existing_raw_metadata);
+
+		let existing_endian = match tiff_data.get(0..2)?
+		{
+			[0x49, 0x49] => Endian::Little,
+			[0x4d, 0x4d] => Endian::Big,
+			_            => return None,
+		};
+
+		if existing_endian == self.endian
+		{
+			return None;
+		}
+
+		let mut adjusted = self.clone();
+		adjusted.set_endian(existing_endian);
+		return Some(adjusted);
+	}
+
 	/// Writes the metadata to an image stored as a Vec<u8>
-	/// For now, this only works for JPGs
+	/// For PNGs, `as_zTXt_chunk` determines whether the metadata is stored
+	/// compressed in a zTXt chunk or uncompressed in a tEXt chunk.
+	/// If the image already contains metadata, its endianness is kept unless
+	/// a different one has been set via `set_endian`.
+	/// For JPEGs, the EXIF data has to fit into a single APP1 segment (i.e.
+	/// 65527 bytes without the "Exif\0\0" header), otherwise an error
+	/// holding a `MetadataTooLarge` is returned and the image stays as it is.
 	#[allow(unreachable_patterns)]
 	pub fn
 	write_to_vec
@@ -333,14 +1112,24 @@ Metadata
 	)
 	-> Result<(), std::io::Error>
 	{
+		if let Ok(Ok(existing_metadata)) = Self::read_raw_metadata(file_buffer, file_type, &ReadOptions::default(), &mut Vec::new(), "write_to_vec")
+		{
+			if let Some(adjusted) = self.with_existing_endian(&existing_metadata)
+			{
+				return adjusted.write_to_vec(file_buffer, file_type);
+			}
+		}
+
 		match file_type
 		{
 			FileExtension::JPEG 
 				=>  jpg::write_metadata(file_buffer, &self),
 			FileExtension::JXL 
 				=>  jxl::write_metadata(file_buffer, &self),
-			FileExtension::PNG { as_zTXt_chunk: _ }
-				=>  png::vec::write_metadata(file_buffer, &self),
+			FileExtension::JP2
+				=>  jp2::write_metadata(file_buffer, &self),
+			FileExtension::PNG { as_zTXt_chunk }
+				=>  png::vec::write_metadata(file_buffer, &self, as_zTXt_chunk),
 			FileExtension::TIFF
 				=> tiff::vec::write_metadata(file_buffer, &self),
 			FileExtension::WEBP
@@ -356,12 +1145,106 @@ Metadata
 		}
 	}
 
+	/// Reads an image of the given type from `source` and writes it with the
+	/// metadata of this struct to `destination`, e.g. a network socket.
+	/// For JPEGs, this is done incrementally without holding the entire image
+	/// in memory; for the other file types the image currently still gets 
+	/// buffered, as e.g. the size information in their headers needs to be 
+	/// updated before anything can be written.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::filetype::FileExtension;
+	///
+	/// let metadata = Metadata::new();
+	/// let source   = std::fs::File::open("image.jpg").unwrap();
+	/// let mut sink = Vec::new();
+	/// metadata.write_to_stream(source, &mut sink, FileExtension::JPEG).unwrap();
+	/// ```
+	pub fn
+	write_to_stream
+	<R: Read, W: Write>
+	(
+		&self,
+		mut source:      R,
+		mut destination: W,
+		file_type:       FileExtension
+	)
+	-> Result<(), std::io::Error>
+	{
+		if file_type == FileExtension::JPEG
+		{
+			return jpg::stream_write_metadata(&mut source, &mut destination, &self);
+		}
+
+		let mut file_buffer = Vec::new();
+		source.read_to_end(&mut file_buffer)?;
+		self.write_to_vec(&mut file_buffer, file_type)?;
+		destination.write_all(&file_buffer)?;
+
+		return Ok(());
+	}
+
+	/// Checks whether writing the metadata to the specified file via
+	/// `write_to_file` would change its EXIF data, by encoding the metadata
+	/// (in the endianness that would be used for writing) and comparing it to
+	/// the EXIF data stored in the file. This allows skipping the write, e.g.
+	/// to preserve the modification time of unchanged files.
+	/// If the file has no EXIF data, this is always true. The same goes for
+	/// TIFFs in practice, as their metadata can't be separated from the
+	/// image data.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let path     = std::path::Path::new("image.jpg");
+	/// let metadata = Metadata::new_from_path(path).unwrap();
+	/// if metadata.would_modify(path).unwrap()
+	/// {
+	///     metadata.write_to_file(path).unwrap();
+	/// }
+	/// ```
+	#[cfg(feature = "std_fs")]
+	pub fn
+	would_modify
+	(
+		&self,
+		path: &Path
+	)
+	-> Result<bool, std::io::Error>
+	{
+		let file_type         = get_file_type(path)?;
+		let existing_metadata = match Self::file_read_raw_metadata(path, file_type, &ReadOptions::default(), &mut Vec::new(), "would_modify")?
+		{
+			Ok(existing_metadata) => existing_metadata,
+			Err(_)                => return Ok(true),
+		};
+
+		let encoded_metadata = match self.with_existing_endian(&existing_metadata)
+		{
+			Some(adjusted) => adjusted.encode()?,
+			None           => self.encode()?,
+		};
+
+		let existing_tiff_data = existing_metadata.strip_prefix(&EXIF_HEADER).unwrap_or(&existing_metadata);
+		let encoded_tiff_data  = encoded_metadata.strip_prefix(&EXIF_HEADER).unwrap_or(&encoded_metadata);
+
+		return Ok(existing_tiff_data != encoded_tiff_data);
+	}
+
 	/// Writes the metadata to the specified file.
 	/// This could return an error for multiple reasons:
 	/// - The file does not exist at the given path
 	/// - Interpreting the given path fails
 	/// - The file type is not supported
-	#[allow(unreachable_patterns)]
+	///
+	/// The metadata is written to a temporary copy of the file in the same
+	/// directory, which then replaces the original. This way, the original
+	/// file stays intact if writing fails (e.g. as the disk is full).
+	/// See `write_to_vec` regarding the endianness of the written metadata.
+	#[cfg(feature = "std_fs")]
 	pub fn
 	write_to_file
 	(
@@ -370,20 +1253,44 @@ Metadata
 	)
 	-> Result<(), std::io::Error>
 	{
-		let file_type = get_file_type(path)?;
+		return self.file_write_metadata(path, get_file_type(path)?);
+	}
 
-		match file_type
+	/// Writes the metadata to the file at the given path, which is of the
+	/// given type, see `write_to_file`
+	#[allow(unreachable_patterns)]
+	#[cfg(feature = "std_fs")]
+	fn
+	file_write_metadata
+	(
+		&self,
+		path:      &Path,
+		file_type: FileExtension
+	)
+	-> Result<(), std::io::Error>
+	{
+		if let Ok(Ok(existing_metadata)) = Self::file_read_raw_metadata(path, file_type, &ReadOptions::default(), &mut Vec::new(), "write_to_file")
+		{
+			if let Some(adjusted) = self.with_existing_endian(&existing_metadata)
+			{
+				return adjusted.file_write_metadata(path, file_type);
+			}
+		}
+
+		return write_atomically(path, |temporary_path| match file_type
 		{
 			FileExtension::JPEG 
-				=>  jpg::file_write_metadata(&path, &self),
+				=>  jpg::file_write_metadata(temporary_path, &self),
 			FileExtension::JXL 
-				=>  jxl::file_write_metadata(&path, &self),
-			FileExtension::PNG { as_zTXt_chunk: _ }
-				=>  png::file::write_metadata(&path, &self),
+				=>  jxl::file_write_metadata(temporary_path, &self),
+			FileExtension::JP2
+				=>  jp2::file_write_metadata(temporary_path, &self),
+			FileExtension::PNG { as_zTXt_chunk }
+				=>  png::file::write_metadata(temporary_path, &self, as_zTXt_chunk),
 			FileExtension::TIFF
-				=> tiff::file::write_metadata(&path, &self),
+				=> tiff::file::write_metadata(temporary_path, &self),
 			FileExtension::WEBP 
-				=> webp::file::write_metadata(&path, &self),
+				=> webp::file::write_metadata(temporary_path, &self),
 			_
 				=> return io_error!(
 					Other, 
@@ -392,6 +1299,146 @@ Metadata
 						file_type
 					)
 				),
+		});
+	}
+
+	/// Writes the metadata to an already opened file of the given type, e.g.
+	/// one opened with custom `OpenOptions`. The handle needs to be opened
+	/// for both reading and writing. The entire file is read into memory,
+	/// updated via `write_to_vec` and written back starting at the beginning
+	/// of the file, whose length then gets adjusted to the new content.
+	/// Unlike `write_to_file`, this does not use a temporary copy, as there
+	/// is no path to rename it to.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use std::fs::OpenOptions;
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::filetype::FileExtension;
+	///
+	/// let metadata = Metadata::new();
+	/// let mut file = OpenOptions::new().read(true).write(true).open("image.jpg").unwrap();
+	/// metadata.write_to_file_handle(&mut file, FileExtension::JPEG).unwrap();
+	/// ```
+	#[cfg(feature = "std_fs")]
+	pub fn
+	write_to_file_handle
+	(
+		&self,
+		file:      &mut File,
+		file_type: FileExtension
+	)
+	-> Result<(), std::io::Error>
+	{
+		let mut file_buffer = Vec::new();
+		file.seek(SeekFrom::Start(0))?;
+		file.read_to_end(&mut file_buffer)?;
+
+		self.write_to_vec(&mut file_buffer, file_type)?;
+
+		file.seek(SeekFrom::Start(0))?;
+		file.set_len(file_buffer.len() as u64)?;
+		file.write_all(&file_buffer)?;
+		file.flush()?;
+
+		return Ok(());
+	}
+
+	/// Same as `write_to_file`, but uses the given `WriteOptions` instead of
+	/// the default ones.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::metadata::options::WriteOptions;
+	/// 
+	/// let metadata = Metadata::new();
+	/// let options  = WriteOptions { preallocate: true, ..WriteOptions::default() };
+	/// metadata.write_to_file_with_options(std::path::Path::new("image.png"), &options).unwrap();
+	/// ```
+	#[cfg(feature = "std_fs")]
+	pub fn
+	write_to_file_with_options
+	(
+		&self,
+		path:    &Path,
+		options: &WriteOptions
+	)
+	-> Result<(), std::io::Error>
+	{
+		let file_type = match get_file_type(path)?
+		{
+			FileExtension::PNG { as_zTXt_chunk: _ } => FileExtension::PNG { as_zTXt_chunk: options.compress_png_exif },
+			other_file_type                          => other_file_type,
+		};
+
+		if !options.preallocate
+		{
+			return self.file_write_metadata(path, file_type);
 		}
+
+		let mut file_buffer = std::fs::read(path)?;
+		self.write_to_vec(&mut file_buffer, file_type)?;
+
+		// Resize the empty temporary file once to its final size before
+		// writing the content
+		return write_atomically_from_scratch(path, |temporary_path| {
+			let mut file = open_write_file(temporary_path)?;
+			file.set_len(file_buffer.len() as u64)?;
+			file.write_all(&file_buffer)?;
+			return Ok(());
+		});
+	}
+
+	/// Same as `write_to_file`, but returns a `WriteReport` with information
+	/// about what has been written. 
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// 
+	/// let metadata = Metadata::new();
+	/// let report   = metadata.write_to_file_detailed(std::path::Path::new("image.png")).unwrap();
+	/// println!("Wrote {} bytes of metadata", report.metadata_bytes);
+	/// ```
+	#[cfg(feature = "std_fs")]
+	pub fn
+	write_to_file_detailed
+	(
+		&self,
+		path: &Path
+	)
+	-> Result<WriteReport, std::io::Error>
+	{
+		let file_type = get_file_type(path)?;
+
+		// Check if there is metadata that is going to be replaced. Errors
+		// other than its absence (e.g. corrupted image data) are passed on
+		let raw_pre_decode_general = Self::file_read_raw_metadata(path, file_type, &ReadOptions::default(), &mut Vec::new(), "write_to_file_detailed")?;
+		let replaced_existing      = Self::exif_found(raw_pre_decode_general)?;
+
+		self.write_to_file(path)?;
+
+		return Ok(WriteReport {
+			file_type,
+			metadata_bytes: self.encoded_size()?,
+			replaced_existing,
+		});
 	}
-}
\ No newline at end of file
+}
+
+/// Information about a write operation performed by `write_to_file_detailed`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct
+WriteReport
+{
+	/// The container format of the written file
+	pub file_type:         FileExtension,
+
+	/// The number of generally encoded metadata bytes (see `encoded_size`),
+	/// not including any file type specific headers or encodings
+	pub metadata_bytes:    usize,
+
+	/// Whether the file contained metadata before that has been replaced
+	pub replaced_existing: bool,
+}
@@ -6,6 +6,44 @@ pub mod iterator;
 pub mod edit;
 pub mod get;
 pub mod set;
+pub mod rating;
+pub mod dimensions;
+pub mod flash;
+pub mod orientation;
+pub mod options;
+pub mod copyright;
+pub mod tree;
+pub mod lens;
+pub mod datetime;
+pub mod gps;
+pub mod iso;
+pub mod iptc;
+pub mod comment;
+pub mod preview;
+pub mod resolution;
+pub mod camera;
+pub mod makernote;
+pub mod xmp;
+pub mod warnings;
+pub mod subfile;
+pub mod exposure;
+pub mod transaction;
+pub mod cfa;
+pub mod error;
+pub mod pretty;
+pub mod picture_style;
+pub mod privacy;
+pub mod components;
+pub mod borrowed;
+pub mod predictor;
+pub mod raw;
+pub mod chromaticity;
+pub mod measurement;
+pub mod undefined;
+pub mod hash;
+pub mod lazy;
+pub mod source;
+pub mod json;
 
 use core::panic;
 use std::io::Cursor;
@@ -14,19 +52,40 @@ use std::io::Seek;
 use std::io::Write;
 
 use crate::endian::*;
+use crate::exif_tag::ExifTag;
 use crate::general_file_io::io_error;
 use crate::general_file_io::EXIF_HEADER;
 use crate::ifd::ExifTagGroup;
 use crate::ifd::ImageFileDirectory;
+use crate::metadata::error::InvalidExifData;
+use crate::metadata::error::UnsupportedFormat;
+use crate::metadata::options::ReadOptions;
+use crate::metadata::options::DEFAULT_MAX_IFD_COUNT;
+use crate::metadata::warnings::ParseWarning;
 use crate::u8conversion::from_u8_vec_macro;
 use crate::u8conversion::U8conversion;
 
+/// The offset of IFD0 as written by `encode`, i.e. directly after the TIFF
+/// header
+const DEFAULT_IFD0_OFFSET: u32 = 8;
+
+/// An offset tag pointing to an IFD that doesn't exist: The group and number
+/// of the IFD containing the tag, the tag itself and the group it points to
+type DanglingOffsetTag = (ExifTagGroup, u32, ExifTag, ExifTagGroup);
+
 #[derive(Clone)]
 pub struct
 Metadata
 {
 	endian:                 Endian,
-	image_file_directories: Vec<ImageFileDirectory>
+	image_file_directories: Vec<ImageFileDirectory>,
+	derived_tags:           Vec<ExifTag>,
+	subifd_previews:        Vec<ImageFileDirectory>,
+	warnings:               Vec<ParseWarning>,
+	endian_is_explicit:     bool,
+	keep_empty_ifds:        bool,
+	raw_data:               Option<Vec<u8>>,
+	max_ifd_count:          usize,
 }
 
 impl
@@ -48,7 +107,7 @@ Metadata
 	()
 	-> Metadata
 	{
-		Metadata { endian: Endian::Little, image_file_directories: Vec::new() }
+		Metadata { endian: Endian::Little, image_file_directories: Vec::new(), derived_tags: Vec::new(), subifd_previews: Vec::new(), warnings: Vec::new(), endian_is_explicit: false, keep_empty_ifds: false, raw_data: None, max_ifd_count: DEFAULT_MAX_IFD_COUNT }
 	}
 
 	/// Creates an IFD in this struct if it does not exist yet.
@@ -79,26 +138,44 @@ Metadata
 	}
 
 
+	/// Decodes the raw EXIF data obtained from the container format. The
+	/// given warnings of the container format come first in the resulting
+	/// struct, followed by those of the decoding process itself.
 	pub(crate) fn
 	general_decoding_wrapper
 	(
-		raw_pre_decode_general: Result<Vec<u8>, std::io::Error>
+		raw_pre_decode_general: Result<Vec<u8>, std::io::Error>,
+		options:                &ReadOptions,
+		mut warnings:           Vec<ParseWarning>
 	)
 	-> Result<Metadata, std::io::Error>
 	{
 		if let Ok(pre_decode_general) = raw_pre_decode_general
 		{
 			let mut pre_decode_cursor = Cursor::new(&pre_decode_general);
-			let     decoding_result   = Self::decode(&mut pre_decode_cursor);
-			if let Ok((endian, image_file_directories)) = decoding_result
+			let     decoding_result   = Self::decode(&mut pre_decode_cursor, options);
+			if let Ok((endian, image_file_directories, decoding_warnings)) = decoding_result
 			{
-				let mut data = Metadata { endian, image_file_directories };
+				warnings.extend(decoding_warnings);
+				let subifd_previews = Self::decode_subifd_previews(&pre_decode_general, &image_file_directories, options, &mut warnings);
+				let raw_data        = if options.retain_raw_data { Some(pre_decode_general) } else { None };
+				let mut data = Metadata { endian, image_file_directories, derived_tags: Vec::new(), subifd_previews, warnings, endian_is_explicit: false, keep_empty_ifds: false, raw_data, max_ifd_count: options.max_ifd_count };
 				data.sort_data();
 				return Ok(data);
 			}
 			else
 			{
-				eprintln!("{}", decoding_result.err().unwrap());
+				let error = decoding_result.err().unwrap();
+
+				// Unlike other issues, an unsupported format and invalid data
+				// that is not tolerated due to the read options are reported
+				// to the caller instead of returning empty metadata
+				if UnsupportedFormat::from_io_error(&error).is_some()
+				|| InvalidExifData::from_io_error(&error).is_some()
+				{
+					return Err(error);
+				}
+				eprintln!("{}", error);
 			}
 		}
 		else
@@ -119,6 +196,59 @@ Metadata
 	)
 	-> Result<Vec<u8>, std::io::Error>
 	{
+		return self.encode_with_ifd0_offset(DEFAULT_IFD0_OFFSET);
+	}
+
+	/// Same as `encode`, but places IFD0 at the given offset instead of
+	/// directly after the 8 byte TIFF header, e.g. to leave room for data
+	/// of a fixed size. The gap gets filled with zeros. As IFDs have to 
+	/// start on a word boundary, the offset needs to be even.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// let encoded  = metadata.encode_with_ifd0_offset(64).unwrap();
+	/// # }
+	/// ```
+	pub fn
+	encode_with_ifd0_offset
+	(
+		&self,
+		ifd0_offset: u32
+	)
+	-> Result<Vec<u8>, std::io::Error>
+	{
+		if ifd0_offset < DEFAULT_IFD0_OFFSET || ifd0_offset % 2 != 0
+		{
+			return io_error!(
+				InvalidInput,
+				format!("Invalid IFD0 offset {} - Needs to be even and at least {}", ifd0_offset, DEFAULT_IFD0_OFFSET)
+			);
+		}
+
+		// The TIFF header always points to IFD0, so it needs to exist even
+		// if there are no tags for it (e.g. for a new & empty struct)
+		if self.get_ifd(ExifTagGroup::GENERIC, 0).is_none()
+		{
+			let mut with_ifd0 = self.clone();
+			with_ifd0.create_ifd(ExifTagGroup::GENERIC, 0);
+			return with_ifd0.encode_with_ifd0_offset(ifd0_offset);
+		}
+
+		// IFDs without tags and offset tags pointing to IFDs that don't exist
+		// need to be handled first on a copy, see `set_keep_empty_ifds`
+		let (dangling_offset_tags, empty_ifds) = self.find_empty_ifds();
+		if !dangling_offset_tags.is_empty() || !empty_ifds.is_empty()
+		{
+			let mut without_empty_ifds = self.clone();
+			without_empty_ifds.handle_empty_ifds();
+			return without_empty_ifds.encode_with_ifd0_offset(ifd0_offset);
+		}
+
 		// Prepare offset information
 		let mut ifds_with_offset_info_only: Vec<ImageFileDirectory> = Vec::new();
 
@@ -173,7 +303,15 @@ Metadata
 		let mut index_of_previous_ifds_link_section: Option<u64> = Some(4);
 
 		let mut encode_vec     = Vec::from(self.endian.header());
-		let mut current_offset = 8;
+		let mut current_offset = ifd0_offset;
+
+		// The header points to IFD0, which comes after the (zero) padding
+		encode_vec[4..8].copy_from_slice(&match self.endian
+		{
+			Endian::Little => ifd0_offset.to_le_bytes(),
+			Endian::Big    => ifd0_offset.to_be_bytes(),
+		});
+		encode_vec.resize(ifd0_offset as usize, 0x00);
 
 		for n in 0..=generic_ifd_count
 		{
@@ -207,6 +345,93 @@ Metadata
 		Ok(encode_vec)
 	}
 
+	/// Finds the offset tags pointing to IFDs that don't exist and - unless
+	/// empty IFDs should be kept - the IFDs without any tags (except for
+	/// IFD0), which need to be handled by `handle_empty_ifds`
+	fn
+	find_empty_ifds
+	(
+		&self
+	)
+	-> (Vec<DanglingOffsetTag>, Vec<(ExifTagGroup, u32)>)
+	{
+		// Offset tags that were set by the user for IFDs that don't exist
+		let dangling_offset_tags = self.image_file_directories.iter()
+			.flat_map(|ifd| ifd.get_tags().iter().filter_map(|tag| 
+				ImageFileDirectory::get_ifd_type_for_offset_tag(tag)
+					.map(|group| (ifd.get_ifd_type(), ifd.get_generic_ifd_nr(), tag.clone(), group))
+			))
+			.filter(|(_, generic_ifd_nr, _, group)| self.get_ifd(*group, *generic_ifd_nr).is_none())
+			.collect::<Vec<DanglingOffsetTag>>();
+
+		// IFDs that have neither tags nor SubIFDs
+		let mut empty_ifds = Vec::new();
+		if !self.keep_empty_ifds
+		{
+			for ifd in self.image_file_directories.iter()
+			{
+				let is_ifd0 = 
+					ifd.get_ifd_type()       == ExifTagGroup::GENERIC && 
+					ifd.get_generic_ifd_nr() == 0;
+
+				let has_tags = ifd.get_tags().iter().any(|tag| 
+					ImageFileDirectory::get_ifd_type_for_offset_tag(tag).is_none()
+				);
+
+				let has_subifds = self.image_file_directories.iter().any(|subifd|
+					subifd.get_generic_ifd_nr() == ifd.get_generic_ifd_nr() &&
+					subifd.get_offset_tag_for_parent_ifd().map_or(false, |(parent_ifd_group, _)| 
+						parent_ifd_group == ifd.get_ifd_type()
+					)
+				);
+
+				if !is_ifd0 && !has_tags && !has_subifds
+				{
+					empty_ifds.push((ifd.get_ifd_type(), ifd.get_generic_ifd_nr()));
+				}
+			}
+		}
+
+		return (dangling_offset_tags, empty_ifds);
+	}
+
+	/// Removes all IFDs without any tags (except for IFD0, which is always
+	/// required) as well as offset tags pointing to IFDs that don't exist.
+	/// If empty IFDs should be kept, such offset tags instead lead to the
+	/// creation of the (empty) IFD they point to.
+	fn
+	handle_empty_ifds
+	(
+		&mut self
+	)
+	{
+		loop
+		{
+			let (dangling_offset_tags, empty_ifds) = self.find_empty_ifds();
+
+			if dangling_offset_tags.is_empty() && empty_ifds.is_empty()
+			{
+				return;
+			}
+
+			for (ifd_type, generic_ifd_nr, tag, group) in dangling_offset_tags.iter()
+			{
+				if self.keep_empty_ifds
+				{
+					self.create_ifd(*group, *generic_ifd_nr);
+				}
+				else
+				{
+					self.get_ifd_mut(*ifd_type, *generic_ifd_nr).remove_tag(tag.clone());
+				}
+			}
+
+			self.image_file_directories.retain(|ifd| 
+				!empty_ifds.contains(&(ifd.get_ifd_type(), ifd.get_generic_ifd_nr()))
+			);
+		}
+	}
+
 	fn
 	sort_data
 	(
@@ -237,12 +462,16 @@ Metadata
 		);
 	}
  
-	fn
-	decode
+	/// Decodes the TIFF header (optionally preceded by the "Exif\0\0" 
+	/// signature) at the current position of the cursor. Returns the 
+	/// endianness, the position of the TIFF header that all offsets are 
+	/// relative to and the offset to IFD0.
+	pub(crate) fn
+	decode_header
 	(
 		data_cursor: &mut Cursor<&Vec<u8>>
 	)
-	-> Result<(Endian, Vec<ImageFileDirectory>), std::io::Error>
+	-> Result<(Endian, u64, u32), std::io::Error>
 	{
 		// Get the start position
 		let mut data_start_position = data_cursor.position();
@@ -285,6 +514,14 @@ Metadata
 		// Validate magic number
 		let mut magic_number_buffer = vec![0u8; 2];
 		data_cursor.read_exact(&mut magic_number_buffer)?;
+		if
+			(endian == Endian::Little && magic_number_buffer == [0x2b, 0x00]) ||
+			(endian == Endian::Big    && magic_number_buffer == [0x00, 0x2b])
+		{
+			// BigTIFF, which uses 8 byte offsets
+			return Err(UnsupportedFormat::io_error("BigTIFF"));
+		}
+
 		if !(
 			(endian == Endian::Little && magic_number_buffer == [0x2a, 0x00]) ||
 			(endian == Endian::Big    && magic_number_buffer == [0x00, 0x2a])
@@ -296,11 +533,36 @@ Metadata
 		// Get offset to IFD0
 		let mut ifd0_offset_buffer = vec![0u8; 4];
 		data_cursor.read_exact(&mut ifd0_offset_buffer)?;
-		let mut ifd_offset_option = Some(from_u8_vec_macro!(u32, &ifd0_offset_buffer.to_vec(), &endian));
+		let ifd0_offset = from_u8_vec_macro!(u32, &ifd0_offset_buffer.to_vec(), &endian);
+
+		// IFD0 does not have to follow the 8 byte header directly, so the
+		// offset is used as-is. However, it can't point into the header or 
+		// beyond the end of the data
+		if ifd0_offset < 8 || data_start_position + ifd0_offset as u64 >= data_cursor.get_ref().len() as u64
+		{
+			return io_error!(Other, format!("Invalid offset to IFD0: {}", ifd0_offset));
+		}
+
+		return Ok((endian, data_start_position, ifd0_offset));
+	}
+
+	fn
+	decode
+	(
+		data_cursor: &mut Cursor<&Vec<u8>>,
+		options:     &ReadOptions
+	)
+	-> Result<(Endian, Vec<ImageFileDirectory>, Vec<ParseWarning>), std::io::Error>
+	{
+		let (endian, data_start_position, ifd0_offset) = Self::decode_header(data_cursor)?;
+
+		let mut ifd_offset_option = Some(ifd0_offset);
 
 		// Decode all the IFDs
 		let mut ifds = Vec::new();
 		let mut generic_ifd_nr = 0;
+		let mut remaining_ifd_count = options.max_ifd_count;
+		let mut warnings = Vec::new();
 		loop
 		{
 			if let Some(ifd_offset) = ifd_offset_option
@@ -314,7 +576,11 @@ Metadata
 					&endian,
 					&ExifTagGroup::GENERIC,
 					generic_ifd_nr,
-					&mut ifds
+					&mut ifds,
+					&mut remaining_ifd_count,
+					options,
+					&mut warnings,
+					None
 				);
 
 				if let Ok(new_ifd_offset_option) = decode_result
@@ -336,7 +602,7 @@ Metadata
 
 
 
-		return Ok((endian, ifds));
+		return Ok((endian, ifds, warnings));
 	}
 }
 
@@ -348,6 +614,13 @@ mod tests
 	use std::io::Cursor;
 
 use super::Metadata;
+	use crate::exif_tag::ExifTag;
+	use crate::filetype::FileExtension;
+	use crate::ifd::ExifTagGroup;
+	use crate::metadata::error::InvalidExifData;
+	use crate::metadata::options::ParseStrictness;
+	use crate::metadata::options::ReadOptions;
+	use crate::metadata::warnings::ParseWarningKind;
 
 	#[test]
 	fn
@@ -356,7 +629,335 @@ use super::Metadata;
 	{
 		let image_data = read("tests/read_sample.tif").unwrap();
 
-		Metadata::decode(&mut Cursor::new(&image_data))?;
+		Metadata::decode(&mut Cursor::new(&image_data), &ReadOptions::default())?;
+
+		Ok(())
+	}
+
+	#[test]
+	fn
+	decode_ifd0_with_nonstandard_offset()
+	-> Result<(), std::io::Error>
+	{
+		// Little endian TIFF header with IFD0 located at offset 16, leaving 
+		// 8 bytes of slack between header and IFD0
+		let mut image_data = vec![0x49, 0x49, 0x2a, 0x00, 0x10, 0x00, 0x00, 0x00];
+		image_data.extend([0xffu8; 8]);
+
+		// IFD0 with a single entry (Orientation = 6) and no link to IFD1
+		image_data.extend([0x01, 0x00]);
+		image_data.extend([0x12, 0x01, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x06, 0x00, 0x00, 0x00]);
+		image_data.extend([0x00, 0x00, 0x00, 0x00]);
+
+		let (_, ifds, _) = Metadata::decode(&mut Cursor::new(&image_data), &ReadOptions::default())?;
+
+		assert_eq!(ifds.len(), 1);
+		assert_eq!(ifds[0].get_tags(), &vec![ExifTag::Orientation(vec![6])]);
+
+		// Offsets pointing into the header are rejected
+		image_data[4] = 0x04;
+		assert!(Metadata::decode(&mut Cursor::new(&image_data), &ReadOptions::default()).is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	fn
+	decode_stops_at_max_ifd_count()
+	-> Result<(), std::io::Error>
+	{
+		// Little endian TIFF header followed by a chain of 300 empty generic
+		// IFDs, each one linking to the next
+		let chain_length = 300u32;
+		let mut image_data = vec![0x49, 0x49, 0x2a, 0x00, 0x08, 0x00, 0x00, 0x00];
+		for ifd_nr in 0..chain_length
+		{
+			let next_offset = if ifd_nr + 1 < chain_length { 8 + (ifd_nr + 1) * 6 } else { 0 };
+			image_data.extend([0x00, 0x00]);
+			image_data.extend(next_offset.to_le_bytes());
+		}
+
+		// Exceeds the default limit of 256, which is also reported by the
+		// public entry points instead of returning empty metadata
+		assert!(Metadata::decode(&mut Cursor::new(&image_data), &ReadOptions::default()).is_err());
+		let error = Metadata::new_from_vec(&image_data, FileExtension::TIFF).err().unwrap();
+		assert!(InvalidExifData::from_io_error(&error).is_some());
+
+		let options   = ReadOptions { max_ifd_count: 1000, ..ReadOptions::default() };
+		let (_, ifds, _) = Metadata::decode(&mut Cursor::new(&image_data), &options)?;
+		assert_eq!(ifds.len(), chain_length as usize);
+
+		// An IFD linking to itself results in an error instead of looping
+		let mut image_data = vec![0x49, 0x49, 0x2a, 0x00, 0x08, 0x00, 0x00, 0x00];
+		image_data.extend([0x00, 0x00, 0x08, 0x00, 0x00, 0x00]);
+		assert!(Metadata::decode(&mut Cursor::new(&image_data), &options).is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	fn
+	decode_duplicate_iso_tag()
+	-> Result<(), std::io::Error>
+	{
+		// Little endian TIFF header, IFD0 with only the offset to the ExifIFD
+		// and the ExifIFD with two ISO entries (100 and 200)
+		let mut image_data = vec![0x49, 0x49, 0x2a, 0x00, 0x08, 0x00, 0x00, 0x00];
+		image_data.extend([0x01, 0x00]);
+		image_data.extend([0x69, 0x87, 0x04, 0x00, 0x01, 0x00, 0x00, 0x00, 0x1a, 0x00, 0x00, 0x00]);
+		image_data.extend([0x00, 0x00, 0x00, 0x00]);
+		image_data.extend([0x02, 0x00]);
+		image_data.extend([0x27, 0x88, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x64, 0x00, 0x00, 0x00]);
+		image_data.extend([0x27, 0x88, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0xc8, 0x00, 0x00, 0x00]);
+		image_data.extend([0x00, 0x00, 0x00, 0x00]);
+
+		// Strict: Error that points to the second entry of the ExifIFD,
+		// which is also reported by the public entry points
+		let error = Metadata::new_from_vec(&image_data, FileExtension::TIFF).err().unwrap();
+		assert!(InvalidExifData::from_io_error(&error).is_some());
+		assert!(error.to_string().contains("0x8827"));
+		assert!(error.to_string().contains("offset 40"));
+
+		// Lenient: The last occurrence is kept and a warning is recorded
+		let options  = ReadOptions { strictness: ParseStrictness::Lenient, ..ReadOptions::default() };
+		let metadata = Metadata::new_from_vec_with_options(&image_data, FileExtension::TIFF, &options)?;
+
+		assert_eq!(metadata.get_tag(&ExifTag::ISO(Vec::new())).collect::<Vec<_>>(), vec![&ExifTag::ISO(vec![200])]);
+		assert_eq!(metadata.warnings().len(), 1);
+		assert_eq!(metadata.warnings()[0].kind, ParseWarningKind::DuplicateTag);
+		assert!(metadata.warnings()[0].message.contains("0x8827"));
+		assert!(metadata.warnings()[0].message.contains("offset 40"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn
+	decode_image_description_with_trailing_nuls()
+	-> Result<(), std::io::Error>
+	{
+		// Little endian TIFF header and IFD0 with an ImageDescription that
+		// is padded with NULs to 8 bytes
+		let mut image_data = vec![0x49, 0x49, 0x2a, 0x00, 0x08, 0x00, 0x00, 0x00];
+		image_data.extend([0x01, 0x00]);
+		image_data.extend([0x0e, 0x01, 0x02, 0x00, 0x08, 0x00, 0x00, 0x00, 0x1c, 0x00, 0x00, 0x00]);
+		image_data.extend([0x00, 0x00, 0x00, 0x00]);
+		image_data.extend([0x00, 0x00]);
+		image_data.extend(b"Hi\0\0\0\0\0\0");
+
+		// By default, all trailing NULs are trimmed...
+		let metadata = Metadata::new_from_vec(&image_data, FileExtension::TIFF)?;
+		assert_eq!(
+			metadata.get_tag(&ExifTag::ImageDescription(String::new())).next(),
+			Some(&ExifTag::ImageDescription("Hi".to_string()))
+		);
+
+		// ...but the tag is still re-encoded with its original length
+		assert!(metadata.encode()?.ends_with(&image_data[8..]));
+
+		// Unless it gets changed
+		let mut changed = metadata.clone();
+		changed.set_tag(ExifTag::ImageDescription("Hi".to_string()));
+		assert!(!changed.encode()?.ends_with(&image_data[8..]));
+
+		// Raw form that keeps the padding except for the NUL terminator
+		let options  = ReadOptions { trim_nuls: false, ..ReadOptions::default() };
+		let metadata = Metadata::new_from_vec_with_options(&image_data, FileExtension::TIFF, &options)?;
+		assert_eq!(
+			metadata.get_tag(&ExifTag::ImageDescription(String::new())).next(),
+			Some(&ExifTag::ImageDescription("Hi\0\0\0\0\0".to_string()))
+		);
+		assert!(metadata.encode()?.ends_with(&image_data[8..]));
+
+		Ok(())
+	}
+
+	#[test]
+	fn
+	decode_with_tag_allowlist()
+	-> Result<(), std::io::Error>
+	{
+		let mut metadata = Metadata::new();
+		metadata.set_tag(ExifTag::ImageDescription("Hello World".to_string()));
+		metadata.set_tag(ExifTag::Artist("Someone".to_string()));
+		metadata.set_tag(ExifTag::ISO(vec![400]));
+		metadata.set_tag(ExifTag::ExposureProgram(vec![2]));
+		metadata.set_tag(ExifTag::UnknownUNDEF(vec![0xff; 64], 0x9999, ExifTagGroup::EXIF));
+		let image_data = metadata.encode()?;
+
+		let options = ReadOptions {
+			tag_allowlist: Some(vec![(0x010e, ExifTagGroup::GENERIC), (0x8827, ExifTagGroup::EXIF)]),
+			..ReadOptions::default()
+		};
+		let metadata = Metadata::new_from_vec_with_options(&image_data, FileExtension::TIFF, &options)?;
+
+		// The ISO tag gets decoded even though the `ExifOffset` is not listed
+		assert_eq!(
+			metadata.get_ifd(ExifTagGroup::GENERIC, 0).unwrap().get_tags(),
+			&vec![ExifTag::ImageDescription("Hello World".to_string())]
+		);
+		assert_eq!(
+			metadata.get_ifd(ExifTagGroup::EXIF, 0).unwrap().get_tags(),
+			&vec![ExifTag::ISO(vec![400])]
+		);
+
+		// Without an allowlist, everything is decoded
+		let metadata = Metadata::new_from_vec(&image_data, FileExtension::TIFF)?;
+		assert_eq!(metadata.get_ifd(ExifTagGroup::EXIF, 0).unwrap().get_tags().len(), 3);
+
+		Ok(())
+	}
+
+	#[test]
+	fn
+	decode_known_tags_with_and_without_format_conversion()
+	-> Result<(), std::io::Error>
+	{
+		// Tag-rich sample: Decoding the re-encoded data yields the same tags
+		let image_data = read("tests/read_sample.tif").unwrap();
+		let (_, ifds, _) = Metadata::decode(&mut Cursor::new(&image_data), &ReadOptions::default())?;
+		assert!(ifds.iter().map(|ifd| ifd.get_tags().len()).sum::<usize>() > 10);
+
+		let metadata = Metadata::new_from_vec(&image_data, FileExtension::TIFF)?;
+		let (_, decoded_ifds, _) = Metadata::decode(&mut Cursor::new(&metadata.encode()?), &ReadOptions::default())?;
+		for ifd in ifds.iter()
+		{
+			let decoded_ifd = decoded_ifds.iter()
+				.find(|decoded_ifd| decoded_ifd.get_ifd_type() == ifd.get_ifd_type() && decoded_ifd.get_generic_ifd_nr() == ifd.get_generic_ifd_nr())
+				.unwrap();
+			for tag in ifd.get_tags().iter().filter(|tag| tag.is_writable())
+			{
+				assert!(decoded_ifd.get_tags().contains(tag), "{:?}", tag);
+			}
+		}
+
+		// IFD0 with `ImageWidth` (INT32U) stored as INT16U and `Orientation`
+		// in its expected format INT16U
+		let mut image_data = vec![0x49, 0x49, 0x2a, 0x00, 0x08, 0x00, 0x00, 0x00];
+		image_data.extend([0x02, 0x00]);
+		image_data.extend([0x00, 0x01, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x40, 0x1f, 0x00, 0x00]);
+		image_data.extend([0x12, 0x01, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x06, 0x00, 0x00, 0x00]);
+		image_data.extend([0x00, 0x00, 0x00, 0x00]);
+
+		let (_, ifds, _) = Metadata::decode(&mut Cursor::new(&image_data), &ReadOptions::default())?;
+		assert_eq!(ifds[0].get_tags(), &vec![ExifTag::ImageWidth(vec![8000]), ExifTag::Orientation(vec![6])]);
+
+		Ok(())
+	}
+
+	#[ignore]
+	#[test]
+	fn
+	decode_tag_rich_data_repeatedly()
+	-> Result<(), std::io::Error>
+	{
+		// Long-running stress test, run explicitly with `--ignored`
+		let image_data = read("tests/read_sample.tif").unwrap();
+		let (_, expected_ifds, _) = Metadata::decode(&mut Cursor::new(&image_data), &ReadOptions::default())?;
+
+		for _ in 0..200
+		{
+			let (_, ifds, _) = Metadata::decode(&mut Cursor::new(&image_data), &ReadOptions::default())?;
+			assert_eq!(ifds.len(), expected_ifds.len());
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn
+	big_tiff_is_unsupported()
+	{
+		use crate::metadata::error::UnsupportedFormat;
+
+		// BigTIFF header: Version 43, offset size 8, reserved, IFD0 offset
+		let mut image_data = vec![0x49, 0x49, 0x2b, 0x00, 0x08, 0x00, 0x00, 0x00];
+		image_data.extend(16u64.to_le_bytes());
+		image_data.extend([0u8; 16]);
+
+		let error = Metadata::new_from_vec(&image_data, FileExtension::TIFF).err().unwrap();
+		assert_eq!(error.kind(), std::io::ErrorKind::Unsupported);
+		assert_eq!(UnsupportedFormat::from_io_error(&error), Some(UnsupportedFormat { format: "BigTIFF" }));
+
+		let mut big_endian_data = vec![0x4d, 0x4d, 0x00, 0x2b, 0x00, 0x08, 0x00, 0x00];
+		big_endian_data.extend(16u64.to_be_bytes());
+		big_endian_data.extend([0u8; 16]);
+		assert!(UnsupportedFormat::from_io_error(&Metadata::new_from_vec(&big_endian_data, FileExtension::TIFF).err().unwrap()).is_some());
+
+		// Writing must not replace the BigTIFF data with classic TIFF data
+		let mut written_data = image_data.clone();
+		assert!(Metadata::new().write_to_vec(&mut written_data, FileExtension::TIFF).is_err());
+		assert_eq!(written_data, image_data);
+	}
+
+	#[test]
+	fn
+	encode_skips_empty_ifds()
+	-> Result<(), std::io::Error>
+	{
+		let mut metadata = Metadata::new();
+		metadata.set_tag(ExifTag::ImageDescription("Hello World".to_string()));
+
+		// Empty GPS SubIFD as well as an ExifOffset tag pointing to an EXIF
+		// SubIFD that does not exist
+		metadata.get_ifd_mut(ExifTagGroup::GPS, 0);
+		metadata.get_ifd_mut(ExifTagGroup::GENERIC, 0).set_tag(ExifTag::ExifOffset(vec![0]));
+
+		let (_, ifds, _) = Metadata::decode(&mut Cursor::new(&metadata.encode()?), &ReadOptions::default())?;
+		assert_eq!(ifds.len(), 1);
+		assert_eq!(ifds[0].get_tags(), &vec![ExifTag::ImageDescription("Hello World".to_string())]);
+
+		// The struct itself is not changed by encoding it
+		assert!(metadata.get_ifd(ExifTagGroup::GPS, 0).is_some());
+
+		Ok(())
+	}
+
+	#[test]
+	fn
+	encode_keeps_empty_ifds()
+	-> Result<(), std::io::Error>
+	{
+		let mut metadata = Metadata::new();
+		metadata.set_keep_empty_ifds(true);
+		metadata.set_tag(ExifTag::ImageDescription("Hello World".to_string()));
+		metadata.get_ifd_mut(ExifTagGroup::GPS, 0);
+		metadata.get_ifd_mut(ExifTagGroup::GENERIC, 0).set_tag(ExifTag::ExifOffset(vec![0]));
+
+		// Both the GPS and the EXIF SubIFD are encoded without any entries
+		let (_, ifds, _) = Metadata::decode(&mut Cursor::new(&metadata.encode()?), &ReadOptions::default())?;
+		assert_eq!(ifds.len(), 3);
+		assert!(ifds.iter().any(|ifd| ifd.get_ifd_type() == ExifTagGroup::EXIF && ifd.get_tags().is_empty()));
+		assert!(ifds.iter().any(|ifd| ifd.get_ifd_type() == ExifTagGroup::GPS  && ifd.get_tags().is_empty()));
+
+		Ok(())
+	}
+
+	#[test]
+	fn
+	encode_with_custom_ifd0_offset()
+	-> Result<(), std::io::Error>
+	{
+		let mut metadata = Metadata::new();
+		metadata.set_tag(ExifTag::ImageDescription("Hello World!".to_string()));
+		metadata.set_tag(ExifTag::ISO(vec![2706]));
+
+		let encoded = metadata.encode_with_ifd0_offset(64)?;
+		assert_eq!(encoded[4..8],   [0x40, 0x00, 0x00, 0x00]);
+		assert_eq!(encoded[8..64],  [0x00; 56]);
+		assert_eq!(encoded[64..66], [0x02, 0x00]);
+
+		let decoded = Metadata::new_from_vec(&encoded, FileExtension::TIFF)?;
+		assert_eq!(
+			decoded.get_tag(&ExifTag::ImageDescription(String::new())).next(),
+			Some(&ExifTag::ImageDescription("Hello World!".to_string()))
+		);
+		assert_eq!(decoded.get_tag(&ExifTag::ISO(Vec::new())).next(), Some(&ExifTag::ISO(vec![2706])));
+
+		// The default stays directly after the header
+		assert_eq!(metadata.encode()?, metadata.encode_with_ifd0_offset(8)?);
+		assert!(metadata.encode_with_ifd0_offset(4 ).is_err());
+		assert!(metadata.encode_with_ifd0_offset(63).is_err());
 
 		Ok(())
 	}
@@ -370,7 +971,7 @@ use super::Metadata;
 		// let image_data = read("tests/multi_page.tif").unwrap();
 		let image_data = read("tests/multi_page_mod.tif").unwrap();
 
-		let data = Metadata::decode(&mut Cursor::new(&image_data))?;
+		let data = Metadata::decode(&mut Cursor::new(&image_data), &ReadOptions::default())?;
 
 		for ifd in data.1
 		{
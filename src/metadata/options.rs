@@ -0,0 +1,161 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::ifd::ExifTagGroup;
+
+/// The default value for `ReadOptions::max_ifd_count`
+pub const DEFAULT_MAX_IFD_COUNT: usize = 256;
+
+/// Determines how strict little_exif is when encountering issues in the
+/// image file while reading its metadata
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum
+ParseStrictness
+{
+	/// Any issue results in an error
+	#[default]
+	Strict,
+
+	/// Issues that don't affect the metadata itself (e.g. a CRC mismatch in
+	/// an ancillary PNG chunk) are reported as warning and reading continues
+	Lenient,
+}
+
+/// Options for reading metadata via `Metadata::new_from_vec_with_options`
+/// and `Metadata::new_from_path_with_options`.
+///
+/// # Examples
+/// ```no_run
+/// # #[cfg(feature = "std_fs")]
+/// # {
+/// use little_exif::metadata::Metadata;
+/// use little_exif::metadata::options::ParseStrictness;
+/// use little_exif::metadata::options::ReadOptions;
+///
+/// let options  = ReadOptions { strictness: ParseStrictness::Lenient, ..ReadOptions::default() };
+/// let metadata = Metadata::new_from_path_with_options(std::path::Path::new("image.png"), &options).unwrap();
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct
+ReadOptions
+{
+	pub strictness:      ParseStrictness,
+
+	/// The maximum total number of IFDs (generic ones and SubIFDs) that get
+	/// decoded before an error is returned. Bounds the work done for 
+	/// untrusted input, e.g. with long chains of IFDs.
+	pub max_ifd_count:   usize,
+
+	/// Remove all trailing NULs from the values of `STRING` tags (default).
+	/// If false, only the NUL terminator gets removed and any additional
+	/// padding is kept as part of the value. Either way, the tags are
+	/// re-encoded with their original length if they are not changed.
+	pub trim_nuls:       bool,
+
+	/// If set, only the tags with the listed hex values and groups are
+	/// decoded, all others are skipped without reading their data. The IFD
+	/// offset tags (e.g. `ExifOffset`) are always followed, so tags in the
+	/// SubIFDs can be listed as well. Note that the data offset tags have
+	/// to be listed in pairs (e.g. `StripOffsets` and `StripByteCounts`).
+	pub tag_allowlist:   Option<Vec<(u16, ExifTagGroup)>>,
+
+	/// Keep the raw EXIF data in the `Metadata` struct after decoding, so
+	/// that `STRING` and `UNDEF` tags can be accessed without copying them,
+	/// see `Metadata::borrowed_tags`
+	pub retain_raw_data: bool,
+}
+
+impl Default for ReadOptions
+{
+	fn
+	default
+	()
+	-> ReadOptions
+	{
+		ReadOptions {
+			strictness:      ParseStrictness::default(),
+			max_ifd_count:   DEFAULT_MAX_IFD_COUNT,
+			trim_nuls:       true,
+			tag_allowlist:   None,
+			retain_raw_data: false,
+		}
+	}
+}
+
+impl
+ReadOptions
+{
+	/// Creates the default options, which are also used by `new_from_vec`
+	/// and `new_from_path`
+	pub fn
+	new
+	()
+	-> ReadOptions
+	{
+		ReadOptions::default()
+	}
+
+	/// Checks if the tag with the given hex value and group gets decoded,
+	/// see `tag_allowlist`
+	pub(crate) fn
+	is_tag_allowed
+	(
+		&self,
+		hex_tag: u16,
+		group:   &ExifTagGroup
+	)
+	-> bool
+	{
+		match &self.tag_allowlist
+		{
+			Some(allowlist) => allowlist.contains(&(hex_tag, *group)),
+			None            => true,
+		}
+	}
+}
+
+/// Options for writing metadata via `Metadata::write_to_file_with_options`
+#[derive(Clone, Debug, PartialEq)]
+pub struct
+WriteOptions
+{
+	/// Compute the final file in memory first and resize the file on disk
+	/// only once to its target size before writing, instead of letting it
+	/// grow and shrink while the metadata gets replaced. This reduces 
+	/// fragmentation when writing many files, at the cost of holding the 
+	/// whole file in memory.
+	pub preallocate:       bool,
+
+	/// Store the EXIF data of PNGs compressed in a zTXt chunk (default). If
+	/// false, an uncompressed tEXt chunk is used instead, just like for
+	/// `FileExtension::PNG { as_zTXt_chunk: false }` with `write_to_vec`.
+	pub compress_png_exif: bool,
+}
+
+impl Default for WriteOptions
+{
+	fn
+	default
+	()
+	-> WriteOptions
+	{
+		WriteOptions {
+			preallocate:       false,
+			compress_png_exif: true,
+		}
+	}
+}
+
+impl
+WriteOptions
+{
+	/// Creates the default options, which are also used by `write_to_file`
+	pub fn
+	new
+	()
+	-> WriteOptions
+	{
+		WriteOptions::default()
+	}
+}
@@ -0,0 +1,226 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::exif_tag::ExifTag;
+
+use super::Metadata;
+
+/// The eight values of the `Orientation` tag (0x0112), describing how the
+/// stored image needs to be transformed for displaying it correctly
+/// See https://exiftool.org/TagNames/EXIF.html (0x0112)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+Orientation
+{
+	Normal,                                                                     // 1
+	FlipHorizontal,                                                             // 2
+	Rotate180,                                                                  // 3
+	FlipVertical,                                                               // 4
+	Transpose,                                                                  // 5: Flip horizontal & rotate 270 CW
+	Rotate90,                                                                   // 6: Rotate 90 CW
+	Transverse,                                                                 // 7: Flip horizontal & rotate 90 CW
+	Rotate270,                                                                  // 8: Rotate 270 CW
+}
+
+impl
+Orientation
+{
+	/// Gets the orientation for a value of the `Orientation` tag, if valid
+	pub fn
+	from_u16
+	(
+		value: u16
+	)
+	-> Option<Orientation>
+	{
+		match value
+		{
+			1 => Some(Orientation::Normal),
+			2 => Some(Orientation::FlipHorizontal),
+			3 => Some(Orientation::Rotate180),
+			4 => Some(Orientation::FlipVertical),
+			5 => Some(Orientation::Transpose),
+			6 => Some(Orientation::Rotate90),
+			7 => Some(Orientation::Transverse),
+			8 => Some(Orientation::Rotate270),
+			_ => None,
+		}
+	}
+
+	/// Gets the value as stored in the `Orientation` tag
+	pub fn
+	as_u16
+	(
+		&self
+	)
+	-> u16
+	{
+		*self as u16 + 1
+	}
+
+	/// Checks if displaying the image swaps its width and height
+	pub fn
+	swaps_dimensions
+	(
+		&self
+	)
+	-> bool
+	{
+		match self
+		{
+			Orientation::Transpose
+			| Orientation::Rotate90
+			| Orientation::Transverse
+			| Orientation::Rotate270
+			=> true,
+
+			_
+			=> false,
+		}
+	}
+
+	/// Gets the dimensions (width, height) of the displayed image for the
+	/// given dimensions of the stored image, see `swaps_dimensions`
+	///
+	/// # Examples
+	/// ```
+	/// use little_exif::metadata::orientation::Orientation;
+	///
+	/// assert_eq!(Orientation::Rotate90.applied_dimensions(4000, 3000),  (3000, 4000));
+	/// assert_eq!(Orientation::Rotate180.applied_dimensions(4000, 3000), (4000, 3000));
+	/// ```
+	pub fn
+	applied_dimensions
+	(
+		&self,
+		width:  u32,
+		height: u32
+	)
+	-> (u32, u32)
+	{
+		if self.swaps_dimensions()
+		{
+			return (height, width);
+		}
+		return (width, height);
+	}
+
+	/// Gets the affine transform `[a, b, c, d, e, f]` that maps a point of
+	/// the stored image to the displayed image:
+	/// ```text
+	/// x' = a * x + b * y + c
+	/// y' = d * x + e * y + f
+	/// ```
+	/// Coordinates are normalized, i.e. (0, 0) is the top left and (1, 1)
+	/// the bottom right corner of the respective image. To get pixel
+	/// coordinates, scale the input by the stored and the output by the
+	/// displayed dimensions (see `swaps_dimensions`).
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::orientation::Orientation;
+	///
+	/// // The top left corner of the stored image ends up at the top right
+	/// let [a, b, c, d, e, f] = Orientation::Rotate90.transform();
+	/// assert_eq!((a * 0.0 + b * 0.0 + c, d * 0.0 + e * 0.0 + f), (1.0, 0.0));
+	/// ```
+	pub fn
+	transform
+	(
+		&self
+	)
+	-> [f32; 6]
+	{
+		match self
+		{
+			Orientation::Normal         => [ 1.0,  0.0, 0.0,    0.0,  1.0, 0.0],
+			Orientation::FlipHorizontal => [-1.0,  0.0, 1.0,    0.0,  1.0, 0.0],
+			Orientation::Rotate180      => [-1.0,  0.0, 1.0,    0.0, -1.0, 1.0],
+			Orientation::FlipVertical   => [ 1.0,  0.0, 0.0,    0.0, -1.0, 1.0],
+			Orientation::Transpose      => [ 0.0,  1.0, 0.0,    1.0,  0.0, 0.0],
+			Orientation::Rotate90       => [ 0.0, -1.0, 1.0,    1.0,  0.0, 0.0],
+			Orientation::Transverse     => [ 0.0, -1.0, 1.0,   -1.0,  0.0, 1.0],
+			Orientation::Rotate270      => [ 0.0,  1.0, 0.0,   -1.0,  0.0, 1.0],
+		}
+	}
+}
+
+impl
+Metadata
+{
+	/// Gets the decoded `Orientation` tag, if present and valid.
+	pub fn
+	get_orientation
+	(
+		&self
+	)
+	-> Option<Orientation>
+	{
+		if let Some(ExifTag::Orientation(value)) = self.get_tag(&ExifTag::Orientation(Vec::new())).next()
+		{
+			return value.first().and_then(|raw_value| Orientation::from_u16(*raw_value));
+		}
+		return None;
+	}
+
+	/// Sets the `Orientation` tag using the typed representation
+	pub fn
+	set_orientation
+	(
+		&mut self,
+		orientation: Orientation
+	)
+	{
+		self.set_tag(ExifTag::Orientation(vec![orientation.as_u16()]));
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::Orientation;
+
+	fn
+	apply
+	(
+		orientation: Orientation,
+		point:       (f32, f32)
+	)
+	-> (f32, f32)
+	{
+		let [a, b, c, d, e, f] = orientation.transform();
+		return (a * point.0 + b * point.1 + c, d * point.0 + e * point.1 + f);
+	}
+
+	#[test]
+	fn
+	transform_rotate_90()
+	{
+		// Rotating clockwise: top left -> top right -> bottom right
+		assert_eq!(apply(Orientation::Rotate90, (0.0, 0.0)), (1.0, 0.0));
+		assert_eq!(apply(Orientation::Rotate90, (1.0, 0.0)), (1.0, 1.0));
+		assert_eq!(apply(Orientation::Rotate90, (0.0, 1.0)), (0.0, 0.0));
+		assert!(Orientation::Rotate90.swaps_dimensions());
+	}
+
+	#[test]
+	fn
+	transform_flip_horizontal()
+	{
+		assert_eq!(apply(Orientation::FlipHorizontal, (0.0,  0.0)), (1.0,  0.0));
+		assert_eq!(apply(Orientation::FlipHorizontal, (0.25, 1.0)), (0.75, 1.0));
+		assert!(!Orientation::FlipHorizontal.swaps_dimensions());
+	}
+
+	#[test]
+	fn
+	round_trip_tag_values()
+	{
+		for value in 1..=8
+		{
+			assert_eq!(Orientation::from_u16(value).unwrap().as_u16(), value);
+		}
+		assert_eq!(Orientation::from_u16(0), None);
+		assert_eq!(Orientation::from_u16(9), None);
+	}
+}
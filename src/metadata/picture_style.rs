@@ -0,0 +1,511 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+// The "picture style" tags of the ExifIFD, i.e. the settings the camera
+// used for processing the image, like the scene mode or the contrast
+
+use crate::exif_tag::ExifTag;
+
+use super::Metadata;
+
+/// The values of the `SceneCaptureType` tag (0xa406), describing the type of
+/// scene that was shot
+/// See https://exiftool.org/TagNames/EXIF.html (0xa406)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+SceneCaptureType
+{
+	Standard,                                                                   // 0
+	Landscape,                                                                  // 1
+	Portrait,                                                                   // 2
+	Night,                                                                      // 3
+	Other(u16),                                                                 // Not part of the EXIF standard
+}
+
+impl
+SceneCaptureType
+{
+	/// Gets the scene type for a value of the `SceneCaptureType` tag
+	pub fn
+	from_u16
+	(
+		value: u16
+	)
+	-> SceneCaptureType
+	{
+		match value
+		{
+			0 => SceneCaptureType::Standard,
+			1 => SceneCaptureType::Landscape,
+			2 => SceneCaptureType::Portrait,
+			3 => SceneCaptureType::Night,
+			_ => SceneCaptureType::Other(value),
+		}
+	}
+
+	/// Gets the value as stored in the `SceneCaptureType` tag
+	pub fn
+	as_u16
+	(
+		&self
+	)
+	-> u16
+	{
+		match self
+		{
+			SceneCaptureType::Standard     => 0,
+			SceneCaptureType::Landscape    => 1,
+			SceneCaptureType::Portrait     => 2,
+			SceneCaptureType::Night        => 3,
+			SceneCaptureType::Other(value) => *value,
+		}
+	}
+}
+
+/// The values of the `GainControl` tag (0xa407), describing the degree of
+/// overall image gain adjustment
+/// See https://exiftool.org/TagNames/EXIF.html (0xa407)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+GainControl
+{
+	NoGain,                                                                     // 0
+	LowGainUp,                                                                  // 1
+	HighGainUp,                                                                 // 2
+	LowGainDown,                                                                // 3
+	HighGainDown,                                                               // 4
+	Other(u16),                                                                 // Not part of the EXIF standard
+}
+
+impl
+GainControl
+{
+	/// Gets the gain control for a value of the `GainControl` tag
+	pub fn
+	from_u16
+	(
+		value: u16
+	)
+	-> GainControl
+	{
+		match value
+		{
+			0 => GainControl::NoGain,
+			1 => GainControl::LowGainUp,
+			2 => GainControl::HighGainUp,
+			3 => GainControl::LowGainDown,
+			4 => GainControl::HighGainDown,
+			_ => GainControl::Other(value),
+		}
+	}
+
+	/// Gets the value as stored in the `GainControl` tag
+	pub fn
+	as_u16
+	(
+		&self
+	)
+	-> u16
+	{
+		match self
+		{
+			GainControl::NoGain       => 0,
+			GainControl::LowGainUp    => 1,
+			GainControl::HighGainUp   => 2,
+			GainControl::LowGainDown  => 3,
+			GainControl::HighGainDown => 4,
+			GainControl::Other(value) => *value,
+		}
+	}
+}
+
+/// The values of the `Contrast` tag (0xa408), describing the direction of
+/// the contrast processing applied by the camera
+/// See https://exiftool.org/TagNames/EXIF.html (0xa408)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+Contrast
+{
+	Normal,                                                                     // 0
+	Soft,                                                                       // 1
+	Hard,                                                                       // 2
+	Other(u16),                                                                 // Not part of the EXIF standard
+}
+
+impl
+Contrast
+{
+	/// Gets the contrast for a value of the `Contrast` tag
+	pub fn
+	from_u16
+	(
+		value: u16
+	)
+	-> Contrast
+	{
+		match value
+		{
+			0 => Contrast::Normal,
+			1 => Contrast::Soft,
+			2 => Contrast::Hard,
+			_ => Contrast::Other(value),
+		}
+	}
+
+	/// Gets the value as stored in the `Contrast` tag
+	pub fn
+	as_u16
+	(
+		&self
+	)
+	-> u16
+	{
+		match self
+		{
+			Contrast::Normal       => 0,
+			Contrast::Soft         => 1,
+			Contrast::Hard         => 2,
+			Contrast::Other(value) => *value,
+		}
+	}
+}
+
+/// The values of the `Saturation` tag (0xa409), describing the direction
+/// of the saturation processing applied by the camera
+/// See https://exiftool.org/TagNames/EXIF.html (0xa409)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+Saturation
+{
+	Normal,                                                                     // 0
+	Low,                                                                        // 1
+	High,                                                                       // 2
+	Other(u16),                                                                 // Not part of the EXIF standard
+}
+
+impl
+Saturation
+{
+	/// Gets the saturation for a value of the `Saturation` tag
+	pub fn
+	from_u16
+	(
+		value: u16
+	)
+	-> Saturation
+	{
+		match value
+		{
+			0 => Saturation::Normal,
+			1 => Saturation::Low,
+			2 => Saturation::High,
+			_ => Saturation::Other(value),
+		}
+	}
+
+	/// Gets the value as stored in the `Saturation` tag
+	pub fn
+	as_u16
+	(
+		&self
+	)
+	-> u16
+	{
+		match self
+		{
+			Saturation::Normal       => 0,
+			Saturation::Low          => 1,
+			Saturation::High         => 2,
+			Saturation::Other(value) => *value,
+		}
+	}
+}
+
+/// The values of the `Sharpness` tag (0xa40a), describing the direction of
+/// the sharpness processing applied by the camera
+/// See https://exiftool.org/TagNames/EXIF.html (0xa40a)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+Sharpness
+{
+	Normal,                                                                     // 0
+	Soft,                                                                       // 1
+	Hard,                                                                       // 2
+	Other(u16),                                                                 // Not part of the EXIF standard
+}
+
+impl
+Sharpness
+{
+	/// Gets the sharpness for a value of the `Sharpness` tag
+	pub fn
+	from_u16
+	(
+		value: u16
+	)
+	-> Sharpness
+	{
+		match value
+		{
+			0 => Sharpness::Normal,
+			1 => Sharpness::Soft,
+			2 => Sharpness::Hard,
+			_ => Sharpness::Other(value),
+		}
+	}
+
+	/// Gets the value as stored in the `Sharpness` tag
+	pub fn
+	as_u16
+	(
+		&self
+	)
+	-> u16
+	{
+		match self
+		{
+			Sharpness::Normal       => 0,
+			Sharpness::Soft         => 1,
+			Sharpness::Hard         => 2,
+			Sharpness::Other(value) => *value,
+		}
+	}
+}
+
+impl
+Metadata
+{
+	/// Gets the decoded `SceneCaptureType` tag, if present.
+	pub fn
+	get_scene_capture_type
+	(
+		&self
+	)
+	-> Option<SceneCaptureType>
+	{
+		if let Some(ExifTag::SceneCaptureType(value)) = self.get_tag(&ExifTag::SceneCaptureType(Vec::new())).next()
+		{
+			return value.first().map(|raw_value| SceneCaptureType::from_u16(*raw_value));
+		}
+		return None;
+	}
+
+	/// Sets the `SceneCaptureType` tag using the typed representation
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::metadata::picture_style::SceneCaptureType;
+	///
+	/// let mut metadata = Metadata::new();
+	/// metadata.set_scene_capture_type(SceneCaptureType::Portrait);
+	/// assert_eq!(metadata.get_scene_capture_type(), Some(SceneCaptureType::Portrait));
+	/// ```
+	pub fn
+	set_scene_capture_type
+	(
+		&mut self,
+		scene_capture_type: SceneCaptureType
+	)
+	{
+		self.set_tag(ExifTag::SceneCaptureType(vec![scene_capture_type.as_u16()]));
+	}
+
+	/// Gets the decoded `GainControl` tag, if present.
+	pub fn
+	get_gain_control
+	(
+		&self
+	)
+	-> Option<GainControl>
+	{
+		if let Some(ExifTag::GainControl(value)) = self.get_tag(&ExifTag::GainControl(Vec::new())).next()
+		{
+			return value.first().map(|raw_value| GainControl::from_u16(*raw_value));
+		}
+		return None;
+	}
+
+	/// Sets the `GainControl` tag using the typed representation
+	pub fn
+	set_gain_control
+	(
+		&mut self,
+		gain_control: GainControl
+	)
+	{
+		self.set_tag(ExifTag::GainControl(vec![gain_control.as_u16()]));
+	}
+
+	/// Gets the decoded `Contrast` tag, if present.
+	pub fn
+	get_contrast
+	(
+		&self
+	)
+	-> Option<Contrast>
+	{
+		if let Some(ExifTag::Contrast(value)) = self.get_tag(&ExifTag::Contrast(Vec::new())).next()
+		{
+			return value.first().map(|raw_value| Contrast::from_u16(*raw_value));
+		}
+		return None;
+	}
+
+	/// Sets the `Contrast` tag using the typed representation
+	pub fn
+	set_contrast
+	(
+		&mut self,
+		contrast: Contrast
+	)
+	{
+		self.set_tag(ExifTag::Contrast(vec![contrast.as_u16()]));
+	}
+
+	/// Gets the decoded `Saturation` tag, if present.
+	pub fn
+	get_saturation
+	(
+		&self
+	)
+	-> Option<Saturation>
+	{
+		if let Some(ExifTag::Saturation(value)) = self.get_tag(&ExifTag::Saturation(Vec::new())).next()
+		{
+			return value.first().map(|raw_value| Saturation::from_u16(*raw_value));
+		}
+		return None;
+	}
+
+	/// Sets the `Saturation` tag using the typed representation
+	pub fn
+	set_saturation
+	(
+		&mut self,
+		saturation: Saturation
+	)
+	{
+		self.set_tag(ExifTag::Saturation(vec![saturation.as_u16()]));
+	}
+
+	/// Gets the decoded `Sharpness` tag, if present.
+	pub fn
+	get_sharpness
+	(
+		&self
+	)
+	-> Option<Sharpness>
+	{
+		if let Some(ExifTag::Sharpness(value)) = self.get_tag(&ExifTag::Sharpness(Vec::new())).next()
+		{
+			return value.first().map(|raw_value| Sharpness::from_u16(*raw_value));
+		}
+		return None;
+	}
+
+	/// Sets the `Sharpness` tag using the typed representation
+	pub fn
+	set_sharpness
+	(
+		&mut self,
+		sharpness: Sharpness
+	)
+	{
+		self.set_tag(ExifTag::Sharpness(vec![sharpness.as_u16()]));
+	}
+
+	/// Gets the gamma value of the image stored in the `Gamma` tag (0xa500),
+	/// e.g. 2.2 for sRGB-like color rendering
+	pub fn
+	get_gamma
+	(
+		&self
+	)
+	-> Option<f64>
+	{
+		if let Some(ExifTag::Gamma(value)) = self.get_tag(&ExifTag::Gamma(Vec::new())).next()
+		{
+			return value.first()
+				.filter(|gamma| gamma.denominator != 0)
+				.map(|gamma| gamma.clone().into());
+		}
+		return None;
+	}
+
+	/// Sets the `Gamma` tag. The value is stored as the closest fraction,
+	/// so common values like 2.2 are stored exactly (as 11/5).
+	pub fn
+	set_gamma
+	(
+		&mut self,
+		gamma: f64
+	)
+	{
+		self.set_tag(ExifTag::Gamma(vec![gamma.into()]));
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use crate::exif_tag::ExifTag;
+	use crate::filetype::FileExtension;
+	use crate::metadata::Metadata;
+	use crate::rational::uR64;
+
+	use super::Contrast;
+	use super::GainControl;
+	use super::Saturation;
+	use super::SceneCaptureType;
+	use super::Sharpness;
+
+	#[test]
+	fn
+	round_trip_values()
+	{
+		for value in 0..=300
+		{
+			assert_eq!(SceneCaptureType::from_u16(value).as_u16(), value);
+			assert_eq!(GainControl::from_u16(value).as_u16(),      value);
+			assert_eq!(Contrast::from_u16(value).as_u16(),         value);
+			assert_eq!(Saturation::from_u16(value).as_u16(),       value);
+			assert_eq!(Sharpness::from_u16(value).as_u16(),        value);
+		}
+
+		assert_eq!(SceneCaptureType::from_u16(4), SceneCaptureType::Other(4));
+	}
+
+	#[test]
+	fn
+	portrait_and_hard_contrast_round_trip()
+	{
+		let mut metadata = Metadata::new();
+		assert_eq!(metadata.get_scene_capture_type(), None);
+
+		metadata.set_scene_capture_type(SceneCaptureType::Portrait);
+		metadata.set_contrast(Contrast::Hard);
+
+		let metadata = Metadata::new_from_vec(&metadata.encode().unwrap(), FileExtension::TIFF).unwrap();
+		assert_eq!(metadata.get_scene_capture_type(), Some(SceneCaptureType::Portrait));
+		assert_eq!(metadata.get_contrast(),           Some(Contrast::Hard));
+		assert_eq!(metadata.get_sharpness(),          None);
+	}
+
+	#[test]
+	fn
+	gamma_round_trip()
+	{
+		let mut metadata = Metadata::new();
+		assert_eq!(metadata.get_gamma(), None);
+
+		metadata.set_gamma(2.2);
+		assert_eq!(
+			metadata.get_tag(&ExifTag::Gamma(Vec::new())).next(),
+			Some(&ExifTag::Gamma(vec![uR64 { nominator: 11, denominator: 5 }]))
+		);
+
+		let metadata = Metadata::new_from_vec(&metadata.encode().unwrap(), FileExtension::TIFF).unwrap();
+		assert!((metadata.get_gamma().unwrap() - 2.2).abs() < 1e-9);
+	}
+}
@@ -0,0 +1,271 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::endian::Endian;
+use crate::exif_tag::ExifTag;
+use crate::general_file_io::io_error;
+use crate::ifd::ExifTagGroup;
+
+use super::Metadata;
+
+/// The predictor applied to the image data before LZW or Deflate
+/// compression, stored in the `Predictor` tag (0x013d).
+/// See https://exiftool.org/TagNames/EXIF.html (0x013d)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+Predictor
+{
+	None,                                                                       // 1
+	HorizontalDifferencing,                                                     // 2
+	FloatingPoint,                                                              // 3
+	Other(u16),
+}
+
+impl
+Predictor
+{
+	pub fn
+	from_u16
+	(
+		value: u16
+	)
+	-> Predictor
+	{
+		match value
+		{
+			1 => Predictor::None,
+			2 => Predictor::HorizontalDifferencing,
+			3 => Predictor::FloatingPoint,
+			_ => Predictor::Other(value),
+		}
+	}
+
+	pub fn
+	as_u16
+	(
+		&self
+	)
+	-> u16
+	{
+		match self
+		{
+			Predictor::None                   => 1,
+			Predictor::HorizontalDifferencing => 2,
+			Predictor::FloatingPoint          => 3,
+			Predictor::Other(value)           => *value,
+		}
+	}
+
+	/// Reverses the predictor on a strip (or tile) of image data after it
+	/// has been decompressed, restoring the original samples in place.
+	/// The strip has to consist of complete rows of `width` pixels with
+	/// `samples_per_pixel` samples of `bits_per_sample` bits each (8, 16 or
+	/// 32, as well as 64 for floating point data). Multi-byte samples are
+	/// read and - for floating point data - written in the given byte order.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::endian::Endian;
+	/// use little_exif::metadata::predictor::Predictor;
+	///
+	/// let mut strip = vec![10, 1, 1, 1];
+	/// Predictor::HorizontalDifferencing.decode_strip(&mut strip, 4, 1, 8, &Endian::Little).unwrap();
+	/// assert_eq!(strip, vec![10, 11, 12, 13]);
+	/// ```
+	pub fn
+	decode_strip
+	(
+		&self,
+		data:              &mut [u8],
+		width:             usize,
+		samples_per_pixel: usize,
+		bits_per_sample:   u16,
+		endian:            &Endian
+	)
+	-> Result<(), std::io::Error>
+	{
+		let bytes_per_sample = match (self, bits_per_sample)
+		{
+			(Predictor::None,                   _ ) => return Ok(()),
+			(Predictor::HorizontalDifferencing, 8 ) => 1,
+			(Predictor::HorizontalDifferencing, 16) => 2,
+			(Predictor::HorizontalDifferencing, 32) => 4,
+			(Predictor::FloatingPoint,          16) => 2,
+			(Predictor::FloatingPoint,          32) => 4,
+			(Predictor::FloatingPoint,          64) => 8,
+			_ => return io_error!(
+				Unsupported,
+				format!("Can't reverse predictor {} for {} bits per sample", self.as_u16(), bits_per_sample)
+			),
+		};
+
+		let samples_per_row = width * samples_per_pixel;
+		let row_length      = samples_per_row * bytes_per_sample;
+		if row_length == 0 || data.len() % row_length != 0
+		{
+			return io_error!(
+				InvalidInput,
+				format!("Strip of {} bytes does not consist of complete rows of {} bytes", data.len(), row_length)
+			);
+		}
+
+		for row in data.chunks_exact_mut(row_length)
+		{
+			if *self == Predictor::FloatingPoint
+			{
+				// The bytes of the samples are stored in separate planes (most
+				// significant byte first) and differenced byte by byte
+				for index in samples_per_pixel..row.len()
+				{
+					row[index] = row[index].wrapping_add(row[index - samples_per_pixel]);
+				}
+
+				let planes = row.to_vec();
+				for sample in 0..samples_per_row
+				{
+					for byte in 0..bytes_per_sample
+					{
+						let plane = match endian
+						{
+							Endian::Big    => byte,
+							Endian::Little => bytes_per_sample - byte - 1,
+						};
+						row[sample * bytes_per_sample + byte] = planes[plane * samples_per_row + sample];
+					}
+				}
+				continue;
+			}
+
+			for index in samples_per_pixel..samples_per_row
+			{
+				let previous = read_sample(row, (index - samples_per_pixel) * bytes_per_sample, bytes_per_sample, endian);
+				let current  = read_sample(row,  index                      * bytes_per_sample, bytes_per_sample, endian);
+				write_sample(row, index * bytes_per_sample, bytes_per_sample, current.wrapping_add(previous), endian);
+			}
+		}
+
+		return Ok(());
+	}
+}
+
+/// Reads an unsigned sample of up to 4 bytes
+fn
+read_sample
+(
+	row:              &[u8],
+	position:         usize,
+	bytes_per_sample: usize,
+	endian:           &Endian
+)
+-> u32
+{
+	let bytes = &row[position..position + bytes_per_sample];
+	let fold  = |value: u32, byte: &u8| value << 8 | *byte as u32;
+	match endian
+	{
+		Endian::Big    => bytes.iter().fold(0, fold),
+		Endian::Little => bytes.iter().rev().fold(0, fold),
+	}
+}
+
+/// Writes an unsigned sample of up to 4 bytes, truncating the value
+fn
+write_sample
+(
+	row:              &mut [u8],
+	position:         usize,
+	bytes_per_sample: usize,
+	value:            u32,
+	endian:           &Endian
+)
+{
+	for byte in 0..bytes_per_sample
+	{
+		let shift = match endian
+		{
+			Endian::Big    => 8 * (bytes_per_sample - byte - 1),
+			Endian::Little => 8 * byte,
+		};
+		row[position + byte] = (value >> shift) as u8;
+	}
+}
+
+impl
+Metadata
+{
+	/// Gets the decoded `Predictor` tag of the generic IFD with the given
+	/// number, if present. Note that the TIFF specification defaults to
+	/// `Predictor::None` if the tag does not exist.
+	pub fn
+	get_predictor
+	(
+		&self,
+		generic_ifd_nr: u32
+	)
+	-> Option<Predictor>
+	{
+		let ifd = self.get_ifd(ExifTagGroup::GENERIC, generic_ifd_nr)?;
+		for tag in ifd.get_tags()
+		{
+			if let ExifTag::Predictor(value) = tag
+			{
+				return value.first().map(|raw_value| Predictor::from_u16(*raw_value));
+			}
+		}
+		return None;
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use crate::endian::Endian;
+	use crate::exif_tag::ExifTag;
+	use crate::filetype::FileExtension;
+	use crate::metadata::Metadata;
+
+	use super::Predictor;
+
+	#[test]
+	fn
+	horizontal_differencing_predictor()
+	{
+		let mut metadata = Metadata::new();
+		metadata.set_tag(ExifTag::Predictor(vec![2]));
+
+		let metadata = Metadata::new_from_vec(&metadata.encode().unwrap(), FileExtension::TIFF).unwrap();
+		assert_eq!(metadata.get_predictor(0), Some(Predictor::HorizontalDifferencing));
+		assert_eq!(metadata.get_predictor(1), None);
+
+		// Two rows of two RGB pixels with 8 bits per sample
+		let mut strip = vec![
+			10, 20, 30,   1,   2, 3,
+			 0,  0,  0, 255, 254, 0,
+		];
+		Predictor::HorizontalDifferencing.decode_strip(&mut strip, 2, 3, 8, &Endian::Little).unwrap();
+		assert_eq!(strip, vec![
+			10, 20, 30,  11,  22, 33,
+			 0,  0,  0, 255, 254,  0,
+		]);
+
+		// One row of three grayscale pixels with 16 bits per sample
+		let mut strip = vec![0x01, 0x00, 0x00, 0xff, 0x00, 0x01];
+		Predictor::HorizontalDifferencing.decode_strip(&mut strip, 3, 1, 16, &Endian::Big).unwrap();
+		assert_eq!(strip, vec![0x01, 0x00, 0x01, 0xff, 0x02, 0x00]);
+
+		// Incomplete rows
+		assert!(Predictor::HorizontalDifferencing.decode_strip(&mut [0; 5], 2, 1, 8, &Endian::Little).is_err());
+	}
+
+	#[test]
+	fn
+	floating_point_predictor()
+	{
+		// 1.0 and 2.0 as byte planes (0x3f800000 and 0x40000000), differenced
+		let mut strip = vec![0x3f, 0x01, 0x40, 0x80, 0x00, 0x00, 0x00, 0x00];
+		Predictor::FloatingPoint.decode_strip(&mut strip, 2, 1, 32, &Endian::Little).unwrap();
+
+		assert_eq!(f32::from_le_bytes([strip[0], strip[1], strip[2], strip[3]]), 1.0);
+		assert_eq!(f32::from_le_bytes([strip[4], strip[5], strip[6], strip[7]]), 2.0);
+	}
+}
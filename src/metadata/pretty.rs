@@ -0,0 +1,264 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::endian::Endian;
+use crate::exif_tag::ExifTag;
+use crate::exif_tag_format::*;
+use crate::ifd::ExifTagGroup;
+use crate::ifd::ImageFileDirectory;
+use crate::rational::iR64;
+use crate::rational::uR64;
+use crate::u8conversion::U8conversion;
+
+use super::datetime::ExifDateTime;
+use super::gps::decimal_to_dms;
+use super::gps::dms_to_decimal;
+use super::Metadata;
+
+/// Binary data up to this number of bytes is printed as hex values, longer
+/// data only with its length
+const MAX_PRINTED_BINARY_BYTES: usize = 16;
+
+impl
+Metadata
+{
+	/// Formats the metadata as human-readable report, similar to the output
+	/// of exiftool: For each IFD a header line, followed by one line per tag
+	/// with the tag name and its value. Rationals are printed as fractions
+	/// (e.g. "1/200"), date/time values as "YYYY-MM-DD hh:mm:ss" and GPS
+	/// coordinates as degrees, minutes and seconds.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// println!("{}", metadata.to_pretty_string());
+	/// # }
+	/// ```
+	pub fn
+	to_pretty_string
+	(
+		&self
+	)
+	-> String
+	{
+		let name_width = self.image_file_directories.iter()
+			.flat_map(|ifd| ifd.get_tags().iter())
+			.map(|tag| tag.name().len())
+			.max()
+			.unwrap_or(0);
+
+		let mut output = String::new();
+		for ifd in self.image_file_directories.iter()
+		{
+			output.push_str(&format!("---- {} ----\n", ifd_label(ifd)));
+
+			for tag in ifd.get_tags()
+			{
+				output.push_str(&format!(
+					"{:<width$} : {}\n",
+					tag.name(),
+					format_value(tag, &self.endian),
+					width = name_width
+				));
+			}
+		}
+		return output;
+	}
+}
+
+/// Gets the name of an IFD as used by exiftool, e.g. "IFD0" or "ExifIFD".
+/// SubIFDs of generic IFDs other than IFD0 additionally name their parent.
+fn
+ifd_label
+(
+	ifd: &ImageFileDirectory
+)
+-> String
+{
+	let generic_ifd_nr = ifd.get_generic_ifd_nr();
+	let label = match ifd.get_ifd_type()
+	{
+		ExifTagGroup::GENERIC => return format!("IFD{}", generic_ifd_nr),
+		ExifTagGroup::EXIF    => "ExifIFD",
+		ExifTagGroup::INTEROP => "InteropIFD",
+		ExifTagGroup::GPS     => "GPS",
+	};
+
+	if generic_ifd_nr == 0
+	{
+		return label.to_string();
+	}
+	return format!("{} (IFD{})", label, generic_ifd_nr);
+}
+
+/// Joins the values with ", " using the given formatting function
+fn
+join
+<T>
+(
+	values: &[T],
+	format: impl Fn(&T) -> String
+)
+-> String
+{
+	values.iter().map(format).collect::<Vec<String>>().join(", ")
+}
+
+/// A tag value decoded according to its format, which is the common basis
+/// of the human-readable and the JSON output
+pub(crate) enum
+DecodedValue
+{
+	Text(String),
+	Binary(Vec<u8>),
+	UnsignedRationals(Vec<uR64>),
+	SignedRationals(Vec<iR64>),
+	Integers(Vec<i64>),
+	Floats(Vec<f32>),
+	Doubles(Vec<f64>),
+}
+
+/// Decodes the value of the tag, with trailing NULs of `STRING` values
+/// removed
+pub(crate) fn
+decode_value
+(
+	tag:    &ExifTag,
+	endian: &Endian
+)
+-> DecodedValue
+{
+	let raw_data = tag.value_as_u8_vec(endian);
+
+	match tag.format()
+	{
+		ExifTagFormat::STRING      => DecodedValue::Text(<STRING as U8conversion<STRING>>::from_u8_vec(&raw_data, endian).trim_end_matches('\0').to_string()),
+		ExifTagFormat::UNDEF       => DecodedValue::Binary(raw_data),
+		ExifTagFormat::RATIONAL64U => DecodedValue::UnsignedRationals(<RATIONAL64U as U8conversion<RATIONAL64U>>::from_u8_vec(&raw_data, endian)),
+		ExifTagFormat::RATIONAL64S => DecodedValue::SignedRationals(<RATIONAL64S as U8conversion<RATIONAL64S>>::from_u8_vec(&raw_data, endian)),
+		ExifTagFormat::INT8U       => DecodedValue::Integers(<INT8U  as U8conversion<INT8U >>::from_u8_vec(&raw_data, endian).iter().map(|value| *value as i64).collect()),
+		ExifTagFormat::INT16U      => DecodedValue::Integers(<INT16U as U8conversion<INT16U>>::from_u8_vec(&raw_data, endian).iter().map(|value| *value as i64).collect()),
+		ExifTagFormat::INT32U
+		| ExifTagFormat::IFD       => DecodedValue::Integers(<INT32U as U8conversion<INT32U>>::from_u8_vec(&raw_data, endian).iter().map(|value| *value as i64).collect()),
+		ExifTagFormat::INT8S       => DecodedValue::Integers(<INT8S  as U8conversion<INT8S >>::from_u8_vec(&raw_data, endian).iter().map(|value| *value as i64).collect()),
+		ExifTagFormat::INT16S      => DecodedValue::Integers(<INT16S as U8conversion<INT16S>>::from_u8_vec(&raw_data, endian).iter().map(|value| *value as i64).collect()),
+		ExifTagFormat::INT32S      => DecodedValue::Integers(<INT32S as U8conversion<INT32S>>::from_u8_vec(&raw_data, endian).iter().map(|value| *value as i64).collect()),
+		ExifTagFormat::FLOAT       => DecodedValue::Floats(<FLOAT  as U8conversion<FLOAT >>::from_u8_vec(&raw_data, endian)),
+		ExifTagFormat::DOUBLE      => DecodedValue::Doubles(<DOUBLE as U8conversion<DOUBLE>>::from_u8_vec(&raw_data, endian)),
+	}
+}
+
+/// Checks if the tag holds GPS coordinates in degrees, minutes and seconds
+pub(crate) fn
+is_coordinate
+(
+	tag: &ExifTag
+)
+-> bool
+{
+	return matches!(tag,
+		ExifTag::GPSLatitude(_)
+		| ExifTag::GPSLongitude(_)
+		| ExifTag::GPSDestLatitude(_)
+		| ExifTag::GPSDestLongitude(_)
+	);
+}
+
+fn
+format_value
+(
+	tag:    &ExifTag,
+	endian: &Endian
+)
+-> String
+{
+	match decode_value(tag, endian)
+	{
+		DecodedValue::Text(value) => {
+			if let Some(date_time) = ExifDateTime::parse(&value)
+			{
+				return format!(
+					"{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+					date_time.year, date_time.month, date_time.day,
+					date_time.hour, date_time.minute, date_time.second
+				);
+			}
+			return value;
+		},
+
+		DecodedValue::UnsignedRationals(values) => {
+			if let (true, Some(decimal)) = (is_coordinate(tag), dms_to_decimal(&values))
+			{
+				let dms = decimal_to_dms(decimal);
+				return format!(
+					"{}° {}' {:.2}\"",
+					dms[0].nominator,
+					dms[1].nominator,
+					dms[2].nominator as f64 / dms[2].denominator as f64
+				);
+			}
+			return join(&values, |value| format!("{}/{}", value.nominator, value.denominator));
+		},
+
+		DecodedValue::SignedRationals(values) => {
+			return join(&values, |value| format!("{}/{}", value.nominator, value.denominator));
+		},
+
+		DecodedValue::Binary(raw_data) => {
+			// Short ASCII values like the `ExifVersion` ("0232") are shown as
+			// text, everything else as binary data
+			if raw_data.len() <= MAX_PRINTED_BINARY_BYTES
+			&& !raw_data.is_empty()
+			&& raw_data.iter().all(|byte| byte.is_ascii_graphic() || *byte == b' ')
+			{
+				return String::from_utf8_lossy(&raw_data).to_string();
+			}
+
+			if raw_data.len() <= MAX_PRINTED_BINARY_BYTES
+			{
+				return raw_data.iter().map(|byte| format!("{:02x}", byte)).collect::<Vec<String>>().join(" ");
+			}
+			return format!("(Binary data {} bytes)", raw_data.len());
+		},
+
+		DecodedValue::Integers(values) => join(&values, |value| value.to_string()),
+		DecodedValue::Floats(values)   => join(&values, |value| value.to_string()),
+		DecodedValue::Doubles(values)  => join(&values, |value| value.to_string()),
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use crate::exif_tag::ExifTag;
+	use crate::metadata::Metadata;
+	use crate::rational::uR64;
+
+	#[test]
+	fn
+	pretty_string_contains_formatted_values()
+	{
+		let mut metadata = Metadata::new();
+		metadata.set_tag(ExifTag::ImageDescription("Hello World".to_string()));
+		metadata.set_tag(ExifTag::ExposureTime(vec![uR64 { nominator: 1, denominator: 200 }]));
+		metadata.set_tag(ExifTag::DateTimeOriginal("2024:05:01 12:34:56".to_string()));
+		metadata.set_tag(ExifTag::GPSLatitude(vec![
+			uR64 { nominator: 48,   denominator: 1   },
+			uR64 { nominator: 51,   denominator: 1   },
+			uR64 { nominator: 2999, denominator: 100 },
+		]));
+
+		let pretty_string = metadata.to_pretty_string();
+
+		assert!(pretty_string.contains("---- IFD0 ----"));
+		assert!(pretty_string.contains("---- GPS ----"));
+		assert!(pretty_string.contains("ImageDescription : Hello World\n"));
+		assert!(pretty_string.contains("ExposureTime     : 1/200\n"));
+		assert!(pretty_string.contains("DateTimeOriginal : 2024-05-01 12:34:56\n"));
+		assert!(pretty_string.contains("GPSLatitude      : 48° 51' 29.99\"\n"));
+	}
+}
@@ -0,0 +1,669 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use std::io::Cursor;
+
+use crate::endian::Endian;
+use crate::exif_tag::ExifTag;
+use crate::general_file_io::io_error;
+use crate::ifd::ExifTagGroup;
+use crate::ifd::ImageFileDirectory;
+use crate::jpg::read_dimensions;
+use crate::jpg::JPG_SIGNATURE;
+
+use super::options::ReadOptions;
+use super::subfile::NewSubfileType;
+use super::subfile::SubfileKind;
+use super::warnings::ParseWarning;
+use super::Metadata;
+
+/// Values of the `Compression` tag (0x0103) that indicate JPEG compression
+/// (6 = old-style JPEG, 7 = JPEG)
+const JPEG_COMPRESSION_VALUES: [u16; 2] = [6, 7];
+
+/// Value of the `Compression` tag (0x0103) for uncompressed image data
+const NO_COMPRESSION: u16 = 1;
+
+/// Value of the `PhotometricInterpretation` tag (0x0106) for RGB, which is
+/// the default for uncompressed thumbnails
+const PHOTOMETRIC_INTERPRETATION_RGB: u16 = 2;
+
+/// Value of the `PhotometricInterpretation` tag (0x0106) for YCbCr, which is
+/// used by JPEG previews
+const PHOTOMETRIC_INTERPRETATION_YCBCR: u16 = 6;
+
+/// The `SubIFDs` tag, which is not part of the tag table and thus decoded as
+/// unknown tag holding the offsets of the SubIFDs
+const SUBIFDS_TAG: u16 = 0x014a;
+
+/// The `NewSubfileType` tag, which decides whether a SubIFD gets decoded
+const NEW_SUBFILE_TYPE_TAG: u16 = 0x00fe;
+
+/// The tags of a SubIFD needed for its image data: `NewSubfileType`,
+/// `ImageWidth`, `ImageHeight`, `Compression`, `PhotometricInterpretation`,
+/// `StripOffsets`, `StripByteCounts`, `ThumbnailOffset` and `ThumbnailLength`
+const SUBIFD_IMAGE_TAGS: [u16; 9] = [0x00fe, 0x0100, 0x0101, 0x0103, 0x0106, 0x0111, 0x0117, 0x0201, 0x0202];
+
+/// The thumbnail image stored in IFD1
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum
+Thumbnail
+{
+	/// A JPEG compressed thumbnail (`Compression` = 6), referenced by the
+	/// `ThumbnailOffset` (aka `JPEGInterchangeFormat`) tag
+	Jpeg(Vec<u8>),
+
+	/// An uncompressed thumbnail (`Compression` = 1), whose pixel data is
+	/// stored in strips. The strips are concatenated into `data`, and
+	/// `photometric` is the value of the `PhotometricInterpretation` tag
+	/// (e.g. 2 for RGB or 6 for YCbCr) describing how to interpret it.
+	Raw
+	{
+		data:        Vec<u8>,
+		width:       u32,
+		height:      u32,
+		photometric: u16,
+	},
+}
+
+/// The role of an image embedded in the metadata, see `EmbeddedImage`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+EmbeddedImageKind
+{
+	/// The thumbnail stored in IFD1
+	Thumbnail,
+
+	/// A reduced-resolution version of the main image, e.g. the JPEG preview
+	/// of a DNG file
+	Preview,
+
+	/// The full-resolution image data, e.g. the strips of a TIFF's IFD0
+	FullResolution,
+}
+
+/// An image embedded in the metadata, as returned by `embedded_images`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct
+EmbeddedImage
+{
+	/// The number of the generic IFD the image is stored in
+	pub source_ifd:  u32,
+	pub kind:        EmbeddedImageKind,
+
+	/// The dimensions of the image, taken from the `ImageWidth` and
+	/// `ImageHeight` tags of the IFD or - for JPEG data - from the image
+	/// data itself. `None` if they can't be determined.
+	pub width:       Option<u32>,
+	pub height:      Option<u32>,
+
+	/// The value of the `Compression` tag of the IFD (e.g. 1 for
+	/// uncompressed or 6 for JPEG data), if present
+	pub compression: Option<u16>,
+	pub data:        Vec<u8>,
+}
+
+impl
+Metadata
+{
+	/// Gets all images embedded in the generic IFDs of the metadata, i.e. the
+	/// data referenced by the `ThumbnailOffset` (aka `JPEGInterchangeFormat`)
+	/// and `StripOffsets` tags. This allows picking e.g. the largest preview
+	/// or the smallest thumbnail regardless of where they are stored.
+	/// The kind of each image is determined via `classify_ifd`, except for
+	/// IFD1 without a `NewSubfileType` tag, which holds the thumbnail. JPEG
+	/// data referenced by `ThumbnailOffset` outside of IFD1 is considered a
+	/// preview, and transparency masks are skipped.
+	/// These are followed by the reduced-resolution images in the SubIFDs
+	/// referenced by the `SubIFDs` tag (0x014a), where e.g. DNG files store
+	/// their large previews. Their `source_ifd` is the generic IFD with the
+	/// `SubIFDs` tag. Images in maker notes are not included.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::metadata::preview::EmbeddedImageKind;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.dng")).unwrap();
+	/// let largest_preview = metadata.embedded_images()
+	///     .into_iter()
+	///     .filter(|image| image.kind == EmbeddedImageKind::Preview)
+	///     .max_by_key(|image| image.data.len());
+	/// # }
+	/// ```
+	pub fn
+	embedded_images
+	(
+		&self
+	)
+	-> Vec<EmbeddedImage>
+	{
+		let mut images = Vec::new();
+
+		for ifd in self.image_file_directories.iter()
+		{
+			if ifd.get_ifd_type() != ExifTagGroup::GENERIC
+			{
+				continue;
+			}
+
+			let generic_ifd_nr = ifd.get_generic_ifd_nr();
+			let strip_kind     = if generic_ifd_nr == 1 && self.get_new_subfile_type(1).is_none()
+			{
+				EmbeddedImageKind::Thumbnail
+			}
+			else
+			{
+				match self.classify_ifd(generic_ifd_nr)
+				{
+					SubfileKind::Main              => EmbeddedImageKind::FullResolution,
+					SubfileKind::Page              => EmbeddedImageKind::FullResolution,
+					SubfileKind::ReducedResolution => EmbeddedImageKind::Preview,
+					SubfileKind::TransparencyMask  => continue,
+				}
+			};
+			let jpeg_kind = if strip_kind == EmbeddedImageKind::Thumbnail
+			{
+				EmbeddedImageKind::Thumbnail
+			}
+			else
+			{
+				EmbeddedImageKind::Preview
+			};
+
+			images.extend(get_images(ifd, strip_kind, jpeg_kind));
+		}
+
+		for subifd in self.subifd_previews.iter()
+		{
+			images.extend(get_images(subifd, EmbeddedImageKind::Preview, EmbeddedImageKind::Preview));
+		}
+
+		return images;
+	}
+
+	/// Gets the largest JPEG preview image embedded in the metadata, e.g. the
+	/// full-size preview of a DNG file. All generic IFDs (IFD0, IFD1, ...)
+	/// are searched, considering both the data referenced by the
+	/// `JPEGInterchangeFormat` tags (called `ThumbnailOffset` in this
+	/// library) and strips of JPEG compressed YCbCr images.
+	/// Unlike `ThumbnailOffset` in IFD1, which usually is a small thumbnail,
+	/// this returns the largest of these candidates.
+	/// The reduced-resolution images in the SubIFDs referenced by the
+	/// `SubIFDs` tag (0x014a) are considered as well, as DNG files usually
+	/// store their full-size preview there.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.dng")).unwrap();
+	/// if let Some(preview) = metadata.get_preview_image()
+	/// {
+	///     std::fs::write("preview.jpg", preview).unwrap();
+	/// }
+	/// # }
+	/// ```
+	pub fn
+	get_preview_image
+	(
+		&self
+	)
+	-> Option<Vec<u8>>
+	{
+		self.image_file_directories.iter()
+			.filter(|ifd| ifd.get_ifd_type() == ExifTagGroup::GENERIC)
+			.chain(self.subifd_previews.iter())
+			.flat_map(|ifd| get_jpeg_candidates(ifd))
+			.filter(|candidate| candidate.starts_with(&JPG_SIGNATURE))
+			.max_by_key(|candidate| candidate.len())
+	}
+
+	/// Gets the thumbnail image stored in IFD1. Depending on the
+	/// `Compression` tag of IFD1, this is either a JPEG image or the raw,
+	/// uncompressed pixel data assembled from the strips of IFD1. If the
+	/// `Compression` tag is missing, a JPEG thumbnail is assumed.
+	/// Returns `None` if there is no thumbnail or its compression is not
+	/// supported.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::metadata::preview::Thumbnail;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some(Thumbnail::Jpeg(thumbnail)) = metadata.get_thumbnail()
+	/// {
+	///     std::fs::write("thumbnail.jpg", thumbnail).unwrap();
+	/// }
+	/// # }
+	/// ```
+	pub fn
+	get_thumbnail
+	(
+		&self
+	)
+	-> Option<Thumbnail>
+	{
+		let ifd1 = self.get_ifd(ExifTagGroup::GENERIC, 1)?;
+
+		let mut compression = None;
+		let mut photometric = PHOTOMETRIC_INTERPRETATION_RGB;
+		let mut width       = None;
+		let mut height      = None;
+		let mut jpeg_data   = None;
+		let mut strip_data  = None;
+
+		for tag in ifd1.get_tags()
+		{
+			match tag
+			{
+				ExifTag::Compression(value)
+					=> compression = value.first().copied(),
+
+				ExifTag::PhotometricInterpretation(value)
+					=> photometric = value.first().copied().unwrap_or(photometric),
+
+				ExifTag::ImageWidth(value)
+					=> width = value.first().copied(),
+
+				ExifTag::ImageHeight(value)
+					=> height = value.first().copied(),
+
+				ExifTag::ThumbnailOffset(_, thumbnail_data)
+					=> jpeg_data = Some(thumbnail_data.clone()),
+
+				ExifTag::StripOffsets(_, strips)
+					=> strip_data = Some(strips.concat()),
+
+				_
+					=> (),
+			}
+		}
+
+		if compression == Some(NO_COMPRESSION)
+		{
+			return Some(Thumbnail::Raw {
+				data:        strip_data.filter(|data| !data.is_empty())?,
+				width:       width?,
+				height:      height?,
+				photometric: photometric,
+			});
+		}
+
+		if compression.map_or(true, |compression| JPEG_COMPRESSION_VALUES.contains(&compression))
+		{
+			return jpeg_data.filter(|data| !data.is_empty()).map(Thumbnail::Jpeg);
+		}
+
+		return None;
+	}
+
+	/// Updates the metadata after the image has been resized: The dimension
+	/// tags are set to the new size (see `set_dimensions`) and the thumbnail
+	/// in IFD1 gets replaced by the given JPEG data or, if `None`, removed
+	/// together with IFD1, so that the metadata does not describe the
+	/// image before resizing.
+	/// Nothing is changed if this fails, i.e. if the thumbnail is not a JPEG
+	/// or IFD1 is not a thumbnail but e.g. the second page of a TIFF.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let thumbnail    = std::fs::read("thumbnail.jpg").unwrap();
+	/// let mut metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// metadata.resized(800, 600, Some(&thumbnail)).unwrap();
+	/// metadata.write_to_file(std::path::Path::new("image.jpg")).unwrap();
+	/// # }
+	/// ```
+	pub fn
+	resized
+	(
+		&mut self,
+		width:         u32,
+		height:        u32,
+		new_thumbnail: Option<&[u8]>
+	)
+	-> Result<(), std::io::Error>
+	{
+		if new_thumbnail.map_or(false, |thumbnail| !thumbnail.starts_with(&JPG_SIGNATURE))
+		{
+			return io_error!(InvalidInput, "The new thumbnail is not a JPEG");
+		}
+
+		if self.image_file_directories.iter().any(|ifd| ifd.get_generic_ifd_nr() > 1)
+		{
+			return io_error!(InvalidInput, "IFD1 is followed by further IFDs and thus no thumbnail");
+		}
+
+		self.set_dimensions(width, height);
+
+		// Drop the old thumbnail, including any tags describing it (e.g.
+		// the dimensions of an uncompressed one) and SubIFDs of IFD1
+		self.image_file_directories.retain(|ifd| ifd.get_generic_ifd_nr() != 1);
+
+		if let Some(thumbnail) = new_thumbnail
+		{
+			let ifd1 = self.get_ifd_mut(ExifTagGroup::GENERIC, 1);
+			ifd1.set_tag(ExifTag::Compression(vec![JPEG_COMPRESSION_VALUES[0]]));
+			ifd1.set_tag(ExifTag::ThumbnailOffset(Vec::new(), thumbnail.to_vec()));
+			ifd1.set_tag(ExifTag::ThumbnailLength(vec![thumbnail.len() as u32]));
+		}
+
+		return Ok(());
+	}
+
+	/// Decodes the SubIFDs referenced by the `SubIFDs` tag (0x014a) of the
+	/// given generic IFDs that hold reduced-resolution images, e.g. the large
+	/// JPEG preview of a DNG file. Other SubIFDs are skipped without reading
+	/// their image data, as for DNG files this is the raw sensor data.
+	/// SubIFDs that can't be decoded are skipped as well.
+	pub(crate) fn
+	decode_subifd_previews
+	(
+		raw_data:               &Vec<u8>,
+		image_file_directories: &[ImageFileDirectory],
+		options:                &ReadOptions,
+		warnings:               &mut Vec<ParseWarning>
+	)
+	-> Vec<ImageFileDirectory>
+	{
+		let mut previews = Vec::new();
+
+		let mut data_cursor = Cursor::new(raw_data);
+		let (endian, data_start_position, _) = match Self::decode_header(&mut data_cursor)
+		{
+			Ok(header) => header,
+			Err(_)     => return previews,
+		};
+
+		// First only decode the `NewSubfileType` of a SubIFD, and all of its
+		// image tags only if it turns out to be a preview
+		let kind_options = ReadOptions {
+			tag_allowlist: Some(vec![(NEW_SUBFILE_TYPE_TAG, ExifTagGroup::GENERIC)]),
+			..options.clone()
+		};
+		let image_options = ReadOptions {
+			tag_allowlist: Some(SUBIFD_IMAGE_TAGS.iter().map(|hex_tag| (*hex_tag, ExifTagGroup::GENERIC)).collect()),
+			..options.clone()
+		};
+		let mut remaining_ifd_count = options.max_ifd_count;
+
+		for ifd in image_file_directories.iter().filter(|ifd| ifd.get_ifd_type() == ExifTagGroup::GENERIC)
+		{
+			let subifd_offsets = ifd.get_tags().iter()
+				.find_map(|tag| match tag
+				{
+					ExifTag::UnknownINT32U(offsets, SUBIFDS_TAG, _) => Some(offsets.clone()),
+					_                                                => None,
+				})
+				.unwrap_or_default();
+
+			for subifd_offset in subifd_offsets
+			{
+				let new_subfile_type = decode_subifd(
+					&mut data_cursor,
+					data_start_position,
+					&endian,
+					subifd_offset,
+					ifd.get_generic_ifd_nr(),
+					&mut remaining_ifd_count,
+					&kind_options,
+					&mut Vec::new()
+				).and_then(|subifd| subifd.get_tags().iter().find_map(|tag| match tag
+				{
+					ExifTag::NewSubfileType(value) => value.first().map(|raw_value| NewSubfileType::from_u32(*raw_value)),
+					_                              => None,
+				}));
+
+				if !new_subfile_type.map_or(false, |kind| kind.reduced_resolution && !kind.transparency_mask)
+				{
+					continue;
+				}
+
+				if let Some(subifd) = decode_subifd(
+					&mut data_cursor,
+					data_start_position,
+					&endian,
+					subifd_offset,
+					ifd.get_generic_ifd_nr(),
+					&mut remaining_ifd_count,
+					&image_options,
+					warnings
+				)
+				{
+					previews.push(subifd);
+				}
+			}
+		}
+
+		return previews;
+	}
+}
+
+/// Decodes the SubIFD at the given offset (relative to the TIFF header) as
+/// generic IFD, without following its link to a next IFD
+fn
+decode_subifd
+(
+	data_cursor:         &mut Cursor<&Vec<u8>>,
+	data_start_position:      u64,
+	endian:              &    Endian,
+	subifd_offset:            u32,
+	generic_ifd_nr:           u32,
+	remaining_ifd_count: &mut usize,
+	options:             &    ReadOptions,
+	warnings:            &mut Vec<ParseWarning>
+)
+-> Option<ImageFileDirectory>
+{
+	data_cursor.set_position(data_start_position + subifd_offset as u64);
+
+	let mut subifds = Vec::new();
+	ImageFileDirectory::decode_ifd(
+		data_cursor,
+		data_start_position,
+		endian,
+		&ExifTagGroup::GENERIC,
+		generic_ifd_nr,
+		&mut subifds,
+		remaining_ifd_count,
+		options,
+		warnings,
+		None
+	).ok()?;
+
+	return subifds.into_iter().find(|subifd| subifd.get_ifd_type() == ExifTagGroup::GENERIC);
+}
+
+/// Gets the images stored in an IFD, i.e. the JPEG data referenced by the
+/// `ThumbnailOffset` tag and the strips, with the given kinds
+fn
+get_images
+(
+	ifd:        &ImageFileDirectory,
+	strip_kind: EmbeddedImageKind,
+	jpeg_kind:  EmbeddedImageKind
+)
+-> Vec<EmbeddedImage>
+{
+	let generic_ifd_nr = ifd.get_generic_ifd_nr();
+	let mut images     = Vec::new();
+
+	let mut compression = None;
+	let mut width       = None;
+	let mut height      = None;
+	let mut jpeg_data   = None;
+	let mut strip_data  = None;
+
+	for tag in ifd.get_tags()
+	{
+		match tag
+		{
+			ExifTag::Compression(value)                 => compression = value.first().copied(),
+			ExifTag::ImageWidth(value)                  => width       = value.first().copied(),
+			ExifTag::ImageHeight(value)                 => height      = value.first().copied(),
+			ExifTag::ThumbnailOffset(_, thumbnail_data) => jpeg_data   = Some(thumbnail_data.clone()),
+			ExifTag::StripOffsets(_, strips)            => strip_data  = Some(strips.concat()),
+			_                                           => (),
+		}
+	}
+
+	if let Some(data) = jpeg_data.filter(|data| !data.is_empty())
+	{
+		let dimensions = read_dimensions(&data).ok();
+		images.push(EmbeddedImage {
+			source_ifd:  generic_ifd_nr,
+			kind:        jpeg_kind,
+			width:       dimensions.map(|(width,  _)| width  as u32),
+			height:      dimensions.map(|(_, height)| height as u32),
+			compression: compression,
+			data:        data,
+		});
+	}
+
+	if let Some(data) = strip_data.filter(|data| !data.is_empty())
+	{
+		images.push(EmbeddedImage {
+			source_ifd:  generic_ifd_nr,
+			kind:        strip_kind,
+			width:       width,
+			height:      height,
+			compression: compression,
+			data:        data,
+		});
+	}
+
+	return images;
+}
+
+/// Collects the JPEG data stored in an IFD, both via `ThumbnailOffset` and,
+/// if the IFD describes a JPEG compressed YCbCr image, via `StripOffsets`
+fn
+get_jpeg_candidates
+(
+	ifd: &ImageFileDirectory
+)
+-> Vec<Vec<u8>>
+{
+	let mut candidates     = Vec::new();
+	let mut is_jpeg_image  = false;
+	let mut is_ycbcr_image = false;
+	let mut strip_data     = None;
+
+	for tag in ifd.get_tags()
+	{
+		match tag
+		{
+			ExifTag::ThumbnailOffset(_, thumbnail_data)
+				=> candidates.push(thumbnail_data.clone()),
+
+			ExifTag::StripOffsets(_, strips)
+				=> strip_data = Some(strips.concat()),
+
+			ExifTag::Compression(value)
+				=> is_jpeg_image = value.first().map_or(false, |compression| JPEG_COMPRESSION_VALUES.contains(compression)),
+
+			ExifTag::PhotometricInterpretation(value)
+				=> is_ycbcr_image = value.first() == Some(&PHOTOMETRIC_INTERPRETATION_YCBCR),
+
+			_
+				=> (),
+		}
+	}
+
+	if let Some(strip_data) = strip_data
+	{
+		if is_jpeg_image && is_ycbcr_image
+		{
+			candidates.push(strip_data);
+		}
+	}
+
+	return candidates;
+}
+
+#[cfg(test)]
+mod tests
+{
+	use crate::endian::Endian;
+	use crate::exif_tag::ExifTag;
+	use crate::filetype::FileExtension;
+	use crate::ifd::ExifTagGroup;
+	use crate::metadata::options::DEFAULT_MAX_IFD_COUNT;
+	use crate::metadata::raw::RawEntryIterator;
+	use crate::metadata::Metadata;
+
+	use super::EmbeddedImage;
+	use super::EmbeddedImageKind;
+
+	/// Encodes an IFD with the given (tag, format, value) entries and
+	/// without a link to a next IFD, for little endian data
+	fn
+	encode_ifd
+	(
+		entries: &[(u16, u16, u32)]
+	)
+	-> Vec<u8>
+	{
+		let mut encoded = (entries.len() as u16).to_le_bytes().to_vec();
+		for (tag, format, value) in entries
+		{
+			encoded.extend(tag.to_le_bytes());
+			encoded.extend(format.to_le_bytes());
+			encoded.extend(1u32.to_le_bytes());
+			encoded.extend(value.to_le_bytes());
+		}
+		encoded.extend(0u32.to_le_bytes());
+		return encoded;
+	}
+
+	#[test]
+	fn
+	previews_in_subifds()
+	{
+		let mut metadata = Metadata::new();
+		metadata.set_endian(Endian::Little);
+		metadata.set_tag(ExifTag::ImageDescription("DNG".to_string()));
+		metadata.set_tag(ExifTag::UnknownINT32U(vec![0, 0], 0x014a, ExifTagGroup::GENERIC));
+		let mut encoded = metadata.encode().unwrap();
+		encoded.resize(encoded.len() + encoded.len() % 2, 0x00);
+
+		// A full-resolution SubIFD with raw data in a strip, followed by a
+		// reduced-resolution one with a JPEG preview, as found in DNG files
+		let preview        = [vec![0xff, 0xd8], vec![0x03; 64], vec![0xff, 0xd9]].concat();
+		let raw_offset     = encoded.len() as u32;
+		let preview_offset = raw_offset + 42 + 4;
+
+		encoded.extend(encode_ifd(&[(0x00fe, 4, 0), (0x0111, 4, raw_offset + 42), (0x0117, 4, 4)]));
+		encoded.extend([0x01, 0x02, 0x03, 0x04]);
+		encoded.extend(encode_ifd(&[(0x00fe, 4, 1), (0x0103, 3, 7), (0x0201, 4, preview_offset + 54), (0x0202, 4, preview.len() as u32)]));
+		encoded.extend(&preview);
+
+		// Point the SubIFDs tag to the two SubIFDs
+		let (_, entry)  = RawEntryIterator::new(&encoded, DEFAULT_MAX_IFD_COUNT).unwrap().find(|(_, entry)| entry.tag == 0x014a).unwrap();
+		let value_start = entry.offset(&Endian::Little) as usize;
+		encoded[value_start..value_start + 4].copy_from_slice(&raw_offset.to_le_bytes());
+		encoded[value_start + 4..value_start + 8].copy_from_slice(&preview_offset.to_le_bytes());
+
+		let metadata = Metadata::new_from_vec(&encoded, FileExtension::TIFF).unwrap();
+		assert_eq!(metadata.get_preview_image(), Some(preview.clone()));
+		assert_eq!(metadata.embedded_images(), vec![EmbeddedImage {
+			source_ifd:  0,
+			kind:        EmbeddedImageKind::Preview,
+			width:       None,
+			height:      None,
+			compression: Some(7),
+			data:        preview,
+		}]);
+	}
+}
@@ -0,0 +1,241 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use std::fmt;
+
+use crate::exif_tag::ExifTag;
+
+use super::datetime::DateTimeKind;
+use super::Metadata;
+
+/// The kind of privacy-sensitive information found by `Metadata::privacy_report`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+PrivacyItemKind
+{
+	GpsPosition,
+	BodySerialNumber,
+	LensSerialNumber,
+	OwnerName,
+	Artist,
+	Software,
+	Timestamp(DateTimeKind),
+	MakerNote,
+}
+
+/// A single privacy-sensitive piece of information, together with a
+/// human-readable summary of its value (e.g. "GPS position: 48.858400, 2.294500")
+#[derive(Clone, Debug, PartialEq)]
+pub struct
+PrivacyItem
+{
+	pub kind:    PrivacyItemKind,
+	pub summary: String,
+}
+
+/// The privacy-sensitive information present in a `Metadata` struct, e.g.
+/// for reviewing an image before sharing it
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct
+PrivacyReport
+{
+	pub items: Vec<PrivacyItem>,
+}
+
+impl
+PrivacyReport
+{
+	/// Checks if no privacy-sensitive information was found
+	pub fn
+	is_empty
+	(
+		&self
+	)
+	-> bool
+	{
+		return self.items.is_empty();
+	}
+
+	/// Checks if the report contains an item of the given kind
+	pub fn
+	contains
+	(
+		&self,
+		kind: PrivacyItemKind
+	)
+	-> bool
+	{
+		return self.items.iter().any(|item| item.kind == kind);
+	}
+
+	fn
+	push
+	(
+		&mut self,
+		kind:    PrivacyItemKind,
+		summary: String
+	)
+	{
+		self.items.push(PrivacyItem { kind, summary });
+	}
+}
+
+impl
+fmt::Display
+for
+PrivacyReport
+{
+	/// Prints the summary of each item on its own line
+	fn
+	fmt
+	(
+		&self,
+		f: &mut fmt::Formatter<'_>
+	)
+	-> fmt::Result
+	{
+		for item in self.items.iter()
+		{
+			writeln!(f, "{}", item.summary)?;
+		}
+		return Ok(());
+	}
+}
+
+impl
+Metadata
+{
+	/// Lists the privacy-sensitive information stored in the metadata: The
+	/// GPS position, serial numbers of camera body and lens, the names of
+	/// the camera owner and artist, the software, timestamps and the maker
+	/// note. Note that maker notes are not decoded and may contain further
+	/// identifying information like serial numbers.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// print!("{}", metadata.privacy_report());
+	/// # }
+	/// ```
+	pub fn
+	privacy_report
+	(
+		&self
+	)
+	-> PrivacyReport
+	{
+		let mut report = PrivacyReport::default();
+
+		if let Some((latitude, longitude)) = self.get_gps_position()
+		{
+			report.push(
+				PrivacyItemKind::GpsPosition,
+				format!("GPS position: {:.6}, {:.6}", latitude, longitude)
+			);
+		}
+
+		if let Some(serial_number) = self.get_body_serial_number()
+		{
+			report.push(
+				PrivacyItemKind::BodySerialNumber,
+				format!("Camera serial number: {}", serial_number.trim_end_matches('\0'))
+			);
+		}
+
+		if let Some(serial_number) = self.get_lens_serial_number()
+		{
+			report.push(
+				PrivacyItemKind::LensSerialNumber,
+				format!("Lens serial number: {}", serial_number.trim_end_matches('\0'))
+			);
+		}
+
+		if let Some(owner_name) = self.get_camera_owner_name()
+		{
+			report.push(
+				PrivacyItemKind::OwnerName,
+				format!("Camera owner: {}", owner_name.trim_end_matches('\0'))
+			);
+		}
+
+		let artists = self.get_artists();
+		if !artists.is_empty()
+		{
+			report.push(
+				PrivacyItemKind::Artist,
+				format!("Artist: {}", artists.join(", "))
+			);
+		}
+
+		if let Some(ExifTag::Software(software)) = self.get_tag(&ExifTag::Software(String::new())).next()
+		{
+			report.push(
+				PrivacyItemKind::Software,
+				format!("Software: {}", software.trim_end_matches('\0'))
+			);
+		}
+
+		for (kind, label) in [
+			(DateTimeKind::Original,  "Date/time original"),
+			(DateTimeKind::Digitized, "Date/time digitized"),
+			(DateTimeKind::Modify,    "Date/time modified"),
+		]
+		{
+			if let Some(date_time) = self.get_date_time(kind)
+			{
+				report.push(
+					PrivacyItemKind::Timestamp(kind),
+					format!("{}: {}", label, date_time.to_exif_string())
+				);
+			}
+		}
+
+		if let Some(maker_note) = self.makernote_raw()
+		{
+			let vendor = self.makernote_vendor().unwrap_or_else(|| "Unknown vendor".to_string());
+			report.push(
+				PrivacyItemKind::MakerNote,
+				format!("Maker note: {} ({} bytes)", vendor, maker_note.len())
+			);
+		}
+
+		return report;
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use crate::filetype::FileExtension;
+	use crate::metadata::datetime::DateTimeKind;
+	use crate::metadata::Metadata;
+
+	use super::PrivacyItemKind;
+
+	#[test]
+	fn
+	report_contains_gps_position_and_serial_number()
+	{
+		assert!(Metadata::new().privacy_report().is_empty());
+
+		let mut metadata = Metadata::new();
+		metadata.set_gps_position(48.8584, -2.2945);
+		metadata.set_body_serial_number("SN-12345");
+
+		let metadata = Metadata::new_from_vec(&metadata.encode().unwrap(), FileExtension::TIFF).unwrap();
+		let report   = metadata.privacy_report();
+
+		assert!(report.contains(PrivacyItemKind::GpsPosition));
+		assert!(report.contains(PrivacyItemKind::BodySerialNumber));
+		assert!(!report.contains(PrivacyItemKind::OwnerName));
+		assert!(!report.contains(PrivacyItemKind::Timestamp(DateTimeKind::Original)));
+
+		let summary = report.to_string();
+		assert!(summary.contains("GPS position: 48.858400, -2.294500\n"));
+		assert!(summary.contains("Camera serial number: SN-12345\n"));
+	}
+}
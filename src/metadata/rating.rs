@@ -0,0 +1,165 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::exif_tag::ExifTag;
+use crate::general_file_io::io_error;
+
+use super::Metadata;
+
+/// The highest star rating that can be stored in the `Rating` tag
+pub const MAX_RATING: u16 = 5;
+
+/// Converts a star rating into the percentage value used by Windows for the
+/// `RatingPercent` tag (1 star = 1%, 2 stars = 25%, ..., 5 stars = 99%)
+fn
+rating_to_percent
+(
+	rating: u16
+)
+-> u16
+{
+	match rating
+	{
+		0 => 0,
+		1 => 1,
+		2 => 25,
+		3 => 50,
+		4 => 75,
+		_ => 99,
+	}
+}
+
+impl
+Metadata
+{
+	/// Gets the star rating (0 to 5) stored in the `Rating` tag, if present.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some(rating) = metadata.get_rating()
+	/// {
+	///     println!("{} stars", rating);
+	/// }
+	/// # }
+	/// ```
+	pub fn
+	get_rating
+	(
+		&self
+	)
+	-> Option<u16>
+	{
+		if let Some(ExifTag::Rating(value)) = self.get_tag(&ExifTag::Rating(Vec::new())).next()
+		{
+			return value.first().copied();
+		}
+		return None;
+	}
+
+	/// Sets the star rating (0 to 5) using the `Rating` tag. Fails if the
+	/// rating is out of range.
+	/// If a `RatingPercent` tag already exists, it is updated accordingly so
+	/// that the two tags don't contradict each other.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let mut metadata = Metadata::new();
+	/// metadata.set_rating(4).unwrap();
+	/// ```
+	pub fn
+	set_rating
+	(
+		&mut self,
+		rating: u16
+	)
+	-> Result<(), std::io::Error>
+	{
+		let has_percent = self.get_tag(&ExifTag::RatingPercent(Vec::new())).next().is_some();
+		return self.set_rating_internal(rating, has_percent);
+	}
+
+	/// Same as `set_rating`, but always writes the `RatingPercent` tag as
+	/// well, as some applications (e.g. Windows Explorer) prefer it.
+	pub fn
+	set_rating_with_percent
+	(
+		&mut self,
+		rating: u16
+	)
+	-> Result<(), std::io::Error>
+	{
+		return self.set_rating_internal(rating, true);
+	}
+
+	fn
+	set_rating_internal
+	(
+		&mut self,
+		rating:       u16,
+		sync_percent: bool
+	)
+	-> Result<(), std::io::Error>
+	{
+		if rating > MAX_RATING
+		{
+			return io_error!(InvalidInput, format!("Rating must be in the range 0 to {}, got {}", MAX_RATING, rating));
+		}
+
+		self.set_tag(ExifTag::Rating(vec![rating]));
+
+		if sync_percent
+		{
+			self.set_tag(ExifTag::RatingPercent(vec![rating_to_percent(rating)]));
+		}
+
+		return Ok(());
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use crate::exif_tag::ExifTag;
+	use crate::metadata::Metadata;
+
+	#[test]
+	fn
+	set_and_get_rating()
+	{
+		let mut metadata = Metadata::new();
+		assert_eq!(metadata.get_rating(), None);
+
+		metadata.set_rating(4).unwrap();
+		assert_eq!(metadata.get_rating(), Some(4));
+		assert!(metadata.get_tag(&ExifTag::RatingPercent(Vec::new())).next().is_none());
+
+		assert!(metadata.set_rating(6).is_err());
+		assert_eq!(metadata.get_rating(), Some(4));
+	}
+
+	#[test]
+	fn
+	rating_percent_is_kept_in_sync()
+	{
+		let mut metadata = Metadata::new();
+
+		metadata.set_rating_with_percent(5).unwrap();
+		assert_eq!(
+			metadata.get_tag(&ExifTag::RatingPercent(Vec::new())).next(),
+			Some(&ExifTag::RatingPercent(vec![99]))
+		);
+
+		metadata.set_rating(3).unwrap();
+		assert_eq!(
+			metadata.get_tag(&ExifTag::RatingPercent(Vec::new())).next(),
+			Some(&ExifTag::RatingPercent(vec![50]))
+		);
+	}
+}
@@ -0,0 +1,385 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use std::collections::VecDeque;
+#[cfg(feature = "std_fs")]
+use std::path::Path;
+
+use crate::endian::Endian;
+use crate::exif_tag::ExifTag;
+#[cfg(feature = "std_fs")]
+use crate::filetype::get_file_type;
+use crate::general_file_io::io_error;
+use crate::general_file_io::EXIF_HEADER;
+use crate::ifd::ExifTagGroup;
+use crate::ifd::ImageFileDirectory;
+
+#[cfg(feature = "std_fs")]
+use super::options::ReadOptions;
+use super::Metadata;
+
+const IFD_ENTRY_LENGTH: usize = 12;
+
+/// A single 12 byte IFD entry exactly as stored in the image, without
+/// interpreting its tag or format. Offsets are relative to the start of the
+/// TIFF header, as are all offsets within EXIF data.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct
+RawIfdEntry
+{
+	/// The offset of the IFD the entry belongs to
+	pub ifd_offset:      u32,
+
+	/// The group of that IFD, as determined by the offset tag pointing to it
+	pub group:           ExifTagGroup,
+
+	/// The number of the generic IFD the entry belongs to (directly or via
+	/// one of its SubIFDs)
+	pub generic_ifd_nr:  u32,
+
+	pub tag:             u16,
+	pub format:          u16,
+	pub count:           u32,
+
+	/// The value itself if it fits into 4 bytes, otherwise the offset to it
+	pub value_or_offset: [u8; 4],
+}
+
+impl
+RawIfdEntry
+{
+	/// Interprets the last 4 bytes of the entry as offset
+	pub fn
+	offset
+	(
+		&self,
+		endian: &Endian
+	)
+	-> u32
+	{
+		match endian
+		{
+			Endian::Little => u32::from_le_bytes(self.value_or_offset),
+			Endian::Big    => u32::from_be_bytes(self.value_or_offset),
+		}
+	}
+}
+
+/// Walks the IFDs of raw EXIF data (with or without the "Exif\0\0" header),
+/// starting with IFD0 and following the links to the next generic IFD as
+/// well as the offset tags of the SubIFDs (e.g. `ExifOffset`). The IFDs are
+/// visited in the order in which they are referenced. Yields the position of
+/// each entry in the data together with the entry itself.
+pub(crate) struct
+RawEntryIterator<'a>
+{
+	data:                &'a [u8],
+	endian:              Endian,
+	pending_ifds:        VecDeque<(usize, ExifTagGroup, u32)>,
+	current_ifd:         Option<(usize, usize, ExifTagGroup, u32)>,
+	current_entry:       usize,
+	remaining_ifd_count: usize,
+}
+
+impl<'a>
+RawEntryIterator<'a>
+{
+	/// Checks the TIFF header of the data. Fails if the byte order or the
+	/// offset to IFD0 can't be read. At most `max_ifd_count` IFDs are
+	/// visited, see `ReadOptions::max_ifd_count`.
+	pub(crate) fn
+	new
+	(
+		raw_data:      &'a [u8],
+		max_ifd_count: usize
+	)
+	-> Result<RawEntryIterator<'a>, std::io::Error>
+	{
+		let data = raw_data.strip_prefix(&EXIF_HEADER[..]).unwrap_or(raw_data);
+
+		let mut iterator = RawEntryIterator::empty();
+		iterator.data                = data;
+		iterator.remaining_ifd_count = max_ifd_count;
+
+		iterator.endian = match data.get(0..2)
+		{
+			Some([0x49, 0x49]) => Endian::Little,
+			Some([0x4d, 0x4d]) => Endian::Big,
+			_                  => return io_error!(Other, "Illegal endian information!"),
+		};
+
+		match iterator.read_u32(4)
+		{
+			Some(ifd0_offset) => iterator.pending_ifds.push_back((ifd0_offset as usize, ExifTagGroup::GENERIC, 0)),
+			None              => return io_error!(UnexpectedEof, "Missing offset to IFD0!"),
+		}
+
+		return Ok(iterator);
+	}
+
+	/// An iterator without any entries
+	pub(crate) fn
+	empty
+	()
+	-> RawEntryIterator<'a>
+	{
+		RawEntryIterator {
+			data:                &[],
+			endian:              Endian::Little,
+			pending_ifds:        VecDeque::new(),
+			current_ifd:         None,
+			current_entry:       0,
+			remaining_ifd_count: 0,
+		}
+	}
+
+	/// Gets the data (starting with the TIFF header) the entries refer to
+	pub(crate) fn
+	data
+	(
+		&self
+	)
+	-> &'a [u8]
+	{
+		self.data
+	}
+
+	pub(crate) fn
+	endian
+	(
+		&self
+	)
+	-> &Endian
+	{
+		&self.endian
+	}
+
+	fn
+	read_u16
+	(
+		&self,
+		position: usize
+	)
+	-> Option<u16>
+	{
+		let bytes = self.data.get(position..position + 2)?;
+		return Some(match self.endian
+		{
+			Endian::Little => u16::from_le_bytes([bytes[0], bytes[1]]),
+			Endian::Big    => u16::from_be_bytes([bytes[0], bytes[1]]),
+		});
+	}
+
+	fn
+	read_u32
+	(
+		&self,
+		position: usize
+	)
+	-> Option<u32>
+	{
+		let bytes = self.data.get(position..position + 4)?;
+		return Some(match self.endian
+		{
+			Endian::Little => u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+			Endian::Big    => u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+		});
+	}
+
+	/// Moves on to the next pending IFD, returns false if there is none
+	fn
+	start_next_ifd
+	(
+		&mut self
+	)
+	-> bool
+	{
+		while let Some((offset, group, generic_ifd_nr)) = self.pending_ifds.pop_front()
+		{
+			if self.remaining_ifd_count == 0
+			{
+				self.pending_ifds.clear();
+				return false;
+			}
+			self.remaining_ifd_count -= 1;
+
+			if let Some(entry_count) = self.read_u16(offset)
+			{
+				self.current_ifd   = Some((offset, entry_count as usize, group, generic_ifd_nr));
+				self.current_entry = 0;
+				return true;
+			}
+		}
+		return false;
+	}
+
+	/// Determines the group of the SubIFD an entry points to, if it is one
+	/// of the IFD offset tags (e.g. `ExifOffset`)
+	fn
+	subifd_group
+	(
+		hex:   u16,
+		group: ExifTagGroup
+	)
+	-> Option<ExifTagGroup>
+	{
+		for offset_tag in [
+			ExifTag::ExifOffset(Vec::new()),
+			ExifTag::GPSInfo(Vec::new()),
+			ExifTag::InteropOffset(Vec::new()),
+		]
+		{
+			if offset_tag.as_u16() == hex && offset_tag.get_group() == group
+			{
+				return ImageFileDirectory::get_ifd_type_for_offset_tag(&offset_tag);
+			}
+		}
+		return None;
+	}
+}
+
+impl<'a>
+Iterator
+for
+RawEntryIterator<'a>
+{
+	type Item = (usize, RawIfdEntry);
+
+	fn
+	next
+	(
+		&mut self
+	)
+	-> Option<Self::Item>
+	{
+		loop
+		{
+			let (ifd_offset, entry_count, group, generic_ifd_nr) = match self.current_ifd
+			{
+				Some(current_ifd) => current_ifd,
+				None              => {
+					if !self.start_next_ifd() { return None; }
+					continue;
+				},
+			};
+
+			if self.current_entry >= entry_count
+			{
+				// Generic IFDs link to the next one after their entries
+				if group == ExifTagGroup::GENERIC
+				{
+					if let Some(next_ifd_offset) = self.read_u32(ifd_offset + 2 + entry_count * IFD_ENTRY_LENGTH)
+					{
+						if next_ifd_offset != 0
+						{
+							self.pending_ifds.push_back((next_ifd_offset as usize, group, generic_ifd_nr + 1));
+						}
+					}
+				}
+				self.current_ifd = None;
+				continue;
+			}
+
+			let entry_position = ifd_offset + 2 + self.current_entry * IFD_ENTRY_LENGTH;
+			self.current_entry += 1;
+
+			let (tag, format, count, value_or_offset) = match (
+				self.read_u16(entry_position),
+				self.read_u16(entry_position + 2),
+				self.read_u32(entry_position + 4),
+				self.data.get(entry_position + 8..entry_position + 12),
+			)
+			{
+				(Some(tag), Some(format), Some(count), Some(value)) => (tag, format, count, [value[0], value[1], value[2], value[3]]),
+				_                                                   => { self.current_ifd = None; continue; },
+			};
+
+			let entry = RawIfdEntry {
+				ifd_offset: ifd_offset as u32,
+				group,
+				generic_ifd_nr,
+				tag,
+				format,
+				count,
+				value_or_offset,
+			};
+
+			if let Some(subifd_group) = Self::subifd_group(tag, group)
+			{
+				self.pending_ifds.push_back((entry.offset(&self.endian) as usize, subifd_group, generic_ifd_nr));
+			}
+
+			return Some((entry_position, entry));
+		}
+	}
+}
+
+impl
+Metadata
+{
+	/// Reads the IFD entries of the image at the given path exactly as they
+	/// are stored, without constructing any `ExifTag`s. Each IFD is listed
+	/// completely before moving on to the next one, in the order in which
+	/// they are referenced (e.g. IFD0, ExifIFD, GPS, IFD1). Useful for 
+	/// debugging files that don't decode as expected.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	///
+	/// for entry in Metadata::raw_entries(std::path::Path::new("image.jpg")).unwrap()
+	/// {
+	///     println!("0x{:04x} format {} count {}", entry.tag, entry.format, entry.count);
+	/// }
+	/// ```
+	#[cfg(feature = "std_fs")]
+	pub fn
+	raw_entries
+	(
+		path: &Path
+	)
+	-> Result<Vec<RawIfdEntry>, std::io::Error>
+	{
+		let file_type = get_file_type(path)?;
+		let options   = ReadOptions::default();
+		let raw_data  = Self::file_read_raw_metadata(path, file_type, &options, &mut Vec::new(), "raw_entries")??;
+
+		return Ok(RawEntryIterator::new(&raw_data, options.max_ifd_count)?.map(|(_, entry)| entry).collect());
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use crate::filetype::FileExtension;
+	use crate::metadata::options::ReadOptions;
+	use crate::metadata::options::DEFAULT_MAX_IFD_COUNT;
+	use crate::metadata::Metadata;
+
+	use super::RawEntryIterator;
+
+	#[test]
+	fn
+	stops_at_max_ifd_count()
+	{
+		// Little endian TIFF header followed by a chain of 300 generic IFDs,
+		// each one holding a `Software` tag ("abc") and linking to the next
+		let chain_length = 300u32;
+		let mut image_data = vec![0x49, 0x49, 0x2a, 0x00, 0x08, 0x00, 0x00, 0x00];
+		for ifd_nr in 0..chain_length
+		{
+			let next_offset = if ifd_nr + 1 < chain_length { 8 + (ifd_nr + 1) * 18 } else { 0 };
+			image_data.extend([0x01, 0x00]);
+			image_data.extend([0x31, 0x01, 0x02, 0x00, 0x04, 0x00, 0x00, 0x00, b'a', b'b', b'c', 0x00]);
+			image_data.extend(next_offset.to_le_bytes());
+		}
+
+		assert_eq!(RawEntryIterator::new(&image_data, DEFAULT_MAX_IFD_COUNT).unwrap().count(), DEFAULT_MAX_IFD_COUNT);
+		assert_eq!(RawEntryIterator::new(&image_data, 1000).unwrap().count(),                  chain_length as usize);
+
+		// The limit of the read options also applies to the retained data
+		let options  = ReadOptions { max_ifd_count: 1000, retain_raw_data: true, ..ReadOptions::default() };
+		let metadata = Metadata::new_from_vec_with_options(&image_data, FileExtension::TIFF, &options).unwrap();
+		assert_eq!(metadata.borrowed_tags().count(), chain_length as usize);
+	}
+}
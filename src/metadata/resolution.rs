@@ -0,0 +1,179 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::exif_tag::ExifTag;
+use crate::rational::uR64;
+
+use super::Metadata;
+
+/// The unit of the `XResolution` and `YResolution` tags as stored in the
+/// `ResolutionUnit` tag (0x0128). If that tag is absent, the unit defaults
+/// to inches.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+ResolutionUnit
+{
+	None,                                                                       // 1
+	Inches,                                                                     // 2
+	Centimeters,                                                                // 3
+}
+
+impl
+ResolutionUnit
+{
+	/// Gets the unit for a value of the `ResolutionUnit` tag
+	pub fn
+	from_u16
+	(
+		value: u16
+	)
+	-> Option<ResolutionUnit>
+	{
+		match value
+		{
+			1 => Some(ResolutionUnit::None),
+			2 => Some(ResolutionUnit::Inches),
+			3 => Some(ResolutionUnit::Centimeters),
+			_ => None,
+		}
+	}
+
+	/// Gets the value as stored in the `ResolutionUnit` tag
+	pub fn
+	as_u16
+	(
+		&self
+	)
+	-> u16
+	{
+		match self
+		{
+			ResolutionUnit::None        => 1,
+			ResolutionUnit::Inches      => 2,
+			ResolutionUnit::Centimeters => 3,
+		}
+	}
+}
+
+/// Gets the value of a single component rational tag as `f64`
+fn
+rational_to_f64
+(
+	value: &Vec<uR64>
+)
+-> Option<f64>
+{
+	let rational = value.first().filter(|rational| rational.denominator != 0)?;
+	return Some(rational.nominator as f64 / rational.denominator as f64);
+}
+
+impl
+Metadata
+{
+	/// Gets the horizontal and vertical resolution from the `XResolution`
+	/// and `YResolution` tags together with their unit. If the
+	/// `ResolutionUnit` tag is absent, inches are assumed as required by the
+	/// standard; an invalid unit results in `None`.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::metadata::resolution::ResolutionUnit;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some((x, y, ResolutionUnit::Inches)) = metadata.get_resolution()
+	/// {
+	///     println!("{}x{} DPI", x, y);
+	/// }
+	/// # }
+	/// ```
+	pub fn
+	get_resolution
+	(
+		&self
+	)
+	-> Option<(f64, f64, ResolutionUnit)>
+	{
+		let x_resolution = match self.get_tag(&ExifTag::XResolution(Vec::new())).next()
+		{
+			Some(ExifTag::XResolution(value)) => rational_to_f64(value)?,
+			_                                 => return None,
+		};
+
+		let y_resolution = match self.get_tag(&ExifTag::YResolution(Vec::new())).next()
+		{
+			Some(ExifTag::YResolution(value)) => rational_to_f64(value)?,
+			_                                 => return None,
+		};
+
+		let unit = match self.get_tag(&ExifTag::ResolutionUnit(Vec::new())).next()
+		{
+			Some(ExifTag::ResolutionUnit(value)) => ResolutionUnit::from_u16(*value.first()?)?,
+			_                                    => ResolutionUnit::Inches,
+		};
+
+		return Some((x_resolution, y_resolution, unit));
+	}
+
+	/// Sets the `XResolution`, `YResolution` and `ResolutionUnit` tags. The
+	/// resolutions are stored with a precision of 1/1000.
+	pub fn
+	set_resolution
+	(
+		&mut self,
+		x_resolution: f64,
+		y_resolution: f64,
+		unit:         ResolutionUnit
+	)
+	{
+		let to_rational = |resolution: f64| uR64 {
+			nominator:   (resolution.abs() * 1000.0).round() as u32,
+			denominator: 1000
+		};
+
+		self.set_tag(ExifTag::XResolution(   vec![to_rational(x_resolution)]));
+		self.set_tag(ExifTag::YResolution(   vec![to_rational(y_resolution)]));
+		self.set_tag(ExifTag::ResolutionUnit(vec![unit.as_u16()]));
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use crate::exif_tag::ExifTag;
+	use crate::metadata::Metadata;
+	use crate::rational::uR64;
+
+	use super::ResolutionUnit;
+
+	#[test]
+	fn
+	resolution_defaults_to_inches()
+	{
+		let mut metadata = Metadata::new();
+		assert_eq!(metadata.get_resolution(), None);
+
+		metadata.set_tag(ExifTag::XResolution(vec![uR64 { nominator: 72, denominator: 1 }]));
+		metadata.set_tag(ExifTag::YResolution(vec![uR64 { nominator: 72, denominator: 1 }]));
+		assert_eq!(metadata.get_resolution(), Some((72.0, 72.0, ResolutionUnit::Inches)));
+
+		metadata.set_tag(ExifTag::ResolutionUnit(vec![4]));
+		assert_eq!(metadata.get_resolution(), None);
+	}
+
+	#[test]
+	fn
+	set_and_get_300_dpi()
+	{
+		let mut metadata = Metadata::new();
+		metadata.set_resolution(300.0, 300.0, ResolutionUnit::Inches);
+
+		assert_eq!(metadata.get_resolution(), Some((300.0, 300.0, ResolutionUnit::Inches)));
+		assert_eq!(
+			metadata.get_tag(&ExifTag::ResolutionUnit(Vec::new())).next(),
+			Some(&ExifTag::ResolutionUnit(vec![2]))
+		);
+	}
+}
@@ -1,6 +1,7 @@
 // Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
 // See https://github.com/TechnikTobi/little_exif#license for licensing details
 
+use crate::endian::Endian;
 use crate::exif_tag::ExifTag;
 
 use super::Metadata;
@@ -23,6 +24,98 @@ Metadata
 		input_tag: ExifTag
 	)
 	{
+		// A tag explicitly set by the user is no longer considered derived
+		self.derived_tags.retain(|derived_tag| 
+			derived_tag.as_u16() != input_tag.as_u16() || derived_tag.get_group() != input_tag.get_group()
+		);
 		self.get_ifd_mut(input_tag.get_group(), 0).set_tag(input_tag);
 	}
+
+	/// Sets the tag in the IFD of a specific page of a multi-page TIFF, i.e.
+	/// the IFD of the tag's group that belongs to the generic IFD number
+	/// `generic_ifd_nr` (0 for the first page, which is equivalent to
+	/// `set_tag`). If that IFD does not exist yet it gets created.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::exif_tag::ExifTag;
+	///
+	/// let mut metadata = Metadata::new_from_path(std::path::Path::new("image.tif")).unwrap();
+	/// metadata.set_tag_in_page(1, ExifTag::PageName("Back side".to_string()));
+	/// metadata.write_to_file(std::path::Path::new("image.tif")).unwrap();
+	/// # }
+	/// ```
+	pub fn
+	set_tag_in_page
+	(
+		&mut self,
+		generic_ifd_nr: u32,
+		input_tag:      ExifTag
+	)
+	{
+		if generic_ifd_nr == 0
+		{
+			self.set_tag(input_tag);
+			return;
+		}
+		self.get_ifd_mut(input_tag.get_group(), generic_ifd_nr).set_tag(input_tag);
+	}
+
+	/// Sets the endianness used for encoding the metadata. 
+	/// By default, writing to an image that already contains metadata keeps
+	/// the endianness of the existing metadata. After calling this, the 
+	/// given endianness is used instead.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::endian::Endian;
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let mut metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// metadata.set_endian(Endian::Big);
+	/// metadata.write_to_file(std::path::Path::new("image.jpg")).unwrap();
+	/// # }
+	/// ```
+	pub fn
+	set_endian
+	(
+		&mut self,
+		endian: Endian
+	)
+	{
+		self.endian             = endian;
+		self.endian_is_explicit = true;
+	}
+
+	/// Sets whether IFDs without any tags are kept when encoding the
+	/// metadata. By default, such IFDs are skipped as they are not valid
+	/// according to the TIFF specification, together with offset tags (e.g.
+	/// `GPSInfo`) pointing to IFDs that don't exist. When keeping empty IFDs,
+	/// the IFDs for such offset tags get created instead.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let mut metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// metadata.set_keep_empty_ifds(true);
+	/// metadata.write_to_file(std::path::Path::new("image.jpg")).unwrap();
+	/// # }
+	/// ```
+	pub fn
+	set_keep_empty_ifds
+	(
+		&mut self,
+		keep_empty_ifds: bool
+	)
+	{
+		self.keep_empty_ifds = keep_empty_ifds;
+	}
 }
\ No newline at end of file
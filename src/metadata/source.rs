@@ -0,0 +1,229 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::exif_tag::ExifTag;
+
+use super::Metadata;
+
+/// The values of the `FileSource` tag (0xa300), describing the device that
+/// created the image. Stored as a single `UNDEF` byte.
+/// See https://exiftool.org/TagNames/EXIF.html (0xa300)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+FileSource
+{
+	Others,                                                                     // 0
+	FilmScanner,                                                                // 1: Scanner of transparent type
+	ReflectionPrintScanner,                                                     // 2: Scanner of reflex type
+	DSC,                                                                        // 3: Digital still camera
+	Other(u8),                                                                  // Not part of the EXIF standard
+}
+
+impl
+FileSource
+{
+	/// Gets the file source for the byte stored in the `FileSource` tag
+	pub fn
+	from_u8
+	(
+		value: u8
+	)
+	-> FileSource
+	{
+		match value
+		{
+			0 => FileSource::Others,
+			1 => FileSource::FilmScanner,
+			2 => FileSource::ReflectionPrintScanner,
+			3 => FileSource::DSC,
+			_ => FileSource::Other(value),
+		}
+	}
+
+	/// Gets the byte as stored in the `FileSource` tag
+	pub fn
+	as_u8
+	(
+		&self
+	)
+	-> u8
+	{
+		match self
+		{
+			FileSource::Others                 => 0,
+			FileSource::FilmScanner            => 1,
+			FileSource::ReflectionPrintScanner => 2,
+			FileSource::DSC                    => 3,
+			FileSource::Other(value)           => *value,
+		}
+	}
+}
+
+/// The values of the `SceneType` tag (0xa301), which tells whether the image
+/// was photographed directly. Stored as a single `UNDEF` byte.
+/// See https://exiftool.org/TagNames/EXIF.html (0xa301)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+SceneType
+{
+	DirectlyPhotographed,                                                       // 1
+	Other(u8),                                                                  // Not part of the EXIF standard
+}
+
+impl
+SceneType
+{
+	/// Gets the scene type for the byte stored in the `SceneType` tag
+	pub fn
+	from_u8
+	(
+		value: u8
+	)
+	-> SceneType
+	{
+		match value
+		{
+			1 => SceneType::DirectlyPhotographed,
+			_ => SceneType::Other(value),
+		}
+	}
+
+	/// Gets the byte as stored in the `SceneType` tag
+	pub fn
+	as_u8
+	(
+		&self
+	)
+	-> u8
+	{
+		match self
+		{
+			SceneType::DirectlyPhotographed => 1,
+			SceneType::Other(value)         => *value,
+		}
+	}
+}
+
+impl
+Metadata
+{
+	/// Gets the decoded `FileSource` tag, if present. Some cameras (e.g. by
+	/// Sigma) pad the value to 4 bytes, in which case the first one is used.
+	pub fn
+	get_file_source
+	(
+		&self
+	)
+	-> Option<FileSource>
+	{
+		if let Some(ExifTag::FileSource(value)) = self.get_tag(&ExifTag::FileSource(Vec::new())).next()
+		{
+			return value.first().map(|raw_value| FileSource::from_u8(*raw_value));
+		}
+		return None;
+	}
+
+	/// Sets the `FileSource` tag as a single `UNDEF` byte
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::metadata::source::FileSource;
+	///
+	/// let mut metadata = Metadata::new();
+	/// metadata.set_file_source(FileSource::DSC);
+	/// assert_eq!(metadata.get_file_source(), Some(FileSource::DSC));
+	/// ```
+	pub fn
+	set_file_source
+	(
+		&mut self,
+		file_source: FileSource
+	)
+	{
+		self.set_tag(ExifTag::FileSource(vec![file_source.as_u8()]));
+	}
+
+	/// Gets the decoded `SceneType` tag, if present. If the value has more
+	/// than one byte, the first one is used.
+	pub fn
+	get_scene_type
+	(
+		&self
+	)
+	-> Option<SceneType>
+	{
+		if let Some(ExifTag::SceneType(value)) = self.get_tag(&ExifTag::SceneType(Vec::new())).next()
+		{
+			return value.first().map(|raw_value| SceneType::from_u8(*raw_value));
+		}
+		return None;
+	}
+
+	/// Sets the `SceneType` tag as a single `UNDEF` byte
+	pub fn
+	set_scene_type
+	(
+		&mut self,
+		scene_type: SceneType
+	)
+	{
+		self.set_tag(ExifTag::SceneType(vec![scene_type.as_u8()]));
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use crate::endian::Endian;
+	use crate::exif_tag::ExifTag;
+	use crate::filetype::FileExtension;
+	use crate::metadata::options::DEFAULT_MAX_IFD_COUNT;
+	use crate::metadata::raw::RawEntryIterator;
+	use crate::metadata::Metadata;
+
+	use super::FileSource;
+	use super::SceneType;
+
+	#[test]
+	fn
+	round_trip_values()
+	{
+		for value in 0..=255
+		{
+			assert_eq!(FileSource::from_u8(value).as_u8(), value);
+			assert_eq!(SceneType::from_u8(value).as_u8(),  value);
+		}
+	}
+
+	#[test]
+	fn
+	file_source_and_scene_type_round_trip()
+	{
+		let mut metadata = Metadata::new();
+		assert_eq!(metadata.get_file_source(), None);
+		assert_eq!(metadata.get_scene_type(),  None);
+
+		metadata.set_endian(Endian::Big);
+		metadata.set_file_source(FileSource::DSC);
+		metadata.set_scene_type(SceneType::DirectlyPhotographed);
+
+		// Both are stored as a single UNDEF byte within the entry itself
+		let encoded = metadata.encode().unwrap();
+		for (hex, value) in [(0xa300, 3), (0xa301, 1)]
+		{
+			let (_, entry) = RawEntryIterator::new(&encoded, DEFAULT_MAX_IFD_COUNT).unwrap().find(|(_, entry)| entry.tag == hex).unwrap();
+			assert_eq!(entry.format,          7);
+			assert_eq!(entry.count,           1);
+			assert_eq!(entry.value_or_offset, [value, 0, 0, 0]);
+		}
+
+		let metadata = Metadata::new_from_vec(&encoded, FileExtension::TIFF).unwrap();
+		assert_eq!(metadata.get_file_source(), Some(FileSource::DSC));
+		assert_eq!(metadata.get_scene_type(),  Some(SceneType::DirectlyPhotographed));
+		assert_eq!(
+			metadata.get_tag(&ExifTag::FileSource(Vec::new())).next(),
+			Some(&ExifTag::FileSource(vec![3]))
+		);
+	}
+}
@@ -0,0 +1,177 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::exif_tag::ExifTag;
+use crate::ifd::ExifTagGroup;
+
+use super::Metadata;
+
+/// The flags of the `NewSubfileType` tag (0x00fe), describing the kind of
+/// image stored in an IFD. 
+/// See https://exiftool.org/TagNames/EXIF.html (0x00fe)
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct
+NewSubfileType
+{
+	/// Bit 0: The image is a reduced-resolution version of another image
+	pub reduced_resolution: bool,
+
+	/// Bit 1: The image is a single page of a multi-page image
+	pub page:               bool,
+
+	/// Bit 2: The image defines a transparency mask for another image
+	pub transparency_mask:  bool,
+}
+
+impl
+NewSubfileType
+{
+	/// Interprets the bitfield as stored in the `NewSubfileType` tag. 
+	/// Unknown bits (e.g. the DNG specific ones) are ignored.
+	pub fn
+	from_u32
+	(
+		value: u32
+	)
+	-> NewSubfileType
+	{
+		NewSubfileType
+		{
+			reduced_resolution: value & 0b001 != 0,
+			page:               value & 0b010 != 0,
+			transparency_mask:  value & 0b100 != 0,
+		}
+	}
+
+	/// Gets the value as stored in the `NewSubfileType` tag
+	pub fn
+	as_u32
+	(
+		&self
+	)
+	-> u32
+	{
+		(self.reduced_resolution as u32)
+		| (self.page              as u32) << 1
+		| (self.transparency_mask as u32) << 2
+	}
+}
+
+/// What kind of image an IFD describes, e.g. for distinguishing the main
+/// image of a multi-IFD TIFF or DNG from its previews and masks
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+SubfileKind
+{
+	/// The full-resolution image
+	Main,
+
+	/// A reduced-resolution version of another image (e.g. a preview)
+	ReducedResolution,
+
+	/// A page of a multi-page image
+	Page,
+
+	/// A transparency mask for another image
+	TransparencyMask,
+}
+
+impl
+Metadata
+{
+	/// Gets the decoded `NewSubfileType` tag of the generic IFD with the
+	/// given number, if present.
+	pub fn
+	get_new_subfile_type
+	(
+		&self,
+		generic_ifd_nr: u32
+	)
+	-> Option<NewSubfileType>
+	{
+		let ifd = self.get_ifd(ExifTagGroup::GENERIC, generic_ifd_nr)?;
+		for tag in ifd.get_tags()
+		{
+			if let ExifTag::NewSubfileType(value) = tag
+			{
+				return value.first().map(|raw_value| NewSubfileType::from_u32(*raw_value));
+			}
+		}
+		return None;
+	}
+
+	/// Classifies the generic IFD with the given number using its 
+	/// `NewSubfileType` tag or - if that does not exist - the deprecated
+	/// `SubfileType` tag (0x00ff). A transparency mask takes precedence over
+	/// the other flags, and a reduced-resolution page counts as reduced-
+	/// resolution image. Without either of the tags, the IFD is considered
+	/// to contain the main image, as this is the default of the tags.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::metadata::subfile::SubfileKind;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.dng")).unwrap();
+	/// if metadata.classify_ifd(1) == SubfileKind::ReducedResolution
+	/// {
+	///     println!("IFD1 contains a preview");
+	/// }
+	/// # }
+	/// ```
+	pub fn
+	classify_ifd
+	(
+		&self,
+		generic_ifd_nr: u32
+	)
+	-> SubfileKind
+	{
+		if let Some(new_subfile_type) = self.get_new_subfile_type(generic_ifd_nr)
+		{
+			if new_subfile_type.transparency_mask  { return SubfileKind::TransparencyMask;  }
+			if new_subfile_type.reduced_resolution { return SubfileKind::ReducedResolution; }
+			if new_subfile_type.page               { return SubfileKind::Page;              }
+			return SubfileKind::Main;
+		}
+
+		if let Some(ifd) = self.get_ifd(ExifTagGroup::GENERIC, generic_ifd_nr)
+		{
+			for tag in ifd.get_tags()
+			{
+				if let ExifTag::SubfileType(value) = tag
+				{
+					return match value.first()
+					{
+						Some(2) => SubfileKind::ReducedResolution,
+						Some(3) => SubfileKind::Page,
+						_       => SubfileKind::Main,
+					};
+				}
+			}
+		}
+
+		return SubfileKind::Main;
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::NewSubfileType;
+
+	#[test]
+	fn
+	new_subfile_type_round_trip()
+	{
+		for value in 0..8
+		{
+			assert_eq!(NewSubfileType::from_u32(value).as_u32(), value);
+		}
+
+		// DNG specific bits get ignored
+		assert_eq!(NewSubfileType::from_u32(0x10001), NewSubfileType { reduced_resolution: true, ..NewSubfileType::default() });
+	}
+}
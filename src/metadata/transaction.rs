@@ -0,0 +1,282 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use std::io::Error;
+use std::io::ErrorKind;
+
+use crate::endian::Endian;
+use crate::exif_tag::ExifTag;
+
+use super::Metadata;
+
+/// A single operation of a `MetadataTransaction`
+#[derive(Clone, Debug, PartialEq)]
+pub enum
+TransactionOperation
+{
+	Set(ExifTag),
+	Remove(ExifTag),
+}
+
+/// A batch of tag edits that gets validated as a whole before any of them
+/// is applied to a `Metadata` struct. If a single operation is invalid,
+/// none of the operations are applied.
+///
+/// # Examples
+/// ```no_run
+/// # #[cfg(feature = "std_fs")]
+/// # {
+/// use little_exif::exif_tag::ExifTag;
+/// use little_exif::metadata::Metadata;
+/// use little_exif::metadata::transaction::MetadataTransaction;
+///
+/// let mut metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+///
+/// let mut transaction = MetadataTransaction::new();
+/// transaction.set(ExifTag::ISO(vec![400]));
+/// transaction.remove(ExifTag::Artist(String::new()));
+///
+/// if let Err(errors) = transaction.commit(&mut metadata)
+/// {
+///     for error in errors
+///     {
+///         println!("{}", error);
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct
+MetadataTransaction
+{
+	operations: Vec<TransactionOperation>,
+}
+
+impl
+MetadataTransaction
+{
+	/// Constructs a new, empty transaction
+	pub fn
+	new
+	()
+	-> MetadataTransaction
+	{
+		MetadataTransaction::default()
+	}
+
+	/// Gets the operations of the transaction in the order they were added
+	pub fn
+	get_operations
+	(
+		&self
+	)
+	-> &Vec<TransactionOperation>
+	{
+		&self.operations
+	}
+
+	/// Adds an operation setting the given tag, see `Metadata::set_tag`
+	pub fn
+	set
+	(
+		&mut self,
+		tag: ExifTag
+	)
+	-> &mut MetadataTransaction
+	{
+		self.operations.push(TransactionOperation::Set(tag));
+		return self;
+	}
+
+	/// Adds an operation removing the given tag from the IFD of its group
+	/// (starting with IFD0). Only the hex value and group of the tag are
+	/// relevant, not its value.
+	pub fn
+	remove
+	(
+		&mut self,
+		tag: ExifTag
+	)
+	-> &mut MetadataTransaction
+	{
+		self.operations.push(TransactionOperation::Remove(tag));
+		return self;
+	}
+
+	/// Checks all operations of the transaction and returns an error for
+	/// each invalid one. An operation is invalid if
+	/// - its tag is not writable (e.g. offset tags like `ExifOffset`),
+	/// - an unknown tag is used for a hex value known to little_exif or
+	/// - a set tag has not the number of components required by the EXIF
+	///   specification (e.g. 3 for `GPSLatitude`).
+	pub fn
+	validate
+	(
+		&self
+	)
+	-> Result<(), Vec<Error>>
+	{
+		let mut errors = Vec::new();
+
+		for (index, operation) in self.operations.iter().enumerate()
+		{
+			let tag = match operation
+			{
+				TransactionOperation::Set(tag)    => tag,
+				TransactionOperation::Remove(tag) => tag,
+			};
+
+			if !tag.is_writable()
+			{
+				errors.push(Error::new(
+					ErrorKind::InvalidInput,
+					format!("Operation {}: Tag {:?} is not writable", index, tag)
+				));
+				continue;
+			}
+
+			if !tag.unknown_is_justified()
+			{
+				errors.push(Error::new(
+					ErrorKind::InvalidInput,
+					format!("Operation {}: Unknown tag used for known hex value 0x{:04x}", index, tag.as_u16())
+				));
+				continue;
+			}
+
+			if let TransactionOperation::Set(tag) = operation
+			{
+				if let Some(error) = check_number_of_components(tag)
+				{
+					errors.push(Error::new(
+						ErrorKind::InvalidInput,
+						format!("Operation {}: {}", index, error)
+					));
+				}
+			}
+		}
+
+		if errors.is_empty()
+		{
+			return Ok(());
+		}
+		return Err(errors);
+	}
+
+	/// Validates the transaction and - if all operations are valid - applies
+	/// them in order to the given `Metadata` struct. Otherwise, the struct
+	/// stays untouched and the errors of `validate` are returned.
+	pub fn
+	commit
+	(
+		&self,
+		metadata: &mut Metadata
+	)
+	-> Result<(), Vec<Error>>
+	{
+		self.validate()?;
+
+		for operation in self.operations.iter()
+		{
+			match operation
+			{
+				TransactionOperation::Set(tag)
+				=> metadata.set_tag(tag.clone()),
+
+				TransactionOperation::Remove(tag)
+				=> metadata.get_ifd_mut(tag.get_group(), 0).remove_tag(tag.clone()),
+			}
+		}
+
+		return Ok(());
+	}
+}
+
+/// Compares the number of components of a tag's value with the number
+/// predefined by the specification, if there is one. Strings are not
+/// checked, as their length gets adjusted via padding when encoding.
+fn
+check_number_of_components
+(
+	tag: &ExifTag
+)
+-> Option<String>
+{
+	if tag.is_string() || tag.is_unknown()
+	{
+		return None;
+	}
+
+	// A tag created without data reports the predefined number
+	let expected = match ExifTag::from_u16(tag.as_u16(), &tag.get_group())
+	{
+		Ok(empty_tag) => empty_tag.number_of_components(),
+		Err(_)        => return None,
+	};
+
+	let actual = tag.value_as_u8_vec(&Endian::Little).len() as u32 / tag.format().bytes_per_component();
+
+	if expected > 0 && actual != expected
+	{
+		return Some(format!("Tag {:?} requires {} components, got {}", tag, expected, actual));
+	}
+	return None;
+}
+
+#[cfg(test)]
+mod tests
+{
+	use crate::exif_tag::ExifTag;
+	use crate::ifd::ExifTagGroup;
+	use crate::metadata::Metadata;
+	use crate::rational::uR64;
+
+	use super::MetadataTransaction;
+
+	#[test]
+	fn
+	invalid_operation_aborts_transaction()
+	{
+		let mut metadata = Metadata::new();
+		metadata.set_tag(ExifTag::Artist("Someone".to_string()));
+
+		let mut transaction = MetadataTransaction::new();
+		transaction
+			.set(ExifTag::ISO(vec![400]))
+			.remove(ExifTag::Artist(String::new()))
+			.set(ExifTag::GPSLatitude(vec![uR64 { nominator: 48, denominator: 1 }]));
+
+		let errors = transaction.validate().unwrap_err();
+		assert_eq!(errors.len(), 1);
+
+		// Nothing may be applied, not even the valid operations
+		assert!(transaction.commit(&mut metadata).is_err());
+		assert_eq!(metadata.get_tag(&ExifTag::ISO(Vec::new())).next(), None);
+		assert_eq!(
+			metadata.get_tag(&ExifTag::Artist(String::new())).next(),
+			Some(&ExifTag::Artist("Someone".to_string()))
+		);
+
+		// Without the invalid operation, everything is applied
+		let mut transaction = MetadataTransaction::new();
+		transaction
+			.set(ExifTag::ISO(vec![400]))
+			.remove(ExifTag::Artist(String::new()))
+			.set(ExifTag::GPSLatitude(vec![uR64 { nominator: 48, denominator: 1 }; 3]));
+
+		assert!(transaction.commit(&mut metadata).is_ok());
+		assert_eq!(metadata.get_tag(&ExifTag::ISO(Vec::new())).next(), Some(&ExifTag::ISO(vec![400])));
+		assert_eq!(metadata.get_tag(&ExifTag::Artist(String::new())).next(), None);
+	}
+
+	#[test]
+	fn
+	offset_and_unjustified_unknown_tags_are_invalid()
+	{
+		let mut transaction = MetadataTransaction::new();
+		transaction.set(ExifTag::ExifOffset(vec![8]));
+		transaction.remove(ExifTag::UnknownINT16U(vec![400], 0x8827, ExifTagGroup::EXIF));
+
+		assert_eq!(transaction.validate().unwrap_err().len(), 2);
+	}
+}
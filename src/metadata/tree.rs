@@ -0,0 +1,133 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::ifd::ExifTagGroup;
+use crate::ifd::ImageFileDirectory;
+
+use super::Metadata;
+
+/// A node in the tree of image file directories as returned by
+/// `Metadata::ifd_tree`. The `tag_count` does not include offset tags that
+/// link to the `children` (e.g. `ExifOffset` in IFD0), as these are not
+/// stored but only created while encoding.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct
+IfdNode
+{
+	pub group:      ExifTagGroup,
+	pub generic_nr: u32,
+	pub tag_count:  usize,
+	pub children:   Vec<IfdNode>,
+}
+
+impl
+Metadata
+{
+	/// Reconstructs the structure of the image file directories: The roots
+	/// are the generic IFDs (IFD0, IFD1, ...) in ascending order, with the
+	/// SubIFDs (e.g. ExifIFD, GPS) that are linked from them via offset tags
+	/// (e.g. `ExifOffset`, `GPSInfo`) as children. IFDs that can't be linked
+	/// to a parent are returned as additional roots so nothing gets hidden.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// for node in metadata.ifd_tree()
+	/// {
+	///     println!("{:?} {}: {} tags", node.group, node.generic_nr, node.tag_count);
+	/// }
+	/// # }
+	/// ```
+	pub fn
+	ifd_tree
+	(
+		&self
+	)
+	-> Vec<IfdNode>
+	{
+		let mut visited = vec![false; self.image_file_directories.len()];
+		let mut roots   = Vec::new();
+
+		let mut generic_ifd_indices = self.image_file_directories.iter()
+			.enumerate()
+			.filter(|(_, ifd)| ifd.get_ifd_type() == ExifTagGroup::GENERIC)
+			.map(|(index, _)| index)
+			.collect::<Vec<usize>>();
+		generic_ifd_indices.sort_by_key(|index| self.image_file_directories[*index].get_generic_ifd_nr());
+
+		for index in generic_ifd_indices
+		{
+			roots.push(self.build_ifd_node(index, &mut visited));
+		}
+
+		for index in 0..self.image_file_directories.len()
+		{
+			if !visited[index]
+			{
+				roots.push(self.build_ifd_node(index, &mut visited));
+			}
+		}
+
+		return roots;
+	}
+
+	fn
+	build_ifd_node
+	(
+		&self,
+		index:   usize,
+		visited: &mut Vec<bool>
+	)
+	-> IfdNode
+	{
+		visited[index] = true;
+
+		let ifd      = &self.image_file_directories[index];
+		let children = self.image_file_directories.iter()
+			.enumerate()
+			.filter(|(child_index, child)|
+				!visited[*child_index] &&
+				is_linked_child(ifd, child)
+			)
+			.map(|(child_index, _)| child_index)
+			.collect::<Vec<usize>>();
+
+		return IfdNode {
+			group:      ifd.get_ifd_type(),
+			generic_nr: ifd.get_generic_ifd_nr(),
+			tag_count:  ifd.get_tags().len(),
+			children:   children.into_iter()
+				.map(|child_index| self.build_ifd_node(child_index, visited))
+				.collect(),
+		};
+	}
+}
+
+/// Checks if the `child` IFD is linked from the `parent` IFD via an offset
+/// tag, e.g. the `GPSInfo` tag in IFD0 for the GPS IFD of IFD0.
+/// The offset tag itself is not stored after decoding, so the link is
+/// determined via the group of the two IFDs.
+fn
+is_linked_child
+(
+	parent: &ImageFileDirectory,
+	child:  &ImageFileDirectory
+)
+-> bool
+{
+	if parent.get_generic_ifd_nr() != child.get_generic_ifd_nr()
+	{
+		return false;
+	}
+
+	if let Some((parent_group, _)) = child.get_offset_tag_for_parent_ifd()
+	{
+		return parent_group == parent.get_ifd_type();
+	}
+
+	return false;
+}
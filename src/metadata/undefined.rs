@@ -0,0 +1,212 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use std::collections::HashMap;
+
+use crate::endian::Endian;
+use crate::exif_tag::ExifTag;
+use crate::ifd::ExifTagGroup;
+
+use super::Metadata;
+
+/// A decoder for the raw bytes of an `UNDEF` tag, getting the byte order of
+/// the EXIF data for interpreting multi-byte values
+pub type UndefinedDecoder<T> = Box<dyn Fn(&[u8], &Endian) -> Option<T>>;
+
+/// Custom decoders for structured `UNDEF` tags that little_exif does not
+/// interpret itself (e.g. `DeviceSettingDescription` or vendor specific
+/// tags), keyed by the hex value of the tag. All decoders produce the same
+/// type `T`, which usually is an enum defined by the user with one variant
+/// per decoded structure.
+pub struct
+UndefinedDecoders<T>
+{
+	decoders: HashMap<u16, UndefinedDecoder<T>>,
+}
+
+impl<T>
+Default
+for
+UndefinedDecoders<T>
+{
+	fn
+	default
+	()
+	-> Self
+	{
+		return UndefinedDecoders { decoders: HashMap::new() };
+	}
+}
+
+impl<T>
+UndefinedDecoders<T>
+{
+	pub fn
+	new
+	()
+	-> Self
+	{
+		return Self::default();
+	}
+
+	/// Registers a decoder for the tag with the given hex value, replacing
+	/// any decoder previously registered for it. The decoder returns `None`
+	/// if the data can't be decoded.
+	pub fn
+	register
+	(
+		&mut self,
+		hex_tag: u16,
+		decoder: impl Fn(&[u8], &Endian) -> Option<T> + 'static
+	)
+	-> &mut Self
+	{
+		self.decoders.insert(hex_tag, Box::new(decoder));
+		return self;
+	}
+
+	/// Decodes the given tag using the decoder registered for its hex value.
+	/// Returns `None` if the tag is not an `UNDEF` tag, there is no decoder
+	/// for it or the decoder fails.
+	pub fn
+	decode
+	(
+		&self,
+		tag:    &ExifTag,
+		endian: &Endian
+	)
+	-> Option<T>
+	{
+		let decoder = self.decoders.get(&tag.as_u16())?;
+		return decoder(tag.as_undefined_bytes()?, endian);
+	}
+}
+
+impl
+Metadata
+{
+	/// Decodes all `UNDEF` tags for which a decoder is registered, yielding
+	/// the hex value and group of each decoded tag together with the result.
+	/// Tags that fail to decode are skipped.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::metadata::undefined::UndefinedDecoders;
+	///
+	/// // Decode the number of columns and rows of DeviceSettingDescription
+	/// let mut decoders = UndefinedDecoders::new();
+	/// decoders.register(0xa40b, |data, _| Some((*data.get(1)?, *data.get(3)?)));
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// for (hex, group, (columns, rows)) in metadata.decode_undefined_tags(&decoders)
+	/// {
+	///     println!("0x{:04x} in {:?}: {}x{}", hex, group, columns, rows);
+	/// }
+	/// # }
+	/// ```
+	pub fn
+	decode_undefined_tags
+	<T>
+	(
+		&self,
+		decoders: &UndefinedDecoders<T>
+	)
+	-> Vec<(u16, ExifTagGroup, T)>
+	{
+		let mut decoded = Vec::new();
+		for ifd in &self.image_file_directories
+		{
+			for tag in ifd.get_tags()
+			{
+				if let Some(value) = decoders.decode(tag, &self.endian)
+				{
+					decoded.push((tag.as_u16(), ifd.get_ifd_type(), value));
+				}
+			}
+		}
+		return decoded;
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use crate::endian::Endian;
+	use crate::exif_tag::ExifTag;
+	use crate::filetype::FileExtension;
+	use crate::ifd::ExifTagGroup;
+	use crate::metadata::Metadata;
+
+	use super::UndefinedDecoders;
+
+	#[derive(Debug, PartialEq)]
+	enum
+	DecodedStructure
+	{
+		DeviceSettings { columns: u16, rows: u16, settings: Vec<String> },
+		Vendor(u8),
+	}
+
+	#[test]
+	fn
+	custom_undefined_decoder()
+	{
+		// DeviceSettingDescription: Columns and rows (big endian, as this is
+		// the byte order of the EXIF data), followed by UCS-2 strings
+		let mut device_settings = vec![0x00, 0x01, 0x00, 0x02];
+		for setting in ["Mode", "HDR"]
+		{
+			device_settings.extend(setting.encode_utf16().flat_map(|unit| unit.to_be_bytes()));
+			device_settings.extend([0x00, 0x00]);
+		}
+
+		let mut metadata = Metadata::new();
+		metadata.set_endian(Endian::Big);
+		metadata.set_tag(ExifTag::DeviceSettingDescription(device_settings.clone()));
+		metadata.set_tag(ExifTag::UnknownUNDEF(vec![42], 0xc000, ExifTagGroup::EXIF));
+		metadata.set_tag(ExifTag::ISO(vec![100]));
+
+		// The structure is preserved byte-exact
+		let metadata = Metadata::new_from_vec(&metadata.encode().unwrap(), FileExtension::TIFF).unwrap();
+		let tag      = metadata.get_tag(&ExifTag::DeviceSettingDescription(Vec::new())).next().unwrap();
+		assert_eq!(tag.as_undefined_bytes(), Some(&device_settings[..]));
+
+		let mut decoders = UndefinedDecoders::new();
+		decoders
+			.register(0xa40b, |data, endian| {
+				let read_u16 = |bytes: &[u8]| match endian
+				{
+					Endian::Little => u16::from_le_bytes([bytes[0], bytes[1]]),
+					Endian::Big    => u16::from_be_bytes([bytes[0], bytes[1]]),
+				};
+
+				let units = data.get(4..)?.chunks_exact(2).map(read_u16).collect::<Vec<u16>>();
+				return Some(DecodedStructure::DeviceSettings {
+					columns:  read_u16(data.get(0..2)?),
+					rows:     read_u16(data.get(2..4)?),
+					settings: units.split(|unit| *unit == 0)
+						.filter(|setting| !setting.is_empty())
+						.map(String::from_utf16_lossy)
+						.collect(),
+				});
+			})
+			.register(0xc000, |data, _| Some(DecodedStructure::Vendor(*data.first()?)))
+			.register(0x8827, |_, _| Some(DecodedStructure::Vendor(0)));
+
+		let mut decoded = metadata.decode_undefined_tags(&decoders);
+		decoded.sort_by_key(|(hex, _, _)| *hex);
+
+		// ISO (0x8827) is no UNDEF tag and thus not decoded
+		assert_eq!(decoded, vec![
+			(0xa40b, ExifTagGroup::EXIF, DecodedStructure::DeviceSettings {
+				columns:  1,
+				rows:     2,
+				settings: vec!["Mode".to_string(), "HDR".to_string()],
+			}),
+			(0xc000, ExifTagGroup::EXIF, DecodedStructure::Vendor(42)),
+		]);
+	}
+}
@@ -0,0 +1,94 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use super::Metadata;
+
+/// The different kinds of non-fatal issues that are reported as 
+/// `ParseWarning` while reading metadata. Most of them only occur when 
+/// reading with `ParseStrictness::Lenient` as they are errors otherwise.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum
+ParseWarningKind
+{
+	/// A tag occurs more than once within the same IFD. Only the last
+	/// occurrence is kept.
+	DuplicateTag,
+
+	/// The data ends before everything announced by it could be read, e.g.
+	/// an IFD with less entries than stated or a truncated zlib stream. 
+	/// Whatever could be read is kept.
+	TruncatedData,
+
+	/// The checksum of an ancillary PNG chunk does not match its data
+	ChecksumMismatch,
+
+	/// Some data is inconsistent, e.g. a stored length does not match the
+	/// actual length, and is used nonetheless (or ignored, if unusable)
+	InvalidData,
+
+	/// The image holds more than one copy of the EXIF data (e.g. a PNG with
+	/// an eXIf chunk and a "Raw profile type exif" text chunk, or a JPEG with
+	/// multiple APP1 segments holding EXIF data). The first copy in the file
+	/// takes precedence, the others only add missing tags.
+	MultipleExifSources,
+}
+
+/// An issue that was encountered while reading the metadata but did not
+/// stop the reading process
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct
+ParseWarning
+{
+	pub kind:    ParseWarningKind,
+	pub message: String,
+}
+
+impl
+ParseWarning
+{
+	pub fn
+	new
+	(
+		kind:    ParseWarningKind,
+		message: String
+	)
+	-> ParseWarning
+	{
+		ParseWarning { kind, message }
+	}
+}
+
+impl
+Metadata
+{
+	/// Gets the warnings that were recorded while reading the metadata, e.g.
+	/// when reading with `ParseStrictness::Lenient` and encountering an IFD
+	/// that contains the same tag twice. Always empty for metadata that was
+	/// not read from an image.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::metadata::options::ParseStrictness;
+	/// use little_exif::metadata::options::ReadOptions;
+	///
+	/// let options  = ReadOptions { strictness: ParseStrictness::Lenient, ..ReadOptions::default() };
+	/// let metadata = Metadata::new_from_path_with_options(std::path::Path::new("image.jpg"), &options).unwrap();
+	/// for warning in metadata.warnings()
+	/// {
+	///     println!("{:?}: {}", warning.kind, warning.message);
+	/// }
+	/// # }
+	/// ```
+	pub fn
+	warnings
+	(
+		&self
+	)
+	-> &[ParseWarning]
+	{
+		&self.warnings
+	}
+}
@@ -0,0 +1,470 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+// Mapping of EXIF values to their XMP counterparts and back, e.g. when
+// transferring metadata between formats. Note that little_exif does not
+// parse XMP packets - only simple properties are looked up in the packet
+// read via `Metadata::get_xmp`.
+
+use std::io::ErrorKind;
+
+use crate::exif_tag::ExifTag;
+use crate::filetype::FileExtension;
+
+use super::datetime::format_sub_sec;
+use super::datetime::parse_sub_sec;
+use super::datetime::DateTimeKind;
+use super::datetime::ExifDateTime;
+use super::Metadata;
+
+/// Determines which value is used if the EXIF and the XMP date/time
+/// disagree, see `Metadata::resolve_date_time`
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum
+DatePrecedence
+{
+	/// The EXIF value wins, as recommended by the Metadata Working Group
+	#[default]
+	Exif,
+
+	/// The XMP value wins
+	Xmp,
+}
+
+/// A date/time as stored in XMP ("YYYY-MM-DDThh:mm:ss.s+hh:mm"), i.e. with
+/// an optional time zone offset. In EXIF, the offset is stored separately
+/// in the `OffsetTime`, `OffsetTimeOriginal` and `OffsetTimeDigitized` tags.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct
+XmpDateTime
+{
+	pub date_time: ExifDateTime,
+
+	/// The time zone offset ("+hh:mm" or "-hh:mm"), with "Z" (UTC) being
+	/// normalized to "+00:00"
+	pub offset:    Option<String>,
+}
+
+impl
+XmpDateTime
+{
+	/// Parses a date/time as stored in XMP. Reduced precision without the
+	/// seconds ("YYYY-MM-DDThh:mm") is accepted, while date only values
+	/// (e.g. "YYYY-MM-DD") can't be represented in EXIF and return `None`,
+	/// just like values out of range (e.g. month 13 or offset "+25:00").
+	pub fn
+	parse
+	(
+		value: &str
+	)
+	-> Option<XmpDateTime>
+	{
+		let value = value.trim();
+		let bytes = value.as_bytes();
+		if bytes.len() < 16 || bytes[4] != b'-' || bytes[7] != b'-' || bytes[10] != b'T' || bytes[13] != b':'
+		{
+			return None;
+		}
+
+		let number = |start: usize, end: usize| -> Option<u16>
+		{
+			let digits = value.get(start..end)?;
+			if !digits.bytes().all(|byte| byte.is_ascii_digit())
+			{
+				return None;
+			}
+			return digits.parse::<u16>().ok();
+		};
+
+		let mut date_time = ExifDateTime {
+			year:       number( 0,  4)?,
+			month:      number( 5,  7)? as u8,
+			day:        number( 8, 10)? as u8,
+			hour:       number(11, 13)? as u8,
+			minute:     number(14, 16)? as u8,
+			second:     0,
+			nanosecond: 0,
+		};
+
+		// Optional seconds and fractional seconds
+		let mut rest = &value[16..];
+		if let Some(seconds) = rest.strip_prefix(':')
+		{
+			date_time.second = number(17, 19)? as u8;
+			rest = seconds.get(2..)?;
+
+			if let Some(fraction) = rest.strip_prefix('.')
+			{
+				let digit_count = fraction.bytes().take_while(|byte| byte.is_ascii_digit()).count();
+				date_time.nanosecond = parse_sub_sec(&fraction[..digit_count])?;
+				rest = &fraction[digit_count..];
+			}
+		}
+
+		// Optional time zone offset
+		let offset = match rest
+		{
+			""  => None,
+			"Z" => Some(String::from("+00:00")),
+			_   => {
+				let offset_bytes = rest.as_bytes();
+				if offset_bytes.len() != 6 
+				|| (offset_bytes[0] != b'+' && offset_bytes[0] != b'-') 
+				|| offset_bytes[3] != b':'
+				{
+					return None;
+				}
+
+				let offset_start = value.len() - 6;
+				if number(offset_start + 1, offset_start + 3)? > 23
+				|| number(offset_start + 4, offset_start + 6)? > 59
+				{
+					return None;
+				}
+				Some(rest.to_string())
+			}
+		};
+
+		if !(1..=12).contains(&date_time.month)
+		|| !(1..=31).contains(&date_time.day)
+		|| date_time.hour   > 23
+		|| date_time.minute > 59
+		|| date_time.second > 59
+		{
+			return None;
+		}
+
+		return Some(XmpDateTime { date_time, offset });
+	}
+
+	/// Formats the date/time as stored in XMP, including the fractional
+	/// seconds and the time zone offset if present
+	pub fn
+	to_xmp_string
+	(
+		&self
+	)
+	-> String
+	{
+		let mut xmp_string = format!(
+			"{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+			self.date_time.year, 
+			self.date_time.month, 
+			self.date_time.day, 
+			self.date_time.hour, 
+			self.date_time.minute, 
+			self.date_time.second
+		);
+
+		if self.date_time.nanosecond != 0
+		{
+			xmp_string.push('.');
+			xmp_string.push_str(&format_sub_sec(self.date_time.nanosecond));
+		}
+
+		if let Some(offset) = &self.offset
+		{
+			xmp_string.push_str(offset);
+		}
+
+		return xmp_string;
+	}
+}
+
+/// Gets the XMP property corresponding to the given kind of date/time:
+/// - `Modify`:    `xmp:ModifyDate`
+/// - `Original`:  `exif:DateTimeOriginal` (aka `photoshop:DateCreated`)
+/// - `Digitized`: `xmp:CreateDate`
+pub fn
+xmp_date_property
+(
+	kind: DateTimeKind
+)
+-> &'static str
+{
+	match kind
+	{
+		DateTimeKind::Modify    => "xmp:ModifyDate",
+		DateTimeKind::Original  => "exif:DateTimeOriginal",
+		DateTimeKind::Digitized => "xmp:CreateDate",
+	}
+}
+
+/// Gets the value of a simple property (e.g. "xmp:CreateDate") from an XMP
+/// packet, stored either as attribute (`xmp:CreateDate="..."`) or as
+/// element (`<xmp:CreateDate>...</xmp:CreateDate>`).
+/// This is no complete XML parser - entities and namespace prefixes
+/// other than the given one are not resolved.
+pub fn
+find_xmp_property
+(
+	packet:   &str,
+	property: &str
+)
+-> Option<String>
+{
+	// Attribute notation, making sure the name isn't just the end of
+	// another property's name
+	let attribute = format!("{}=", property);
+	let mut search_start = 0;
+	while let Some(position) = packet[search_start..].find(&attribute)
+	{
+		let start = search_start + position;
+		let after = start + attribute.len();
+		search_start = after;
+
+		let preceded_by_name = packet[..start].chars().last().map_or(false, |c| c.is_alphanumeric() || c == ':' || c == '_');
+		let quote            = packet[after..].chars().next();
+		if preceded_by_name || (quote != Some('"') && quote != Some('\''))
+		{
+			continue;
+		}
+
+		let value_start = after + 1;
+		let value_end   = value_start + packet[value_start..].find(quote.unwrap())?;
+		return Some(packet[value_start..value_end].to_string());
+	}
+
+	// Element notation
+	let opening = format!("<{}>", property);
+	let closing = format!("</{}>", property);
+	let value_start = packet.find(&opening)? + opening.len();
+	let value_end   = value_start + packet[value_start..].find(&closing)?;
+	return Some(packet[value_start..value_end].trim().to_string());
+}
+
+/// Gets the tag storing the time zone offset for the given kind of date/time
+fn
+offset_tag
+(
+	kind:  DateTimeKind,
+	value: String
+)
+-> ExifTag
+{
+	match kind
+	{
+		DateTimeKind::Modify    => ExifTag::OffsetTime(value),
+		DateTimeKind::Original  => ExifTag::OffsetTimeOriginal(value),
+		DateTimeKind::Digitized => ExifTag::OffsetTimeDigitized(value),
+	}
+}
+
+impl
+Metadata
+{
+	/// Gets the given kind of date/time in the form used by XMP, i.e. with
+	/// the time zone offset from the corresponding `OffsetTime...` tag.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # #[cfg(feature = "std_fs")]
+	/// # {
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::metadata::datetime::DateTimeKind;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some(date_time) = metadata.get_xmp_date_time(DateTimeKind::Original)
+	/// {
+	///     println!("exif:DateTimeOriginal=\"{}\"", date_time.to_xmp_string());
+	/// }
+	/// # }
+	/// ```
+	pub fn
+	get_xmp_date_time
+	(
+		&self,
+		kind: DateTimeKind
+	)
+	-> Option<XmpDateTime>
+	{
+		let date_time = self.get_date_time(kind)?;
+		let offset    = match self.get_tag(&offset_tag(kind, String::new())).next()
+		{
+			Some(ExifTag::OffsetTime(value))
+			| Some(ExifTag::OffsetTimeOriginal(value))
+			| Some(ExifTag::OffsetTimeDigitized(value))
+			=> Some(value.trim_end_matches('\0').to_string()).filter(|offset| !offset.trim().is_empty()),
+
+			_
+			=> None,
+		};
+
+		return Some(XmpDateTime { date_time, offset });
+	}
+
+	/// Sets the given kind of date/time from its XMP form, writing the time
+	/// zone offset (if present) to the corresponding `OffsetTime...` tag
+	pub fn
+	set_xmp_date_time
+	(
+		&mut self,
+		kind:      DateTimeKind,
+		date_time: &XmpDateTime
+	)
+	{
+		self.set_date_time(kind, &date_time.date_time);
+
+		if let Some(offset) = &date_time.offset
+		{
+			self.set_tag(offset_tag(kind, offset.clone()));
+		}
+	}
+
+	/// Resolves the given kind of date/time from this struct's EXIF data
+	/// and the corresponding property (see `xmp_date_property`) of the XMP
+	/// packet in the given file buffer, as read by `Metadata::get_xmp`.
+	/// If only one of them is present, it is used; if both are present but
+	/// disagree, `precedence` decides.
+	/// If the winning value lacks a time zone offset, the one of the other
+	/// value is used if their date and time match.
+	/// Fails if the XMP packet can't be read, except if there is none.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::metadata::Metadata;
+	/// use little_exif::metadata::datetime::DateTimeKind;
+	/// use little_exif::metadata::xmp::DatePrecedence;
+	/// use little_exif::filetype::FileExtension;
+	///
+	/// let file_type = FileExtension::PNG { as_zTXt_chunk: true };
+	/// let file_data = std::fs::read("image.png").unwrap();
+	/// let metadata  = Metadata::new_from_vec(&file_data, file_type).unwrap();
+	/// let created   = metadata.resolve_date_time(DateTimeKind::Original, &file_data, file_type, DatePrecedence::Exif).unwrap();
+	/// ```
+	pub fn
+	resolve_date_time
+	(
+		&self,
+		kind:        DateTimeKind,
+		file_buffer: &Vec<u8>,
+		file_type:   FileExtension,
+		precedence:  DatePrecedence
+	)
+	-> Result<Option<XmpDateTime>, std::io::Error>
+	{
+		let xmp_packet = match Metadata::get_xmp(file_buffer, file_type)
+		{
+			Ok(packet)                                        => packet,
+			Err(error) if error.kind() == ErrorKind::NotFound => String::new(),
+			Err(error)                                        => return Err(error),
+		};
+
+		let exif_date_time = self.get_xmp_date_time(kind);
+		let xmp_date_time  = find_xmp_property(&xmp_packet, xmp_date_property(kind))
+			.and_then(|value| XmpDateTime::parse(&value));
+
+		let (mut winner, other) = match precedence
+		{
+			DatePrecedence::Exif => (exif_date_time, xmp_date_time ),
+			DatePrecedence::Xmp  => (xmp_date_time,  exif_date_time),
+		};
+
+		match (&mut winner, other)
+		{
+			(None, other) 
+				=> return Ok(other),
+
+			(Some(winner), Some(other)) if winner.offset.is_none() && winner.date_time == other.date_time
+				=> winner.offset = other.offset,
+
+			_
+				=> (),
+		}
+
+		return Ok(winner);
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use crate::exif_tag::ExifTag;
+	use crate::filetype::FileExtension;
+	use crate::metadata::datetime::DateTimeKind;
+	use crate::metadata::Metadata;
+
+	use super::find_xmp_property;
+	use super::DatePrecedence;
+	use super::XmpDateTime;
+
+	#[test]
+	fn
+	xmp_date_time_round_trip()
+	{
+		let date_time = XmpDateTime::parse("2024-05-17T13:37:42.45+02:00").unwrap();
+		assert_eq!(date_time.date_time.to_exif_string(), "2024:05:17 13:37:42");
+		assert_eq!(date_time.date_time.millisecond(),    450);
+		assert_eq!(date_time.offset,                     Some("+02:00".to_string()));
+		assert_eq!(date_time.to_xmp_string(),            "2024-05-17T13:37:42.45+02:00");
+
+		assert_eq!(XmpDateTime::parse("2024-05-17T13:37Z").unwrap().to_xmp_string(), "2024-05-17T13:37:00+00:00");
+		assert_eq!(XmpDateTime::parse("2024-05-17"),            None);
+		assert_eq!(XmpDateTime::parse("2024-05-17T13:37:42+2"),  None);
+		assert_eq!(XmpDateTime::parse("2024-13-17T13:37:42"),    None);
+		assert_eq!(XmpDateTime::parse("2024-05-00T13:37:42"),    None);
+		assert_eq!(XmpDateTime::parse("2024-05-17T24:37:42"),    None);
+		assert_eq!(XmpDateTime::parse("2024-05-17T13:60"),       None);
+		assert_eq!(XmpDateTime::parse("2024-05-17T13:37+25:00"), None);
+
+		// EXIF to XMP and back, keeping the offset
+		let mut metadata = Metadata::new();
+		metadata.set_xmp_date_time(DateTimeKind::Digitized, &date_time);
+		assert_eq!(
+			metadata.get_tag(&ExifTag::OffsetTimeDigitized(String::new())).next(),
+			Some(&ExifTag::OffsetTimeDigitized("+02:00".to_string()))
+		);
+		assert_eq!(metadata.get_xmp_date_time(DateTimeKind::Digitized), Some(date_time));
+	}
+
+	#[test]
+	fn
+	find_xmp_properties()
+	{
+		let packet = r#"<rdf:Description xmp:CreateDate="2024-05-17T13:37:42" photoshop:xmp:ModifyDate="x">
+			<xmp:ModifyDate>2024-05-18T10:00:00</xmp:ModifyDate>
+		</rdf:Description>"#;
+
+		assert_eq!(find_xmp_property(packet, "xmp:CreateDate"),        Some("2024-05-17T13:37:42".to_string()));
+		assert_eq!(find_xmp_property(packet, "xmp:ModifyDate"),        Some("2024-05-18T10:00:00".to_string()));
+		assert_eq!(find_xmp_property(packet, "exif:DateTimeOriginal"), None);
+	}
+
+	#[test]
+	fn
+	resolve_conflicting_dates()
+	{
+		let file_type = FileExtension::PNG { as_zTXt_chunk: true };
+		let packet    = r#"<rdf:Description exif:DateTimeOriginal="2023-01-02T03:04:05+01:00" xmp:CreateDate="2024-05-17T13:37:42-05:00"/>"#;
+
+		let mut metadata = Metadata::new();
+		metadata.set_tag(ExifTag::DateTimeOriginal("2024:05:17 13:37:42".to_string()));
+
+		// Without XMP packet, the EXIF value is used
+		let mut image_data = std::fs::read("tests/sample2.png").unwrap();
+		let resolved = metadata.resolve_date_time(DateTimeKind::Original, &image_data, file_type, DatePrecedence::Xmp).unwrap().unwrap();
+		assert_eq!(resolved.to_xmp_string(), "2024-05-17T13:37:42");
+
+		Metadata::set_xmp(&mut image_data, file_type, packet).unwrap();
+
+		// EXIF wins by default...
+		let resolved = metadata.resolve_date_time(DateTimeKind::Original, &image_data, file_type, DatePrecedence::default()).unwrap().unwrap();
+		assert_eq!(resolved.to_xmp_string(), "2024-05-17T13:37:42");
+
+		// ...but XMP can be preferred
+		let resolved = metadata.resolve_date_time(DateTimeKind::Original, &image_data, file_type, DatePrecedence::Xmp).unwrap().unwrap();
+		assert_eq!(resolved.to_xmp_string(), "2023-01-02T03:04:05+01:00");
+
+		// Only present in XMP
+		let resolved = metadata.resolve_date_time(DateTimeKind::Digitized, &image_data, file_type, DatePrecedence::Exif).unwrap().unwrap();
+		assert_eq!(resolved.to_xmp_string(), "2024-05-17T13:37:42-05:00");
+
+		// Same date/time, but only XMP knows the offset
+		metadata.set_tag(ExifTag::CreateDate("2024:05:17 13:37:42".to_string()));
+		let resolved = metadata.resolve_date_time(DateTimeKind::Digitized, &image_data, file_type, DatePrecedence::Exif).unwrap().unwrap();
+		assert_eq!(resolved.offset, Some("-05:00".to_string()));
+
+		// XMP is only read from PNGs
+		assert!(metadata.resolve_date_time(DateTimeKind::Original, &image_data, FileExtension::JPEG, DatePrecedence::Exif).is_err());
+	}
+}
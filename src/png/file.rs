@@ -10,18 +10,18 @@ use std::fs::File;
 
 use crc::Crc;
 use crc::CRC_32_ISO_HDLC;
-use miniz_oxide::deflate::compress_to_vec_zlib;
-use miniz_oxide::inflate::decompress_to_vec_zlib;
 
 use crate::general_file_io::*;
 use crate::metadata::Metadata;
+use crate::metadata::options::ParseStrictness;
+use crate::metadata::warnings::ParseWarning;
+use crate::metadata::warnings::ParseWarningKind;
 
 use super::PNG_SIGNATURE;
-use super::RAW_PROFILE_TYPE_EXIF;
 
-use super::png_chunk::PngChunk;
-use super::decode_metadata_png;
-use super::encode_metadata_png;
+use super::png_chunk::PngChunkDescriptor;
+use super::build_exif_chunk;
+use super::is_exif_chunk;
 
 fn
 check_signature
@@ -54,9 +54,11 @@ check_signature
 fn
 get_next_chunk_descriptor
 (
-	file: &mut File
+	file:       &mut File,
+	strictness: ParseStrictness,
+	warnings:   &mut Vec<ParseWarning>
 )
--> Result<PngChunk, std::io::Error>
+-> Result<PngChunkDescriptor, std::io::Error>
 {
 	// Read the start of the chunk
 	let mut chunk_start = [0u8; 8];
@@ -100,45 +102,54 @@ get_next_chunk_descriptor
 	let crc_struct = Crc::<u32>::new(&CRC_32_ISO_HDLC);
 	let checksum = crc_struct.checksum(&crc_input) as u32;
 
-	for i in 0..4
-	{
-		if ((checksum >> (8 * (3-i))) as u8) != chunk_crc_buffer[i]
-		{
-			return io_error!(InvalidData, "Checksum check failed while reading PNG!");
-		}
-	}
-
-	// If validating the chunk using the CRC was successful, return its descriptor
+	// Determine the chunk descriptor
 	// Note: chunk_length does NOT include the +4 for the CRC area!
-	if let Ok(png_chunk) = PngChunk::from_string(
+	let png_chunk = match PngChunkDescriptor::from_string(
 		&chunk_name.unwrap(),
 		chunk_length
 	)
 	{
-		return Ok(png_chunk);
-	}
-	else
+		Ok(png_chunk) => png_chunk,
+		Err(_)        => return io_error!(Other, "Invalid PNG chunk name"),
+	};
+
+	// Validate the chunk using the CRC
+	// In lenient mode, a mismatch is only an error for critical chunks
+	if checksum.to_be_bytes() != chunk_crc_buffer
 	{
-		return io_error!(Other, "Invalid PNG chunk name");
+		if strictness == ParseStrictness::Strict || png_chunk.is_critical()
+		{
+			return io_error!(InvalidData, "Checksum check failed while reading PNG!");
+		}
+
+		warnings.push(ParseWarning::new(
+			ParseWarningKind::ChecksumMismatch,
+			format!("Checksum check failed for ancillary PNG chunk {} - Ignoring", png_chunk.as_string())
+		));
 	}
+
+	return Ok(png_chunk);
 }
 
 /// "Parses" the PNG by checking various properties:
 /// - Can the file be opened and is the signature valid?
 /// - Are the various chunks OK or not? For this, the local subroutine `get_next_chunk_descriptor` is used
+/// The strictness determines how CRC mismatches of ancillary chunks are handled
 pub(crate) fn
 parse_png
 (
-	path: &Path
+	path:       &Path,
+	strictness: ParseStrictness,
+	warnings:   &mut Vec<ParseWarning>
 )
--> Result<Vec<PngChunk>, std::io::Error>
+-> Result<Vec<PngChunkDescriptor>, std::io::Error>
 {
 	let mut file = check_signature(path)?;
 	let mut chunks = Vec::new();
 
 	loop
 	{
-		let chunk_descriptor = get_next_chunk_descriptor(&mut file)?;
+		let chunk_descriptor = get_next_chunk_descriptor(&mut file, strictness, warnings)?;
 		chunks.push(chunk_descriptor);
 
 		if chunks.last().unwrap().as_string() == "IEND".to_string()
@@ -152,7 +163,6 @@ parse_png
 
 // Clears existing metadata chunk from a png file
 // Gets called before writing any new metadata
-#[allow(non_snake_case)]
 pub(crate) fn
 clear_metadata
 (
@@ -162,16 +172,22 @@ clear_metadata
 {
 
 	// Parse the PNG - if this fails, the clear operation fails as well
-	let parse_png_result = parse_png(path)?;
+	let parse_png_result = parse_png(path, ParseStrictness::Strict, &mut Vec::new())?;
 
 	// Parsed PNG is Ok to use - Open the file and go through the chunks
 	let mut file = open_write_file(path)?;
-	let mut seek_counter = 8u64;
+	let mut seek_counter = PNG_SIGNATURE.len() as u64;
+
+	// Skip the signature
+	perform_file_action!(file.seek(SeekFrom::Start(seek_counter)));
 
 	for chunk in &parse_png_result
 	{
-		// If this is not a zTXt chunk, jump to the next chunk
-		if chunk.as_string() != String::from("zTXt")
+		// eXIf chunks always hold EXIF data, so their content needs no check
+		let is_exif_chunk_type = chunk.as_string() == String::from("eXIf");
+
+		// If this is not a zTXt, tEXt or eXIf chunk, jump to the next chunk
+		if !is_exif_chunk_type && chunk.as_string() != String::from("zTXt") && chunk.as_string() != String::from("tEXt")
 		{
 			seek_counter += chunk.length() as u64 + 12;
 			perform_file_action!(file.seek(SeekFrom::Current(chunk.length() as i64 + 12)));
@@ -183,30 +199,20 @@ clear_metadata
 
 		// Read chunk data into buffer for checking that this is the 
 		// correct chunk to delete
-		let mut zTXt_chunk_data = vec![0u8; chunk.length() as usize];
-		if file.read(&mut zTXt_chunk_data).unwrap() != chunk.length() as usize
+		let mut chunk_data = vec![0u8; chunk.length() as usize];
+		if file.read(&mut chunk_data).unwrap() != chunk.length() as usize
 		{
 			return io_error!(Other, "Could not read chunk data");
 		}
 
-		// Compare to the "Raw profile type exif" string constant
-		let mut correct_zTXt_chunk = true;
-		for i in 0..RAW_PROFILE_TYPE_EXIF.len()
-		{
-			if zTXt_chunk_data[i] != RAW_PROFILE_TYPE_EXIF[i]
-			{
-				correct_zTXt_chunk = false;
-				break;
-			}
-		}
-
 		// Skip the CRC as it is not important at this point
 		perform_file_action!(file.seek(SeekFrom::Current(4)));
 
-		// If this is not the correct zTXt chunk, ignore current
-		// (wrong) zTXt chunk and continue with next chunk
-		if !correct_zTXt_chunk
+		// If this is not a chunk with the "Raw profile type exif" keyword,
+		// ignore it and continue with next chunk
+		if !is_exif_chunk_type && !is_exif_chunk(&chunk.as_string(), &chunk_data)
 		{	
+			seek_counter += chunk.length() as u64 + 12;
 			continue;
 		}
 		
@@ -233,82 +239,31 @@ clear_metadata
 	return Ok(());
 }
 
-#[allow(non_snake_case)]
+/// Gets the EXIF data of the first chunk holding it, see 
+/// `png::vec::read_all_metadata`
 pub(crate) fn
 read_metadata
 (
-	path: &Path
+	path:       &Path,
+	strictness: ParseStrictness,
+	warnings:   &mut Vec<ParseWarning>
 )
 -> Result<Vec<u8>, std::io::Error>
 {
-	// Parse the PNG - if this fails, the read fails as well
-	let parse_png_result = parse_png(path)?;
-
-	// Parsed PNG is Ok to use - Open the file and go through the chunks
-	let mut file = check_signature(path).unwrap();
-	for chunk in &parse_png_result
-	{
-		// Wrong chunk? Seek to the next one
-		if chunk.as_string() != String::from("zTXt")
-		{
-			perform_file_action!(file.seek(SeekFrom::Current(chunk.length() as i64 + 12)));
-			continue;
-		}
-
-		// We now have a zTXt chunk:
-		// Skip chunk length and type (4+4 Bytes)
-		perform_file_action!(file.seek(SeekFrom::Current(8)));
-
-		// Read chunk data into buffer
-		// No need to verify this using CRC as already done by parse_png(path)
-		let mut zTXt_chunk_data = vec![0u8; chunk.length() as usize];
-		if file.read(&mut zTXt_chunk_data).unwrap() != chunk.length() as usize
-		{
-			return io_error!(Other, "Could not read chunk data");
-		}
-
-		// Check that this is the correct zTXt chunk...
-		let mut correct_zTXt_chunk = true;
-		for i in 0..RAW_PROFILE_TYPE_EXIF.len()
-		{
-			if zTXt_chunk_data[i] != RAW_PROFILE_TYPE_EXIF[i]
-			{
-				correct_zTXt_chunk = false;
-				break;
-			}
-		}
-
-		if !correct_zTXt_chunk
-		{
-			// Skip CRC from current (wrong) zTXt chunk and continue
-			perform_file_action!(file.seek(SeekFrom::Current(4)));
-			continue;
-		}
-
-		// Decode zlib data...
-		if let Ok(decompressed_data) = decompress_to_vec_zlib(&zTXt_chunk_data[RAW_PROFILE_TYPE_EXIF.len()..])
-		{
-			// ...and perform PNG-specific decoding & return the result
-			return Ok(decode_metadata_png(&decompressed_data).unwrap());
-		}
-		else
-		{
-			return io_error!(Other, "Could not inflate compressed chunk data!");
-		}
-	}
-
-	return io_error!(Other, "No metadata found!");
-
+	return super::vec::read_metadata(&std::fs::read(path)?, strictness, warnings);
 }
 
 
 
+/// Writes the metadata right after the IHDR chunk, either compressed in a
+/// zTXt chunk or - if `as_zTXt_chunk` is false - uncompressed in a tEXt one
 #[allow(non_snake_case)]
 pub(crate) fn
 write_metadata
 (
-	path:     &Path,
-	metadata: &Metadata
+	path:          &Path,
+	metadata:      &Metadata,
+	as_zTXt_chunk: bool
 )
 -> Result<(), std::io::Error>
 {
@@ -319,13 +274,11 @@ write_metadata
 	let _ = clear_metadata(path)?;
 
 	let mut IHDR_length = 0u32;
-	if let Ok(chunks) = parse_png(path)
+	if let Ok(chunks) = parse_png(path, ParseStrictness::Strict, &mut Vec::new())
 	{
 		IHDR_length = chunks[0].length();
 	}
 
-	// Encode the data specifically for PNG and open the image file
-	let encoded_metadata = encode_metadata_png(&metadata.encode()?);
 	let seek_start = 0u64         // Skip ...
 	+ PNG_SIGNATURE.len() as u64  // PNG Signature
 	+ IHDR_length         as u64  // IHDR data section
@@ -338,29 +291,11 @@ write_metadata
 	perform_file_action!(file.read_to_end(&mut buffer));
 	perform_file_action!(file.seek(SeekFrom::Start(seek_start)));
 
-	// Build data of new chunk using zlib compression (level=8 -> default)
-	let mut zTXt_chunk_data: Vec<u8> = vec![0x7a, 0x54, 0x58, 0x74];
-	zTXt_chunk_data.extend(RAW_PROFILE_TYPE_EXIF.iter());
-	zTXt_chunk_data.extend(compress_to_vec_zlib(&encoded_metadata, 8).iter());
-
-	// Compute CRC and append it to the chunk data
-	let crc_struct = Crc::<u32>::new(&CRC_32_ISO_HDLC);
-	let checksum = crc_struct.checksum(&zTXt_chunk_data) as u32;
-	for i in 0..4
-	{
-		zTXt_chunk_data.push( (checksum >> (8 * (3-i))) as u8);		
-	}
-
-	// Write new data to PNG file
-	// Start with length of the new chunk (subtracting 8 for type and CRC)
-	let chunk_data_len = zTXt_chunk_data.len() as u32 - 8;
-	for i in 0..4
-	{
-		perform_file_action!(file.write( &[(chunk_data_len >> (8 * (3-i))) as u8] ));
-	}
+	// Build the new chunk
+	let exif_chunk = build_exif_chunk(&metadata.encode()?, as_zTXt_chunk);
 
-	// Write data of new chunk and rest of PNG file
-	perform_file_action!(file.write_all(&zTXt_chunk_data));
+	// Write new chunk (length, type, data and CRC) and rest of PNG file
+	perform_file_action!(file.write_all(&exif_chunk.to_bytes()));
 	perform_file_action!(file.write_all(&buffer));
 
 	return Ok(());
@@ -375,7 +310,9 @@ mod tests
 	parsing_test() 
 	{
 		let chunks = crate::png::file::parse_png(
-			std::path::Path::new("tests/png_parse_test_image.png")
+			std::path::Path::new("tests/png_parse_test_image.png"),
+			crate::metadata::options::ParseStrictness::Strict,
+			&mut Vec::new()
 		).unwrap();
 		assert_eq!(chunks.len(), 3);
 	}
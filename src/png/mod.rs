@@ -1,17 +1,26 @@
 // Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
 // See https://github.com/TechnikTobi/little_exif#license for licensing details
 
-pub mod file;
-pub mod vec;
+#[cfg(feature = "std_fs")]
+pub(crate) mod file;
+pub(crate) mod vec;
 mod png_chunk;
 
 use std::collections::VecDeque;
 
 use miniz_oxide::deflate::compress_to_vec_zlib;
+use miniz_oxide::inflate::decompress_to_vec_zlib;
+use miniz_oxide::inflate::stream::inflate;
+use miniz_oxide::inflate::stream::InflateState;
+use miniz_oxide::DataFormat;
+use miniz_oxide::MZFlush;
+use miniz_oxide::MZStatus;
 
 use crate::general_file_io::EXIF_HEADER;
+use crate::metadata::options::ParseStrictness;
+use crate::metadata::warnings::ParseWarning;
+use crate::metadata::warnings::ParseWarningKind;
 use crate::general_file_io::NEWLINE;
-use crate::general_file_io::SPACE;
 
 pub(crate) const PNG_SIGNATURE: [u8; 8] = [0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a];
 pub(crate) const RAW_PROFILE_TYPE_EXIF: [u8; 23] = [
@@ -21,6 +30,331 @@ pub(crate) const RAW_PROFILE_TYPE_EXIF: [u8; 23] = [
 	0x65, 0x78, 0x69, 0x66, 0x00, 0x00                  // exif NUL NUL
 ];
 
+pub(crate) const XMP_KEYWORD: &str = "XML:com.adobe.xmp";
+
+use crate::general_file_io::io_error;
+
+pub use png_chunk::PngChunk;
+
+/// Builds the keyword of a raw profile, e.g. "Raw profile type exif" for 
+/// the profile type "exif", including the NUL separator that follows it
+fn
+raw_profile_keyword
+(
+	profile_type: &str
+)
+-> Vec<u8>
+{
+	let mut keyword = b"Raw profile type ".to_vec();
+	keyword.extend(profile_type.as_bytes());
+	keyword.push(0x00);
+	return keyword;
+}
+
+/// Checks if a chunk with the given type and data holds a raw profile of 
+/// the given type, i.e. is a zTXt (compressed) or tEXt (uncompressed) chunk
+/// with the keyword "Raw profile type <profile_type>"
+fn
+is_raw_profile_chunk
+(
+	chunk_type:   &str,
+	chunk_data:   &[u8],
+	profile_type: &str
+)
+-> bool
+{
+	let mut keyword = raw_profile_keyword(profile_type);
+	match chunk_type
+	{
+		"zTXt" => { keyword.push(0x00); chunk_data.starts_with(&keyword) },     // Compression method
+		"tEXt" => chunk_data.starts_with(&keyword),
+		_      => false
+	}
+}
+
+/// Gets the (still hex-encoded) text of a chunk for which 
+/// `is_raw_profile_chunk` is true, inflating it if necessary.
+/// In lenient mode, the data that could be inflated from a corrupt or 
+/// truncated zlib stream is returned instead of an error.
+fn
+decode_raw_profile_chunk
+(
+	chunk_type:   &str,
+	chunk_data:   &[u8],
+	profile_type: &str,
+	strictness:   ParseStrictness,
+	warnings:     &mut Vec<ParseWarning>
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	let keyword_length = raw_profile_keyword(profile_type).len();
+
+	if chunk_type == "tEXt"
+	{
+		return Ok(chunk_data[keyword_length..].to_vec());
+	}
+
+	// Decode zlib data, skipping the compression method after the keyword
+	let compressed_data = &chunk_data[keyword_length+1..];
+	if let Ok(decompressed_data) = decompress_to_vec_zlib(compressed_data)
+	{
+		return Ok(decompressed_data);
+	}
+
+	if strictness == ParseStrictness::Lenient
+	{
+		let decompressed_data = inflate_partially(compressed_data);
+		if !decompressed_data.is_empty()
+		{
+			warnings.push(ParseWarning::new(
+				ParseWarningKind::TruncatedData,
+				format!("Could only partially inflate compressed chunk data - Continuing with {} bytes", decompressed_data.len())
+			));
+			return Ok(decompressed_data);
+		}
+	}
+
+	return io_error!(Other, "Could not inflate compressed chunk data!");
+}
+
+/// Inflates as much as possible of a corrupt or truncated zlib stream using
+/// the streaming decompressor, which (unlike `decompress_to_vec_zlib`) tells
+/// how many bytes were actually produced before the stream broke off
+fn
+inflate_partially
+(
+	compressed_data: &[u8]
+)
+-> Vec<u8>
+{
+	let mut state  = InflateState::new_boxed(DataFormat::Zlib);
+	let mut input  = compressed_data;
+	let mut buffer = [0u8; 4096];
+	let mut output = Vec::new();
+
+	loop
+	{
+		let result = inflate(&mut state, input, &mut buffer, MZFlush::None);
+		input = &input[result.bytes_consumed..];
+		output.extend(&buffer[..result.bytes_written]);
+
+		match result.status
+		{
+			Ok(MZStatus::Ok) if result.bytes_consumed > 0 || result.bytes_written > 0
+				=> continue,
+			_
+				=> break,
+		}
+	}
+
+	return output;
+}
+
+/// Builds the chunk holding the given hex-encoded text of a raw profile, 
+/// either as zTXt chunk using zlib compression (level=8 -> default) or as
+/// uncompressed tEXt chunk
+#[allow(non_snake_case)]
+fn
+build_raw_profile_chunk
+(
+	profile_type:  &str,
+	encoded_text:  &Vec<u8>,
+	as_zTXt_chunk: bool
+)
+-> PngChunk
+{
+	let mut chunk_data = raw_profile_keyword(profile_type);
+
+	if as_zTXt_chunk
+	{
+		chunk_data.push(0x00);                                                  // Compression method
+		chunk_data.extend(compress_to_vec_zlib(encoded_text, 8).iter());
+		return PngChunk::new("zTXt", chunk_data).unwrap();
+	}
+
+	chunk_data.extend(encoded_text.iter());
+	return PngChunk::new("tEXt", chunk_data).unwrap();
+}
+
+/// Checks if a chunk with the given type and data holds EXIF data
+fn
+is_exif_chunk
+(
+	chunk_type: &str,
+	chunk_data: &[u8]
+)
+-> bool
+{
+	return is_raw_profile_chunk(chunk_type, chunk_data, "exif");
+}
+
+/// Decodes the data of a chunk for which `is_exif_chunk` is true
+fn
+decode_exif_chunk
+(
+	chunk_type: &str,
+	chunk_data: &[u8],
+	strictness: ParseStrictness,
+	warnings:   &mut Vec<ParseWarning>
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	return decode_metadata_png(
+		&decode_raw_profile_chunk(chunk_type, chunk_data, "exif", strictness, warnings)?,
+		strictness,
+		warnings
+	);
+}
+
+/// Builds the chunk holding the given generally encoded metadata
+#[allow(non_snake_case)]
+fn
+build_exif_chunk
+(
+	general_encoded_metadata: &Vec<u8>,
+	as_zTXt_chunk:            bool
+)
+-> PngChunk
+{
+	return build_raw_profile_chunk("exif", &encode_metadata_png(general_encoded_metadata), as_zTXt_chunk);
+}
+
+/// Checks if a chunk with the given type and data is an iTXt chunk with the
+/// keyword "XML:com.adobe.xmp", i.e. holds an XMP packet
+fn
+is_xmp_chunk
+(
+	chunk_type: &str,
+	chunk_data: &[u8]
+)
+-> bool
+{
+	let mut keyword = XMP_KEYWORD.as_bytes().to_vec();
+	keyword.push(0x00);
+	return chunk_type == "iTXt" && chunk_data.starts_with(&keyword);
+}
+
+/// Decodes the XMP packet stored in the data of a chunk for which 
+/// `is_xmp_chunk` is true. An iTXt chunk consists of the keyword, the 
+/// compression flag and method, the language tag and the translated keyword
+/// (both NUL terminated) and finally the UTF-8 text. The XMP specification
+/// requires the packet to be uncompressed, but compressed ones are accepted
+/// when reading nonetheless.
+fn
+decode_xmp_chunk
+(
+	chunk_data: &[u8]
+)
+-> Result<String, std::io::Error>
+{
+	let mut position = XMP_KEYWORD.len() + 1;
+
+	let compression_flag = match chunk_data.get(position)
+	{
+		Some(flag) => *flag,
+		None       => return io_error!(InvalidData, "iTXt chunk is too short"),
+	};
+	position += 2;
+
+	// Skip language tag and translated keyword
+	for _ in 0..2
+	{
+		match chunk_data.get(position..).and_then(|rest| rest.iter().position(|byte| *byte == 0x00))
+		{
+			Some(nul_index) => position += nul_index + 1,
+			None            => return io_error!(InvalidData, "iTXt chunk is missing a NUL separator"),
+		}
+	}
+
+	let text = if compression_flag == 0
+	{
+		chunk_data[position..].to_vec()
+	}
+	else
+	{
+		match decompress_to_vec_zlib(&chunk_data[position..])
+		{
+			Ok(decompressed_data) => decompressed_data,
+			Err(_)                => return io_error!(InvalidData, "Could not inflate compressed iTXt chunk data!"),
+		}
+	};
+
+	match String::from_utf8(text)
+	{
+		Ok(packet) => return Ok(packet),
+		Err(_)     => return io_error!(InvalidData, "XMP packet is not valid UTF-8"),
+	}
+}
+
+/// Builds the uncompressed iTXt chunk holding the given XMP packet, with
+/// empty language tag and translated keyword
+fn
+build_xmp_chunk
+(
+	packet: &str
+)
+-> PngChunk
+{
+	let mut chunk_data = XMP_KEYWORD.as_bytes().to_vec();
+	chunk_data.extend([
+		0x00,                                                                   // NUL separator
+		0x00,                                                                   // Compression flag
+		0x00,                                                                   // Compression method
+		0x00,                                                                   // Language tag
+		0x00,                                                                   // Translated keyword
+	]);
+	chunk_data.extend(packet.as_bytes());
+	return PngChunk::new("iTXt", chunk_data).unwrap();
+}
+
+/// Checks that the profile type only consists of characters that are valid
+/// for a keyword of a PNG text chunk, which is limited to 79 bytes
+fn
+check_profile_type
+(
+	profile_type: &str
+)
+-> Result<(), std::io::Error>
+{
+	if profile_type.is_empty()
+	|| raw_profile_keyword(profile_type).len() - 1 > 79
+	|| !profile_type.bytes().all(|byte| byte.is_ascii_alphanumeric())
+	{
+		return io_error!(InvalidInput, format!("Invalid raw profile type: '{}'", profile_type));
+	}
+	return Ok(());
+}
+
+/// Encodes the payload of a raw profile as done by e.g. ImageMagick: The
+/// profile type, the length of the payload and the hex-encoded payload 
+/// itself, using 72 characters per line
+fn
+encode_raw_profile
+(
+	profile_type: &str,
+	payload:      &[u8]
+)
+-> Vec<u8>
+{
+	let mut encoded = Vec::new();
+	encoded.push(NEWLINE);
+	encoded.extend(profile_type.as_bytes());
+	encoded.push(NEWLINE);
+	encoded.extend(format!("{:>8}", payload.len()).as_bytes());
+	encoded.push(NEWLINE);
+
+	for line in payload.chunks(36)
+	{
+		for byte in line
+		{
+			encoded.extend(encode_byte(byte).iter());
+		}
+		encoded.push(NEWLINE);
+	}
+
+	return encoded;
+}
+
 // The bytes during encoding need to be encoded themselves:
 // A given byte (e.g. 0x30 for the char '0') has two values in the string of its hex representation ('3' and '0')
 // These two characters need to be encoded themselves (51 for '3', 48 for '0'), resulting in the final encoded
@@ -41,25 +375,23 @@ encode_metadata_png
 )
 -> Vec<u8>
 {
-	// The size of the EXIF data area, consists of
-	// - length of EXIF header (follows after ssss)
-	// - length of exif_vec
-	// - 1 for ssss itself (why not 4? idk)
-	let ssss = (
+	// Following the convention of ImageMagick, the size line (ssss) holds
+	// the number of bytes in the hex dump that follows as decimal number,
+	// right-aligned to 8 characters. This dump consists of
+	// - the EXIF header
+	// - the EXIF data in exif_vec
+	// - a final NUL byte (see below), hence the + 1
+	let ssss = format!("{:>8}", 
 		EXIF_HEADER.len() as u32 
 		+ exif_vec.len()  as u32 
 		+ 1               as u32
-	).to_string();
+	);
 
 	// Construct final vector with the bytes as they will be sent to the encoder
 	//                               \n       e     x     i     f     \n
 	let mut png_exif: Vec<u8> = vec![NEWLINE, 0x65, 0x78, 0x69, 0x66, NEWLINE];
 
 	// Write ssss
-	for _ in 0..(8-ssss.len())
-	{
-		png_exif.push(SPACE);
-	}
 	png_exif.extend(ssss.as_bytes().to_vec().iter());
 	png_exif.push(NEWLINE);
 
@@ -82,99 +414,112 @@ encode_metadata_png
 	return png_exif;
 }
 
+/// Reads the size line of a raw profile (e.g. "     153"), which follows
+/// the line holding the profile type
 fn
-decode_metadata_png
+read_raw_profile_size
+(
+	encoded_text: &[u8]
+)
+-> Option<usize>
+{
+	return encoded_text
+		.split(|byte| *byte == NEWLINE)
+		.filter(|line| !line.is_empty())
+		.nth(1)
+		.and_then(|line| String::from_utf8_lossy(line).trim().parse::<usize>().ok());
+}
+
+/// Decodes the hex dump of a raw profile (see `encode_raw_profile`), whose
+/// size line announces the number of bytes in the dump. In lenient mode, a
+/// mismatch between the announced and the actual length (e.g. due to 
+/// partially inflated data) only results in a warning.
+fn
+decode_raw_profile
 (
-	encoded_data: &Vec<u8>
+	encoded_text: &[u8],
+	strictness:   ParseStrictness,
+	warnings:     &mut Vec<ParseWarning>
 )
 -> Result<Vec<u8>, std::io::Error>
 {
+	let given_len = match read_raw_profile_size(encoded_text)
+	{
+		Some(given_len) => given_len,
+		None            => return io_error!(InvalidData, "Missing size information in raw profile!"),
+	};
 
-	let mut exif_all: VecDeque<u8> = VecDeque::new();
-	let mut other_byte: Option<u8> = None;
+	// The hex dump starts after the profile type and size lines
+	let hex_digits = encoded_text
+		.split(|byte| *byte == NEWLINE)
+		.filter(|line| !line.is_empty())
+		.skip(2)
+		.flatten()
+		.filter(|byte| !byte.is_ascii_whitespace())
+		.copied()
+		.collect::<Vec<u8>>();
 
 	// This performs the reverse operation to encode_byte:
 	// Two succeeding bytes represent the ASCII values of the digits of 
 	// a hex value, e.g. 0x31, 0x32 represent '1' and '2', so the resulting
-	// hex value is 0x12, which gets pushed onto exif_all
-	for byte in encoded_data
+	// hex value is 0x12, which gets pushed onto the payload
+	let mut payload = Vec::with_capacity(hex_digits.len() / 2);
+	for pair in hex_digits.chunks_exact(2)
 	{
-		// Ignore newline characters
-		if *byte == '\n' as u8
+		if let Ok(value) = u8::from_str_radix(&String::from_utf8_lossy(pair), 16)
 		{
-			continue;
+			payload.push(value);
 		}
+	}
 
-		if other_byte.is_none()
+	if given_len != payload.len()
+	{
+		if strictness == ParseStrictness::Strict
 		{
-			other_byte = Some(*byte);
-			continue;
+			return io_error!(
+				InvalidData, 
+				format!("Expected {} bytes of raw profile data but got {}!", given_len, payload.len())
+			);
 		}
 
-		let value_string = "".to_owned()
-			+ &(other_byte.unwrap() as char).to_string()
-			+ &(*byte as char).to_string();
-			
-		if let Ok(value) = u8::from_str_radix(value_string.trim(), 16)
-		{
-			exif_all.push_back(value);
-		}
-		
-		other_byte = None;
+		warnings.push(ParseWarning::new(
+			ParseWarningKind::InvalidData,
+			format!("Expected {} bytes of raw profile data but got {} - Continuing", given_len, payload.len())
+		));
+		payload.truncate(given_len);
 	}
 
-	// Now remove the first element until the exif header is found
-	// Store the popped elements to get the size information
-	let mut exif_header_found = false;
-	let mut pop_storage: Vec<u8> = Vec::new();
+	return Ok(payload);
+}
+
+/// Decodes the hex-encoded EXIF data, see `decode_raw_profile`
+fn
+decode_metadata_png
+(
+	encoded_data: &Vec<u8>,
+	strictness:   ParseStrictness,
+	warnings:     &mut Vec<ParseWarning>
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	let mut exif_all = VecDeque::from(decode_raw_profile(encoded_data, strictness, warnings)?);
 
-	while !exif_header_found
+	// Now remove the first element until the exif header is found
+	loop
 	{
-		let mut counter = 0;
-		for header_value in &EXIF_HEADER
+		if exif_all.len() < EXIF_HEADER.len()
 		{
-			if *header_value != exif_all[counter]
-			{
-				break;
-			}
-			counter += 1;
+			return io_error!(InvalidData, "Could not find EXIF header in PNG data!");
 		}
 
-		exif_header_found = counter == EXIF_HEADER.len();
-
-		if exif_header_found
+		if exif_all.iter().zip(EXIF_HEADER.iter()).all(|(value, header_value)| value == header_value)
 		{
 			break;
 		}
-		pop_storage.push(exif_all.pop_front().unwrap());
-	}
 
-	// The exif header has been found
-	// -> exif_all now starts with the exif header information
-	// -> pop_storage has in its last 4 elements the size information
-	//    that will now get extracted
-	// Consider this part optional as it might be removed in the future and
-	// isn't strictly necessary and just for validating the data we get
-	assert!(pop_storage.len() > 0);
-
-	// Using the encode_byte function re-encode the bytes regarding the size
-	// information and construct its value using decimal based shifting
-	// Example: 153 = 0
-	// + 5*10*10^(2*0) + 3*1*10^(2*0) 
-	// + 0*10*10^(2*1) + 1*1*10^(2*1)
-	let mut given_exif_len = 0u64;
-	for i in 0..std::cmp::min(4, pop_storage.len())
-	{
-		let re_encoded_byte = encode_byte(&pop_storage[pop_storage.len() -1 -i]);
-		let tens_place = u64::from_str_radix(&(re_encoded_byte[0] as char).to_string(), 10).unwrap();
-		let ones_place = u64::from_str_radix(&(re_encoded_byte[1] as char).to_string(), 10).unwrap();
-		given_exif_len = given_exif_len + tens_place * 10 * 10_u64.pow((2 * i).try_into().unwrap());
-		given_exif_len = given_exif_len + ones_place *  1 * 10_u64.pow((2 * i).try_into().unwrap());
+		exif_all.pop_front();
 	}
 
-	assert!(given_exif_len == exif_all.len().try_into().unwrap());
-	// End optional part
-
 	return Ok(Vec::from(exif_all));
 }
 
@@ -202,4 +547,63 @@ as_u8_vec
 	zTXt_chunk_data.extend(compress_to_vec_zlib(&basic_png_encode_result, 8).iter());
 
 	return zTXt_chunk_data;
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests
+{
+	use crate::general_file_io::EXIF_HEADER;
+	use crate::metadata::options::ParseStrictness;
+
+	use super::decode_metadata_png;
+	use super::encode_metadata_png;
+	use super::encode_raw_profile;
+
+	#[test]
+	fn
+	size_round_trip_test()
+	{
+		// Sizes of the hex dump (EXIF header + data + NUL) around the 
+		// boundaries of 100, 10000 and 1000000
+		for exif_len in [0, 92, 93, 9992, 9993, 999_992, 999_993]
+		{
+			let exif_vec = (0..exif_len).map(|index| (index % 251) as u8).collect::<Vec<u8>>();
+			let encoded  = encode_metadata_png(&exif_vec);
+
+			let size_line = format!("{:>8}", EXIF_HEADER.len() + exif_len + 1);
+			assert_eq!(&encoded[6..14], size_line.as_bytes());
+
+			let decoded = decode_metadata_png(&encoded, ParseStrictness::Strict, &mut Vec::new()).unwrap();
+			assert_eq!(decoded, [EXIF_HEADER.to_vec(), exif_vec, vec![0x00]].concat());
+		}
+	}
+
+	#[test]
+	fn
+	imagemagick_size_test()
+	{
+		// ImageMagick announces the exact size of the dump without a final
+		// NUL byte and breaks the dump into lines of 72 characters
+		for exif_len in [93, 94, 9994]
+		{
+			let payload = [EXIF_HEADER.to_vec(), vec![0x2a; exif_len]].concat();
+			let encoded = encode_raw_profile("exif", &payload);
+
+			let decoded = decode_metadata_png(&encoded, ParseStrictness::Strict, &mut Vec::new()).unwrap();
+			assert_eq!(decoded, payload);
+
+			// A size that is off by one is rejected in strict mode and 
+			// results in a warning in lenient mode
+			let wrong_size = String::from_utf8(encoded).unwrap().replacen(
+				&format!("{:>8}", payload.len()), 
+				&format!("{:>8}", payload.len() + 1), 
+				1
+			).into_bytes();
+			assert!(decode_metadata_png(&wrong_size, ParseStrictness::Strict, &mut Vec::new()).is_err());
+
+			let mut warnings = Vec::new();
+			assert_eq!(decode_metadata_png(&wrong_size, ParseStrictness::Lenient, &mut warnings).unwrap(), payload);
+			assert_eq!(warnings.len(), 1);
+		}
+	}
+}
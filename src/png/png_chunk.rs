@@ -1,6 +1,13 @@
 // Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
 // See https://github.com/TechnikTobi/little_exif#license for licensing details
 
+use crc::Crc;
+use crc::CRC_32_ISO_HDLC;
+
+use crate::endian::Endian;
+use crate::u8conversion::to_u8_vec_macro;
+use crate::u8conversion::U8conversion;
+
 #[allow(non_camel_case_types, dead_code)]
 pub(crate) enum
 PngChunkOrdering
@@ -27,16 +34,18 @@ macro_rules! build_png_chunk_type_enum {
 	{
 		/// These are the different PNG chunk types currently known to
 		/// little_exif. These might be expanded in the future if necessary.
+		/// The value stored for each type is the length of the chunk's data
+		/// section (i.e. without length, type and CRC fields)
 		#[allow(non_camel_case_types)]
 		pub(crate) enum
-		PngChunk
+		PngChunkDescriptor
 		{
 			$(
 				$tag(u32),
 			)*
 		}
 
-		impl PngChunk
+		impl PngChunkDescriptor
 		{
 			pub(crate) fn
 			length
@@ -48,7 +57,7 @@ macro_rules! build_png_chunk_type_enum {
 				match *self
 				{
 					$(
-						PngChunk::$tag(length) => length,
+						PngChunkDescriptor::$tag(length) => length,
 					)*
 				}
 			}
@@ -63,7 +72,24 @@ macro_rules! build_png_chunk_type_enum {
 				match *self
 				{
 					$(
-						PngChunk::$tag(_) => String::from(stringify!($tag)),
+						PngChunkDescriptor::$tag(_) => String::from(stringify!($tag)),
+					)*
+				}
+			}
+
+			/// Critical chunks are required for decoding the image, while
+			/// ancillary (i.e. non-critical) chunks may be ignored
+			pub(crate) fn
+			is_critical
+			(
+				&self
+			)
+			-> bool
+			{
+				match *self
+				{
+					$(
+						PngChunkDescriptor::$tag(_) => $critical,
 					)*
 				}
 			}
@@ -74,12 +100,12 @@ macro_rules! build_png_chunk_type_enum {
 				string_name: &String,
 				length: u32
 			)
-			-> Result<PngChunk, String>
+			-> Result<PngChunkDescriptor, String>
 			{
 				match &(*string_name.as_str())
 				{
 					$(
-						stringify!($tag) => Ok(PngChunk::$tag(length)),
+						stringify!($tag) => Ok(PngChunkDescriptor::$tag(length)),
 					)*
 					_ => Err("Invalid chunk name".to_string()),
 				}
@@ -94,5 +120,180 @@ build_png_chunk_type_enum![
 	(PLTE,  true,       false,      BEFORE_IDAT),
 	(IDAT,  true,       true,       NONE),
 	(IEND,  true,       false,      LAST),
-	(zTXt,  false,      true,       NONE)
+	(tEXt,  false,      true,       NONE),
+	(zTXt,  false,      true,       NONE),
+	(iTXt,  false,      true,       NONE),
+	(eXIf,  false,      false,      BEFORE_IDAT)
 ];
+
+/// Checks that the given chunk name consists of exactly four ASCII letters
+/// and that the reserved bit (the case of the third letter) is not set, as
+/// required by the PNG specification
+pub(crate) fn
+check_chunk_name
+(
+	chunk_name: &str
+)
+-> Result<(), String>
+{
+	if chunk_name.len() != 4 || !chunk_name.bytes().all(|byte| byte.is_ascii_alphabetic())
+	{
+		return Err(format!("Invalid chunk name '{}': Must consist of four ASCII letters", chunk_name));
+	}
+
+	if chunk_property_bit_is_set(chunk_name, 2)
+	{
+		return Err(format!("Invalid chunk name '{}': Third letter must be uppercase", chunk_name));
+	}
+
+	return Ok(());
+}
+
+/// The properties of a chunk are encoded in bit 5 (i.e. the case) of each
+/// letter of its name: Lowercase means that the bit is set
+fn
+chunk_property_bit_is_set
+(
+	chunk_name: &str,
+	index:      usize
+)
+-> bool
+{
+	return chunk_name.as_bytes().get(index).map_or(false, |byte| byte & 0x20 != 0);
+}
+
+/// A PNG chunk including its data. Can be serialized into the form it has
+/// within a PNG file, i.e. length, type, data and CRC.
+///
+/// # Examples
+/// ```
+/// use little_exif::png::PngChunk;
+///
+/// let chunk = PngChunk::new("tEXt", b"Comment\0Hello".to_vec()).unwrap();
+/// assert!(chunk.is_ancillary());
+/// assert_eq!(&chunk.to_bytes()[4..8], b"tEXt");
+/// ```
+pub struct
+PngChunk
+{
+	descriptor: PngChunkDescriptor,
+	data:       Vec<u8>
+}
+
+impl
+PngChunk
+{
+	/// Creates a new chunk of the given type with the given data. 
+	/// Fails if the chunk type is not known to little_exif.
+	pub fn
+	new
+	(
+		chunk_type: &str,
+		data:       Vec<u8>
+	)
+	-> Result<PngChunk, String>
+	{
+		check_chunk_name(chunk_type)?;
+
+		let descriptor = PngChunkDescriptor::from_string(
+			&chunk_type.to_string(), 
+			data.len() as u32
+		)?;
+
+		return Ok(PngChunk { descriptor, data });
+	}
+
+	/// Ancillary chunks (lowercase first letter) are not required for
+	/// displaying the image
+	pub fn
+	is_ancillary
+	(
+		&self
+	)
+	-> bool
+	{
+		return chunk_property_bit_is_set(&self.descriptor.as_string(), 0);
+	}
+
+	/// Private chunks (lowercase second letter) are not defined by the PNG
+	/// specification or registered as public chunk types
+	pub fn
+	is_private
+	(
+		&self
+	)
+	-> bool
+	{
+		return chunk_property_bit_is_set(&self.descriptor.as_string(), 1);
+	}
+
+	/// Safe-to-copy chunks (lowercase fourth letter) may be copied by PNG
+	/// editors that don't know the chunk type, even if the image data has
+	/// been modified
+	pub fn
+	is_safe_to_copy
+	(
+		&self
+	)
+	-> bool
+	{
+		return chunk_property_bit_is_set(&self.descriptor.as_string(), 3);
+	}
+
+	/// Serializes the chunk: 4 bytes length (big endian), 4 bytes chunk type, 
+	/// the data itself and finally the 4 bytes CRC computed over the chunk
+	/// type and data
+	pub fn
+	to_bytes
+	(
+		&self
+	)
+	-> Vec<u8>
+	{
+		let mut type_and_data: Vec<u8> = self.descriptor.as_string().as_bytes().to_vec();
+		type_and_data.extend(self.data.iter());
+
+		let crc_struct = Crc::<u32>::new(&CRC_32_ISO_HDLC);
+		let checksum   = crc_struct.checksum(&type_and_data);
+
+		let mut chunk_bytes = to_u8_vec_macro!(u32, &self.descriptor.length(), &Endian::Big);
+		chunk_bytes.extend(type_and_data.iter());
+		chunk_bytes.extend(to_u8_vec_macro!(u32, &checksum, &Endian::Big));
+
+		return chunk_bytes;
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::check_chunk_name;
+	use super::PngChunk;
+
+	#[test]
+	fn
+	chunk_properties_from_name()
+	{
+		let critical = PngChunk::new("IHDR", vec![0u8; 13]).unwrap();
+		assert!(!critical.is_ancillary());
+		assert!(!critical.is_private());
+		assert!(!critical.is_safe_to_copy());
+
+		let ancillary = PngChunk::new("tEXt", b"Comment\0Hi".to_vec()).unwrap();
+		assert!(ancillary.is_ancillary());
+		assert!(!ancillary.is_private());
+		assert!(ancillary.is_safe_to_copy());
+	}
+
+	#[test]
+	fn
+	check_custom_chunk_names()
+	{
+		assert!(check_chunk_name("prIv").is_ok());
+		assert!(check_chunk_name("prIV").is_ok());
+		assert!(check_chunk_name("pr1v").is_err());
+		assert!(check_chunk_name("priv").is_err());
+		assert!(check_chunk_name("pri").is_err());
+		assert!(PngChunk::new("t3Xt", Vec::new()).is_err());
+	}
+}
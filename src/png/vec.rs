@@ -7,20 +7,31 @@ use std::io::Seek;
 
 use crc::Crc;
 use crc::CRC_32_ISO_HDLC;
-use miniz_oxide::deflate::compress_to_vec_zlib;
-use miniz_oxide::inflate::decompress_to_vec_zlib;
 
 use crate::general_file_io::*;
 use crate::metadata::Metadata;
+use crate::metadata::options::ParseStrictness;
+use crate::metadata::warnings::ParseWarning;
+use crate::metadata::warnings::ParseWarningKind;
 use crate::util::insert_multiple_at;
 use crate::util::range_remove;
 
 use super::PNG_SIGNATURE;
-use super::RAW_PROFILE_TYPE_EXIF;
 
 use super::png_chunk::PngChunk;
-use super::decode_metadata_png;
-use super::encode_metadata_png;
+use super::png_chunk::PngChunkDescriptor;
+use super::build_exif_chunk;
+use super::build_raw_profile_chunk;
+use super::build_xmp_chunk;
+use super::check_profile_type;
+use super::decode_raw_profile;
+use super::decode_exif_chunk;
+use super::decode_raw_profile_chunk;
+use super::decode_xmp_chunk;
+use super::encode_raw_profile;
+use super::is_exif_chunk;
+use super::is_raw_profile_chunk;
+use super::is_xmp_chunk;
 
 fn
 check_signature
@@ -51,9 +62,11 @@ check_signature
 fn
 get_next_chunk_descriptor
 (
-	cursor: &mut Cursor<&Vec<u8>>
+	cursor:     &mut Cursor<&Vec<u8>>,
+	strictness: ParseStrictness,
+	warnings:   &mut Vec<ParseWarning>
 )
--> Result<PngChunk, std::io::Error>
+-> Result<PngChunkDescriptor, std::io::Error>
 {
 	// Read the start of the chunk
 	let mut chunk_start = [0u8; 8];
@@ -97,45 +110,54 @@ get_next_chunk_descriptor
 	let crc_struct = Crc::<u32>::new(&CRC_32_ISO_HDLC);
 	let checksum = crc_struct.checksum(&crc_input) as u32;
 
-	for i in 0..4
-	{
-		if ((checksum >> (8 * (3-i))) as u8) != chunk_crc_buffer[i]
-		{
-			return io_error!(InvalidData, "Checksum check failed while reading PNG!");
-		}
-	}
-
-	// If validating the chunk using the CRC was successful, return its descriptor
+	// Determine the chunk descriptor
 	// Note: chunk_length does NOT include the +4 for the CRC area!
-	if let Ok(png_chunk) = PngChunk::from_string(
+	let png_chunk = match PngChunkDescriptor::from_string(
 		&chunk_name.unwrap(),
 		chunk_length
 	)
 	{
-		return Ok(png_chunk);
-	}
-	else
+		Ok(png_chunk) => png_chunk,
+		Err(_)        => return io_error!(Other, "Invalid PNG chunk name"),
+	};
+
+	// Validate the chunk using the CRC
+	// In lenient mode, a mismatch is only an error for critical chunks
+	if checksum.to_be_bytes() != chunk_crc_buffer
 	{
-		return io_error!(Other, "Invalid PNG chunk name");
+		if strictness == ParseStrictness::Strict || png_chunk.is_critical()
+		{
+			return io_error!(InvalidData, "Checksum check failed while reading PNG!");
+		}
+
+		warnings.push(ParseWarning::new(
+			ParseWarningKind::ChecksumMismatch,
+			format!("Checksum check failed for ancillary PNG chunk {} - Ignoring", png_chunk.as_string())
+		));
 	}
+
+	return Ok(png_chunk);
 }
 
 /// "Parses" the PNG by checking various properties:
 /// - Can the file be opened and is the signature valid?
 /// - Are the various chunks OK or not? For this, the local subroutine `get_next_chunk_descriptor` is used
+/// The strictness determines how CRC mismatches of ancillary chunks are handled
 pub(crate) fn
 parse_png
 (
-	file_buffer: &Vec<u8>
+	file_buffer: &Vec<u8>,
+	strictness: ParseStrictness,
+	warnings:   &mut Vec<ParseWarning>
 )
--> Result<Vec<PngChunk>, std::io::Error>
+-> Result<Vec<PngChunkDescriptor>, std::io::Error>
 {
 	let mut cursor = check_signature(file_buffer)?;
 	let mut chunks = Vec::new();
 
 	loop
 	{
-		let chunk_descriptor = get_next_chunk_descriptor(&mut cursor)?;
+		let chunk_descriptor = get_next_chunk_descriptor(&mut cursor, strictness, warnings)?;
 		chunks.push(chunk_descriptor);
 
 		if chunks.last().unwrap().as_string() == "IEND".to_string()
@@ -149,27 +171,44 @@ parse_png
 
 // Clears existing metadata chunk from a png file
 // Gets called before writing any new metadata
-#[allow(non_snake_case)]
+// All chunks are checked, as some writers place the eXIf chunk after the
+// IDAT chunks even though the PNG specification requires it before them
 pub(crate) fn
 clear_metadata
 (
 	file_buffer: &mut Vec<u8>
 )
 -> Result<(), std::io::Error>
+{
+	clear_raw_profile(file_buffer, "exif")?;
+	return remove_chunks(file_buffer, &["eXIf"]);
+}
+
+/// Clears all chunks holding a raw profile of the given type
+pub(crate) fn
+clear_raw_profile
+(
+	file_buffer:  &mut Vec<u8>,
+	profile_type: &str
+)
+-> Result<(), std::io::Error>
 {
 
 	// Parse the PNG - if this fails, the clear operation fails as well
-	let parse_png_result = parse_png(&file_buffer)?;
+	let parse_png_result = parse_png(&file_buffer, ParseStrictness::Strict, &mut Vec::new())?;
 
 	// Parsed PNG is Ok to use - Open the file and go through the chunks
 	// let mut file = open_write_file(path)?;
 	let mut cursor = Cursor::new(file_buffer);
-	let mut seek_counter = 8u64;
+	let mut seek_counter = PNG_SIGNATURE.len() as u64;
+
+	// Skip the signature
+	cursor.set_position(seek_counter);
 
 	for chunk in &parse_png_result
 	{
-		// If this is not a zTXt chunk, jump to the next chunk
-		if chunk.as_string() != String::from("zTXt")
+		// If this is not a zTXt or tEXt chunk, jump to the next chunk
+		if chunk.as_string() != String::from("zTXt") && chunk.as_string() != String::from("tEXt")
 		{
 			seek_counter += chunk.length() as u64 + 12;
 			cursor.seek(std::io::SeekFrom::Current(chunk.length() as i64 + 12))?;
@@ -181,31 +220,21 @@ clear_metadata
 
 		// Read chunk data into buffer for checking that this is the 
 		// correct chunk to delete
-		let mut zTXt_chunk_data = vec![0u8; chunk.length() as usize];
+		let mut chunk_data = vec![0u8; chunk.length() as usize];
 
-		if cursor.read(&mut zTXt_chunk_data).unwrap() != chunk.length() as usize
+		if cursor.read(&mut chunk_data).unwrap() != chunk.length() as usize
 		{
 			return io_error!(Other, "Could not read chunk data");
 		}
 
-		// Compare to the "Raw profile type exif" string constant
-		let mut correct_zTXt_chunk = true;
-		for i in 0..RAW_PROFILE_TYPE_EXIF.len()
-		{
-			if zTXt_chunk_data[i] != RAW_PROFILE_TYPE_EXIF[i]
-			{
-				correct_zTXt_chunk = false;
-				break;
-			}
-		}
-
 		// Skip the CRC as it is not important at this point
 		cursor.seek(std::io::SeekFrom::Current(4))?;
 
-		// If this is not the correct zTXt chunk, ignore current
-		// (wrong) zTXt chunk and continue with next chunk
-		if !correct_zTXt_chunk
+		// If this is not a chunk with the "Raw profile type <profile_type>"
+		// keyword, ignore it and continue with next chunk
+		if !is_raw_profile_chunk(&chunk.as_string(), &chunk_data, profile_type)
 		{	
+			seek_counter += chunk.length() as u64 + 12;
 			continue;
 		}
 		
@@ -213,72 +242,238 @@ clear_metadata
 		let remove_start = seek_counter as usize;
 		let remove_end   = cursor.position() as usize;
 		range_remove(cursor.get_mut(), remove_start, remove_end);
+		cursor.set_position(remove_start as u64);
 	}
 
 	return Ok(());
 }
 
-#[allow(non_snake_case)]
+/// Clears all iTXt chunks holding an XMP packet
 pub(crate) fn
-read_metadata
+clear_xmp
+(
+	file_buffer: &mut Vec<u8>
+)
+-> Result<(), std::io::Error>
+{
+	// Parse the PNG - if this fails, the clear operation fails as well
+	let parse_png_result = parse_png(&file_buffer, ParseStrictness::Strict, &mut Vec::new())?;
+
+	let mut position = PNG_SIGNATURE.len();
+
+	for chunk in &parse_png_result
+	{
+		let chunk_byte_count = chunk.length() as usize + 12;
+		let chunk_data       = &file_buffer[position+8..position+8+chunk.length() as usize];
+
+		if is_xmp_chunk(&chunk.as_string(), chunk_data)
+		{
+			range_remove(file_buffer, position, position + chunk_byte_count);
+		}
+		else
+		{
+			position += chunk_byte_count;
+		}
+	}
+
+	return Ok(());
+}
+
+/// Removes all textual chunks (tEXt, zTXt and iTXt), regardless of their
+/// keyword, as well as any eXIf chunk
+pub(crate) fn
+strip_all_metadata
+(
+	file_buffer: &mut Vec<u8>
+)
+-> Result<(), std::io::Error>
+{
+	return remove_chunks(file_buffer, &["tEXt", "zTXt", "iTXt", "eXIf"]);
+}
+
+/// Removes all chunks of the given types
+fn
+remove_chunks
+(
+	file_buffer: &mut Vec<u8>,
+	chunk_types: &[&str]
+)
+-> Result<(), std::io::Error>
+{
+	// Parse the PNG - if this fails, the remove operation fails as well
+	let parse_png_result = parse_png(&file_buffer, ParseStrictness::Strict, &mut Vec::new())?;
+
+	let mut position = PNG_SIGNATURE.len();
+
+	for chunk in &parse_png_result
+	{
+		// Length, type and CRC take up 12 bytes in addition to the data
+		let chunk_byte_count = chunk.length() as usize + 12;
+
+		if chunk_types.contains(&chunk.as_string().as_str())
+		{
+			range_remove(file_buffer, position, position + chunk_byte_count);
+		}
+		else
+		{
+			position += chunk_byte_count;
+		}
+	}
+
+	return Ok(());
+}
+
+/// Gets the start offset and length of the data of the first chunk holding
+/// the EXIF data as raw profile. Note that this data is not the TIFF payload
+/// itself but its hex-encoded (and in case of zTXt chunks compressed) form,
+/// preceded by the "Raw profile type exif" keyword.
+pub(crate) fn
+exif_byte_range
 (
 	file_buffer: &Vec<u8>
 )
+-> Result<Option<(u64, u64)>, std::io::Error>
+{
+	let parse_png_result = parse_png(file_buffer, ParseStrictness::Strict, &mut Vec::new())?;
+
+	let mut position = PNG_SIGNATURE.len();
+
+	for chunk in &parse_png_result
+	{
+		let data_start = position + 8;
+		let data_end   = data_start + chunk.length() as usize;
+
+		if is_raw_profile_chunk(&chunk.as_string(), &file_buffer[data_start..data_end], "exif")
+		{
+			return Ok(Some((data_start as u64, chunk.length() as u64)));
+		}
+
+		position = data_end + 4;
+	}
+
+	return Ok(None);
+}
+
+/// Gets the EXIF data of all chunks holding it in the order in which they
+/// appear in the file: eXIf chunks as well as zTXt/tEXt chunks with the
+/// "Raw profile type exif" keyword. Usually there is only one of them, but
+/// converting an image with different tools may result in multiple copies.
+pub(crate) fn
+read_all_metadata
+(
+	file_buffer: &Vec<u8>,
+	strictness:  ParseStrictness,
+	warnings:    &mut Vec<ParseWarning>
+)
+-> Result<Vec<Vec<u8>>, std::io::Error>
+{
+	// Parse the PNG - if this fails, the read fails as well
+	let parse_png_result = parse_png(file_buffer, strictness, warnings)?;
+
+	let mut exif_sources = Vec::new();
+	let mut position     = PNG_SIGNATURE.len();
+
+	for chunk in &parse_png_result
+	{
+		let data_start = position + 8;
+		let data_end   = data_start + chunk.length() as usize;
+		let chunk_data = &file_buffer[data_start..data_end];
+
+		// Skip CRC
+		position = data_end + 4;
+
+		if chunk.as_string() == String::from("eXIf")
+		{
+			exif_sources.push(chunk_data.to_vec());
+		}
+		else if is_exif_chunk(&chunk.as_string(), chunk_data)
+		{
+			exif_sources.push(decode_exif_chunk(&chunk.as_string(), chunk_data, strictness, warnings)?);
+		}
+	}
+
+	return Ok(exif_sources);
+}
+
+/// Gets the EXIF data of the first chunk holding it, see `read_all_metadata`
+pub(crate) fn
+read_metadata
+(
+	file_buffer: &Vec<u8>,
+	strictness:  ParseStrictness,
+	warnings:    &mut Vec<ParseWarning>
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	if let Some(exif) = read_all_metadata(file_buffer, strictness, warnings)?.into_iter().next()
+	{
+		return Ok(exif);
+	}
+	return io_error!(NotFound, "No metadata found!");
+}
+
+/// Reads the payload of the first raw profile of the given type, e.g. the
+/// IPTC data stored with the keyword "Raw profile type iptc"
+pub(crate) fn
+read_raw_profile
+(
+	file_buffer:  &Vec<u8>,
+	strictness:   ParseStrictness,
+	warnings:     &mut Vec<ParseWarning>,
+	profile_type: &str
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	check_profile_type(profile_type)?;
+	let encoded_text = read_raw_profile_text(file_buffer, strictness, warnings, profile_type)?;
+	return decode_raw_profile(&encoded_text, strictness, warnings);
+}
+
+/// Gets the (still hex-encoded) text of the first chunk holding a raw 
+/// profile of the given type
+fn
+read_raw_profile_text
+(
+	file_buffer:  &Vec<u8>,
+	strictness:   ParseStrictness,
+	warnings:     &mut Vec<ParseWarning>,
+	profile_type: &str
+)
 -> Result<Vec<u8>, std::io::Error>
 {
 	// Parse the PNG - if this fails, the read fails as well
-	let parse_png_result = parse_png(file_buffer)?;
+	let parse_png_result = parse_png(file_buffer, strictness, warnings)?;
 
 	// Parsed PNG is Ok to use - Open the file and go through the chunks
 	let mut cursor = check_signature(file_buffer).unwrap();
 	for chunk in &parse_png_result
 	{
 		// Wrong chunk? Seek to the next one
-		if chunk.as_string() != String::from("zTXt")
+		if chunk.as_string() != String::from("zTXt") && chunk.as_string() != String::from("tEXt")
 		{
 			cursor.seek(std::io::SeekFrom::Current(chunk.length() as i64 + 12))?;
 			continue;
 		}
 
-		// We now have a zTXt chunk:
+		// We now have a zTXt or tEXt chunk:
 		// Skip chunk length and type (4+4 Bytes)
 		cursor.seek(std::io::SeekFrom::Current(4+4))?;
 
 		// Read chunk data into buffer
 		// No need to verify this using CRC as already done by parse_png(path)
-		let mut zTXt_chunk_data = vec![0u8; chunk.length() as usize];
-		if cursor.read(&mut zTXt_chunk_data).unwrap() != chunk.length() as usize
+		let mut chunk_data = vec![0u8; chunk.length() as usize];
+		if cursor.read(&mut chunk_data).unwrap() != chunk.length() as usize
 		{
 			return io_error!(Other, "Could not read chunk data");
 		}
 
-		// Check that this is the correct zTXt chunk...
-		let mut correct_zTXt_chunk = true;
-		for i in 0..RAW_PROFILE_TYPE_EXIF.len()
-		{
-			if zTXt_chunk_data[i] != RAW_PROFILE_TYPE_EXIF[i]
-			{
-				correct_zTXt_chunk = false;
-				break;
-			}
-		}
-
-		if !correct_zTXt_chunk
-		{
-			// Skip CRC from current (wrong) zTXt chunk and continue
-			cursor.seek(std::io::SeekFrom::Current(4))?;
-			continue;
-		}
+		// Skip CRC
+		cursor.seek(std::io::SeekFrom::Current(4))?;
 
-		// Decode zlib data...
-		if let Ok(decompressed_data) = decompress_to_vec_zlib(&zTXt_chunk_data[RAW_PROFILE_TYPE_EXIF.len()..])
+		// Check that this is the correct chunk and decode it
+		if is_raw_profile_chunk(&chunk.as_string(), &chunk_data, profile_type)
 		{
-			// ...and perform PNG-specific decoding & return the result
-			return Ok(decode_metadata_png(&decompressed_data).unwrap());
-		}
-		else
-		{
-			return io_error!(Other, "Could not inflate compressed chunk data!");
+			return decode_raw_profile_chunk(&chunk.as_string(), &chunk_data, profile_type, strictness, warnings);
 		}
 	}
 
@@ -286,14 +481,65 @@ read_metadata
 
 }
 
+/// Gets the XMP packet of the first iTXt chunk with the keyword 
+/// "XML:com.adobe.xmp"
+pub(crate) fn
+read_xmp
+(
+	file_buffer: &Vec<u8>,
+	strictness:  ParseStrictness,
+	warnings:    &mut Vec<ParseWarning>
+)
+-> Result<String, std::io::Error>
+{
+	// Parse the PNG - if this fails, the read fails as well
+	let parse_png_result = parse_png(file_buffer, strictness, warnings)?;
+
+	let mut position = PNG_SIGNATURE.len();
 
+	for chunk in &parse_png_result
+	{
+		let data_start = position + 8;
+		let data_end   = data_start + chunk.length() as usize;
+		let chunk_data = &file_buffer[data_start..data_end];
+
+		if is_xmp_chunk(&chunk.as_string(), chunk_data)
+		{
+			return decode_xmp_chunk(chunk_data);
+		}
+
+		// Skip CRC
+		position = data_end + 4;
+	}
+
+	return io_error!(NotFound, "No XMP data found!");
+}
+
+/// Writes the XMP packet as uncompressed iTXt chunk right after the IHDR 
+/// chunk, replacing any previous XMP packet. Chunks holding EXIF data or
+/// other raw profiles are not affected.
+pub(crate) fn
+write_xmp
+(
+	file_buffer: &mut Vec<u8>,
+	packet:      &str
+)
+-> Result<(), std::io::Error>
+{
+	clear_xmp(file_buffer)?;
+	insert_after_IHDR(file_buffer, &build_xmp_chunk(packet));
+	return Ok(());
+}
 
+/// Writes the metadata as zTXt chunk (or as uncompressed tEXt chunk if 
+/// `as_zTXt_chunk` is false) right after the IHDR chunk
 #[allow(non_snake_case)]
 pub(crate) fn
 write_metadata
 (
-	file_buffer: &mut Vec<u8>,
-	metadata:    &Metadata
+	file_buffer:   &mut Vec<u8>,
+	metadata:      &Metadata,
+	as_zTXt_chunk: bool
 )
 -> Result<(), std::io::Error>
 {
@@ -302,47 +548,106 @@ write_metadata
 	// assume that is, in fact, a usable PNG file
 	let _ = clear_metadata(file_buffer)?;
 
+	// Build the new chunk and insert it (length, type, data and CRC)
+	let exif_chunk = build_exif_chunk(&metadata.encode()?, as_zTXt_chunk);
+	insert_after_IHDR(file_buffer, &exif_chunk);
+
+	return Ok(());
+}
+
+/// Writes the payload as raw profile of the given type, e.g. IPTC data with
+/// the keyword "Raw profile type iptc", using the same encoding as for the
+/// EXIF data. Any previous raw profile of this type gets removed first.
+#[allow(non_snake_case)]
+pub(crate) fn
+write_raw_profile
+(
+	file_buffer:   &mut Vec<u8>,
+	profile_type:  &str,
+	payload:       &[u8],
+	as_zTXt_chunk: bool
+)
+-> Result<(), std::io::Error>
+{
+	check_profile_type(profile_type)?;
+	clear_raw_profile(file_buffer, profile_type)?;
+
+	let chunk = build_raw_profile_chunk(profile_type, &encode_raw_profile(profile_type, payload), as_zTXt_chunk);
+	insert_after_IHDR(file_buffer, &chunk);
+
+	return Ok(());
+}
+
+/// Inserts the chunk (length, type, data and CRC) right after the IHDR chunk
+#[allow(non_snake_case)]
+fn
+insert_after_IHDR
+(
+	file_buffer: &mut Vec<u8>,
+	chunk:       &PngChunk
+)
+{
 	let mut IHDR_length = 0u32;
-	if let Ok(chunks) = parse_png(file_buffer)
+	if let Ok(chunks) = parse_png(file_buffer, ParseStrictness::Strict, &mut Vec::new())
 	{
 		IHDR_length = chunks[0].length();
 	}
 
-	// Encode the data specifically for PNG and open the image file
-	let encoded_metadata = encode_metadata_png(&metadata.encode()?);
 	let seek_start = 0u64         // Skip ...
 	+ PNG_SIGNATURE.len() as u64  // PNG Signature
 	+ IHDR_length         as u64  // IHDR data section
 	+ 12                  as u64; // rest of IHDR chunk (length, type, CRC)
 
-	// Build data of new chunk using zlib compression (level=8 -> default)
-	let mut zTXt_chunk_data: Vec<u8> = vec![0x7a, 0x54, 0x58, 0x74];
-	zTXt_chunk_data.extend(RAW_PROFILE_TYPE_EXIF.iter());
-	zTXt_chunk_data.extend(compress_to_vec_zlib(&encoded_metadata, 8).iter());
+	insert_multiple_at(file_buffer, seek_start as usize, &mut chunk.to_bytes());
+}
 
-	// Compute CRC and append it to the chunk data
-	let crc_struct = Crc::<u32>::new(&CRC_32_ISO_HDLC);
-	let checksum = crc_struct.checksum(&zTXt_chunk_data) as u32;
-	for i in 0..4
-	{
-		zTXt_chunk_data.push( (checksum >> (8 * (3-i))) as u8);		
-	}
+/// Recomputes the CRC of every chunk and replaces the stored CRC if it does
+/// not match. Returns the number of repaired chunks.
+pub(crate) fn
+repair_crcs
+(
+	file_buffer: &mut Vec<u8>
+)
+-> Result<u32, std::io::Error>
+{
+	check_signature(file_buffer)?;
+
+	let     crc_struct     = Crc::<u32>::new(&CRC_32_ISO_HDLC);
+	let mut position       = PNG_SIGNATURE.len();
+	let mut repair_counter = 0;
 
-	// Prepare the length of the new chunk (subtracting 8 for type and CRC) for
-	// inserting prior to the new chunk
-	let     chunk_data_len        = zTXt_chunk_data.len() as u32 - 8;
-	let mut chunk_data_len_buffer = [0u8; 4];
-	for i in 0..4
+	loop
 	{
-		chunk_data_len_buffer[i] = (chunk_data_len >> (8 * (3-i))) as u8;
+		if position + 12 > file_buffer.len()
+		{
+			return io_error!(UnexpectedEof, "Could not read start of chunk");
+		}
+
+		let chunk_length = u32::from_be_bytes(file_buffer[position..position+4].try_into().unwrap()) as usize;
+		let crc_position = position + 8 + chunk_length;
+
+		if crc_position + 4 > file_buffer.len()
+		{
+			return io_error!(UnexpectedEof, "Could not read chunk data");
+		}
+
+		// CRC is computed over chunk type and data
+		let checksum = crc_struct.checksum(&file_buffer[position+4..crc_position]).to_be_bytes();
+		if file_buffer[crc_position..crc_position+4] != checksum
+		{
+			file_buffer[crc_position..crc_position+4].copy_from_slice(&checksum);
+			repair_counter += 1;
+		}
+
+		if &file_buffer[position+4..position+8] == b"IEND"
+		{
+			break;
+		}
+
+		position = crc_position + 4;
 	}
-	
-	// Write data of new chunk length and chunk itself
-	let insert_position = seek_start as usize;
-	insert_multiple_at(file_buffer, insert_position,   &mut chunk_data_len_buffer.to_vec());
-	insert_multiple_at(file_buffer, insert_position+4, &mut zTXt_chunk_data);
 
-	return Ok(());
+	return Ok(repair_counter);
 }
 
 #[cfg(test)]
@@ -350,13 +655,272 @@ mod tests
 {
 
 	#[test]
+	#[cfg(feature = "std_fs")]
 	fn
 	parsing_test() 
 	{
 		let chunks = crate::png::file::parse_png(
-			std::path::Path::new("tests/png_parse_test_image.png")
+			std::path::Path::new("tests/png_parse_test_image.png"),
+			crate::metadata::options::ParseStrictness::Strict,
+			&mut Vec::new()
 		).unwrap();
 		assert_eq!(chunks.len(), 3);
 	}
+
+	#[test]
+	fn
+	lenient_crc_mismatch_test()
+	{
+		use crate::metadata::Metadata;
+		use crate::metadata::options::ParseStrictness;
+		use crate::metadata::warnings::ParseWarningKind;
+
+		let mut metadata = Metadata::new();
+		metadata.set_tag(crate::exif_tag::ExifTag::ISO(vec![2706]));
+
+		let mut image_data = std::fs::read("tests/sample2.png").unwrap();
+		crate::png::vec::write_metadata(&mut image_data, &metadata, true).unwrap();
+
+		// Insert an ancillary tEXt chunk with a corrupted CRC after IHDR
+		let mut comment = crate::png::png_chunk::PngChunk::new("tEXt", b"Comment\0Hi".to_vec()).unwrap().to_bytes();
+		let last = comment.len() - 1;
+		comment[last] ^= 0xff;
+		crate::util::insert_multiple_at(&mut image_data, 33, &mut comment);
+
+		// Strict mode fails, lenient mode still finds the EXIF data
+		let mut warnings = Vec::new();
+		assert!(crate::png::vec::read_metadata(&image_data, ParseStrictness::Strict,  &mut warnings).is_err());
+		assert!(crate::png::vec::read_metadata(&image_data, ParseStrictness::Lenient, &mut warnings).is_ok());
+
+		// The mismatch is reported as warning
+		assert_eq!(warnings.len(), 1);
+		assert_eq!(warnings[0].kind, ParseWarningKind::ChecksumMismatch);
+
+		// Critical chunks (here: IHDR) still result in an error
+		let mut broken_critical = image_data.clone();
+		broken_critical[32] ^= 0xff;
+		assert!(crate::png::vec::read_metadata(&broken_critical, ParseStrictness::Lenient, &mut Vec::new()).is_err());
+
+		// After repairing, strict mode works again
+		assert_eq!(crate::png::vec::repair_crcs(&mut image_data).unwrap(), 1);
+		assert!(crate::png::vec::read_metadata(&image_data, ParseStrictness::Strict, &mut Vec::new()).is_ok());
+		assert_eq!(crate::png::vec::repair_crcs(&mut image_data).unwrap(), 0);
+	}
+
+	#[test]
+	fn
+	raw_profile_iptc_round_trip_test()
+	{
+		use crate::metadata::Metadata;
+		use crate::metadata::options::ParseStrictness;
+
+		let iptc_payload = (0..100u8).collect::<Vec<u8>>();
+
+		let mut metadata = Metadata::new();
+		metadata.set_tag(crate::exif_tag::ExifTag::ISO(vec![2706]));
+
+		let mut image_data = std::fs::read("tests/sample2.png").unwrap();
+		crate::png::vec::write_metadata(&mut image_data, &metadata, true).unwrap();
+
+		for compressed in [true, false]
+		{
+			crate::png::vec::write_raw_profile(&mut image_data, "iptc", &iptc_payload, compressed).unwrap();
+
+			assert_eq!(
+				crate::png::vec::read_raw_profile(&image_data, ParseStrictness::Strict, &mut Vec::new(), "iptc").unwrap(),
+				iptc_payload
+			);
+
+			// The EXIF data is not affected
+			assert!(crate::png::vec::read_metadata(&image_data, ParseStrictness::Strict, &mut Vec::new()).is_ok());
+		}
+
+		// Replacing the EXIF data keeps the IPTC profile and vice versa
+		crate::png::vec::write_metadata(&mut image_data, &metadata, false).unwrap();
+		assert!(crate::png::vec::read_raw_profile(&image_data, ParseStrictness::Strict, &mut Vec::new(), "iptc").is_ok());
+		assert_eq!(crate::png::vec::parse_png(&image_data, ParseStrictness::Strict, &mut Vec::new()).unwrap().iter()
+			.filter(|chunk| chunk.as_string() == "zTXt" || chunk.as_string() == "tEXt")
+			.count(), 2
+		);
+
+		assert!(crate::png::vec::read_raw_profile(&image_data, ParseStrictness::Strict, &mut Vec::new(), "xmp").is_err());
+		assert!(crate::png::vec::write_raw_profile(&mut image_data, "no spaces", &iptc_payload, true).is_err());
+	}
+
+	#[test]
+	fn
+	truncated_zlib_exif_chunk_test()
+	{
+		use crate::metadata::Metadata;
+		use crate::metadata::options::ParseStrictness;
+		use crate::metadata::warnings::ParseWarningKind;
+
+		let mut metadata = Metadata::new();
+		metadata.set_tag(crate::exif_tag::ExifTag::ImageDescription(
+			(0..500u32).map(|i| (i*i).to_string()).collect::<Vec<String>>().join(" ")
+		));
+		let general_encoded_metadata = metadata.encode().unwrap();
+
+		// Build a zTXt EXIF chunk and cut off the second half of its data
+		let mut chunk_data = crate::png::raw_profile_keyword("exif");
+		chunk_data.push(0x00);
+		let compressed_data = miniz_oxide::deflate::compress_to_vec_zlib(
+			&crate::png::encode_metadata_png(&general_encoded_metadata), 
+			8
+		);
+		chunk_data.extend(&compressed_data[..compressed_data.len() / 2]);
+		
+		let mut exif_chunk = crate::png::png_chunk::PngChunk::new("zTXt", chunk_data).unwrap().to_bytes();
+		let mut image_data = std::fs::read("tests/sample2.png").unwrap();
+		crate::util::insert_multiple_at(&mut image_data, 33, &mut exif_chunk);
+
+		// Strict mode fails, lenient mode recovers the start of the EXIF data
+		assert!(crate::png::vec::read_metadata(&image_data, ParseStrictness::Strict, &mut Vec::new()).is_err());
+
+		let mut expected = crate::general_file_io::EXIF_HEADER.to_vec();
+		expected.extend(general_encoded_metadata);
+
+		let mut warnings = Vec::new();
+		let recovered = crate::png::vec::read_metadata(&image_data, ParseStrictness::Lenient, &mut warnings).unwrap();
+		assert!(recovered.len() > crate::general_file_io::EXIF_HEADER.len());
+		assert!(recovered.len() < expected.len());
+		assert!(expected.starts_with(&recovered));
+		assert!(warnings.iter().any(|warning| warning.kind == ParseWarningKind::TruncatedData));
+	}
+
+	#[test]
+	fn
+	multiple_exif_sources_test()
+	{
+		use crate::exif_tag::ExifTag;
+		use crate::filetype::FileExtension;
+		use crate::metadata::Metadata;
+		use crate::metadata::options::ParseStrictness;
+		use crate::metadata::warnings::ParseWarningKind;
+
+		// "Raw profile type exif" text chunk as written by little_exif...
+		let mut raw_profile_metadata = Metadata::new();
+		raw_profile_metadata.set_tag(ExifTag::Model("Raw profile".to_string()));
+		raw_profile_metadata.set_tag(ExifTag::ImageDescription("Hello World".to_string()));
+
+		let mut image_data = std::fs::read("tests/sample2.png").unwrap();
+		crate::png::vec::write_metadata(&mut image_data, &raw_profile_metadata, true).unwrap();
+
+		// ...and an eXIf chunk in front of it, e.g. written by another tool
+		let mut exif_chunk_metadata = Metadata::new();
+		exif_chunk_metadata.set_tag(ExifTag::Model("eXIf".to_string()));
+		exif_chunk_metadata.set_tag(ExifTag::Artist("Someone".to_string()));
+
+		let mut exif_chunk = crate::png::png_chunk::PngChunk::new("eXIf", exif_chunk_metadata.encode().unwrap()).unwrap().to_bytes();
+		crate::util::insert_multiple_at(&mut image_data, 33, &mut exif_chunk);
+
+		// The eXIf chunk comes first and takes precedence
+		let metadata = Metadata::new_from_vec(&image_data, FileExtension::PNG { as_zTXt_chunk: true }).unwrap();
+		assert_eq!(metadata.get_tag(&ExifTag::Model(String::new())).next(),            Some(&ExifTag::Model("eXIf".to_string())));
+		assert_eq!(metadata.get_tag(&ExifTag::Artist(String::new())).next(),           Some(&ExifTag::Artist("Someone".to_string())));
+		assert_eq!(metadata.get_tag(&ExifTag::ImageDescription(String::new())).next(), Some(&ExifTag::ImageDescription("Hello World".to_string())));
+		assert_eq!(metadata.warnings().len(), 1);
+		assert_eq!(metadata.warnings()[0].kind, ParseWarningKind::MultipleExifSources);
+
+		// Clearing the metadata removes both sources
+		crate::png::vec::clear_metadata(&mut image_data).unwrap();
+		assert!(crate::png::vec::read_all_metadata(&image_data, ParseStrictness::Strict, &mut Vec::new()).unwrap().is_empty());
+	}
+
+	#[test]
+	fn
+	exif_chunk_after_idat_test()
+	{
+		use crate::exif_tag::ExifTag;
+		use crate::filetype::FileExtension;
+		use crate::metadata::Metadata;
+		use crate::metadata::options::ParseStrictness;
+
+		let file_type = FileExtension::PNG { as_zTXt_chunk: true };
+
+		let mut metadata = Metadata::new();
+		metadata.set_tag(ExifTag::Artist("Someone".to_string()));
+
+		// eXIf chunk right before the IEND chunk (which is 12 bytes long),
+		// i.e. after the IDAT chunks, as done by some writers
+		let mut image_data = std::fs::read("tests/sample2.png").unwrap();
+		let iend_position  = image_data.len() - 12;
+		let mut exif_chunk = crate::png::png_chunk::PngChunk::new("eXIf", metadata.encode().unwrap()).unwrap().to_bytes();
+		crate::util::insert_multiple_at(&mut image_data, iend_position, &mut exif_chunk);
+
+		let metadata = Metadata::new_from_vec(&image_data, file_type).unwrap();
+		assert_eq!(metadata.get_tag(&ExifTag::Artist(String::new())).next(), Some(&ExifTag::Artist("Someone".to_string())));
+
+		// Writing replaces the chunk instead of adding a second source
+		let mut written_data = image_data.clone();
+		metadata.write_to_vec(&mut written_data, file_type).unwrap();
+		assert_eq!(crate::png::vec::read_all_metadata(&written_data, ParseStrictness::Strict, &mut Vec::new()).unwrap().len(), 1);
+
+		// Clearing removes the chunk wherever it is located
+		crate::png::vec::clear_metadata(&mut image_data).unwrap();
+		assert!(crate::png::vec::read_all_metadata(&image_data, ParseStrictness::Strict, &mut Vec::new()).unwrap().is_empty());
+	}
+
+	#[test]
+	fn
+	xmp_round_trip_test()
+	{
+		use crate::exif_tag::ExifTag;
+		use crate::filetype::FileExtension;
+		use crate::metadata::Metadata;
+		use crate::metadata::options::ParseStrictness;
+
+		let file_type = FileExtension::PNG { as_zTXt_chunk: true };
+		let packet    = "<x:xmpmeta xmlns:x=\"adobe:ns:meta/\"><rdf:RDF/></x:xmpmeta>";
+
+		let mut metadata = Metadata::new();
+		metadata.set_tag(ExifTag::ISO(vec![2706]));
+
+		let mut image_data = std::fs::read("tests/sample2.png").unwrap();
+		assert!(Metadata::get_xmp(&image_data, file_type).is_err());
+
+		Metadata::set_xmp(&mut image_data, file_type, packet).unwrap();
+		metadata.write_to_vec(&mut image_data, file_type).unwrap();
+		assert_eq!(Metadata::get_xmp(&image_data, file_type).unwrap(), packet);
+
+		// Replacing the XMP packet keeps the EXIF data and vice versa
+		Metadata::set_xmp(&mut image_data, file_type, "<x:xmpmeta/>").unwrap();
+		metadata.write_to_vec(&mut image_data, file_type).unwrap();
+		assert_eq!(Metadata::get_xmp(&image_data, file_type).unwrap(), "<x:xmpmeta/>");
+		assert_eq!(
+			Metadata::new_from_vec(&image_data, file_type).unwrap().get_tag(&ExifTag::ISO(Vec::new())).next(),
+			Some(&ExifTag::ISO(vec![2706]))
+		);
+		assert_eq!(crate::png::vec::parse_png(&image_data, ParseStrictness::Strict, &mut Vec::new()).unwrap().iter()
+			.filter(|chunk| chunk.as_string() == "iTXt")
+			.count(), 1
+		);
+
+		// Compressed iTXt chunks with a language tag are accepted as well
+		let mut chunk_data = b"XML:com.adobe.xmp\0\x01\x00en\0\0".to_vec();
+		chunk_data.extend(miniz_oxide::deflate::compress_to_vec_zlib(packet.as_bytes(), 8));
+		let mut chunk = crate::png::png_chunk::PngChunk::new("iTXt", chunk_data).unwrap().to_bytes();
+
+		crate::png::vec::clear_xmp(&mut image_data).unwrap();
+		crate::util::insert_multiple_at(&mut image_data, 33, &mut chunk);
+		assert_eq!(Metadata::get_xmp(&image_data, file_type).unwrap(), packet);
+	}
+
+	#[test]
+	fn
+	chunk_builder_round_trip_test()
+	{
+		let text_data = b"Comment\0Hello, World!".to_vec();
+		let chunk     = crate::png::png_chunk::PngChunk::new("tEXt", text_data.clone()).unwrap();
+		let bytes     = chunk.to_bytes();
+
+		assert_eq!(bytes.len(), text_data.len() + 12);
+
+		let mut cursor = std::io::Cursor::new(&bytes);
+		let descriptor = crate::png::vec::get_next_chunk_descriptor(&mut cursor, crate::metadata::options::ParseStrictness::Strict, &mut Vec::new()).unwrap();
+
+		assert_eq!(descriptor.as_string(), "tEXt".to_string());
+		assert_eq!(descriptor.length(),    text_data.len() as u32);
+	}
 	
 }
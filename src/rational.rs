@@ -27,12 +27,12 @@ pub struct iR64
 
 
 
-fn greatest_common_divisor
+pub(crate) fn greatest_common_divisor
 (
-	mut a: u32,
-	mut b: u32
+	mut a: u64,
+	mut b: u64
 )
--> u32
+-> u64
 {
 	while b != 0
 	{
@@ -43,6 +43,66 @@ fn greatest_common_divisor
 	return a;
 }
 
+/// Reduces a fraction so that both terms are less or equal than `max_term`.
+/// First, the fraction gets reduced using the GCD. If the terms are still
+/// too large, both are scaled down proportionally (with rounding), which
+/// results in an approximation of the original value. If the value itself 
+/// is too large to be represented, it gets clamped to `max_term / 1`.
+fn clamp_terms
+(
+	nominator:   u64,
+	denominator: u64,
+	max_term:    u64
+)
+-> (u64, u64)
+{
+	let gcd = greatest_common_divisor(nominator, denominator);
+	let (mut nominator, mut denominator) = if gcd > 1
+	{
+		(nominator / gcd, denominator / gcd)
+	}
+	else
+	{
+		(nominator, denominator)
+	};
+
+	if nominator <= max_term && denominator <= max_term
+	{
+		return (nominator, denominator);
+	}
+
+	// Determine how far the terms need to be shifted so that the larger one
+	// fits, taking into account that rounding may add one
+	let larger_term = std::cmp::max(nominator, denominator);
+	let mut shift   = 0;
+	while (larger_term >> shift) >= max_term
+	{
+		shift += 1;
+	}
+
+	let round = 1u64 << (shift - 1);
+	let scaled_denominator = (denominator >> shift) + ((denominator & ((1u64 << shift) - 1)) >= round) as u64;
+	let scaled_nominator   = (nominator   >> shift) + ((nominator   & ((1u64 << shift) - 1)) >= round) as u64;
+
+	if scaled_denominator == 0 && denominator != 0
+	{
+		// Value is too large to be represented
+		return (max_term, 1);
+	}
+
+	nominator   = scaled_nominator;
+	denominator = scaled_denominator;
+
+	let gcd = greatest_common_divisor(nominator, denominator);
+	if gcd > 1
+	{
+		nominator   /= gcd;
+		denominator /= gcd;
+	}
+
+	return (nominator, denominator);
+}
+
 fn add_next_fraction_term
 (
 	term:                &u32,
@@ -102,6 +162,39 @@ f64_to_rational64u
 	real_number:     f64,
 )
 -> uR64
+{
+	return f64_to_rational64u_with_max_denominator(real_number, i32::MAX as u32);
+}
+
+/// Finds the fraction closest to the given value whose denominator does not
+/// exceed `max_denominator`, e.g. for getting clean values for tags like
+/// `FNumber` or `ExposureTime`. A `max_denominator` of 0 is treated as 1.
+///
+/// # Examples
+/// ```no_run
+/// use little_exif::rational::f64_to_rational64u_bounded;
+/// use little_exif::rational::uR64;
+///
+/// assert_eq!(f64_to_rational64u_bounded(3.14159265, 10), uR64 { nominator: 22, denominator: 7 });
+/// ```
+pub fn
+f64_to_rational64u_bounded
+(
+	real_number:     f64,
+	max_denominator: u32,
+)
+-> uR64
+{
+	return f64_to_rational64u_with_max_denominator(real_number, std::cmp::max(max_denominator, 1));
+}
+
+fn
+f64_to_rational64u_with_max_denominator
+(
+	real_number:     f64,
+	max_denominator: u32,
+)
+-> uR64
 {
 	// Make sure that we are dealing with positive real numbers
 	let real_number = real_number.abs();
@@ -142,7 +235,7 @@ f64_to_rational64u
 		continued_fraction_term = reciprocal_residual.floor();
 
 		
-		n = (i32::MAX as u32 - previous_convergent.denominator) / convergent.denominator;
+		n = max_denominator.saturating_sub(previous_convergent.denominator) / convergent.denominator;
 		if convergent.nominator > 0
 		{
 			n = std::cmp::min(
@@ -185,9 +278,9 @@ f64_to_rational64u
 	}
 
 	let gcd = greatest_common_divisor(
-		best_approximation.nominator, 
-		best_approximation.denominator
-	);
+		best_approximation.nominator   as u64, 
+		best_approximation.denominator as u64
+	) as u32;
 
 	return uR64 {
 		nominator:   best_approximation.nominator   / gcd,
@@ -195,6 +288,62 @@ f64_to_rational64u
 	};
 }
 
+impl
+uR64
+{
+	/// Creates a rational from terms that may exceed the range of `u32`, e.g.
+	/// after some arithmetic, instead of letting them silently wrap when 
+	/// casting. The fraction gets reduced using the GCD first and, if that
+	/// is not enough, scaled down proportionally to the closest
+	/// representable approximation.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use little_exif::rational::uR64;
+	///
+	/// let fraction = uR64::clamp_from_u64(3 << 33, 4 << 33);
+	/// assert_eq!(fraction, uR64 { nominator: 3, denominator: 4 });
+	/// ```
+	pub fn
+	clamp_from_u64
+	(
+		nominator:   u64,
+		denominator: u64
+	)
+	-> uR64
+	{
+		let (nominator, denominator) = clamp_terms(nominator, denominator, u32::MAX as u64);
+		return uR64 { nominator: nominator as u32, denominator: denominator as u32 };
+	}
+}
+
+impl
+iR64
+{
+	/// Same as `uR64::clamp_from_u64`, but for signed terms that may exceed
+	/// the range of `i32`. The sign of the result is stored in the nominator.
+	pub fn
+	clamp_from_i64
+	(
+		nominator:   i64,
+		denominator: i64
+	)
+	-> iR64
+	{
+		let is_negative = (nominator < 0) != (denominator < 0);
+		let (nominator, denominator) = clamp_terms(
+			nominator.unsigned_abs(), 
+			denominator.unsigned_abs(), 
+			i32::MAX as u64
+		);
+
+		return iR64 { 
+			nominator:   if is_negative { -(nominator as i32) } else { nominator as i32 },
+			denominator: denominator as i32
+		};
+	}
+}
+
 impl Into<uR64> for f64 { fn into (self) -> uR64 { f64_to_rational64u(self) } }
 impl Into<iR64> for f64 { fn into (self) -> iR64 { f64_to_rational64s(self) } }
 
@@ -208,4 +357,26 @@ impl Into<f64> for iR64 { fn into (self) -> f64 { rational64s_to_f64(&self) } }
 
 impl Into<u32> for uR64 { fn into (self) -> u32 { rational64u_to_f64(&self) as u32 } }
 impl Into<u32> for iR64 { fn into (self) -> u32 { rational64s_to_f64(&self) as u32 } }
-impl Into<i32> for iR64 { fn into (self) -> i32 { rational64s_to_f64(&self) as i32 } }
\ No newline at end of file
+impl Into<i32> for iR64 { fn into (self) -> i32 { rational64s_to_f64(&self) as i32 } }
+
+#[cfg(test)]
+mod tests
+{
+	use super::f64_to_rational64u;
+	use super::f64_to_rational64u_bounded;
+	use super::uR64;
+
+	#[test]
+	fn
+	bounded_denominator()
+	{
+		assert_eq!(f64_to_rational64u_bounded(5.6,        10 ), uR64 { nominator: 28,  denominator: 5   });
+		assert_eq!(f64_to_rational64u_bounded(1.0 / 3.0,  10 ), uR64 { nominator: 1,   denominator: 3   });
+		assert_eq!(f64_to_rational64u_bounded(3.14159265, 10 ), uR64 { nominator: 22,  denominator: 7   });
+		assert_eq!(f64_to_rational64u_bounded(3.14159265, 100), uR64 { nominator: 311, denominator: 99  });
+		assert_eq!(f64_to_rational64u_bounded(0.004,      0  ), uR64 { nominator: 0,   denominator: 1   });
+
+		// Same as the unbounded conversion if the bound is large enough
+		assert_eq!(f64_to_rational64u_bounded(0.004, 1000), f64_to_rational64u(0.004));
+	}
+}
@@ -3,12 +3,15 @@
 
 use std::io::BufReader;
 use std::io::BufWriter;
+use std::io::Read;
 use std::path::Path;
 
 use crate::general_file_io::open_read_file;
 use crate::general_file_io::open_write_file;
+use crate::metadata::options::ReadOptions;
 use crate::metadata::Metadata;
 
+use super::check_not_big_tiff;
 use super::generic_read_metadata;
 use super::generic_write_metadata;
 
@@ -32,7 +35,7 @@ clear_metadata
 {
 	// Read in the data
 	let     raw_data = generic_read_metadata(&mut BufReader::new(open_read_file(path)?));
-	let mut data     = Metadata::general_decoding_wrapper(raw_data)?;
+	let mut data     = Metadata::general_decoding_wrapper(raw_data, &ReadOptions::default(), Vec::new())?;
 
 	// Remove all IFDs that aren't required
 	data.reduce_to_a_minimum();
@@ -51,6 +54,10 @@ write_metadata
 )
 -> Result<(), std::io::Error>
 {
+	let mut header = Vec::new();
+	open_read_file(path)?.take(4).read_to_end(&mut header)?;
+	check_not_big_tiff(&header)?;
+
 	let mut buffered_file = BufWriter::new(open_write_file(path)?);
 	return generic_write_metadata(&mut buffered_file, metadata);
 }
\ No newline at end of file
@@ -6,11 +6,42 @@ use std::io::Read;
 use std::io::Write;
 
 use crate::general_file_io::EXIF_HEADER;
+use crate::metadata::error::UnsupportedFormat;
 use crate::metadata::Metadata;
 
+#[cfg(feature = "std_fs")]
 pub mod file;
 pub mod vec;
 
+/// Checks if the data starts with the header of a BigTIFF file, which uses
+/// the version number 43 instead of 42 (the "magic number")
+pub(crate) fn
+is_big_tiff
+(
+	header: &[u8]
+)
+-> bool
+{
+	return header.starts_with(&[0x49, 0x49, 0x2b, 0x00])
+		|| header.starts_with(&[0x4d, 0x4d, 0x00, 0x2b]);
+}
+
+/// Returns an `UnsupportedFormat` error for BigTIFF data, as writing the
+/// metadata in the classic TIFF format would destroy the image
+pub(crate) fn
+check_not_big_tiff
+(
+	header: &[u8]
+)
+-> Result<(), std::io::Error>
+{
+	if is_big_tiff(header)
+	{
+		return Err(UnsupportedFormat::io_error("BigTIFF"));
+	}
+	return Ok(());
+}
+
 pub(crate) fn
 generic_write_metadata
 <T: Seek + Write>
@@ -3,8 +3,10 @@
 
 use std::io::Cursor;
 
+use crate::metadata::options::ReadOptions;
 use crate::metadata::Metadata;
 
+use super::check_not_big_tiff;
 use super::generic_read_metadata;
 use super::generic_write_metadata;
 
@@ -32,7 +34,7 @@ clear_metadata
 
 	// Read in the data
 	let     raw_data = generic_read_metadata(&mut cursor);
-	let mut data     = Metadata::general_decoding_wrapper(raw_data)?;
+	let mut data     = Metadata::general_decoding_wrapper(raw_data, &ReadOptions::default(), Vec::new())?;
 
 	// Remove all IFDs that aren't required
 	data.reduce_to_a_minimum();
@@ -50,6 +52,8 @@ write_metadata
 )
 -> Result<(), std::io::Error>
 {
+	check_not_big_tiff(file_buffer)?;
+
 	let mut cursor = Cursor::new(file_buffer);
 	return generic_write_metadata(&mut cursor, metadata);
 }
\ No newline at end of file
@@ -216,7 +216,7 @@ check_exif_in_file
 		if first_chunk.header().to_lowercase() != VP8X_HEADER.to_lowercase()
 		{
 			return io_error!(
-				Other, 
+				NotFound, 
 				format!("Expected first chunk of WebP file to be of type 'VP8X' but instead got {}!", first_chunk.header())
 			);
 		}
@@ -245,7 +245,7 @@ check_exif_in_file
 	// https://developers.google.com/speed/webp/docs/riff_container#extended_file_format
 	if flag_buffer[0] & 0x08 != 0x08
 	{
-		return io_error!(Other, "No EXIF chunk according to VP8X flags!");
+		return io_error!(NotFound, "No EXIF chunk according to VP8X flags!");
 	}
 
 	return Ok((file, parsed_webp_result.unwrap()));
@@ -264,7 +264,7 @@ read_metadata
 {
 	// Check the file signature, parse it, check that it has a VP8X chunk and
 	// the EXIF flag is set there
-	let (mut file, parse_webp_result) = check_exif_in_file(path).unwrap();
+	let (mut file, parse_webp_result) = check_exif_in_file(path)?;
 
 	// At this point we have established that the file has to contain an EXIF
 	// chunk at some point. So, now we need to find & return it
@@ -636,25 +636,22 @@ write_metadata
 )
 -> Result<(), std::io::Error>
 {
+	// Encode the general metadata format to WebP specifications first, so 
+	// that the file stays unchanged if this fails
+	let encoded_metadata = encode_metadata_webp(&metadata.encode()?)?;
+
 	// Clear the metadata from the file and return if this results in an error
 	clear_metadata(path)?;
 
-	// Encode the general metadata format to WebP specifications
-	let encoded_metadata = encode_metadata_webp(&metadata.encode()?);
-
 	// Open the file...
 	let mut file = check_signature(path)?;
 
 	// ...and find a location where to put the EXIF chunk
-	// This is done by requesting a chunk descriptor as long as we find a chunk
-	// that is both known and should be located *before* the EXIF chunk
-	let pre_exif_chunks = [
-		"VP8X",
-		"VP8",
-		"VP8L",
-		"ICCP",
-		"ANIM"
-	];
+	// This is done by going through the chunks until the XMP chunk or the
+	// end of the file is reached and remembering the position after the last
+	// chunk that should be located *before* the EXIF chunk. Any other chunks
+	// (e.g. unknown ones) are left untouched at their location
+	let mut insert_position = file.seek(SeekFrom::Current(0))?;
 
 	loop
 	{
@@ -664,18 +661,14 @@ write_metadata
 
 		if let Ok(chunk_descriptor) = chunk_descriptor_result
 		{
-			let mut chunk_type_found_in_pre_exif_chunks = false;
-
-			// Check header of chunk descriptor against any of the known chunks
-			// that should come before the EXIF chunk
-			for pre_exif_chunk in &pre_exif_chunks
+			if chunk_descriptor.header() == XMP_CHUNK_HEADER
 			{
-				chunk_type_found_in_pre_exif_chunks |= pre_exif_chunk.to_lowercase() == chunk_descriptor.header().to_lowercase();
+				break;
 			}
 
-			if !chunk_type_found_in_pre_exif_chunks
+			if PRE_EXIF_CHUNK_HEADERS.contains(&chunk_descriptor.header().as_str())
 			{
-				break;
+				insert_position = file.seek(SeekFrom::Current(0))?;
 			}
 		}
 		else
@@ -691,7 +684,8 @@ write_metadata
 	}
 
 	// Next, read remaining file into a buffer...
-	let current_file_cursor = SeekFrom::Start(file.seek(SeekFrom::Current(0)).unwrap());
+	let current_file_cursor = SeekFrom::Start(insert_position);
+	perform_file_action!(file.seek(current_file_cursor));
 	let mut read_buffer = Vec::new();
 	perform_file_action!(file.read_to_end(&mut read_buffer));
 
@@ -1,3 +1,4 @@
+#[cfg(feature = "std_fs")]
 pub mod file;
 pub mod vec;
 
@@ -7,11 +8,28 @@ pub(crate) const RIFF_SIGNATURE:       [u8; 4] = [0x52, 0x49, 0x46, 0x46];
 pub(crate) const WEBP_SIGNATURE:       [u8; 4] = [0x57, 0x45, 0x42, 0x50];
 pub(crate) const VP8X_HEADER:          &str    = "VP8X";
 pub(crate) const EXIF_CHUNK_HEADER:    &str    = "EXIF";
+pub(crate) const XMP_CHUNK_HEADER:     &str    = "XMP ";
+
+/// The chunks that need to be located *before* the EXIF chunk according to
+/// the WebP specification: VP8X, ICCP, ANIM and the image data, which is
+/// either a sequence of ANMF frames or ALPH followed by VP8 or VP8L.
+/// The EXIF chunk is placed after the last of these, followed by XMP and
+/// unknown chunks.
+pub(crate) const PRE_EXIF_CHUNK_HEADERS: [&str; 7] = [
+	"VP8X",
+	"ICCP",
+	"ANIM",
+	"ANMF",
+	"ALPH",
+	"VP8 ",
+	"VP8L",
+];
 
 use std::fs::File;
 
 use crate::endian::Endian;
 use crate::general_file_io::io_error;
+use crate::metadata::error::MetadataTooLarge;
 use crate::u8conversion::from_u8_vec_macro;
 use crate::u8conversion::to_u8_vec_macro;
 use crate::u8conversion::U8conversion;
@@ -91,13 +109,23 @@ check_byte_count
 	return Ok(());
 }
 
+/// The maximum size of the EXIF data. The size fields of the EXIF chunk and
+/// the RIFF header consist of 4 bytes, but the RIFF size gets updated using
+/// the size of the EXIF chunk as signed 32 bit delta. Besides the EXIF data,
+/// that chunk consists of its header (8 bytes) and a padding byte, and the
+/// RIFF size also covers the "WEBP" signature (4 bytes) and the VP8X chunk
+/// (18 bytes) that is required for storing EXIF data.
+pub(crate) const WEBP_MAX_EXIF_SIZE: usize = i32::MAX as usize - 8 - 1 - 4 - 18;
+
 fn
 encode_metadata_webp
 (
 	exif_vec: &Vec<u8>
 )
--> Vec<u8>
+-> Result<Vec<u8>, std::io::Error>
 {
+	MetadataTooLarge::check(WEBP_MAX_EXIF_SIZE, exif_vec.len())?;
+
 	// Vector storing the data that will be returned
 	let mut webp_exif: Vec<u8> = Vec::new();
 
@@ -118,7 +146,7 @@ encode_metadata_webp
 		webp_exif.extend([0x00]);
 	}
 
-	return webp_exif;
+	return Ok(webp_exif);
 }
 
 
@@ -130,7 +158,7 @@ as_u8_vec
 (
 	general_encoded_metadata: &Vec<u8>
 )
--> Vec<u8>
+-> Result<Vec<u8>, std::io::Error>
 {
 	encode_metadata_webp(general_encoded_metadata)
 }
\ No newline at end of file
@@ -203,7 +203,7 @@ check_exif_in_file
 		if first_chunk.header().to_lowercase() != VP8X_HEADER.to_lowercase()
 		{
 			return io_error!(
-				Other, 
+				NotFound, 
 				format!("Expected first chunk of WebP file to be of type 'VP8X' but instead got {}!", first_chunk.header())
 			);
 		}
@@ -232,7 +232,7 @@ check_exif_in_file
 	// https://developers.google.com/speed/webp/docs/riff_container#extended_file_format
 	if flag_buffer[0] & 0x08 != 0x08
 	{
-		return io_error!(Other, "No EXIF chunk according to VP8X flags!");
+		return io_error!(NotFound, "No EXIF chunk according to VP8X flags!");
 	}
 
 	return Ok((cursor, parsed_webp_result.unwrap()));
@@ -240,6 +240,51 @@ check_exif_in_file
 
 
 
+/// Gets the start offset and length of the EXIF TIFF payload of the first
+/// EXIF chunk, skipping an "Exif\0\0" header that some writers add
+pub(crate) fn
+exif_byte_range
+(
+	file_buffer: &Vec<u8>
+)
+-> Result<Option<(u64, u64)>, std::io::Error>
+{
+	let parse_webp_result = parse_webp(file_buffer)?;
+
+	// Skip the RIFF signature, file size and WEBP signature
+	let mut position = 12usize;
+
+	for parsed_chunk in parse_webp_result
+	{
+		if parsed_chunk.header().to_lowercase() == EXIF_CHUNK_HEADER.to_lowercase()
+		{
+			// The parsed length includes the padding byte, so use the size
+			// information stored in the file instead
+			let mut start  = position + 8;
+			let mut length = from_u8_vec_macro!(u32, &file_buffer[position+4..position+8].to_vec(), &Endian::Little) as usize;
+			let exif_data  = match start.checked_add(length).and_then(|end| file_buffer.get(start..end))
+			{
+				Some(exif_data) => exif_data,
+				None            => return io_error!(InvalidData, "EXIF chunk exceeds WebP data!"),
+			};
+
+			// Only strip the header if the chunk is large enough to hold it
+			if exif_data.starts_with(&EXIF_HEADER)
+			{
+				start  += EXIF_HEADER.len();
+				length -= EXIF_HEADER.len();
+			}
+			return Ok(Some((start as u64, length as u64)));
+		}
+
+		position += 4 + 4 + parsed_chunk.len() + parsed_chunk.len() % 2;
+	}
+
+	return Ok(None);
+}
+
+
+
 /// Reads the raw EXIF data from the WebP file. Note that if the file contains
 /// multiple such chunks, the first one is returned and the others get ignored.
 pub(crate) fn
@@ -251,7 +296,7 @@ read_metadata
 {
 	// Check the signature, parse it, check that it has a VP8X chunk and the
 	// EXIF flag is set there
-	let (mut cursor, parse_webp_result) = check_exif_in_file(file_buffer).unwrap();
+	let (mut cursor, parse_webp_result) = check_exif_in_file(file_buffer)?;
 
 	// At this point we have established that the file has to contain an EXIF
 	// chunk at some point. So, now we need to find & return it
@@ -565,6 +610,63 @@ clear_metadata
 
 
 
+/// Removes the EXIF and XMP chunks and unsets their flags in the VP8X chunk.
+/// Simple File Format WebP files can't contain any metadata and are left
+/// untouched.
+pub(crate) fn
+strip_all_metadata
+(
+	file_buffer: &mut Vec<u8>
+)
+-> Result<(), std::io::Error>
+{
+	let parse_webp_result = parse_webp(file_buffer)?;
+
+	if let Some(first_chunk) = parse_webp_result.first()
+	{
+		if first_chunk.header().to_lowercase() != VP8X_HEADER.to_lowercase()
+		{
+			return Ok(());
+		}
+	}
+	else
+	{
+		return io_error!(Other, "Could not read first chunk descriptor of WebP file!");
+	}
+
+	let mut cursor = Cursor::new(file_buffer);
+	let mut delta  = 0i32;
+
+	// Skip the RIFF signature, file size and WEBP signature
+	let mut position = 12usize;
+
+	for parsed_chunk in parse_webp_result
+	{
+		// fourCC section, size information, data and possible padding byte
+		let parsed_chunk_byte_count = 4 + 4 + parsed_chunk.len() + parsed_chunk.len() % 2;
+
+		if parsed_chunk.header().to_lowercase() == EXIF_CHUNK_HEADER.to_lowercase()
+		|| parsed_chunk.header().to_lowercase() == XMP_CHUNK_HEADER.to_lowercase()
+		{
+			range_remove(cursor.get_mut(), position, position + parsed_chunk_byte_count);
+			delta -= parsed_chunk_byte_count as i32;
+		}
+		else
+		{
+			position += parsed_chunk_byte_count;
+		}
+	}
+
+	update_file_size_information(&mut cursor, delta)?;
+
+	// Unset the EXIF (0x08) and XMP (0x04) flags of the VP8X chunk
+	cursor.get_mut()[20] &= 0b11110011;
+
+	return Ok(());
+}
+
+
+
 /// Writes the given generally encoded metadata to the WebP image file at 
 /// the specified path. 
 /// Note that *all* previously stored EXIF metadata gets removed first before
@@ -577,25 +679,21 @@ write_metadata
 )
 -> Result<(), std::io::Error>
 {
+	// Encode the general metadata format to WebP specifications first, so 
+	// that the file buffer stays unchanged if this fails
+	let mut encoded_metadata = encode_metadata_webp(&metadata.encode()?)?;
+
 	// Clear the metadata from the file and return if this results in an error
 	clear_metadata(file_buffer)?;
-
-	// Encode the general metadata format to WebP specifications
-	let mut encoded_metadata = encode_metadata_webp(&metadata.encode()?);
 	let encoded_metadata_len = encoded_metadata.len() as i32;
 
 	// Find a location where to put the EXIF chunk
-	// This is done by requesting a chunk descriptor as long as we find a chunk
-	// that is both known and should be located *before* the EXIF chunk
-	let pre_exif_chunks = [
-		"VP8X",
-		"VP8",
-		"VP8L",
-		"ICCP",
-		"ANIM"
-	];
-
-	let mut read_cursor = Cursor::new(file_buffer.as_ref());
+	// This is done by going through the chunks until the XMP chunk or the
+	// end of the file is reached and remembering the position after the last
+	// chunk that should be located *before* the EXIF chunk. Any other chunks
+	// (e.g. unknown ones) are left untouched at their location
+	let mut read_cursor     = check_signature(file_buffer)?;
+	let mut insert_position = read_cursor.position();
 
 	loop
 	{
@@ -605,18 +703,14 @@ write_metadata
 
 		if let Ok(chunk_descriptor) = chunk_descriptor_result
 		{
-			let mut chunk_type_found_in_pre_exif_chunks = false;
-
-			// Check header of chunk descriptor against any of the known chunks
-			// that should come before the EXIF chunk
-			for pre_exif_chunk in &pre_exif_chunks
+			if chunk_descriptor.header() == XMP_CHUNK_HEADER
 			{
-				chunk_type_found_in_pre_exif_chunks |= pre_exif_chunk.to_lowercase() == chunk_descriptor.header().to_lowercase();
+				break;
 			}
 
-			if !chunk_type_found_in_pre_exif_chunks
+			if PRE_EXIF_CHUNK_HEADERS.contains(&chunk_descriptor.header().as_str())
 			{
-				break;
+				insert_position = read_cursor.position();
 			}
 		}
 		else
@@ -632,7 +726,7 @@ write_metadata
 	}
 
 	// Write the EXIF chunk at the found location
-	insert_multiple_at(file_buffer, read_cursor.position() as usize, &mut encoded_metadata);
+	insert_multiple_at(file_buffer, insert_position as usize, &mut encoded_metadata);
 
 	// Update the file size information by adding the byte count of the EXIF chunk
 	// (Note: Due to  the WebP specific encoding function, this vector already
@@ -0,0 +1,127 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+// Compares the number of allocations of the owned and the borrowed read
+// path. This needs its own global allocator and thus its own test binary.
+
+use std::alloc::GlobalAlloc;
+use std::alloc::Layout;
+use std::alloc::System;
+use std::cell::Cell;
+
+use little_exif::exif_tag::ExifTag;
+use little_exif::filetype::FileExtension;
+use little_exif::ifd::ExifTagGroup;
+use little_exif::metadata::borrowed::BorrowedValue;
+use little_exif::metadata::options::ReadOptions;
+use little_exif::metadata::Metadata;
+
+thread_local!
+{
+	static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+struct
+CountingAllocator;
+
+unsafe impl
+GlobalAlloc
+for
+CountingAllocator
+{
+	unsafe fn
+	alloc
+	(
+		&self,
+		layout: Layout
+	)
+	-> *mut u8
+	{
+		ALLOCATIONS.with(|allocations| allocations.set(allocations.get() + 1));
+		System.alloc(layout)
+	}
+
+	unsafe fn
+	dealloc
+	(
+		&self,
+		ptr:    *mut u8,
+		layout: Layout
+	)
+	{
+		System.dealloc(ptr, layout)
+	}
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Counts the allocations of the current thread while running the closure
+fn
+count_allocations
+<T>
+(
+	function: impl FnOnce() -> T
+)
+-> (usize, T)
+{
+	let before = ALLOCATIONS.with(|allocations| allocations.get());
+	let result = function();
+	let after  = ALLOCATIONS.with(|allocations| allocations.get());
+	return (after - before, result);
+}
+
+#[test]
+fn
+borrowed_read_path_allocates_less()
+{
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::ImageDescription("A string-heavy test image".to_string()));
+	metadata.set_tag(ExifTag::Make(            "little_exif".to_string()));
+	metadata.set_tag(ExifTag::Model(           "Test Camera".to_string()));
+	metadata.set_tag(ExifTag::Software(        "little_exif tests".to_string()));
+	metadata.set_tag(ExifTag::Artist(          "Someone".to_string()));
+	metadata.set_tag(ExifTag::Copyright(       "Someone else".to_string()));
+	metadata.set_tag(ExifTag::ModifyDate(      "2024:05:01 12:34:56".to_string()));
+	metadata.set_tag(ExifTag::DateTimeOriginal("2024:05:01 12:34:56".to_string()));
+	metadata.set_tag(ExifTag::CreateDate(      "2024:05:01 12:34:56".to_string()));
+	metadata.set_tag(ExifTag::OwnerName(       "Owner".to_string()));
+	metadata.set_tag(ExifTag::SerialNumber(    "SN-12345".to_string()));
+	metadata.set_tag(ExifTag::LensModel(       "Test Lens".to_string()));
+	metadata.set_tag(ExifTag::LensSerialNumber("LSN-6789".to_string()));
+	metadata.set_tag(ExifTag::UserComment(     b"ASCII\0\0\0A comment".to_vec()));
+	let file = metadata.encode().unwrap();
+
+	let (owned_allocations, owned_strings) = count_allocations(|| {
+		let metadata = Metadata::new_from_vec(&file, FileExtension::TIFF).unwrap();
+		return metadata.into_iter()
+			.filter(|tag| tag.is_string())
+			.count();
+	});
+
+	let options = ReadOptions { retain_raw_data: true, tag_allowlist: Some(Vec::new()), ..ReadOptions::default() };
+	let (borrowed_allocations, borrowed_strings) = count_allocations(|| {
+		let metadata = Metadata::new_from_vec_with_options(&file, FileExtension::TIFF, &options).unwrap();
+		return metadata.borrowed_tags()
+			.filter(|tag| matches!(tag.value, BorrowedValue::Str(_)))
+			.count();
+	});
+
+	assert_eq!(owned_strings,    13);
+	assert_eq!(borrowed_strings, 13);
+	assert!(
+		borrowed_allocations < owned_allocations,
+		"borrowed: {} allocations, owned: {} allocations", borrowed_allocations, owned_allocations
+	);
+
+	// The values point into the retained data
+	let metadata = Metadata::new_from_vec_with_options(&file, FileExtension::TIFF, &options).unwrap();
+	assert_eq!(metadata.get_tag(&ExifTag::Make(String::new())).next(), None);
+	assert_eq!(metadata.get_borrowed_str(  0x010f, ExifTagGroup::GENERIC), Some("little_exif"));
+	assert_eq!(metadata.get_borrowed_str(  0xa431, ExifTagGroup::EXIF),    Some("SN-12345"));
+	assert_eq!(metadata.get_borrowed_bytes(0x9286, ExifTagGroup::EXIF),    Some(&b"ASCII\0\0\0A comment"[..]));
+
+	// Without retaining the data, there is nothing to borrow from
+	let metadata = Metadata::new_from_vec(&file, FileExtension::TIFF).unwrap();
+	assert_eq!(metadata.borrowed_tags().count(), 0);
+}
@@ -0,0 +1,51 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+// Exercises the in-memory API only, without any file system access (the
+// images are embedded into the test binary). This also runs without the
+// "std_fs" feature and on targets like wasm32-unknown-unknown.
+
+use little_exif::exif_tag::ExifTag;
+use little_exif::filetype::FileExtension;
+use little_exif::metadata::Metadata;
+
+const JPG_IMAGE:  &[u8] = include_bytes!("sample2.jpg");
+const PNG_IMAGE:  &[u8] = include_bytes!("sample2.png");
+const WEBP_IMAGE: &[u8] = include_bytes!("sample2_extended.webp");
+
+#[test]
+fn
+in_memory_round_trip()
+-> Result<(), std::io::Error>
+{
+	for (image, file_type) in [
+		(JPG_IMAGE,  FileExtension::JPEG),
+		(PNG_IMAGE,  FileExtension::PNG { as_zTXt_chunk: true }),
+		(WEBP_IMAGE, FileExtension::WEBP),
+	]
+	{
+		let mut file_buffer = image.to_vec();
+
+		let mut metadata = Metadata::new_from_vec(&file_buffer, file_type)?;
+		metadata.set_tag(ExifTag::ImageDescription("Written in memory".to_string()));
+		metadata.set_tag(ExifTag::ISO(vec![2706]));
+		metadata.write_to_vec(&mut file_buffer, file_type)?;
+
+		let read_back = Metadata::new_from_vec(&file_buffer, file_type)?;
+		assert_eq!(
+			read_back.get_tag(&ExifTag::ImageDescription(String::new())).next(),
+			Some(&ExifTag::ImageDescription("Written in memory".to_string())),
+			"{:?}", file_type
+		);
+		assert_eq!(read_back.get_tag(&ExifTag::ISO(Vec::new())).next(), Some(&ExifTag::ISO(vec![2706])));
+
+		assert!(Metadata::has_exif(&file_buffer, file_type)?);
+		assert!(Metadata::exif_byte_range_in_vec(&file_buffer, file_type)?.is_some());
+		assert!(!read_back.as_u8_vec(file_type)?.is_empty());
+
+		Metadata::clear_metadata(&mut file_buffer, file_type)?;
+		assert!(!Metadata::has_exif(&file_buffer, file_type)?);
+	}
+
+	Ok(())
+}
@@ -8,7 +8,10 @@ use std::path::Path;
 
 extern crate little_exif;
 use little_exif::metadata::Metadata;
+use little_exif::metadata::options::WriteOptions;
 use little_exif::exif_tag::ExifTag;
+use little_exif::ifd::ExifTagGroup;
+use little_exif::rational::uR64;
 use little_exif::u8conversion::U8conversion;
 
 #[test]
@@ -561,7 +564,16 @@ compare_write_to_generic
 		println!("{}", error);
 	}
 	copy(original_file, copy1_file)?;
-	metadata.write_to_file(Path::new(copy1_file))?;
+
+	// PNGs are written with the chunk type given for the vec-based function
+	let options = match file_extension
+	{
+		little_exif::filetype::FileExtension::PNG { as_zTXt_chunk }
+			=> WriteOptions { compress_png_exif: as_zTXt_chunk, ..WriteOptions::default() },
+		_
+			=> WriteOptions::default(),
+	};
+	metadata.write_to_file_with_options(Path::new(copy1_file), &options)?;
 
 	// Now do the same but via the vec-based function
 	if let Err(error) = remove_file(copy2_file)
@@ -633,6 +645,20 @@ compare_write_to_png()
 	);
 }
 
+#[test]
+#[allow(non_snake_case)]
+fn 
+compare_write_to_png_zTXt()
+-> Result<(), std::io::Error>
+{
+	return compare_write_to_generic(
+		"tests/sample2.png",
+		"tests/sample2_copy1_zTXt.png",
+		"tests/sample2_copy2_zTXt.png",
+		little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true }
+	);
+}
+
 #[test]
 fn 
 compare_write_to_webp_lossless()
@@ -682,3 +708,1588 @@ utf8_string()
     assert_eq!(utf8, utf8_reverted);
     assert_eq!([0x74, 0xC3, 0xAA, 0x74, 0x75], utf8_reverted.as_bytes());
 }
+
+#[test]
+fn
+rating_round_trip_jpg()
+-> Result<(), std::io::Error>
+{
+	let mut metadata = Metadata::new();
+	metadata.set_rating(4)?;
+
+	let mut image_data = read("tests/sample2.jpg").unwrap();
+	metadata.write_to_vec(&mut image_data, little_exif::filetype::FileExtension::JPEG)?;
+
+	let read_back = Metadata::new_from_vec(&image_data, little_exif::filetype::FileExtension::JPEG)?;
+	assert_eq!(read_back.get_rating(), Some(4));
+
+	Ok(())
+}
+
+#[test]
+fn
+copyright_round_trip_jpg()
+-> Result<(), std::io::Error>
+{
+	let mut metadata = Metadata::new();
+	metadata.set_copyright("Jane Doe, 2024", Some("John Doe, 2024"));
+
+	let mut image_data = read("tests/sample2.jpg").unwrap();
+	metadata.write_to_vec(&mut image_data, little_exif::filetype::FileExtension::JPEG)?;
+
+	let read_back = Metadata::new_from_vec(&image_data, little_exif::filetype::FileExtension::JPEG)?;
+	assert_eq!(
+		read_back.get_copyright(),
+		Some(("Jane Doe, 2024".to_string(), Some("John Doe, 2024".to_string())))
+	);
+
+	Ok(())
+}
+
+#[test]
+fn
+ifd_tree_with_gps_and_exif()
+-> Result<(), std::io::Error>
+{
+	let mut metadata = get_test_metadata()?;
+	metadata.set_tag(ExifTag::GPSLatitudeRef("N".to_string()));
+	metadata.set_tag(ExifTag::InteroperabilityIndex("R98".to_string()));
+
+	let mut image_data = read("tests/sample2.jpg").unwrap();
+	metadata.write_to_vec(&mut image_data, little_exif::filetype::FileExtension::JPEG)?;
+
+	let read_back = Metadata::new_from_vec(&image_data, little_exif::filetype::FileExtension::JPEG)?;
+	let tree      = read_back.ifd_tree();
+
+	// IFD0 with ImageDescription and Model
+	assert_eq!(tree.len(), 1);
+	assert_eq!(tree[0].group,      ExifTagGroup::GENERIC);
+	assert_eq!(tree[0].generic_nr, 0);
+	assert_eq!(tree[0].tag_count,  2);
+
+	let exif = tree[0].children.iter().find(|node| node.group == ExifTagGroup::EXIF).unwrap();
+	let gps  = tree[0].children.iter().find(|node| node.group == ExifTagGroup::GPS ).unwrap();
+	assert_eq!(tree[0].children.len(), 2);
+
+	// ExifIFD with ExposureProgram and ISO (the image dimensions derived
+	// from the JPEG are not part of it), linking to the Interop IFD
+	assert_eq!(exif.tag_count, 2);
+	assert_eq!(exif.children.len(), 1);
+	assert_eq!(exif.children[0].group,     ExifTagGroup::INTEROP);
+	assert_eq!(exif.children[0].tag_count, 1);
+
+	assert_eq!(gps.tag_count, 1);
+	assert!(gps.children.is_empty());
+
+	Ok(())
+}
+
+#[test]
+fn
+lens_round_trip_jpg()
+-> Result<(), std::io::Error>
+{
+	let lens_specification = little_exif::metadata::lens::LensSpecification {
+		min_focal_length: uR64 { nominator: 24, denominator: 1  },
+		max_focal_length: uR64 { nominator: 70, denominator: 1  },
+		min_f_number:     uR64 { nominator: 28, denominator: 10 },
+		max_f_number:     uR64 { nominator: 0,  denominator: 0  },
+	};
+
+	let mut metadata = Metadata::new();
+	metadata.set_lens_model("EF24-70mm f/2.8L II USM");
+	metadata.set_lens_specification(lens_specification.clone());
+
+	let mut image_data = read("tests/sample2.jpg").unwrap();
+	metadata.write_to_vec(&mut image_data, little_exif::filetype::FileExtension::JPEG)?;
+
+	let read_back = Metadata::new_from_vec(&image_data, little_exif::filetype::FileExtension::JPEG)?;
+	assert_eq!(read_back.get_lens_model(),         Some("EF24-70mm f/2.8L II USM".to_string()));
+	assert_eq!(read_back.get_lens_specification(), Some(lens_specification));
+
+	Ok(())
+}
+
+#[test]
+fn
+rational_clamp_from_wide_terms()
+{
+	// Reducible using the GCD
+	assert_eq!(uR64::clamp_from_u64(3 << 33, 4 << 33), uR64 { nominator: 3, denominator: 4 });
+
+	// Not reducible, needs to be approximated
+	let nominator   = u32::MAX as u64 * 3 + 1;
+	let denominator = u32::MAX as u64 * 2;
+	let fraction    = uR64::clamp_from_u64(nominator, denominator);
+	let approximated: f64 = fraction.clone().into();
+	assert!((approximated - nominator as f64 / denominator as f64).abs() < 1e-6);
+
+	// Too large to be represented at all
+	assert_eq!(uR64::clamp_from_u64(u64::MAX, 3), uR64 { nominator: u32::MAX, denominator: 1 });
+
+	// Signed variant keeps the sign in the nominator
+	let fraction = little_exif::rational::iR64::clamp_from_i64(-(3 << 40), 4 << 40);
+	assert_eq!(fraction, little_exif::rational::iR64 { nominator: -3, denominator: 4 });
+	let fraction = little_exif::rational::iR64::clamp_from_i64(i64::MAX, -(i64::MAX / 2));
+	assert_eq!(fraction.nominator, -2);
+	assert_eq!(fraction.denominator, 1);
+}
+
+#[test]
+fn
+has_exif()
+-> Result<(), std::io::Error>
+{
+	for (path, expected) in [
+		("tests/read_sample.jpg",                true ),
+		("tests/no_exif.jpeg",                   false),
+		("tests/with_exif.jxl",                  true ),
+		("tests/no_exif.jxl",                    false),
+		("tests/iso_no_exif.jxl",                false),
+		("tests/read_sample.webp",               true ),
+		("tests/sample2_simple_lossless.webp",   false),
+		("tests/sample2_simple_lossy.webp",      false),
+		("tests/read_sample.tif",                true ),
+		("tests/with_exif.jp2",                  true ),
+		("tests/sample2.png",                    false),
+	]
+	{
+		assert_eq!(Metadata::file_has_exif(Path::new(path))?, expected, "{}", path);
+	}
+
+	// Write EXIF data to a PNG without any and check again
+	let mut image_data = read("tests/sample2.png").unwrap();
+	get_test_metadata()?.write_to_vec(&mut image_data, little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true })?;
+	assert!(Metadata::has_exif(&image_data, little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true })?);
+
+	// Corrupted data is an error instead of just not having EXIF data
+	image_data.truncate(40);
+	assert!(Metadata::has_exif(&image_data, little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true }).is_err());
+	assert!(Metadata::has_exif(&vec![0u8; 16], little_exif::filetype::FileExtension::JPEG).is_err());
+
+	Ok(())
+}
+
+#[test]
+fn
+write_to_file_preallocated_jpg()
+-> Result<(), std::io::Error>
+{
+	// Remove file from previous run and replace it with fresh copy
+	if let Err(error) = remove_file("tests/sample2_copy_preallocated.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.jpg", "tests/sample2_copy_preallocated.jpg")?;
+
+	let metadata = get_test_metadata()?;
+	let options  = WriteOptions { preallocate: true, ..WriteOptions::default() };
+
+	// Compute the expected result in memory
+	let mut expected = read("tests/sample2.jpg").unwrap();
+	metadata.write_to_vec(&mut expected, little_exif::filetype::FileExtension::JPEG)?;
+
+	let path = Path::new("tests/sample2_copy_preallocated.jpg");
+	metadata.write_to_file_with_options(path, &options)?;
+
+	assert_eq!(std::fs::metadata(path)?.len(), expected.len() as u64);
+	assert_eq!(read(path)?, expected);
+
+	Ok(())
+}
+
+#[test]
+fn
+new_from_vec_at_offset_jpg()
+-> Result<(), std::io::Error>
+{
+	let mut image_data = read("tests/sample2.jpg").unwrap();
+	get_test_metadata()?.write_to_vec(&mut image_data, little_exif::filetype::FileExtension::JPEG)?;
+
+	// Embed the JPEG in a larger blob with arbitrary leading bytes
+	let mut blob = b"%PDF-1.4 some leading bytes \xff\xd8 that are not a JPEG".to_vec();
+	let offset   = blob.len();
+	blob.extend(image_data.iter());
+	blob.extend(b"trailing bytes".iter());
+
+	let metadata = Metadata::new_from_vec_at(&blob, offset, little_exif::filetype::FileExtension::JPEG)?;
+	assert_eq!(
+		metadata.get_tag(&ExifTag::ISO(Vec::new())).next(),
+		Some(&ExifTag::ISO(vec![2706]))
+	);
+
+	assert!(Metadata::new_from_vec_at(&blob, blob.len() + 1, little_exif::filetype::FileExtension::JPEG).is_err());
+
+	Ok(())
+}
+
+#[test]
+fn
+gps_destination_round_trip_jpg()
+-> Result<(), std::io::Error>
+{
+	use little_exif::metadata::gps::GpsDirectionRef;
+
+	let mut metadata = Metadata::new();
+	metadata.set_gps_dest_position(47.0707, -15.4395);
+	metadata.set_gps_img_direction(90.25, GpsDirectionRef::TrueNorth);
+
+	let mut image_data = read("tests/sample2.jpg").unwrap();
+	metadata.write_to_vec(&mut image_data, little_exif::filetype::FileExtension::JPEG)?;
+
+	let read_back = Metadata::new_from_vec(&image_data, little_exif::filetype::FileExtension::JPEG)?;
+	let (latitude, longitude) = read_back.get_gps_dest_position().unwrap();
+	assert!((latitude  -  47.0707).abs() < 1e-6);
+	assert!((longitude - -15.4395).abs() < 1e-6);
+	assert_eq!(read_back.get_gps_img_direction(), Some((90.25, GpsDirectionRef::TrueNorth)));
+
+	Ok(())
+}
+
+#[test]
+fn
+get_iso_from_file()
+-> Result<(), std::io::Error>
+{
+	// Old convention: only the ISO tag
+	let mut image_data = read("tests/sample2.jpg").unwrap();
+	get_test_metadata()?.write_to_vec(&mut image_data, little_exif::filetype::FileExtension::JPEG)?;
+	let read_back = Metadata::new_from_vec(&image_data, little_exif::filetype::FileExtension::JPEG)?;
+	assert_eq!(read_back.get_iso(), Some(2706));
+
+	// New convention: ISO tag, SensitivityType and ISOSpeed
+	let mut metadata = Metadata::new();
+	metadata.set_iso(128000);
+	let mut image_data = read("tests/sample2.jpg").unwrap();
+	metadata.write_to_vec(&mut image_data, little_exif::filetype::FileExtension::JPEG)?;
+	let read_back = Metadata::new_from_vec(&image_data, little_exif::filetype::FileExtension::JPEG)?;
+	assert_eq!(read_back.get_iso(), Some(128000));
+
+	Ok(())
+}
+
+#[test]
+fn
+raw_profile_iptc_png()
+-> Result<(), std::io::Error>
+{
+	let file_type    = little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true };
+	let iptc_payload = b"\x1c\x02\x00\x00\x02\x00\x04".to_vec();
+
+	let mut image_data = read("tests/sample2.png").unwrap();
+	Metadata::write_raw_profile(&mut image_data, file_type, "iptc", &iptc_payload)?;
+
+	assert_eq!(Metadata::read_raw_profile(&image_data, file_type, "iptc")?, iptc_payload);
+	assert!(!Metadata::has_exif(&image_data, file_type)?);
+
+	Ok(())
+}
+
+#[test]
+fn
+get_iptc_from_tiff()
+-> Result<(), std::io::Error>
+{
+	// Remove file from previous run and replace it with fresh copy
+	if let Err(error) = remove_file("tests/read_sample_copy_iptc.tif")
+	{
+		println!("{}", error);
+	}
+	copy("tests/read_sample.tif", "tests/read_sample_copy_iptc.tif")?;
+
+	// Photoshop IRB with a single IPTC resource holding the caption
+	let iptc = b"\x1c\x02\x78\x00\x07Caption";
+	let mut irb = b"8BIM\x04\x04\x00\x00".to_vec();
+	irb.extend((iptc.len() as u32).to_be_bytes());
+	irb.extend(iptc);
+	irb.push(0x00);
+
+	let mut metadata = Metadata::new_from_path(Path::new("tests/read_sample_copy_iptc.tif"))?;
+	assert_eq!(metadata.get_iptc(), None);
+	metadata.set_tag(ExifTag::PhotoshopSettings(irb));
+	metadata.write_to_file(Path::new("tests/read_sample_copy_iptc.tif"))?;
+
+	let metadata = Metadata::new_from_path(Path::new("tests/read_sample_copy_iptc.tif"))?;
+	let iptc     = metadata.get_iptc().unwrap();
+	assert_eq!(iptc.len(),     1);
+	assert_eq!(iptc[0].record,  2);
+	assert_eq!(iptc[0].dataset, 120);
+	assert_eq!(iptc[0].data,    b"Caption".to_vec());
+
+	Ok(())
+}
+
+#[test]
+fn
+write_fresh_exif_to_stripped_jpg()
+-> Result<(), std::io::Error>
+{
+	let original = read("tests/no_exif.jpeg").unwrap();
+	assert!(!Metadata::has_exif(&original, little_exif::filetype::FileExtension::JPEG)?);
+
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::ImageDescription("Fresh".to_string()));
+	metadata.set_tag(ExifTag::ISO(vec![800]));
+	metadata.set_tag(ExifTag::GPSAltitudeRef(vec![0]));
+
+	let mut image_data = original.clone();
+	metadata.write_to_vec(&mut image_data, little_exif::filetype::FileExtension::JPEG)?;
+
+	// APP1 segment follows the JFIF APP0 segment, starting with the EXIF
+	// header and a little endian TIFF header pointing to IFD0 at offset 8
+	let app1_position = 2 + 2 + u16::from_be_bytes([original[4], original[5]]) as usize;
+	let app1_length   = u16::from_be_bytes([image_data[app1_position+2], image_data[app1_position+3]]) as usize;
+	assert_eq!(image_data[app1_position..app1_position+2], [0xff, 0xe1]);
+	assert_eq!(image_data[app1_position+4..app1_position+10], *b"Exif\0\0");
+	assert_eq!(image_data[app1_position+10..app1_position+18], [0x49, 0x49, 0x2a, 0x00, 0x08, 0x00, 0x00, 0x00]);
+
+	// The rest of the image is untouched
+	assert_eq!(image_data.len(), original.len() + 2 + app1_length);
+	assert_eq!(image_data[app1_position+2+app1_length..], original[app1_position..]);
+
+	let read_back = Metadata::new_from_vec(&image_data, little_exif::filetype::FileExtension::JPEG)?;
+	assert_eq!(read_back.get_tag(&ExifTag::ImageDescription(String::new())).next(), Some(&ExifTag::ImageDescription("Fresh".to_string())));
+	assert_eq!(read_back.get_tag(&ExifTag::ISO(Vec::new())).next(),                 Some(&ExifTag::ISO(vec![800])));
+	assert_eq!(read_back.get_tag(&ExifTag::GPSAltitudeRef(Vec::new())).next(),      Some(&ExifTag::GPSAltitudeRef(vec![0])));
+
+	// Even an empty struct results in a valid (empty) IFD0
+	let mut image_data = original.clone();
+	Metadata::new().write_to_vec(&mut image_data, little_exif::filetype::FileExtension::JPEG)?;
+	assert!(Metadata::has_exif(&image_data, little_exif::filetype::FileExtension::JPEG)?);
+
+	Ok(())
+}
+
+#[test]
+fn
+unknown_tags_jpg()
+-> Result<(), std::io::Error>
+{
+	let mut metadata = get_test_metadata()?;
+	metadata.set_tag(ExifTag::UnknownINT16U(vec![42],          0xbeef, ExifTagGroup::EXIF   ));
+	metadata.set_tag(ExifTag::UnknownSTRING("Hi".to_string(),   0xcafe, ExifTagGroup::GENERIC));
+
+	let mut image_data = read("tests/no_exif.jpeg").unwrap();
+	metadata.write_to_vec(&mut image_data, little_exif::filetype::FileExtension::JPEG)?;
+
+	let read_back = Metadata::new_from_vec(&image_data, little_exif::filetype::FileExtension::JPEG)?;
+	let mut unknown = read_back.unknown_tags().iter()
+		.map(|tag| (tag.as_u16(), tag.get_group()))
+		.collect::<Vec<(u16, ExifTagGroup)>>();
+	unknown.sort_by_key(|(hex, _)| *hex);
+
+	assert_eq!(unknown, vec![(0xbeef, ExifTagGroup::EXIF), (0xcafe, ExifTagGroup::GENERIC)]);
+	assert!(read_back.total_tag_count() > unknown.len());
+
+	Ok(())
+}
+
+#[test]
+fn
+gps_processing_method_round_trip_jpg()
+-> Result<(), std::io::Error>
+{
+	let mut metadata = get_test_metadata()?;
+	metadata.set_gps_processing_method("GPS");
+
+	let mut image_data = read("tests/no_exif.jpeg").unwrap();
+	metadata.write_to_vec(&mut image_data, little_exif::filetype::FileExtension::JPEG)?;
+
+	let read_back = Metadata::new_from_vec(&image_data, little_exif::filetype::FileExtension::JPEG)?;
+	assert_eq!(read_back.get_gps_processing_method(), Some("GPS".to_string()));
+	assert_eq!(read_back.get_gps_area_information(),  None);
+
+	Ok(())
+}
+
+#[test]
+fn
+get_preview_image_tiff()
+-> Result<(), std::io::Error>
+{
+	// Remove file from previous run and replace it with fresh copy
+	if let Err(error) = remove_file("tests/read_sample_copy_preview.tif")
+	{
+		println!("{}", error);
+	}
+	copy("tests/read_sample.tif", "tests/read_sample_copy_preview.tif")?;
+
+	let thumbnail = [vec![0xff, 0xd8], vec![0x01; 16],  vec![0xff, 0xd9]].concat();
+	let preview   = [vec![0xff, 0xd8], vec![0x02; 256], vec![0xff, 0xd9]].concat();
+
+	// Small thumbnail in IFD1 and a larger, JPEG compressed YCbCr image in
+	// IFD2, as found in e.g. DNG files
+	let mut metadata = Metadata::new_from_path(Path::new("tests/read_sample_copy_preview.tif"))?;
+	assert_eq!(metadata.get_preview_image(), None);
+
+	metadata.get_ifd_mut(ExifTagGroup::GENERIC, 1).set_tag(ExifTag::ThumbnailOffset(Vec::new(), thumbnail.clone()));
+	metadata.get_ifd_mut(ExifTagGroup::GENERIC, 1).set_tag(ExifTag::ThumbnailLength(vec![thumbnail.len() as u32]));
+	metadata.get_ifd_mut(ExifTagGroup::GENERIC, 2).set_tag(ExifTag::Compression(vec![7]));
+	metadata.get_ifd_mut(ExifTagGroup::GENERIC, 2).set_tag(ExifTag::PhotometricInterpretation(vec![6]));
+	metadata.get_ifd_mut(ExifTagGroup::GENERIC, 2).set_tag(ExifTag::StripOffsets(Vec::new(), vec![preview.clone()]));
+	metadata.get_ifd_mut(ExifTagGroup::GENERIC, 2).set_tag(ExifTag::StripByteCounts(vec![preview.len() as u32]));
+	metadata.write_to_file(Path::new("tests/read_sample_copy_preview.tif"))?;
+
+	let metadata = Metadata::new_from_path(Path::new("tests/read_sample_copy_preview.tif"))?;
+	assert_eq!(metadata.get_preview_image(), Some(preview));
+
+	Ok(())
+}
+
+#[test]
+fn
+write_to_stream_matches_file()
+-> Result<(), std::io::Error>
+{
+	let metadata = get_test_metadata()?;
+
+	for (original, copy_file, file_type) in [
+		("tests/sample2.jpg", "tests/sample2_copy_stream.jpg", little_exif::filetype::FileExtension::JPEG),
+		("tests/sample2.png", "tests/sample2_copy_stream.png", little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true }),
+	]
+	{
+		// Remove file from previous run and replace it with fresh copy
+		if let Err(error) = remove_file(copy_file)
+		{
+			println!("{}", error);
+		}
+		copy(original, copy_file)?;
+		metadata.write_to_file(Path::new(copy_file))?;
+
+		let mut sink = Vec::new();
+		metadata.write_to_stream(std::fs::File::open(original)?, &mut sink, file_type)?;
+
+		assert_eq!(sink, read(copy_file)?);
+	}
+
+	Ok(())
+}
+
+#[test]
+fn
+lenient_read_of_truncated_tiff()
+-> Result<(), std::io::Error>
+{
+	use little_exif::filetype::FileExtension;
+	use little_exif::metadata::error::InvalidExifData;
+	use little_exif::metadata::options::ParseStrictness;
+	use little_exif::metadata::options::ReadOptions;
+	use little_exif::metadata::warnings::ParseWarningKind;
+
+	// Cut off IFD0 (located at offset 338 with 24 entries) after 4 entries
+	let mut image_data = read("tests/read_sample.tif")?;
+	image_data.truncate(338 + 2 + 4 * 12);
+
+	// Strict mode can't decode the metadata at all and reports this as
+	// error, so that it can be told apart from a file without EXIF data
+	let error = Metadata::new_from_vec(&image_data, FileExtension::TIFF).err().unwrap();
+	assert!(InvalidExifData::from_io_error(&error).is_some());
+
+	// Lenient mode decodes what is available and reports the rest
+	let options  = ReadOptions { strictness: ParseStrictness::Lenient, ..ReadOptions::default() };
+	let metadata = Metadata::new_from_vec_with_options(&image_data, FileExtension::TIFF, &options)?;
+
+	assert_eq!(
+		metadata.get_tag(&ExifTag::ImageWidth(Vec::new())).next(),
+		Some(&ExifTag::ImageWidth(vec![2]))
+	);
+	assert!(metadata.warnings().len() >= 1);
+	assert!(metadata.warnings().iter().all(|warning| warning.kind == ParseWarningKind::TruncatedData));
+
+	Ok(())
+}
+
+#[test]
+fn
+body_serial_number_round_trip_jpg()
+-> Result<(), std::io::Error>
+{
+	let mut metadata = get_test_metadata()?;
+	metadata.set_body_serial_number("2706-1337");
+	metadata.set_camera_owner_name("Jane Doe");
+	metadata.set_lens_serial_number("42");
+
+	let mut image_data = read("tests/no_exif.jpeg").unwrap();
+	metadata.write_to_vec(&mut image_data, little_exif::filetype::FileExtension::JPEG)?;
+
+	let mut read_back = Metadata::new_from_vec(&image_data, little_exif::filetype::FileExtension::JPEG)?;
+	assert_eq!(read_back.get_body_serial_number(), Some("2706-1337".to_string()));
+	assert_eq!(read_back.get_camera_owner_name(),  Some("Jane Doe".to_string()));
+
+	// Stripping removes the personal tags but keeps the others
+	read_back.strip_personal_info();
+	assert_eq!(read_back.get_body_serial_number(), None);
+	assert_eq!(read_back.get_camera_owner_name(),  None);
+	assert_eq!(read_back.get_lens_serial_number(), None);
+	assert_eq!(read_back.get_tag(&ExifTag::ISO(Vec::new())).next(), Some(&ExifTag::ISO(vec![2706])));
+
+	Ok(())
+}
+
+#[test]
+fn
+write_keeps_big_endian_jpg()
+-> Result<(), std::io::Error>
+{
+	use little_exif::endian::Endian;
+
+	// Remove file from previous run and replace it with fresh copy
+	if let Err(error) = remove_file("tests/sample2_copy_endian.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.jpg", "tests/sample2_copy_endian.jpg")?;
+	let path = Path::new("tests/sample2_copy_endian.jpg");
+
+	// Read the big endian file, edit one tag and write it back
+	let mut metadata = Metadata::new_from_path(path)?;
+	assert_eq!(metadata.get_endian(), Endian::Big);
+	metadata.set_tag(ExifTag::ISO(vec![2706]));
+	metadata.write_to_file(path)?;
+
+	let read_back = Metadata::new_from_path(path)?;
+	assert_eq!(read_back.get_endian(), Endian::Big);
+	assert_eq!(read_back.get_tag(&ExifTag::ISO(Vec::new())).next(), Some(&ExifTag::ISO(vec![2706])));
+
+	// A new struct (little endian by default) also keeps the endianness
+	let mut new_metadata = Metadata::new();
+	new_metadata.set_tag(ExifTag::ISO(vec![1337]));
+	new_metadata.write_to_file(path)?;
+	assert_eq!(Metadata::new_from_path(path)?.get_endian(), Endian::Big);
+
+	// Unless the endianness is set explicitly
+	new_metadata.set_endian(Endian::Little);
+	new_metadata.write_to_file(path)?;
+	assert_eq!(Metadata::new_from_path(path)?.get_endian(), Endian::Little);
+
+	Ok(())
+}
+
+#[test]
+fn
+strip_all_metadata_vec()
+-> Result<(), std::io::Error>
+{
+	for (path, file_type) in [
+		("tests/sample2.jpg",          little_exif::filetype::FileExtension::JPEG),
+		("tests/with_exif.jxl",        little_exif::filetype::FileExtension::JXL),
+		("tests/sample2.png",          little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true }),
+		("tests/sample2_extended.webp", little_exif::filetype::FileExtension::WEBP),
+	]
+	{
+		let mut image_data = read(path).unwrap();
+		get_test_metadata()?.write_to_vec(&mut image_data, file_type)?;
+		assert!(Metadata::has_exif(&image_data, file_type)?, "{}", path);
+
+		Metadata::strip_all_metadata(&mut image_data, file_type)?;
+		assert!(!Metadata::has_exif(&image_data, file_type)?, "{}", path);
+
+		// The structure of the file is still intact, so new metadata can be
+		// written and read back
+		let mut metadata = Metadata::new();
+		metadata.set_tag(ExifTag::ImageDescription("Stripped".to_string()));
+		metadata.write_to_vec(&mut image_data, file_type)?;
+		assert_eq!(
+			Metadata::new_from_vec(&image_data, file_type)?.get_tag(&ExifTag::ImageDescription(String::new())).next(),
+			Some(&ExifTag::ImageDescription("Stripped".to_string())),
+			"{}", path
+		);
+	}
+
+	// Also removes the comment segment of JPEGs
+	let mut image_data = read("tests/sample2.jpg").unwrap();
+	Metadata::strip_all_metadata(&mut image_data, little_exif::filetype::FileExtension::JPEG)?;
+	let mut with_comment = image_data[..2].to_vec();
+	with_comment.extend([0xff, 0xfe, 0x00, 0x07, b'H', b'e', b'l', b'l', b'o']);
+	with_comment.extend(&image_data[2..]);
+	Metadata::strip_all_metadata(&mut with_comment, little_exif::filetype::FileExtension::JPEG)?;
+	assert_eq!(with_comment, image_data);
+
+	// The image itself can still be found, e.g. its dimensions
+	let metadata = Metadata::new_from_vec(&image_data, little_exif::filetype::FileExtension::JPEG)?;
+	assert!(metadata.get_tag(&ExifTag::ExifImageWidth(Vec::new())).next().is_some());
+
+	Ok(())
+}
+
+#[test]
+fn
+strip_all_metadata_file_webp()
+-> Result<(), std::io::Error>
+{
+	// Remove file from previous run and replace it with fresh copy
+	if let Err(error) = remove_file("tests/read_sample_copy_stripped.webp")
+	{
+		println!("{}", error);
+	}
+	copy("tests/read_sample.webp", "tests/read_sample_copy_stripped.webp")?;
+
+	little_exif::strip_all_metadata(Path::new("tests/read_sample_copy_stripped.webp"))?;
+	assert!(!Metadata::file_has_exif(Path::new("tests/read_sample_copy_stripped.webp"))?);
+
+	// VP8X flags for EXIF and XMP are unset
+	let image_data = read("tests/read_sample_copy_stripped.webp").unwrap();
+	assert_eq!(&image_data[12..16], b"VP8X");
+	assert_eq!(image_data[20] & 0x0c, 0x00);
+
+	Ok(())
+}
+
+#[test]
+fn
+get_thumbnail_jpg()
+-> Result<(), std::io::Error>
+{
+	use little_exif::metadata::preview::Thumbnail;
+
+	let jpeg_thumbnail = [vec![0xff, 0xd8], vec![0x01; 16], vec![0xff, 0xd9]].concat();
+	let raw_thumbnail  = vec![0xff, 0x00, 0x00, 0x00, 0xff, 0x00];
+
+	// JPEG compressed thumbnail
+	let mut image_data = read("tests/no_exif.jpeg").unwrap();
+	let mut metadata   = Metadata::new();
+	metadata.get_ifd_mut(ExifTagGroup::GENERIC, 1).set_tag(ExifTag::Compression(vec![6]));
+	metadata.get_ifd_mut(ExifTagGroup::GENERIC, 1).set_tag(ExifTag::ThumbnailOffset(Vec::new(), jpeg_thumbnail.clone()));
+	metadata.get_ifd_mut(ExifTagGroup::GENERIC, 1).set_tag(ExifTag::ThumbnailLength(vec![jpeg_thumbnail.len() as u32]));
+	metadata.write_to_vec(&mut image_data, little_exif::filetype::FileExtension::JPEG)?;
+
+	let metadata = Metadata::new_from_vec(&image_data, little_exif::filetype::FileExtension::JPEG)?;
+	assert_eq!(metadata.get_thumbnail(), Some(Thumbnail::Jpeg(jpeg_thumbnail)));
+
+	// Uncompressed 2x1 RGB thumbnail, split into two strips
+	let mut image_data = read("tests/no_exif.jpeg").unwrap();
+	let mut metadata   = Metadata::new();
+	metadata.get_ifd_mut(ExifTagGroup::GENERIC, 1).set_tag(ExifTag::Compression(vec![1]));
+	metadata.get_ifd_mut(ExifTagGroup::GENERIC, 1).set_tag(ExifTag::ImageWidth(vec![2]));
+	metadata.get_ifd_mut(ExifTagGroup::GENERIC, 1).set_tag(ExifTag::ImageHeight(vec![1]));
+	metadata.get_ifd_mut(ExifTagGroup::GENERIC, 1).set_tag(ExifTag::PhotometricInterpretation(vec![2]));
+	metadata.get_ifd_mut(ExifTagGroup::GENERIC, 1).set_tag(ExifTag::StripOffsets(Vec::new(), vec![raw_thumbnail[..3].to_vec(), raw_thumbnail[3..].to_vec()]));
+	metadata.get_ifd_mut(ExifTagGroup::GENERIC, 1).set_tag(ExifTag::StripByteCounts(vec![3, 3]));
+	metadata.write_to_vec(&mut image_data, little_exif::filetype::FileExtension::JPEG)?;
+
+	let metadata = Metadata::new_from_vec(&image_data, little_exif::filetype::FileExtension::JPEG)?;
+	assert_eq!(metadata.get_thumbnail(), Some(Thumbnail::Raw {
+		data:        raw_thumbnail,
+		width:       2,
+		height:      1,
+		photometric: 2,
+	}));
+
+	// No thumbnail at all
+	assert_eq!(Metadata::new().get_thumbnail(), None);
+
+	Ok(())
+}
+
+#[test]
+fn
+resized_jpg()
+-> Result<(), std::io::Error>
+{
+	use little_exif::filetype::FileExtension;
+	use little_exif::metadata::preview::Thumbnail;
+
+	let old_thumbnail = [vec![0xff, 0xd8], vec![0x01; 32], vec![0xff, 0xd9]].concat();
+	let new_thumbnail = [vec![0xff, 0xd8], vec![0x02; 8],  vec![0xff, 0xd9]].concat();
+
+	let mut image_data = read("tests/no_exif.jpeg").unwrap();
+	let mut metadata   = Metadata::new();
+	metadata.set_dimensions(4000, 3000);
+	metadata.get_ifd_mut(ExifTagGroup::GENERIC, 1).set_tag(ExifTag::Compression(vec![6]));
+	metadata.get_ifd_mut(ExifTagGroup::GENERIC, 1).set_tag(ExifTag::ThumbnailOffset(Vec::new(), old_thumbnail.clone()));
+	metadata.get_ifd_mut(ExifTagGroup::GENERIC, 1).set_tag(ExifTag::ThumbnailLength(vec![old_thumbnail.len() as u32]));
+	metadata.write_to_vec(&mut image_data, FileExtension::JPEG)?;
+
+	// An invalid thumbnail does not change anything
+	let mut metadata = Metadata::new_from_vec(&image_data, FileExtension::JPEG)?;
+	assert!(metadata.resized(800, 600, Some(&[0x00, 0x01])).is_err());
+	assert_eq!(metadata.get_tag(&ExifTag::ExifImageWidth(Vec::new())).next(), Some(&ExifTag::ExifImageWidth(vec![4000])));
+	assert_eq!(metadata.get_thumbnail(), Some(Thumbnail::Jpeg(old_thumbnail)));
+
+	metadata.resized(800, 600, Some(&new_thumbnail))?;
+	metadata.write_to_vec(&mut image_data, FileExtension::JPEG)?;
+
+	let mut metadata = Metadata::new_from_vec(&image_data, FileExtension::JPEG)?;
+	assert_eq!(metadata.get_tag(&ExifTag::ExifImageWidth( Vec::new())).next(), Some(&ExifTag::ExifImageWidth( vec![800])));
+	assert_eq!(metadata.get_tag(&ExifTag::ExifImageHeight(Vec::new())).next(), Some(&ExifTag::ExifImageHeight(vec![600])));
+	assert_eq!(metadata.get_tag(&ExifTag::ImageWidth(     Vec::new())).next(), Some(&ExifTag::ImageWidth(     vec![800])));
+	assert_eq!(metadata.get_thumbnail(), Some(Thumbnail::Jpeg(new_thumbnail.clone())));
+	assert_eq!(
+		metadata.get_tag_in_page(1, 0x0202, ExifTagGroup::GENERIC),
+		Some(&ExifTag::ThumbnailLength(vec![new_thumbnail.len() as u32]))
+	);
+
+	// Without a new thumbnail, IFD1 gets removed
+	metadata.resized(400, 300, None)?;
+	metadata.write_to_vec(&mut image_data, FileExtension::JPEG)?;
+
+	let metadata = Metadata::new_from_vec(&image_data, FileExtension::JPEG)?;
+	assert_eq!(metadata.get_tag(&ExifTag::ExifImageWidth(Vec::new())).next(), Some(&ExifTag::ExifImageWidth(vec![400])));
+	assert_eq!(metadata.get_thumbnail(), None);
+	assert!(metadata.get_ifd(ExifTagGroup::GENERIC, 1).is_none());
+
+	Ok(())
+}
+
+#[test]
+fn
+exif_byte_range_jpg()
+-> Result<(), std::io::Error>
+{
+	let (start, length) = Metadata::exif_byte_range(Path::new("tests/sample2.jpg"))?.unwrap();
+
+	// The range holds the TIFF payload that can be decoded on its own
+	let file_buffer = read("tests/sample2.jpg").unwrap();
+	let tiff_data   = file_buffer[start as usize..(start + length) as usize].to_vec();
+	assert!(tiff_data.starts_with(&[0x4d, 0x4d, 0x00, 0x2a]));
+
+	let from_range = Metadata::new_from_vec(&tiff_data, little_exif::filetype::FileExtension::TIFF)?;
+	let from_file  = Metadata::new_from_path(Path::new("tests/sample2.jpg"))?;
+	assert_eq!(
+		from_range.get_tag(&ExifTag::ImageDescription(String::new())).next(),
+		from_file.get_tag(&ExifTag::ImageDescription(String::new())).next()
+	);
+
+	assert_eq!(Metadata::exif_byte_range(Path::new("tests/no_exif.jpeg"))?, None);
+
+	// Other containers also give the range of the TIFF payload
+	for path in ["tests/read_sample.webp", "tests/with_exif.jxl", "tests/read_sample.tif"]
+	{
+		let (start, length) = Metadata::exif_byte_range(Path::new(path))?.unwrap();
+		let file_buffer     = read(path).unwrap();
+		let tiff_data       = file_buffer[start as usize..(start + length) as usize].to_vec();
+		assert!(tiff_data.starts_with(b"II") || tiff_data.starts_with(b"MM"), "{}", path);
+		assert!(Metadata::new_from_vec(&tiff_data, little_exif::filetype::FileExtension::TIFF).is_ok(), "{}", path);
+	}
+	assert_eq!(Metadata::exif_byte_range(Path::new("tests/no_exif.jxl"))?, None);
+
+	Ok(())
+}
+
+#[test]
+fn
+exif_byte_range_truncated_segments()
+{
+	use little_exif::filetype::FileExtension;
+
+	let is_invalid_data = |result: Result<Option<(u64, u64)>, std::io::Error>| 
+		matches!(result, Err(error) if error.kind() == std::io::ErrorKind::InvalidData);
+
+	// JPEG: Segment length too small for the length field itself, too small
+	// for the EXIF header and exceeding the file data
+	let mut app1_segments = vec![
+		vec![0xff, 0xd8, 0xff, 0xe1, 0x00, 0x01],
+		vec![0xff, 0xd8, 0xff, 0xe1, 0x00, 0x04],
+		vec![0xff, 0xd8, 0xff, 0xe1, 0x00, 0x40],
+	];
+	app1_segments[1].extend(b"Exif\0\0");
+	app1_segments[2].extend(b"Exif\0\0MM\0\x2a");
+	for file_buffer in app1_segments
+	{
+		assert!(is_invalid_data(Metadata::exif_byte_range_in_vec(&file_buffer, FileExtension::JPEG)));
+	}
+
+	// JXL: Exif box too small for the offset to the TIFF header and
+	// exceeding the file data
+	let jxl_image = read("tests/with_exif.jxl").unwrap();
+	let exif_box  = jxl_image.windows(4).position(|window| window == b"Exif").unwrap() - 4;
+	for box_length in [10u32, 0xffff_fff0]
+	{
+		let mut file_buffer = jxl_image.clone();
+		file_buffer[exif_box..exif_box + 4].copy_from_slice(&box_length.to_be_bytes());
+		assert!(is_invalid_data(Metadata::exif_byte_range_in_vec(&file_buffer, FileExtension::JXL)));
+	}
+
+	// WebP: EXIF chunk that is too small for the "Exif\0\0" header, which
+	// only appears to be there as the bytes after the chunk continue it...
+	let webp_image = read("tests/read_sample.webp").unwrap();
+	let exif_chunk = webp_image.windows(4).position(|window| window == b"EXIF").unwrap();
+	let mut file_buffer = webp_image[..exif_chunk].to_vec();
+	file_buffer.extend(b"EXIF");
+	file_buffer.extend(4u32.to_le_bytes());
+	file_buffer.extend(b"Exif\0\0");
+	let riff_size = (file_buffer.len() - 8) as u32;
+	file_buffer[4..8].copy_from_slice(&riff_size.to_le_bytes());
+	assert_eq!(
+		Metadata::exif_byte_range_in_vec(&file_buffer, FileExtension::WEBP).unwrap(),
+		Some((exif_chunk as u64 + 8, 4))
+	);
+
+	// ...and an EXIF chunk exceeding the file data
+	let mut file_buffer = webp_image.clone();
+	file_buffer.truncate(exif_chunk + 8 + 10);
+	assert!(Metadata::exif_byte_range_in_vec(&file_buffer, FileExtension::WEBP).is_err());
+}
+
+#[test]
+fn
+exif_byte_range_png()
+-> Result<(), std::io::Error>
+{
+	// Remove file from previous run and replace it with fresh copy
+	if let Err(error) = remove_file("tests/sample2_copy_range.png")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.png", "tests/sample2_copy_range.png")?;
+
+	assert_eq!(Metadata::exif_byte_range(Path::new("tests/sample2_copy_range.png"))?, None);
+
+	get_test_metadata()?.write_to_file(Path::new("tests/sample2_copy_range.png"))?;
+
+	// The range holds the data of the zTXt chunk, starting with its keyword
+	let (start, length) = Metadata::exif_byte_range(Path::new("tests/sample2_copy_range.png"))?.unwrap();
+	let file_buffer     = read("tests/sample2_copy_range.png").unwrap();
+	let chunk_data      = &file_buffer[start as usize..(start + length) as usize];
+	assert!(chunk_data.starts_with(b"Raw profile type exif\0"));
+	assert_eq!(&file_buffer[start as usize - 4..start as usize], b"zTXt");
+
+	Ok(())
+}
+
+#[test]
+fn
+camera_identity_jpg()
+-> Result<(), std::io::Error>
+{
+	// The sample only has a Model tag
+	assert_eq!(Metadata::camera_identity(Path::new("tests/read_sample.jpg"))?, None);
+
+	// Remove file from previous run and replace it with fresh copy
+	if let Err(error) = remove_file("tests/read_sample_copy_camera_identity.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/read_sample.jpg", "tests/read_sample_copy_camera_identity.jpg")?;
+
+	let path = Path::new("tests/read_sample_copy_camera_identity.jpg");
+	let mut metadata = Metadata::new_from_path(path)?;
+	metadata.set_tag(ExifTag::Make("Testmaker\0\0".to_string()));
+	metadata.write_to_file(path)?;
+
+	assert_eq!(
+		Metadata::camera_identity(path)?, 
+		Some(("Testmaker".to_string(), "Testcam(1)".to_string()))
+	);
+
+	Ok(())
+}
+
+#[test]
+fn
+classify_ifd_tiff()
+-> Result<(), std::io::Error>
+{
+	use little_exif::filetype::FileExtension;
+	use little_exif::metadata::subfile::SubfileKind;
+
+	// Add a reduced-resolution image (a single RGB pixel) as IFD1
+	let mut image_data = read("tests/read_sample.tif")?;
+	let mut metadata   = Metadata::new_from_vec(&image_data, FileExtension::TIFF)?;
+	let ifd1 = metadata.get_ifd_mut(ExifTagGroup::GENERIC, 1);
+	ifd1.set_tag(ExifTag::NewSubfileType(vec![1]));
+	ifd1.set_tag(ExifTag::ImageWidth(vec![1]));
+	ifd1.set_tag(ExifTag::ImageHeight(vec![1]));
+	ifd1.set_tag(ExifTag::StripOffsets(Vec::new(), vec![vec![0xff, 0x00, 0x00]]));
+	ifd1.set_tag(ExifTag::StripByteCounts(vec![3]));
+	metadata.write_to_vec(&mut image_data, FileExtension::TIFF)?;
+
+	let metadata = Metadata::new_from_vec(&image_data, FileExtension::TIFF)?;
+	assert_eq!(metadata.classify_ifd(0), SubfileKind::Main);
+	assert_eq!(metadata.classify_ifd(1), SubfileKind::ReducedResolution);
+	assert!(metadata.get_new_subfile_type(1).unwrap().reduced_resolution);
+
+	Ok(())
+}
+
+#[test]
+fn
+write_to_animated_webp()
+-> Result<(), std::io::Error>
+{
+	fn
+	chunk
+	(
+		header:  &str,
+		payload: &[u8]
+	)
+	-> Vec<u8>
+	{
+		let mut chunk = header.as_bytes().to_vec();
+		chunk.extend((payload.len() as u32).to_le_bytes());
+		chunk.extend(payload);
+		if payload.len() % 2 != 0 { chunk.push(0x00); }
+		return chunk;
+	}
+
+	fn
+	chunk_headers_and_payloads
+	(
+		file_buffer: &[u8]
+	)
+	-> Vec<(String, Vec<u8>)>
+	{
+		let mut chunks   = Vec::new();
+		let mut position = 12;
+		while position + 8 <= file_buffer.len()
+		{
+			let length = u32::from_le_bytes(file_buffer[position+4..position+8].try_into().unwrap()) as usize;
+			chunks.push((
+				String::from_utf8(file_buffer[position..position+4].to_vec()).unwrap(),
+				file_buffer[position+8..position+8+length].to_vec()
+			));
+			position += 8 + length + length % 2;
+		}
+		return chunks;
+	}
+
+	// Animated 2x2 WebP with two (dummy) frames, an XMP and an unknown chunk
+	let frame1 = [vec![0x00; 16], chunk("VP8L", &[0x2f, 0x01, 0x40])].concat();
+	let frame2 = [vec![0x01; 16], chunk("VP8L", &[0x2f, 0x01, 0x40, 0x00, 0x10])].concat();
+	let chunks = [
+		chunk("VP8X", &[0x02, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x01, 0x00, 0x00]),
+		chunk("ANIM", &[0xff, 0xff, 0xff, 0xff, 0x00, 0x00]),
+		chunk("ANMF", &frame1),
+		chunk("ANMF", &frame2),
+		chunk("XMP ", b"<x:xmpmeta/>"),
+		chunk("ABCD", &[0x42]),
+	].concat();
+
+	let mut image_data = b"RIFF".to_vec();
+	image_data.extend((chunks.len() as u32 + 4).to_le_bytes());
+	image_data.extend(b"WEBP");
+	image_data.extend(chunks);
+
+	// Remove file from previous run and replace it with fresh copy
+	if let Err(error) = remove_file("tests/animated_copy.webp")
+	{
+		println!("{}", error);
+	}
+	std::fs::write("tests/animated_copy.webp", &image_data)?;
+
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::ImageDescription("Hello World".to_string()));
+	metadata.write_to_vec(&mut image_data, little_exif::filetype::FileExtension::WEBP)?;
+	metadata.write_to_file(Path::new("tests/animated_copy.webp"))?;
+	assert_eq!(image_data, read("tests/animated_copy.webp")?);
+
+	// The EXIF chunk is placed after the frames and before the XMP chunk
+	let chunks = chunk_headers_and_payloads(&image_data);
+	assert_eq!(
+		chunks.iter().map(|(header, _)| header.as_str()).collect::<Vec<&str>>(),
+		vec!["VP8X", "ANIM", "ANMF", "ANMF", "EXIF", "XMP ", "ABCD"]
+	);
+
+	// Animation and EXIF flags are set, the frames are unchanged
+	assert_eq!(chunks[0].1[0], 0x02 | 0x08);
+	assert_eq!(chunks[2].1, frame1);
+	assert_eq!(chunks[3].1, frame2);
+
+	assert_eq!(
+		Metadata::new_from_vec(&image_data, little_exif::filetype::FileExtension::WEBP)?
+			.get_tag(&ExifTag::ImageDescription(String::new())).next(),
+		Some(&ExifTag::ImageDescription("Hello World".to_string()))
+	);
+
+	Ok(())
+}
+
+#[test]
+fn
+exposure_program_round_trip()
+-> Result<(), std::io::Error>
+{
+	use little_exif::metadata::exposure::ExposureProgram;
+	use little_exif::metadata::exposure::MeteringMode;
+	use little_exif::metadata::exposure::WhiteBalance;
+
+	for exposure_program in [ExposureProgram::AperturePriority, ExposureProgram::Manual]
+	{
+		let mut image_data = read("tests/no_exif.jpeg").unwrap();
+		let mut metadata   = Metadata::new();
+		metadata.set_exposure_program(exposure_program);
+		metadata.set_metering_mode(MeteringMode::Spot);
+		metadata.set_white_balance(WhiteBalance::Manual);
+		metadata.write_to_vec(&mut image_data, little_exif::filetype::FileExtension::JPEG)?;
+
+		let metadata = Metadata::new_from_vec(&image_data, little_exif::filetype::FileExtension::JPEG)?;
+		assert_eq!(metadata.get_exposure_program(), Some(exposure_program));
+		assert_eq!(metadata.get_metering_mode(),    Some(MeteringMode::Spot));
+		assert_eq!(metadata.get_white_balance(),    Some(WhiteBalance::Manual));
+	}
+
+	Ok(())
+}
+
+#[test]
+fn
+read_second_image_of_mpo()
+-> Result<(), std::io::Error>
+{
+	let mut first_image = read("tests/sample2.jpg").unwrap();
+	let mut metadata    = Metadata::new();
+	metadata.set_tag(ExifTag::Model("Left".to_string()));
+	metadata.write_to_vec(&mut first_image, little_exif::filetype::FileExtension::JPEG)?;
+
+	let mut second_image = read("tests/sample2.jpg").unwrap();
+	metadata.set_tag(ExifTag::Model("Right".to_string()));
+	metadata.write_to_vec(&mut second_image, little_exif::filetype::FileExtension::JPEG)?;
+
+	// APP2 segment of the first image with a big endian MP Index IFD that
+	// holds the MPF version (0xb000) and two MP entries (0xb002)
+	let mut mpf_segment = vec![0xff, 0xe2, 0x00, 0x00];
+	mpf_segment.extend(b"MPF\0");
+	mpf_segment.extend([0x4d, 0x4d, 0x00, 0x2a, 0x00, 0x00, 0x00, 0x08]);
+	mpf_segment.extend([0x00, 0x02]);
+	mpf_segment.extend([0xb0, 0x00, 0x00, 0x07, 0x00, 0x00, 0x00, 0x04, 0x30, 0x31, 0x30, 0x30]);
+	mpf_segment.extend([0xb0, 0x02, 0x00, 0x07, 0x00, 0x00, 0x00, 0x20, 0x00, 0x00, 0x00, 0x26]);
+	mpf_segment.extend([0x00, 0x00, 0x00, 0x00]);
+
+	let first_image_length  = first_image.len() + mpf_segment.len() + 32;
+	let second_image_offset = first_image_length as u32 - 10;                  // Relative to the MP header after "MPF\0"
+	mpf_segment.extend([0x20, 0x03, 0x00, 0x00]);
+	mpf_segment.extend((first_image_length as u32).to_be_bytes());
+	mpf_segment.extend([0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+	mpf_segment.extend([0x00, 0x00, 0x00, 0x00]);
+	mpf_segment.extend((second_image.len() as u32).to_be_bytes());
+	mpf_segment.extend(second_image_offset.to_be_bytes());
+	mpf_segment.extend([0x00, 0x00, 0x00, 0x00]);
+
+	let segment_length = (mpf_segment.len() - 2) as u16;
+	mpf_segment[2..4].copy_from_slice(&segment_length.to_be_bytes());
+
+	let mut mpo_data = first_image[..2].to_vec();
+	mpo_data.extend(mpf_segment);
+	mpo_data.extend(&first_image[2..]);
+	mpo_data.extend(second_image);
+
+	assert_eq!(Metadata::mpo_image_count(&mpo_data)?, 2);
+	assert_eq!(
+		Metadata::new_from_mpo_image(&mpo_data, 0)?.get_tag(&ExifTag::Model(String::new())).next(),
+		Some(&ExifTag::Model("Left".to_string()))
+	);
+	assert_eq!(
+		Metadata::new_from_mpo_image(&mpo_data, 1)?.get_tag(&ExifTag::Model(String::new())).next(),
+		Some(&ExifTag::Model("Right".to_string()))
+	);
+	assert!(Metadata::new_from_mpo_image(&mpo_data, 2).is_err());
+
+	// Regular JPEGs consist of a single image
+	assert_eq!(Metadata::mpo_image_count(&read("tests/sample2.jpg").unwrap())?, 1);
+
+	Ok(())
+}
+
+#[test]
+fn
+write_oversized_exif_to_jpg()
+-> Result<(), std::io::Error>
+{
+	let original_data  = read("tests/sample2.jpg").unwrap();
+	let mut image_data = original_data.clone();
+
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::ImageDescription("x".repeat(70000)));
+
+	let error     = metadata.write_to_vec(&mut image_data, little_exif::filetype::FileExtension::JPEG).unwrap_err();
+	let too_large = little_exif::metadata::error::MetadataTooLarge::from_io_error(&error).unwrap();
+	assert_eq!(error.kind(),     std::io::ErrorKind::InvalidInput);
+	assert_eq!(too_large.limit,  65527);
+	assert_eq!(too_large.actual, metadata.encoded_size()?);
+
+	// The image is left untouched instead of losing its previous metadata
+	assert_eq!(image_data, original_data);
+	assert!(metadata.as_u8_vec(little_exif::filetype::FileExtension::JPEG).is_err());
+
+	Ok(())
+}
+
+#[test]
+fn
+embedded_images_jpg()
+-> Result<(), std::io::Error>
+{
+	use little_exif::metadata::preview::EmbeddedImageKind;
+
+	let thumbnail = [vec![0xff, 0xd8], vec![0x01; 16],  vec![0xff, 0xd9]].concat();
+	let preview   = [vec![0xff, 0xd8], vec![0x02; 256], vec![0xff, 0xd9]].concat();
+
+	// Small thumbnail in IFD1 and a larger, JPEG compressed preview in IFD2
+	let mut image_data = read("tests/no_exif.jpeg").unwrap();
+	let mut metadata   = Metadata::new();
+	metadata.get_ifd_mut(ExifTagGroup::GENERIC, 1).set_tag(ExifTag::ThumbnailOffset(Vec::new(), thumbnail.clone()));
+	metadata.get_ifd_mut(ExifTagGroup::GENERIC, 1).set_tag(ExifTag::ThumbnailLength(vec![thumbnail.len() as u32]));
+	metadata.get_ifd_mut(ExifTagGroup::GENERIC, 2).set_tag(ExifTag::NewSubfileType(vec![1]));
+	metadata.get_ifd_mut(ExifTagGroup::GENERIC, 2).set_tag(ExifTag::Compression(vec![7]));
+	metadata.get_ifd_mut(ExifTagGroup::GENERIC, 2).set_tag(ExifTag::ImageWidth(vec![320]));
+	metadata.get_ifd_mut(ExifTagGroup::GENERIC, 2).set_tag(ExifTag::ImageHeight(vec![240]));
+	metadata.get_ifd_mut(ExifTagGroup::GENERIC, 2).set_tag(ExifTag::StripOffsets(Vec::new(), vec![preview.clone()]));
+	metadata.get_ifd_mut(ExifTagGroup::GENERIC, 2).set_tag(ExifTag::StripByteCounts(vec![preview.len() as u32]));
+	metadata.write_to_vec(&mut image_data, little_exif::filetype::FileExtension::JPEG)?;
+
+	let metadata = Metadata::new_from_vec(&image_data, little_exif::filetype::FileExtension::JPEG)?;
+	let images   = metadata.embedded_images();
+	assert_eq!(images.len(), 2);
+
+	assert_eq!(images[0].source_ifd,  1);
+	assert_eq!(images[0].kind,        EmbeddedImageKind::Thumbnail);
+	assert_eq!(images[0].data,        thumbnail);
+
+	assert_eq!(images[1].source_ifd,  2);
+	assert_eq!(images[1].kind,        EmbeddedImageKind::Preview);
+	assert_eq!(images[1].width,       Some(320));
+	assert_eq!(images[1].height,      Some(240));
+	assert_eq!(images[1].compression, Some(7));
+	assert_eq!(images[1].data,        preview);
+
+	assert!(Metadata::new().embedded_images().is_empty());
+
+	Ok(())
+}
+
+#[test]
+fn
+write_to_file_handle_jpg()
+-> Result<(), std::io::Error>
+{
+	// Remove file from previous run and replace it with fresh copy
+	if let Err(error) = remove_file("tests/sample2_copy_handle.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.jpg", "tests/sample2_copy_handle.jpg")?;
+
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::ImageDescription("Written via handle".to_string()));
+
+	let mut file = std::fs::OpenOptions::new()
+		.read(true)
+		.write(true)
+		.open("tests/sample2_copy_handle.jpg")?;
+	metadata.write_to_file_handle(&mut file, little_exif::filetype::FileExtension::JPEG)?;
+	drop(file);
+
+	// Same result as when writing to a buffer
+	let mut expected = read("tests/sample2.jpg").unwrap();
+	metadata.write_to_vec(&mut expected, little_exif::filetype::FileExtension::JPEG)?;
+	assert_eq!(read("tests/sample2_copy_handle.jpg").unwrap(), expected);
+
+	let metadata = Metadata::new_from_path(Path::new("tests/sample2_copy_handle.jpg"))?;
+	assert_eq!(
+		metadata.get_tag(&ExifTag::ImageDescription(String::new())).next(),
+		Some(&ExifTag::ImageDescription("Written via handle".to_string()))
+	);
+
+	Ok(())
+}
+
+#[test]
+fn
+read_from_file_jp2()
+-> Result<(), std::io::Error>
+{
+	let metadata = Metadata::new_from_path(Path::new("tests/with_exif.jp2"))?;
+
+	assert_eq!(metadata.get_tag(&ExifTag::ISO(Vec::new())).next(), Some(&ExifTag::ISO(vec![2706])));
+	assert_eq!(
+		metadata.get_tag(&ExifTag::ImageDescription(String::new())).next(),
+		Some(&ExifTag::ImageDescription("Hello World!".to_string()))
+	);
+
+	Ok(())
+}
+
+#[test]
+fn
+read_from_vec_jp2()
+-> Result<(), std::io::Error>
+{
+	return read_from_vec_generic(&read("tests/with_exif.jp2").unwrap(), little_exif::filetype::FileExtension::JP2);
+}
+
+#[test]
+fn
+write_to_file_jp2()
+-> Result<(), std::io::Error>
+{
+	// Remove file from previous run and replace it with fresh copy
+	if let Err(error) = remove_file("tests/with_exif_copy.jp2")
+	{
+		println!("{}", error);
+	}
+	copy("tests/with_exif.jp2", "tests/with_exif_copy.jp2")?;
+
+	// Replace the existing EXIF box
+	let metadata = get_test_metadata()?;
+	metadata.write_to_file(Path::new("tests/with_exif_copy.jp2"))?;
+
+	// The codestream box (the last 90 bytes) stays untouched
+	let image_data = read("tests/with_exif_copy.jp2")?;
+	let original   = read("tests/with_exif.jp2")?;
+	assert!(image_data.ends_with(&original[original.len()-90..]));
+
+	let written = Metadata::new_from_vec(&image_data, little_exif::filetype::FileExtension::JP2)?;
+	assert_eq!(written.get_tag(&ExifTag::ISO(Vec::new())).next(), Some(&ExifTag::ISO(vec![2706])));
+	assert_eq!(
+		written.get_tag(&ExifTag::Model(String::new())).next(),
+		Some(&ExifTag::Model("Testcam(1)".to_string()))
+	);
+
+	// Remove it again, leaving only the image itself
+	Metadata::file_clear_metadata(Path::new("tests/with_exif_copy.jp2"))?;
+	assert_eq!(read("tests/with_exif_copy.jp2")?.len(), original.len() - 93);
+	assert!(Metadata::exif_byte_range(Path::new("tests/with_exif_copy.jp2"))?.is_none());
+
+	Ok(())
+}
+
+#[test]
+fn
+raw_entries_jp2()
+-> Result<(), std::io::Error>
+{
+	use little_exif::ifd::ExifTagGroup;
+	use little_exif::metadata::raw::RawIfdEntry;
+
+	// IFD0 with ImageDescription and ExifOffset, followed by the ExifIFD
+	// with the ISO value stored directly in the entry
+	let entries = Metadata::raw_entries(Path::new("tests/with_exif.jp2"))?;
+	assert_eq!(entries, vec![
+		RawIfdEntry { ifd_offset: 0x08, group: ExifTagGroup::GENERIC, generic_ifd_nr: 0, tag: 0x010e, format: 2, count: 13, value_or_offset: [0x26, 0x00, 0x00, 0x00] },
+		RawIfdEntry { ifd_offset: 0x08, group: ExifTagGroup::GENERIC, generic_ifd_nr: 0, tag: 0x8769, format: 4, count: 1,  value_or_offset: [0x33, 0x00, 0x00, 0x00] },
+		RawIfdEntry { ifd_offset: 0x33, group: ExifTagGroup::EXIF,    generic_ifd_nr: 0, tag: 0x8827, format: 3, count: 1,  value_or_offset: [0x92, 0x0a, 0x00, 0x00] },
+	]);
+
+	Ok(())
+}
+
+#[test]
+fn
+would_modify_jpg()
+-> Result<(), std::io::Error>
+{
+	// Remove file from previous run and replace it with fresh copy
+	if let Err(error) = remove_file("tests/would_modify_copy.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.jpg", "tests/would_modify_copy.jpg")?;
+	let path = Path::new("tests/would_modify_copy.jpg");
+
+	// Once the metadata has been written back unchanged, the file holds
+	// exactly its encoding
+	let mut metadata = Metadata::new_from_path(path)?;
+	metadata.write_to_file(path)?;
+	assert!(!metadata.would_modify(path)?);
+
+	metadata.set_tag(ExifTag::ImageDescription("Changed".to_string()));
+	assert!(metadata.would_modify(path)?);
+
+	// A file without EXIF data always gets modified
+	assert!(Metadata::new().would_modify(Path::new("tests/no_exif.jpeg"))?);
+
+	Ok(())
+}
+
+#[test]
+fn
+per_page_description_tif()
+-> Result<(), std::io::Error>
+{
+	copy("tests/read_sample.tif", "tests/read_sample_copy_pages.tif")?;
+	let path = Path::new("tests/read_sample_copy_pages.tif");
+
+	let mut metadata = Metadata::new_from_path(path)?;
+	metadata.set_tag_in_page(0, ExifTag::ImageDescription("Front side".to_string()));
+	metadata.set_tag_in_page(1, ExifTag::ImageDescription("Back side".to_string()));
+	metadata.set_tag_in_page(1, ExifTag::PageName("Page 2".to_string()));
+	metadata.write_to_file(path)?;
+
+	let metadata = Metadata::new_from_path(path)?;
+	assert_eq!(metadata.get_max_generic_ifd_number(), 1);
+	assert_eq!(
+		metadata.get_tag_in_page(0, 0x010e, ExifTagGroup::GENERIC),
+		Some(&ExifTag::ImageDescription("Front side".to_string()))
+	);
+	assert_eq!(
+		metadata.get_tag_in_page(1, 0x010e, ExifTagGroup::GENERIC),
+		Some(&ExifTag::ImageDescription("Back side".to_string()))
+	);
+	assert_eq!(
+		metadata.get_tag_in_page(1, 0x011d, ExifTagGroup::GENERIC),
+		Some(&ExifTag::PageName("Page 2".to_string()))
+	);
+	assert_eq!(metadata.get_tag_in_page(0, 0x011d, ExifTagGroup::GENERIC), None);
+	assert_eq!(metadata.get_tag_in_page(2, 0x010e, ExifTagGroup::GENERIC), None);
+
+	remove_file(path)?;
+	Ok(())
+}
+
+#[test]
+fn
+file_writes_replace_the_original_atomically()
+-> Result<(), std::io::Error>
+{
+	let path           = Path::new("tests/sample2_copy_atomic.jpg");
+	let temporary_path = Path::new("tests/.sample2_copy_atomic.jpg.little_exif.tmp");
+
+	// A successful write replaces the file and leaves no temporary copy
+	copy("tests/sample2.jpg", path)?;
+	Metadata::file_clear_metadata(path)?;
+	assert!(!Metadata::file_has_exif(path)?);
+	assert!(!temporary_path.exists());
+
+	// A failing write (here: a PNG with a JPEG extension) leaves the
+	// original file untouched and removes the temporary copy
+	copy("tests/sample2.png", path)?;
+	let original = read(path)?;
+	for result in [
+		Metadata::file_clear_metadata(path),
+		Metadata::file_strip_all_metadata(path),
+		Metadata::file_clear_app12_segment(path),
+	]
+	{
+		assert!(result.is_err());
+		assert_eq!(read(path)?, original);
+		assert!(!temporary_path.exists());
+	}
+
+	remove_file(path)?;
+	Ok(())
+}
+
+#[test]
+fn
+jp2_box_length_exceeding_file()
+{
+	use little_exif::filetype::FileExtension;
+
+	// Box with an extended length (length field 1) so large that the end of
+	// the box overflows - this must be an error instead of a panic
+	let mut file_buffer = read("tests/with_exif.jp2").unwrap()[..12].to_vec();
+	file_buffer.extend(1u32.to_be_bytes());
+	file_buffer.extend(b"uuid");
+	file_buffer.extend(0xffff_ffff_ffff_fff0u64.to_be_bytes());
+
+	assert!(Metadata::exif_byte_range_in_vec(&file_buffer, FileExtension::JP2).is_err());
+}
+
+#[test]
+fn
+multiple_exif_sources_jpg()
+-> Result<(), std::io::Error>
+{
+	use little_exif::filetype::FileExtension;
+	use little_exif::metadata::warnings::ParseWarningKind;
+
+	let mut image_data = read("tests/no_exif.jpeg")?;
+	get_test_metadata()?.write_to_vec(&mut image_data, FileExtension::JPEG)?;
+
+	// Second APP1 segment with EXIF data right after the first one, e.g.
+	// added by another tool
+	let mut other_metadata = Metadata::new();
+	other_metadata.set_tag(ExifTag::Model("Other".to_string()));
+	other_metadata.set_tag(ExifTag::Artist("Someone".to_string()));
+	let other_exif = [b"Exif\0\0".to_vec(), other_metadata.encode()?].concat();
+
+	let mut app1_segment = vec![0xff, 0xe1];
+	app1_segment.extend((other_exif.len() as u16 + 2).to_be_bytes());
+	app1_segment.extend(other_exif);
+
+	let (exif_start, exif_length) = Metadata::exif_byte_range_in_vec(&image_data, FileExtension::JPEG)?.unwrap();
+	let first_app1_end = (exif_start + exif_length) as usize;
+	image_data.splice(first_app1_end..first_app1_end, app1_segment);
+
+	// The first segment takes precedence, the second adds missing tags
+	let metadata = Metadata::new_from_vec(&image_data, FileExtension::JPEG)?;
+	assert_eq!(metadata.get_tag(&ExifTag::Model(String::new())).next(),  Some(&ExifTag::Model("Testcam(1)".to_string())));
+	assert_eq!(metadata.get_tag(&ExifTag::Artist(String::new())).next(), Some(&ExifTag::Artist("Someone".to_string())));
+	assert_eq!(metadata.get_tag(&ExifTag::ISO(Vec::new())).next(),      Some(&ExifTag::ISO(vec![2706])));
+	assert!(metadata.warnings().iter().any(|warning| warning.kind == ParseWarningKind::MultipleExifSources));
+
+	Ok(())
+}
+
+#[test]
+fn
+tag_counts()
+-> Result<(), std::io::Error>
+{
+	let metadata = get_test_metadata()?;
+	let counts   = metadata.tag_counts();
+
+	assert_eq!(counts.get(&little_exif::ifd::ExifTagGroup::GENERIC), Some(&2));
+	assert_eq!(counts.get(&little_exif::ifd::ExifTagGroup::EXIF),    Some(&2));
+	assert_eq!(counts.get(&little_exif::ifd::ExifTagGroup::GPS),     None);
+	assert_eq!(metadata.total_tag_count(), 4);
+
+	Ok(())
+}
+
+#[test]
+fn
+jpg_dimensions_from_sof()
+-> Result<(), std::io::Error>
+{
+	let image_data = read("tests/no_exif.jpeg").unwrap();
+	let metadata   = Metadata::new_from_vec(&image_data, little_exif::filetype::FileExtension::JPEG)?;
+
+	let width  = metadata.get_tag(&ExifTag::ExifImageWidth( Vec::new())).next().unwrap();
+	let height = metadata.get_tag(&ExifTag::ExifImageHeight(Vec::new())).next().unwrap();
+
+	assert_eq!(width,  &ExifTag::ExifImageWidth( vec![2]));
+	assert_eq!(height, &ExifTag::ExifImageHeight(vec![1]));
+	assert!(metadata.is_derived(width));
+	assert!(metadata.is_derived(height));
+
+	// Values from the EXIF data itself are not overwritten and not derived
+	let metadata = Metadata::new_from_path(Path::new("tests/sample2.jpg"))?;
+	let width    = metadata.get_tag(&ExifTag::ExifImageWidth(Vec::new())).next().unwrap();
+	assert!(!metadata.is_derived(width));
+
+	// Derived values are never written back, so reading and writing an
+	// image without changes does not add any tags
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::ImageDescription("No dimensions".to_string()));
+	let path = Path::new("tests/no_exif_derived_copy.jpeg");
+	copy("tests/no_exif.jpeg", path)?;
+	metadata.write_to_file(path)?;
+
+	let read_back = Metadata::new_from_path(path)?;
+	assert!(read_back.get_tag(&ExifTag::ExifImageWidth(Vec::new())).next().is_some());
+	assert!(!read_back.would_modify(path)?);
+
+	let encoded = Metadata::new_from_vec(&read_back.encode()?, little_exif::filetype::FileExtension::TIFF)?;
+	assert_eq!(encoded.get_tag(&ExifTag::ExifImageWidth(Vec::new())).next(), None);
+	assert_eq!(encoded.into_iter().count(), 1);
+
+	Ok(())
+}
+
+#[test]
+fn
+write_to_vec_jpg_keeps_app0_intact()
+-> Result<(), std::io::Error>
+{
+	let original = read("tests/no_exif.jpeg").unwrap();
+
+	// no_exif.jpeg starts with a JFIF APP0 segment, add a JFXX APP0 segment
+	// carrying a (tiny) 1x1 RGB thumbnail right after it
+	let jfif_length = 2 + ((original[4] as usize) << 8 | original[5] as usize);
+	let mut jfxx    = vec![0xff, 0xe0, 0x00, 0x0d, b'J', b'F', b'X', b'X', 0x00, 0x13, 0x01, 0x01, 0xff, 0x00, 0x00];
+	let mut image_data = original[..2 + jfif_length].to_vec();
+	image_data.append(&mut jfxx);
+	image_data.extend(original[2 + jfif_length..].iter());
+
+	let app0_segments = image_data[..2 + jfif_length + 15].to_vec();
+
+	get_test_metadata()?.write_to_vec(&mut image_data, little_exif::filetype::FileExtension::JPEG)?;
+
+	// APP0 segments are byte-identical and are directly followed by APP1
+	assert_eq!(image_data[..app0_segments.len()], app0_segments[..]);
+	assert_eq!(image_data[app0_segments.len()..app0_segments.len()+2], [0xff, 0xe1]);
+
+	let metadata = Metadata::new_from_vec(&image_data, little_exif::filetype::FileExtension::JPEG)?;
+	assert_eq!(metadata.get_tag(&ExifTag::ISO(Vec::new())).next(), Some(&ExifTag::ISO(vec![2706])));
+
+	Ok(())
+}
+
+#[test]
+fn
+tag_group()
+{
+	use little_exif::ifd::ExifTagGroup;
+
+	assert_eq!(ExifTag::GPSLatitude(Vec::new()).get_group(),                            ExifTagGroup::GPS);
+	assert_eq!(ExifTag::ImageDescription(String::new()).get_group(),                    ExifTagGroup::GENERIC);
+	assert_eq!(ExifTag::UnknownINT16U(vec![1], 0xbeef, ExifTagGroup::EXIF).get_group(), ExifTagGroup::EXIF);
+
+	// Group of a tag read from a file
+	let metadata = Metadata::new_from_path(Path::new("tests/read_sample.tif")).unwrap();
+	assert_eq!(metadata.get_tag(&ExifTag::ISO(Vec::new())).next().unwrap().get_group(), ExifTagGroup::EXIF);
+}
+
+#[test]
+fn
+tag_identity()
+{
+	use std::collections::HashSet;
+
+	let iso_100 = ExifTag::ISO(vec![100]);
+	let iso_200 = ExifTag::ISO(vec![200]);
+
+	assert_ne!(iso_100, iso_200);
+	assert_eq!(iso_100.identity(), iso_200.identity());
+	assert!(iso_100.has_same_identity(&iso_200));
+
+	// Same hex value but different group
+	assert!(!ExifTag::UnknownINT16U(vec![1], 0x8827, ExifTagGroup::GPS).has_same_identity(&iso_100));
+
+	let identities = [&iso_100, &iso_200, &ExifTag::Model(String::new())].iter()
+		.map(|tag| tag.identity())
+		.collect::<HashSet<_>>();
+	assert_eq!(identities.len(), 2);
+
+	// Setting a tag with the same identity replaces the existing one
+	let mut metadata = Metadata::new();
+	metadata.set_tag(iso_100);
+	metadata.set_tag(iso_200.clone());
+	assert_eq!(metadata.get_tag(&ExifTag::ISO(Vec::new())).collect::<Vec<_>>(), vec![&iso_200]);
+	assert_eq!(metadata.total_tag_count(), 1);
+}
+
+#[test]
+fn
+write_to_file_detailed_png()
+-> Result<(), std::io::Error>
+{
+	// Remove file from previous run and replace it with fresh copy
+	if let Err(error) = remove_file("tests/sample2_copy_detailed.png")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.png", "tests/sample2_copy_detailed.png")?;
+
+	let metadata = get_test_metadata()?;
+
+	// First write adds new metadata, the second one replaces it
+	let report = metadata.write_to_file_detailed(Path::new("tests/sample2_copy_detailed.png"))?;
+	assert_eq!(report.file_type,      little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true });
+	assert_eq!(report.metadata_bytes, metadata.encoded_size()?);
+	assert!(!report.replaced_existing);
+
+	let report = metadata.write_to_file_detailed(Path::new("tests/sample2_copy_detailed.png"))?;
+	assert!(report.replaced_existing);
+
+	Ok(())
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn
+write_to_vec_png_uncompressed_tEXt()
+-> Result<(), std::io::Error>
+{
+	let     metadata   = get_test_metadata()?;
+	let mut image_data = read("tests/sample2.png").unwrap();
+
+	metadata.write_to_vec(&mut image_data, little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: false })?;
+
+	// The chunk following IHDR is an uncompressed tEXt chunk
+	assert_eq!(&image_data[37..41], b"tEXt");
+	assert_eq!(&image_data[41..63], b"Raw profile type exif\0");
+
+	// Reading it back yields the same tags
+	let read_back = Metadata::new_from_vec(&image_data, little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: false })?;
+	assert_eq!(read_back.get_tag(&ExifTag::ISO(Vec::new())).next(), Some(&ExifTag::ISO(vec![2706])));
+	assert_eq!(read_back.total_tag_count(), metadata.total_tag_count());
+
+	// Writing again replaces the tEXt chunk instead of adding another one
+	let length_after_first_write = image_data.len();
+	metadata.write_to_vec(&mut image_data, little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: false })?;
+	assert_eq!(image_data.len(), length_after_first_write);
+
+	Ok(())
+}